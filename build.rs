@@ -80,7 +80,7 @@ fn main() {
 			.map(|c| *c as char)
 			.collect();
 
-	main_chars.append(&mut vec!['π', '"']);
+	main_chars.append(&mut vec!['π', 'θ', '"']);
 
 	{
 		let filtered_chars: Vec<char> = main_chars
@@ -108,7 +108,13 @@ fn main() {
 					font_stripper(
 						"Ubuntu-Light.ttf",
 						"ubuntu-light.ttf",
-						[main_chars, vec!['∫']].concat(),
+						[
+							main_chars,
+							vec!['∫', '∞', '≤'],
+							// Letters of the translations, such as Spanish's
+							"áéíóúüñÁÉÍÓÚÜÑ¿¡".chars().collect(),
+						]
+						.concat(),
 					)
 					.unwrap(),
 				),
@@ -119,7 +125,7 @@ fn main() {
 					font_stripper(
 						"NotoEmoji-Regular.ttf",
 						"noto-emoji.ttf",
-						vec!['🌞', '🌙', '✖'],
+						vec!['🌞', '🌙', '✖', '🕘', '📋'],
 					)
 					.unwrap(),
 				),
@@ -127,7 +133,7 @@ fn main() {
 			(
 				"emoji-icon-font".to_owned(),
 				FontData::from_owned(
-					font_stripper("emoji-icon-font.ttf", "emoji-icon.ttf", vec!['⚙']).unwrap(),
+					font_stripper("emoji-icon-font.ttf", "emoji-icon.ttf", vec!['⚙', '☰']).unwrap(),
 				)
 				.tweak(FontTweak {
 					scale: 0.8,
@@ -168,4 +174,46 @@ fn main() {
 
 	file.write_all(data_compressed.as_slice())
 		.expect("Failed to save compressed data");
+
+	translations_compressor(*zstd_levels.end());
+}
+
+/// Compresses the text of each language in `assets/i18n` into its own file (`i18n_<code>.json.zst`), after checking it has text for
+/// the same keys as English
+fn translations_compressor(level: i32) {
+	println!("cargo:rerun-if-changed=assets/i18n");
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/i18n");
+	let read = |path: &Path| -> (String, BTreeMap<String, String>) {
+		let source = std::fs::read_to_string(path).expect("Could not read translation");
+		let strings = serde_json::from_str(&source)
+			.unwrap_or_else(|error| panic!("Invalid translation {}: {}", path.display(), error));
+		(source, strings)
+	};
+	let (_, english) = read(&dir.join("en.json"));
+
+	for entry in std::fs::read_dir(&dir).expect("Could not read assets/i18n") {
+		let path = entry.expect("Could not read assets/i18n").path();
+		if !path
+			.extension()
+			.is_some_and(|extension| extension == "json")
+		{
+			continue;
+		}
+
+		let (source, strings) = read(&path);
+		assert!(
+			strings.keys().eq(english.keys()),
+			"{} doesn't have text for the same keys as en.json",
+			path.display()
+		);
+
+		let compressed =
+			zstd::encode_all(source.as_bytes(), level).expect("Could not compress translation");
+		let code = path.file_stem().unwrap().to_string_lossy();
+		std::fs::write(
+			Path::new(&env::var("OUT_DIR").unwrap()).join(format!("i18n_{}.json.zst", code)),
+			compressed,
+		)
+		.expect("Could not save translation");
+	}
 }
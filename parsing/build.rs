@@ -3,11 +3,8 @@ use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-/// REMEMBER TO UPDATE THIS IF EXMEX ADDS NEW FUNCTIONS
-const SUPPORTED_FUNCTIONS: [&str; 22] = [
-	"abs", "signum", "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh", "floor",
-	"round", "ceil", "trunc", "fract", "exp", "sqrt", "cbrt", "ln", "log2", "log10",
-];
+/// Variables (other than single characters) that are suggested
+const SUPPORTED_VARIABLES: [&str; 1] = ["theta"];
 
 fn main() {
 	println!("cargo:rerun-if-changed=src/*");
@@ -19,8 +16,21 @@ fn generate_hashmap() {
 	let path = Path::new(&env::var("OUT_DIR").unwrap()).join("codegen.rs");
 	let mut file = BufWriter::new(File::create(path).expect("Could not create file"));
 
-	let string_hashmap =
-		compile_hashmap(SUPPORTED_FUNCTIONS.iter().map(|a| a.to_string()).collect());
+	let string_hashmap = compile_hashmap_terms(
+		SUPPORTED_FUNCTIONS
+			.iter()
+			.map(|a| a.to_string() + "(")
+			.chain(SUPPORTED_VARIABLES.iter().map(|a| a.to_string()))
+			// Constants are suggested by their first name, unless it's a single character
+			.chain(
+				CONSTANTS
+					.iter()
+					.map(|constant| constant.names[0])
+					.filter(|name| name.chars().count() > 1)
+					.map(|name| name.to_string()),
+			)
+			.collect(),
+	);
 
 	let mut hashmap = phf_codegen::Map::new();
 
@@ -34,17 +44,13 @@ fn generate_hashmap() {
 		hashmap.build()
 	)
 	.expect("Could not write to file");
-
-	write!(
-		&mut file,
-		"#[allow(dead_code)] pub const SUPPORTED_FUNCTIONS: [&str; {}] = {:?};",
-		SUPPORTED_FUNCTIONS.len(),
-		SUPPORTED_FUNCTIONS.to_vec()
-	)
-	.expect("Could not write to file");
 }
 
 include!(concat!(
 	env!("CARGO_MANIFEST_DIR"),
 	"/src/autocomplete_hashmap.rs"
 ));
+
+include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/constants.rs"));
+
+include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/functions.rs"));
@@ -1,6 +1,10 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 use std::hint::unreachable_unchecked;
+use std::ops::Range;
 
-use crate::{generate_hint, Hint, HINT_EMPTY};
+use crate::suggestions::name_before;
+use crate::{complete_terms, generate_hint_at, Hint, HINT_EMPTY};
 
 #[derive(PartialEq, Debug)]
 pub enum Movement {
@@ -27,6 +31,21 @@ pub struct AutoComplete<'a> {
 	pub i: usize,
 	pub hint: &'a Hint<'a>,
 	pub string: String,
+
+	/// Character index in `string` of the cursor, which hints are generated for and inserted at
+	pub cursor: usize,
+
+	/// Names only known at runtime, such as user defined functions (ending in `(`) and parameters, which are completed along with the built in ones
+	pub terms: Vec<String>,
+
+	/// Completions from `terms` of the name before the cursor, which are offered before the ones in `hint`
+	pub term_hints: Vec<String>,
+
+	/// Name before the cursor that's being completed, which is empty if there isn't one
+	pub name: String,
+
+	/// Number of times each completion (see [`AutoComplete::completion`]) has been accepted, which candidates are ranked by
+	pub usage: BTreeMap<String, usize>,
 }
 
 impl<'a> const Default for AutoComplete<'a> {
@@ -38,71 +57,160 @@ impl<'a> AutoComplete<'a> {
 		i: 0,
 		hint: &HINT_EMPTY,
 		string: String::new(),
+		cursor: 0,
+		terms: Vec::new(),
+		term_hints: Vec::new(),
+		name: String::new(),
+		usage: BTreeMap::new(),
 	};
 
+	/// Updates the string, with the cursor at its end
 	#[allow(dead_code)]
 	pub fn update_string(&mut self, string: &str) {
-		if self.string != string {
-			// catch empty strings here to avoid call to `generate_hint` and unnecessary logic
+		self.update_string_at(string, string.chars().count());
+	}
+
+	/// Updates the string and the (character) index of the cursor in it
+	pub fn update_string_at(&mut self, string: &str, cursor: usize) {
+		if (self.string != string) | (self.cursor != cursor) {
+			// catch empty strings here to avoid call to `generate_hint_at` and unnecessary logic
 			if string.is_empty() {
-				*self = Self::EMPTY;
+				*self = Self {
+					terms: std::mem::take(&mut self.terms),
+					usage: std::mem::take(&mut self.usage),
+					..Self::EMPTY
+				};
 			} else {
 				self.string = string.to_owned();
+				self.cursor = cursor;
 				self.do_update_logic();
 			}
 		}
 	}
 
-	/// Runs update logic assuming that a change to `self.string` has been made
+	/// Sets the names only known at runtime that are completed
+	pub fn set_terms(&mut self, terms: Vec<String>) {
+		if self.terms != terms {
+			self.terms = terms;
+			self.do_update_logic();
+		}
+	}
+
+	/// Sets how many times each completion has been accepted
+	pub fn set_usage(&mut self, usage: &BTreeMap<String, usize>) {
+		if &self.usage != usage {
+			self.usage = usage.clone();
+			self.do_update_logic();
+		}
+	}
+
+	/// Runs update logic assuming that a change to `self.string`, `self.cursor`, `self.terms`, or `self.usage` has been made
 	fn do_update_logic(&mut self) {
 		self.i = 0;
-		self.hint = generate_hint(&self.string);
+		self.hint = generate_hint_at(&self.string, self.cursor);
+		self.term_hints = complete_terms(&self.string, self.cursor, &self.terms);
+		self.name = name_before(&self.string, self.cursor).unwrap_or_default();
+	}
+
+	/// What accepting `hint` completes, being the name it completes (such as `sin(` for the hint `in(` after `s`), or the hint itself
+	/// if it doesn't complete one
+	pub fn completion(&self, hint: &str) -> String { format!("{}{}", self.name, hint) }
+
+	/// Every hint that can be completed, the one at `self.i` being selected.
+	/// The ones that have been accepted the most come first, otherwise the ones from `terms` come before the built in ones.
+	pub fn candidates(&self) -> Vec<&str> {
+		let built_in: &[&str] = match self.hint {
+			Hint::Single(hint) => std::slice::from_ref(hint),
+			Hint::Many(hints) => hints,
+			Hint::None => &[],
+		};
+		let mut candidates: Vec<&str> = self
+			.term_hints
+			.iter()
+			.map(String::as_str)
+			.chain(built_in.iter().copied())
+			.collect();
+		candidates.sort_by_cached_key(|hint| {
+			Reverse(self.usage.get(&self.completion(hint)).copied().unwrap_or(0))
+		});
+		candidates
 	}
 
 	#[allow(dead_code)]
 	pub fn register_movement(&mut self, movement: &Movement) {
-		if movement.is_none() | self.hint.is_none() {
+		let count = self.candidates().len();
+		if movement.is_none() | (count == 0) {
 			return;
 		}
 
-		match self.hint {
-			Hint::Many(hints) => {
-				// Impossible for plural hints to be singular or non-existant
-				debug_assert!(hints.len() > 1); // check on debug
-
-				match movement {
-					Movement::Up => {
-						// Wrap self.i to maximum `i` value if needed
-						if self.i == 0 {
-							self.i = hints.len() - 1;
-						} else {
-							self.i -= 1;
-						}
-					}
-					Movement::Down => {
-						// Add one, if resulting value is above maximum `i` value, set `i` to 0
-						self.i += 1;
-						if self.i > (hints.len() - 1) {
-							self.i = 0;
-						}
-					}
-					Movement::Complete => {
-						self.apply_hint(unsafe { hints.get_unchecked(self.i) });
-					}
-					_ => unsafe { unreachable_unchecked() },
-				}
+		match movement {
+			// Wrap around to the last candidate if needed
+			Movement::Up => self.i = (self.i + count - 1) % count,
+			// Wrap around to the first candidate if needed
+			Movement::Down => self.i = (self.i + 1) % count,
+			Movement::Complete => {
+				let hint = self.candidates()[self.i].to_owned();
+				self.apply_hint(&hint);
 			}
-			Hint::Single(hint) => {
-				if movement.is_complete() {
-					self.apply_hint(hint);
-				}
-			}
-			Hint::None => unsafe { unreachable_unchecked() },
+			Movement::None => unsafe { unreachable_unchecked() },
 		}
 	}
 
+	/// If the cursor is at the end of the string, which is where hints are shown after in the text box
+	pub fn cursor_at_end(&self) -> bool { self.cursor >= self.string.chars().count() }
+
+	/// Text of the hint that would be applied by completing, which is the currently selected one if there are multiple
+	pub fn current_hint(&self) -> &str { self.candidates().get(self.i).copied().unwrap_or("") }
+
+	/// Byte index in the string of the cursor
+	fn cursor_index(&self) -> usize {
+		self.string
+			.char_indices()
+			.nth(self.cursor)
+			.map_or(self.string.len(), |(index, _)| index)
+	}
+
+	/// Inserts `hint` at the cursor, moving the cursor to after it
 	pub fn apply_hint(&mut self, hint: &str) {
-		self.string.push_str(hint);
+		let index = self.cursor_index();
+		self.string.insert_str(index, hint);
+
+		// Parentheses opened by hints (such as `sin(`) are closed right away, leaving the cursor inside of them
+		if hint.ends_with('(') {
+			self.string.insert(index + hint.len(), ')');
+		}
+
+		self.cursor += hint.chars().count();
+		self.do_update_logic();
+	}
+
+	/// If `string` (with the cursor at `cursor`) is the current string with a `)` typed right before one that's already
+	/// there, in which case the existing one should be skipped over rather than another being inserted
+	pub fn skips_closing_parens(&self, string: &str, cursor: usize) -> bool {
+		let index = self.cursor_index();
+		(cursor == self.cursor + 1)
+			&& self.string[index..].starts_with(')')
+			&& string
+				.strip_prefix(&self.string[..index])
+				.and_then(|rest| rest.strip_prefix(')'))
+				== Some(&self.string[index..])
+	}
+
+	/// Replaces the bytes `range` of the string with `replacement` (such as a misspelled function name with the one it's
+	/// corrected to), keeping the cursor in the same place relative to the text around it
+	pub fn apply_correction(&mut self, range: Range<usize>, replacement: &str) {
+		let Some(replaced) = self.string.get(range.clone()) else {
+			return;
+		};
+
+		let start = self.string[..range.start].chars().count();
+		let (old_len, new_len) = (replaced.chars().count(), replacement.chars().count());
+		self.cursor = match self.cursor {
+			cursor if cursor >= start + old_len => cursor - old_len + new_len,
+			cursor if cursor > start => start + new_len,
+			cursor => cursor,
+		};
+		self.string.replace_range(range, replacement);
 		self.do_update_logic();
 	}
 }
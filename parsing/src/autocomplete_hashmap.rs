@@ -9,15 +9,20 @@ fn compare_len_reverse_alpha(a: &String, b: &String) -> Ordering {
 	}
 }
 
-/// Generates hashmap (well really a vector of tuple of strings that are then turned into a hashmap by phf)
+/// Generates hashmap (well really a vector of tuple of strings that are then turned into a hashmap by phf) of the functions in `data`
 #[allow(dead_code)]
 pub fn compile_hashmap(data: Vec<String>) -> Vec<(String, String)> {
+	compile_hashmap_terms(data.iter().map(|e| e.to_string() + "(").collect())
+}
+
+/// Generates hashmap of the terms in `data`, unlike [`compile_hashmap`] the terms are completed as-is (so functions need to already end in an opening parenthesis)
+#[allow(dead_code)]
+pub fn compile_hashmap_terms(data: Vec<String>) -> Vec<(String, String)> {
 	let mut seen = HashSet::new();
 
 	let tuple_list_1: Vec<(String, String)> = data
-		.iter()
-		.map(|e| e.to_string() + "(")
-		.flat_map(|func| all_possible_splits(func, &mut seen))
+		.into_iter()
+		.flat_map(|term| all_possible_splits(term, &mut seen))
 		.collect();
 
 	let keys: Vec<&String> = tuple_list_1.iter().map(|(a, _)| a).collect();
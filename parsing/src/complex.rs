@@ -0,0 +1,236 @@
+use std::f64::consts::{FRAC_PI_2, LN_10, LN_2};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Complex number `re + im*i`, used to evaluate functions whose results aren't real
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Complex {
+	pub re: f64,
+	pub im: f64,
+}
+
+impl Complex {
+	pub const I: Complex = Complex::new(0.0, 1.0);
+	pub const ONE: Complex = Complex::new(1.0, 0.0);
+	pub const ZERO: Complex = Complex::new(0.0, 0.0);
+
+	pub const fn new(re: f64, im: f64) -> Self { Self { re, im } }
+
+	/// Magnitude |z|
+	pub fn abs(self) -> f64 { self.re.hypot(self.im) }
+
+	/// Argument (angle) of z, in the range (-π, π]
+	pub fn arg(self) -> f64 { self.im.atan2(self.re) }
+
+	pub fn is_finite(self) -> bool { self.re.is_finite() && self.im.is_finite() }
+
+	/// Applies `f` to both the real and imaginary parts
+	fn map(self, f: impl Fn(f64) -> f64) -> Self { Self::new(f(self.re), f(self.im)) }
+
+	pub fn exp(self) -> Self {
+		let magnitude = self.re.exp();
+		Self::new(magnitude * self.im.cos(), magnitude * self.im.sin())
+	}
+
+	/// Principal natural logarithm
+	pub fn ln(self) -> Self { Self::new(self.abs().ln(), self.arg()) }
+
+	/// Principal square root
+	pub fn sqrt(self) -> Self {
+		let abs = self.abs();
+		let re = ((abs + self.re) / 2.0).sqrt();
+		let im = ((abs - self.re) / 2.0).sqrt();
+		match self.im.is_sign_negative() {
+			true => Self::new(re, -im),
+			false => Self::new(re, im),
+		}
+	}
+
+	/// Cube root, which is the real cube root for real numbers, and the principal cube root otherwise
+	pub fn cbrt(self) -> Self {
+		match self.im == 0.0 {
+			true => Self::new(self.re.cbrt(), 0.0),
+			false => (self.ln() / Self::new(3.0, 0.0)).exp(),
+		}
+	}
+
+	/// `self` raised to the integer power `n`, via repeated squaring
+	fn powi(self, n: i32) -> Self {
+		let mut result = Self::ONE;
+		let mut base = self;
+		let mut exponent = n.unsigned_abs();
+		while exponent > 0 {
+			if exponent % 2 == 1 {
+				result = result * base;
+			}
+			base = base * base;
+			exponent /= 2;
+		}
+
+		match n < 0 {
+			true => Self::ONE / result,
+			false => result,
+		}
+	}
+
+	/// Principal value of `self` raised to the power `exponent`
+	pub fn pow(self, exponent: Self) -> Self {
+		/// Largest integer exponent that is calculated via repeated squaring (which is exact for real numbers)
+		const MAX_INTEGER_EXPONENT: f64 = 1024.0;
+
+		if (exponent.im == 0.0)
+			&& (exponent.re.fract() == 0.0)
+			&& (exponent.re.abs() <= MAX_INTEGER_EXPONENT)
+		{
+			return self.powi(exponent.re as i32);
+		}
+
+		if self == Self::ZERO {
+			return match exponent.re > 0.0 {
+				true => Self::ZERO,
+				false => Self::new(f64::NAN, f64::NAN),
+			};
+		}
+
+		// Real powers of positive numbers are real
+		if (self.im == 0.0) && (self.re > 0.0) && (exponent.im == 0.0) {
+			return Self::new(self.re.powf(exponent.re), 0.0);
+		}
+
+		(exponent * self.ln()).exp()
+	}
+
+	pub fn sin(self) -> Self {
+		Self::new(
+			self.re.sin() * self.im.cosh(),
+			self.re.cos() * self.im.sinh(),
+		)
+	}
+
+	pub fn cos(self) -> Self {
+		Self::new(
+			self.re.cos() * self.im.cosh(),
+			-self.re.sin() * self.im.sinh(),
+		)
+	}
+
+	pub fn tan(self) -> Self { self.sin() / self.cos() }
+
+	pub fn sinh(self) -> Self {
+		Self::new(
+			self.re.sinh() * self.im.cos(),
+			self.re.cosh() * self.im.sin(),
+		)
+	}
+
+	pub fn cosh(self) -> Self {
+		Self::new(
+			self.re.cosh() * self.im.cos(),
+			self.re.sinh() * self.im.sin(),
+		)
+	}
+
+	pub fn tanh(self) -> Self { self.sinh() / self.cosh() }
+
+	/// Principal inverse sine, -i ln(iz + √(1 - z²))
+	pub fn asin(self) -> Self {
+		-Self::I * ((Self::I * self) + (Self::ONE - (self * self)).sqrt()).ln()
+	}
+
+	/// Principal inverse cosine, π/2 - asin(z)
+	pub fn acos(self) -> Self { Self::new(FRAC_PI_2, 0.0) - self.asin() }
+
+	/// Principal inverse tangent, (i/2) (ln(1 - iz) - ln(1 + iz))
+	pub fn atan(self) -> Self {
+		let iz = Self::I * self;
+		Self::new(0.0, 0.5) * ((Self::ONE - iz).ln() - (Self::ONE + iz).ln())
+	}
+
+	/// Principal inverse hyperbolic sine, ln(z + √(z² + 1))
+	pub fn asinh(self) -> Self { (self + ((self * self) + Self::ONE).sqrt()).ln() }
+
+	/// Principal inverse hyperbolic cosine, ln(z + √(z + 1) √(z - 1))
+	pub fn acosh(self) -> Self {
+		(self + ((self + Self::ONE).sqrt() * (self - Self::ONE).sqrt())).ln()
+	}
+
+	/// Principal inverse hyperbolic tangent, (ln(1 + z) - ln(1 - z)) / 2
+	pub fn atanh(self) -> Self {
+		Self::new(0.5, 0.0) * ((Self::ONE + self).ln() - (Self::ONE - self).ln())
+	}
+
+	/// z / |z|, or 0 if z is 0
+	pub fn signum(self) -> Self {
+		match self == Self::ZERO {
+			true => Self::ZERO,
+			false => self / Self::new(self.abs(), 0.0),
+		}
+	}
+
+	pub fn floor(self) -> Self { self.map(f64::floor) }
+
+	pub fn round(self) -> Self { self.map(f64::round) }
+
+	pub fn ceil(self) -> Self { self.map(f64::ceil) }
+
+	pub fn trunc(self) -> Self { self.map(f64::trunc) }
+
+	pub fn fract(self) -> Self { self.map(f64::fract) }
+
+	pub fn log2(self) -> Self { self.ln() / Self::new(LN_2, 0.0) }
+
+	pub fn log10(self) -> Self { self.ln() / Self::new(LN_10, 0.0) }
+}
+
+impl From<f64> for Complex {
+	fn from(re: f64) -> Self { Self::new(re, 0.0) }
+}
+
+impl Add for Complex {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self { Self::new(self.re + other.re, self.im + other.im) }
+}
+
+impl Sub for Complex {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self { Self::new(self.re - other.re, self.im - other.im) }
+}
+
+impl Mul for Complex {
+	type Output = Self;
+
+	fn mul(self, other: Self) -> Self {
+		// Real numbers are multiplied separately so that infinities don't produce NaNs from multiplying by 0
+		if (self.im == 0.0) && (other.im == 0.0) {
+			return Self::new(self.re * other.re, 0.0);
+		}
+
+		Self::new(
+			(self.re * other.re) - (self.im * other.im),
+			(self.re * other.im) + (self.im * other.re),
+		)
+	}
+}
+
+impl Div for Complex {
+	type Output = Self;
+
+	fn div(self, other: Self) -> Self {
+		if (self.im == 0.0) && (other.im == 0.0) {
+			return Self::new(self.re / other.re, 0.0);
+		}
+
+		let denominator = (other.re * other.re) + (other.im * other.im);
+		Self::new(
+			((self.re * other.re) + (self.im * other.im)) / denominator,
+			((self.im * other.re) - (self.re * other.im)) / denominator,
+		)
+	}
+}
+
+impl Neg for Complex {
+	type Output = Self;
+
+	fn neg(self) -> Self { Self::new(-self.re, -self.im) }
+}
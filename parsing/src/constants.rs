@@ -0,0 +1,75 @@
+// Also included by build.rs (to generate completions of the constants' names), so this can't depend on the rest of the crate
+
+/// Named constant that can be used in expressions
+#[allow(dead_code)]
+pub struct Constant {
+	/// Names the constant can be written as, the first being the one that's suggested
+	pub names: &'static [&'static str],
+	pub value: f64,
+
+	/// Description shown in the help
+	pub description: &'static str,
+}
+
+/// Constants that can be used in expressions, with physical constants being in SI units
+pub const CONSTANTS: [Constant; 10] = [
+	Constant {
+		names: &["e", "E"],
+		value: std::f64::consts::E,
+		description: "Euler's number",
+	},
+	Constant {
+		names: &["pi", "π"],
+		value: std::f64::consts::PI,
+		description: "π, the ratio of a circle's circumference to its diameter",
+	},
+	Constant {
+		names: &["tau", "τ"],
+		value: std::f64::consts::TAU,
+		description: "τ = 2π",
+	},
+	Constant {
+		names: &["phi", "φ"],
+		value: 1.618_033_988_749_895,
+		description: "φ, the golden ratio (1 + √5)/2",
+	},
+	Constant {
+		names: &["lightspeed"],
+		value: 299_792_458.0,
+		description: "The speed of light in m/s",
+	},
+	Constant {
+		names: &["gravity"],
+		value: 9.806_65,
+		description: "Standard gravity in m/s²",
+	},
+	Constant {
+		names: &["gravconst"],
+		value: 6.674_30e-11,
+		description: "The gravitational constant in m³/(kg s²)",
+	},
+	Constant {
+		names: &["planck"],
+		value: 6.626_070_15e-34,
+		description: "Planck's constant in J s",
+	},
+	Constant {
+		names: &["boltzmann"],
+		value: 1.380_649e-23,
+		description: "Boltzmann's constant in J/K",
+	},
+	Constant {
+		names: &["avogadro"],
+		value: 6.022_140_76e23,
+		description: "Avogadro's number in 1/mol",
+	},
+];
+
+/// Value of the constant called `name`
+#[allow(dead_code)]
+pub fn constant_value(name: &str) -> Option<f64> {
+	CONSTANTS
+		.iter()
+		.find(|constant| constant.names.contains(&name))
+		.map(|constant| constant.value)
+}
@@ -0,0 +1,1766 @@
+use crate::complex::Complex;
+use crate::constants::{constant_value, CONSTANTS};
+use crate::gamma::{complex_factorial, digamma, factorial, ln_gamma, trigamma};
+use crate::interval::Interval;
+use crate::special::{bessel_j, erf, erfc};
+use crate::SUPPORTED_FUNCTIONS;
+use std::collections::BTreeMap;
+use std::f64::consts::{LN_10, LN_2, PI};
+use std::fmt;
+use std::ops::{Add, Mul, Range};
+
+/// Binary operators, in the order of their precedence
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Operator {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Pow,
+}
+
+/// Variables that can be used in an [`Expression`]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Variable {
+	X,
+	Y,
+}
+
+/// Unit of the angles trigonometric functions take, and inverse trigonometric functions return
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AngleUnit {
+	#[default]
+	Radians,
+	Degrees,
+}
+
+impl fmt::Display for AngleUnit {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{:?}", self) }
+}
+
+/// Functions of a single argument that can be used in an [`Expression`], including ones without names (such as factorials, written as `x!`)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Function {
+	Abs,
+	Signum,
+	Sin,
+	Cos,
+	Tan,
+	Asin,
+	Acos,
+	Atan,
+	Sinh,
+	Cosh,
+	Tanh,
+	Asinh,
+	Acosh,
+	Atanh,
+	Floor,
+	Round,
+	Ceil,
+	Trunc,
+	Fract,
+	Exp,
+	Sqrt,
+	Cbrt,
+	Ln,
+	Log2,
+	Log10,
+	Factorial,
+	/// ψ(x + 1), the derivative of ln(x!)
+	Digamma,
+	/// ψ₁(x + 1), the derivative of ψ(x + 1)
+	Trigamma,
+	Erf,
+	Erfc,
+	/// Γ(x) = (x - 1)!
+	Gamma,
+	/// ln|Γ(x)|
+	Lgamma,
+}
+
+impl Function {
+	/// Looks up the function called `name`
+	fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
+			"abs" => Self::Abs,
+			"signum" => Self::Signum,
+			"sin" => Self::Sin,
+			"cos" => Self::Cos,
+			"tan" => Self::Tan,
+			"asin" => Self::Asin,
+			"acos" => Self::Acos,
+			"atan" => Self::Atan,
+			"sinh" => Self::Sinh,
+			"cosh" => Self::Cosh,
+			"tanh" => Self::Tanh,
+			"asinh" => Self::Asinh,
+			"acosh" => Self::Acosh,
+			"atanh" => Self::Atanh,
+			"floor" => Self::Floor,
+			"round" => Self::Round,
+			"ceil" => Self::Ceil,
+			"trunc" => Self::Trunc,
+			"fract" => Self::Fract,
+			"exp" => Self::Exp,
+			"sqrt" => Self::Sqrt,
+			"cbrt" => Self::Cbrt,
+			"ln" | "log" => Self::Ln,
+			"log2" => Self::Log2,
+			"log10" => Self::Log10,
+			"erf" => Self::Erf,
+			"erfc" => Self::Erfc,
+			"gamma" => Self::Gamma,
+			"lgamma" => Self::Lgamma,
+			_ => return None,
+		})
+	}
+
+	/// Name the function is written as, or its symbol if it doesn't have one (such as `!` for factorials)
+	pub(crate) const fn name(self) -> &'static str {
+		match self {
+			Self::Abs => "abs",
+			Self::Signum => "signum",
+			Self::Sin => "sin",
+			Self::Cos => "cos",
+			Self::Tan => "tan",
+			Self::Asin => "asin",
+			Self::Acos => "acos",
+			Self::Atan => "atan",
+			Self::Sinh => "sinh",
+			Self::Cosh => "cosh",
+			Self::Tanh => "tanh",
+			Self::Asinh => "asinh",
+			Self::Acosh => "acosh",
+			Self::Atanh => "atanh",
+			Self::Floor => "floor",
+			Self::Round => "round",
+			Self::Ceil => "ceil",
+			Self::Trunc => "trunc",
+			Self::Fract => "fract",
+			Self::Exp => "exp",
+			Self::Sqrt => "sqrt",
+			Self::Cbrt => "cbrt",
+			Self::Ln => "ln",
+			Self::Log2 => "log2",
+			Self::Log10 => "log10",
+			Self::Factorial => "!",
+			Self::Digamma => "ψ",
+			Self::Trigamma => "ψ₁",
+			Self::Erf => "erf",
+			Self::Erfc => "erfc",
+			Self::Gamma => "gamma",
+			Self::Lgamma => "lgamma",
+		}
+	}
+
+	/// Applies the function to the real number `x`
+	fn apply(self, x: f64) -> f64 {
+		match self {
+			Self::Abs => x.abs(),
+			Self::Signum => x.signum(),
+			Self::Sin => x.sin(),
+			Self::Cos => x.cos(),
+			Self::Tan => x.tan(),
+			Self::Asin => x.asin(),
+			Self::Acos => x.acos(),
+			Self::Atan => x.atan(),
+			Self::Sinh => x.sinh(),
+			Self::Cosh => x.cosh(),
+			Self::Tanh => x.tanh(),
+			Self::Asinh => x.asinh(),
+			Self::Acosh => x.acosh(),
+			Self::Atanh => x.atanh(),
+			Self::Floor => x.floor(),
+			Self::Round => x.round(),
+			Self::Ceil => x.ceil(),
+			Self::Trunc => x.trunc(),
+			Self::Fract => x.fract(),
+			Self::Exp => x.exp(),
+			Self::Sqrt => x.sqrt(),
+			Self::Cbrt => x.cbrt(),
+			Self::Ln => x.ln(),
+			Self::Log2 => x.log2(),
+			Self::Log10 => x.log10(),
+			Self::Factorial => factorial(x),
+			Self::Digamma => digamma(x + 1.0),
+			Self::Trigamma => trigamma(x + 1.0),
+			Self::Erf => erf(x),
+			Self::Erfc => erfc(x),
+			Self::Gamma => factorial(x - 1.0),
+			Self::Lgamma => ln_gamma(x).0,
+		}
+	}
+
+	/// Applies the function to the complex number `z`
+	fn apply_complex(self, z: Complex) -> Complex {
+		match self {
+			Self::Abs => Complex::from(z.abs()),
+			Self::Signum => z.signum(),
+			Self::Sin => z.sin(),
+			Self::Cos => z.cos(),
+			Self::Tan => z.tan(),
+			Self::Asin => z.asin(),
+			Self::Acos => z.acos(),
+			Self::Atan => z.atan(),
+			Self::Sinh => z.sinh(),
+			Self::Cosh => z.cosh(),
+			Self::Tanh => z.tanh(),
+			Self::Asinh => z.asinh(),
+			Self::Acosh => z.acosh(),
+			Self::Atanh => z.atanh(),
+			Self::Floor => z.floor(),
+			Self::Round => z.round(),
+			Self::Ceil => z.ceil(),
+			Self::Trunc => z.trunc(),
+			Self::Fract => z.fract(),
+			Self::Exp => z.exp(),
+			Self::Sqrt => z.sqrt(),
+			Self::Cbrt => z.cbrt(),
+			Self::Ln => z.ln(),
+			Self::Log2 => z.log2(),
+			Self::Log10 => z.log10(),
+			Self::Factorial => complex_factorial(z),
+			Self::Gamma => complex_factorial(z - Complex::ONE),
+			// The derivatives of factorials, error functions, and ln|Γ(x)| are only calculated for real numbers
+			Self::Digamma | Self::Trigamma | Self::Erf | Self::Erfc | Self::Lgamma => {
+				match z.im == 0.0 {
+					true => Complex::from(self.apply(z.re)),
+					false => Complex::new(f64::NAN, f64::NAN),
+				}
+			}
+		}
+	}
+
+	/// Bounds the values of the function over the real numbers in `x`
+	fn apply_interval(self, x: Interval) -> Interval {
+		if x.is_point() {
+			return Interval::from(self.apply(x.lo));
+		}
+
+		match self {
+			Self::Abs => x.abs(),
+			Self::Sin => x.sin(),
+			Self::Cos => x.cos(),
+			Self::Tan => x.tan(),
+			Self::Fract => x.fract(),
+			Self::Cosh => x.even(f64::cosh),
+			Self::Asin | Self::Acos | Self::Atanh => x.monotonic_unit(|x| self.apply(x)),
+			Self::Acosh => x.monotonic_from(1.0, f64::acosh),
+			Self::Sqrt | Self::Ln | Self::Log2 | Self::Log10 => {
+				x.monotonic_from(0.0, |x| self.apply(x))
+			}
+			Self::Signum
+			| Self::Atan
+			| Self::Sinh
+			| Self::Tanh
+			| Self::Asinh
+			| Self::Floor
+			| Self::Round
+			| Self::Ceil
+			| Self::Trunc
+			| Self::Exp
+			| Self::Cbrt
+			| Self::Erf
+			| Self::Erfc => x.monotonic(|x| self.apply(x)),
+			// Factorials and their relatives have poles at the negative integers, so they're only bounded at single numbers
+			Self::Factorial | Self::Digamma | Self::Trigamma | Self::Gamma | Self::Lgamma => {
+				match x.is_empty() {
+					true => Interval::EMPTY,
+					false => Interval::ENTIRE,
+				}
+			}
+		}
+	}
+
+	/// Derivative of the function, in terms of its argument `u`
+	fn derivative(self, u: &Expression) -> Expression {
+		use Expression as Ex;
+		let u = u.clone();
+		let one_over = |denominator: Expression| Ex::div(Ex::Number(1.0), denominator);
+		match self {
+			Self::Abs => Ex::Function(Self::Signum, Box::new(u)),
+			Self::Signum | Self::Floor | Self::Round | Self::Ceil | Self::Trunc => Ex::Number(0.0),
+			Self::Fract => Ex::Number(1.0),
+			Self::Sin => Ex::Function(Self::Cos, Box::new(u)),
+			Self::Cos => Ex::neg(Ex::Function(Self::Sin, Box::new(u))),
+			Self::Tan => one_over(Ex::pow(
+				Ex::Function(Self::Cos, Box::new(u)),
+				Ex::Number(2.0),
+			)),
+			Self::Asin => one_over(Ex::Function(
+				Self::Sqrt,
+				Box::new(Ex::sub(Ex::Number(1.0), Ex::pow(u, Ex::Number(2.0)))),
+			)),
+			Self::Acos => Ex::neg(one_over(Ex::Function(
+				Self::Sqrt,
+				Box::new(Ex::sub(Ex::Number(1.0), Ex::pow(u, Ex::Number(2.0)))),
+			))),
+			Self::Atan => one_over(Ex::add(Ex::Number(1.0), Ex::pow(u, Ex::Number(2.0)))),
+			Self::Sinh => Ex::Function(Self::Cosh, Box::new(u)),
+			Self::Cosh => Ex::Function(Self::Sinh, Box::new(u)),
+			Self::Tanh => one_over(Ex::pow(
+				Ex::Function(Self::Cosh, Box::new(u)),
+				Ex::Number(2.0),
+			)),
+			Self::Asinh => one_over(Ex::Function(
+				Self::Sqrt,
+				Box::new(Ex::add(Ex::pow(u, Ex::Number(2.0)), Ex::Number(1.0))),
+			)),
+			Self::Acosh => one_over(Ex::Function(
+				Self::Sqrt,
+				Box::new(Ex::sub(Ex::pow(u, Ex::Number(2.0)), Ex::Number(1.0))),
+			)),
+			Self::Atanh => one_over(Ex::sub(Ex::Number(1.0), Ex::pow(u, Ex::Number(2.0)))),
+			Self::Exp => Ex::Function(Self::Exp, Box::new(u)),
+			Self::Sqrt => one_over(Ex::mul(
+				Ex::Number(2.0),
+				Ex::Function(Self::Sqrt, Box::new(u)),
+			)),
+			Self::Cbrt => one_over(Ex::mul(
+				Ex::Number(3.0),
+				Ex::pow(Ex::Function(Self::Cbrt, Box::new(u)), Ex::Number(2.0)),
+			)),
+			Self::Ln => one_over(u),
+			Self::Log2 => one_over(Ex::mul(u, Ex::Number(LN_2))),
+			Self::Log10 => one_over(Ex::mul(u, Ex::Number(LN_10))),
+			// (x!)' = x! ψ(x + 1)
+			Self::Factorial => Ex::mul(
+				Ex::Function(Self::Factorial, Box::new(u.clone())),
+				Ex::Function(Self::Digamma, Box::new(u)),
+			),
+			Self::Digamma => Ex::Function(Self::Trigamma, Box::new(u)),
+			// Derivatives of factorials beyond the second aren't supported
+			Self::Trigamma => Ex::Number(f64::NAN),
+			// erf(x)' = 2/√π e^(-x²)
+			Self::Erf | Self::Erfc => {
+				let derivative = Ex::mul(
+					Ex::Number(2.0 / PI.sqrt()),
+					Ex::Function(Self::Exp, Box::new(Ex::neg(Ex::pow(u, Ex::Number(2.0))))),
+				);
+				match self {
+					Self::Erf => derivative,
+					_ => Ex::neg(derivative),
+				}
+			}
+			// Γ(x)' = Γ(x) ψ(x), where ψ(x) is written as Digamma(x - 1)
+			Self::Gamma => Ex::mul(
+				Ex::Function(Self::Gamma, Box::new(u.clone())),
+				Ex::Function(Self::Digamma, Box::new(Ex::sub(u, Ex::Number(1.0)))),
+			),
+			Self::Lgamma => Ex::Function(Self::Digamma, Box::new(Ex::sub(u, Ex::Number(1.0)))),
+		}
+	}
+}
+
+/// Functions of several arguments that can be used in an [`Expression`], including ones written as operators (such as modulo, written as `x % 3`)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MultiFunction {
+	Min,
+	Max,
+	/// clamp(x, lo, hi)
+	Clamp,
+	/// Modulo with the sign of the divisor, such that mod(-1, 3) = 2
+	Mod,
+	/// besselj(n, x), the Bessel function of the first kind of integer order n
+	BesselJ,
+	/// atan2(y, x), the angle of the point (x, y)
+	Atan2,
+	/// hypot(x, y) = √(x² + y²)
+	Hypot,
+}
+
+impl MultiFunction {
+	/// Looks up the function called `name`
+	fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
+			"min" => Self::Min,
+			"max" => Self::Max,
+			"clamp" => Self::Clamp,
+			"mod" => Self::Mod,
+			"besselj" => Self::BesselJ,
+			"atan2" => Self::Atan2,
+			"hypot" => Self::Hypot,
+			_ => return None,
+		})
+	}
+
+	pub(crate) const fn name(self) -> &'static str {
+		match self {
+			Self::Min => "min",
+			Self::Max => "max",
+			Self::Clamp => "clamp",
+			Self::Mod => "mod",
+			Self::BesselJ => "besselj",
+			Self::Atan2 => "atan2",
+			Self::Hypot => "hypot",
+		}
+	}
+
+	/// Number of arguments the function takes
+	const fn arity(self) -> usize {
+		match self {
+			Self::Min | Self::Max | Self::Mod | Self::BesselJ | Self::Atan2 | Self::Hypot => 2,
+			Self::Clamp => 3,
+		}
+	}
+
+	/// Applies the function to the real numbers `args`
+	fn apply(self, args: &[f64]) -> f64 {
+		match self {
+			Self::Min => args[0].min(args[1]),
+			Self::Max => args[0].max(args[1]),
+			Self::Clamp => args[0].max(args[1]).min(args[2]),
+			Self::Mod => args[0] - (args[1] * (args[0] / args[1]).floor()),
+			Self::BesselJ => bessel_j(args[0], args[1]),
+			Self::Atan2 => args[0].atan2(args[1]),
+			Self::Hypot => args[0].hypot(args[1]),
+		}
+	}
+
+	/// Applies the function to the complex numbers `args`
+	fn apply_complex(self, args: &[Complex]) -> Complex {
+		match self {
+			Self::Mod => args[0] - (args[1] * (args[0] / args[1]).floor()),
+			// Complex numbers aren't ordered, so comparisons are only made between real numbers (and the other functions are only calculated for them)
+			_ => match args.iter().all(|z| z.im == 0.0) {
+				true => Complex::from(self.apply(&args.iter().map(|z| z.re).collect::<Vec<f64>>())),
+				false => Complex::new(f64::NAN, f64::NAN),
+			},
+		}
+	}
+
+	/// Bounds the values of the function over the real numbers in `args`
+	fn apply_interval(self, args: &[Interval]) -> Interval {
+		if args.iter().any(|arg| arg.is_empty()) {
+			return Interval::EMPTY;
+		}
+
+		if args.iter().all(|arg| arg.is_point()) {
+			return Interval::from(
+				self.apply(&args.iter().map(|arg| arg.lo).collect::<Vec<f64>>()),
+			);
+		}
+
+		match self {
+			Self::Min => args[0].min(args[1]),
+			Self::Max => args[0].max(args[1]),
+			Self::Clamp => args[0].max(args[1]).min(args[2]),
+			Self::Mod => {
+				let (dividend, divisor) = (args[0], args[1]);
+				let quotient = (dividend.lo / divisor.lo).floor();
+				// Within a period of the divisor, the remainder is the dividend shifted down
+				match divisor.is_point() && (quotient == (dividend.hi / divisor.lo).floor()) {
+					true => dividend - Interval::from(divisor.lo * quotient),
+					false => Interval::new(divisor.lo.min(0.0), divisor.hi.max(0.0)),
+				}
+			}
+			// |J_n(x)| ≤ 1 for integer orders n
+			Self::BesselJ => Interval::new(-1.0, 1.0),
+			Self::Atan2 => Interval::new(-PI, PI),
+			Self::Hypot => {
+				let square = |arg: Interval| arg.pow(Interval::from(2.0));
+				(square(args[0]) + square(args[1])).monotonic_from(0.0, f64::sqrt)
+			}
+		}
+	}
+
+	/// Derivative of the function with respect to x, in terms of its arguments `args`
+	fn derivative(self, args: &[Expression]) -> Expression {
+		use Expression as Ex;
+		let derivatives: Vec<Expression> = args.iter().map(Expression::derivative).collect();
+		let signum = |u: Expression| Ex::Function(Function::Signum, Box::new(u));
+		match self {
+			// min(a, b) = (a + b - |a - b|) / 2, and max(a, b) = (a + b + |a - b|) / 2
+			Self::Min | Self::Max => {
+				let sum = Ex::add(derivatives[0].clone(), derivatives[1].clone());
+				let difference = Ex::mul(
+					signum(Ex::sub(args[0].clone(), args[1].clone())),
+					Ex::sub(derivatives[0].clone(), derivatives[1].clone()),
+				);
+				Ex::mul(
+					Ex::Number(0.5),
+					match self {
+						Self::Min => Ex::sub(sum, difference),
+						_ => Ex::add(sum, difference),
+					},
+				)
+			}
+			// clamp(x, lo, hi) = min(max(x, lo), hi)
+			Self::Clamp => Self::Min.derivative(&[
+				Ex::MultiFunction(Self::Max, vec![args[0].clone(), args[1].clone()]),
+				args[2].clone(),
+			]),
+			// besselj(n, x)' = (besselj(n - 1, x) - besselj(n + 1, x)) / 2, where n is an integer
+			Self::BesselJ => {
+				let bessel = |order: f64| {
+					Ex::MultiFunction(
+						Self::BesselJ,
+						vec![Ex::add(args[0].clone(), Ex::Number(order)), args[1].clone()],
+					)
+				};
+				Ex::mul(
+					Ex::mul(Ex::Number(0.5), Ex::sub(bessel(-1.0), bessel(1.0))),
+					derivatives[1].clone(),
+				)
+			}
+			// atan2(a, b)' = (b a' - a b') / (a² + b²)
+			Self::Atan2 => Ex::div(
+				Ex::sub(
+					Ex::mul(args[1].clone(), derivatives[0].clone()),
+					Ex::mul(args[0].clone(), derivatives[1].clone()),
+				),
+				Ex::add(
+					Ex::pow(args[0].clone(), Ex::Number(2.0)),
+					Ex::pow(args[1].clone(), Ex::Number(2.0)),
+				),
+			),
+			// hypot(a, b)' = (a a' + b b') / hypot(a, b)
+			Self::Hypot => Ex::div(
+				Ex::add(
+					Ex::mul(args[0].clone(), derivatives[0].clone()),
+					Ex::mul(args[1].clone(), derivatives[1].clone()),
+				),
+				Ex::MultiFunction(Self::Hypot, args.to_vec()),
+			),
+			// mod(a, b) = a - b floor(a / b)
+			Self::Mod => Ex::sub(
+				derivatives[0].clone(),
+				Ex::mul(
+					derivatives[1].clone(),
+					Ex::Function(
+						Function::Floor,
+						Box::new(Ex::div(args[0].clone(), args[1].clone())),
+					),
+				),
+			),
+		}
+	}
+}
+
+/// Most terms a [`Series`] can have before it's treated as undefined, so graphs stay responsive
+const MAX_SERIES_TERMS: f64 = 100_000.0;
+
+/// Sums and products over an index, written like `sum(n, 1, 10, 1/n)`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Series {
+	/// sum(n, a, b, f(n)) = f(a) + f(a + 1) + ... + f(b)
+	Sum,
+	/// prod(n, a, b, f(n)) = f(a) * f(a + 1) * ... * f(b)
+	Product,
+}
+
+impl Series {
+	/// Looks up the series called `name`
+	fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
+			"sum" => Self::Sum,
+			"prod" => Self::Product,
+			_ => return None,
+		})
+	}
+
+	pub(crate) const fn name(self) -> &'static str {
+		match self {
+			Self::Sum => "sum",
+			Self::Product => "prod",
+		}
+	}
+
+	/// Adds or multiplies `term(k)` for the integers k from `from` to `to`, being NaN if there are too many terms.
+	/// Empty sums are 0 and empty products are 1.
+	fn apply<T>(self, from: f64, to: f64, term: impl FnMut(f64) -> T) -> T
+	where
+		T: From<f64> + Add<Output = T> + Mul<Output = T>,
+	{
+		if !(from.is_finite() && to.is_finite()) || ((to - from) >= MAX_SERIES_TERMS) {
+			return T::from(f64::NAN);
+		}
+
+		let terms = (0..((to - from + 1.0).max(0.0) as usize))
+			.map(|k| from + k as f64)
+			.map(term);
+		match self {
+			Self::Sum => terms.fold(T::from(0.0), |sum, term| sum + term),
+			Self::Product => terms.fold(T::from(1.0), |product, term| product * term),
+		}
+	}
+}
+
+/// Error from parsing an [`Expression`], along with the range of bytes of the input it was caused by
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseError {
+	pub message: String,
+	pub span: Range<usize>,
+}
+
+impl ParseError {
+	fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+		Self {
+			message: message.into(),
+			span,
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Error: {} at position {}", self.message, self.span.start)
+	}
+}
+
+/// User functions (such as `f` defined by `f(x) = x^2 + 1`) which can be called by expressions parsed using them, along with how their variables are matched
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Definitions {
+	/// Each function's body (a function of x), or why it couldn't be defined
+	functions: BTreeMap<String, Result<Expression, String>>,
+
+	/// If variables are only matched when written in the same case (so `X` is a parameter rather than x).
+	/// Names of functions and constants are matched regardless of case.
+	case_sensitive: bool,
+}
+
+impl Definitions {
+	pub const EMPTY: Self = Self {
+		functions: BTreeMap::new(),
+		case_sensitive: false,
+	};
+
+	/// Defines the functions `sources` (mapping names to bodies), which can call each other.
+	/// Functions are parsed after the ones they call, and ones defined in terms of themselves (directly or through other functions) are left undefined.
+	pub fn new(sources: &BTreeMap<String, String>, case_sensitive: bool) -> Self {
+		let mut definitions = Self {
+			case_sensitive,
+			..Self::EMPTY
+		};
+		sources
+			.keys()
+			.for_each(|name| definitions.define(name, sources, &mut Vec::new()));
+		definitions
+	}
+
+	/// Defines `name` after the functions it calls, `path` being the functions waiting on its definition
+	fn define(&mut self, name: &str, sources: &BTreeMap<String, String>, path: &mut Vec<String>) {
+		if self.functions.contains_key(name) {
+			return;
+		}
+
+		if let Some(start) = path.iter().position(|waiting| waiting == name) {
+			let cycle = path[start..]
+				.iter()
+				.map(String::as_str)
+				.chain([name])
+				.collect::<Vec<&str>>()
+				.join(" → ");
+			path[start..].iter().for_each(|function| {
+				self.functions.insert(
+					function.clone(),
+					Err(format!(
+						"'{}' is defined in terms of itself ({})",
+						function, cycle
+					)),
+				);
+			});
+			return;
+		}
+
+		let body = &sources[name];
+		path.push(name.to_owned());
+		Self::calls(body)
+			.iter()
+			.filter(|called| sources.contains_key(*called))
+			.for_each(|called| self.define(called, sources, path));
+		path.pop();
+
+		// Functions in a cycle were given an error while defining the functions they call
+		if self.functions.contains_key(name) {
+			return;
+		}
+
+		let function = Expression::parse_using(body, &['x'], self)
+			.map_err(|error| format!("'{}' has an error: {}", name, error.message));
+		self.functions.insert(name.to_owned(), function);
+	}
+
+	/// If variables are only matched when written in the same case
+	pub const fn case_sensitive(&self) -> bool { self.case_sensitive }
+
+	/// Names of the functions that `func_str` calls which aren't built in, in the order they're called
+	pub fn calls(func_str: &str) -> Vec<String> {
+		tokenize(func_str, false)
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(|(token, _)| match token {
+				Token::Unknown(name) => Some(name),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Splits the definition `func_str` (such as `f(x) = x^2 + 1`) into the function's name and the index its body starts at, returning `None` if it isn't one
+	pub fn split(func_str: &str) -> Option<(String, usize)> {
+		let equals = func_str.find('=')?;
+		let tokens: Vec<Token> = tokenize(&func_str[..equals], false)
+			.ok()?
+			.into_iter()
+			.map(|(token, _)| token)
+			.collect();
+		match tokens.as_slice() {
+			[Token::Unknown(name), Token::Symbol('('), Token::Name(variable), Token::Symbol(')')]
+				if variable == "x" =>
+			{
+				Some((name.clone(), equals + 1))
+			}
+			_ => None,
+		}
+	}
+
+	/// The body of the function `name`, or why it couldn't be defined
+	pub fn get(&self, name: &str) -> Option<&Result<Expression, String>> {
+		self.functions.get(name)
+	}
+}
+
+/// Parsed mathematical expression, which can be evaluated over real or complex numbers
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expression {
+	Number(f64),
+	Variable(Variable),
+	ImaginaryUnit,
+
+	/// Free parameter (such as `a` in `a*sin(x)`), which is replaced by its value with [`Expression::with_parameters`]
+	Parameter(String),
+	Negate(Box<Expression>),
+	Binary(Operator, Box<Expression>, Box<Expression>),
+	Function(Function, Box<Expression>),
+	MultiFunction(MultiFunction, Vec<Expression>),
+
+	/// Sum or product of the body over integer values of the index (a parameter) between the floored bounds, with the arguments being the bounds and then the body
+	Series(Series, String, Vec<Expression>),
+}
+
+impl Expression {
+	/// Parses `func_str`, where juxtaposed operands (such as `2x`) are multiplied.
+	/// `variables` are the variables allowed to be used, out of 'x', 'y', and 'i' (the imaginary unit).
+	pub fn parse(func_str: &str, variables: &[char]) -> Result<Self, ParseError> {
+		Self::parse_using(func_str, variables, &Definitions::EMPTY)
+	}
+
+	/// Parses `func_str` like [`Expression::parse`], where the user functions in `definitions` can be called
+	pub fn parse_using(
+		func_str: &str, variables: &[char], definitions: &Definitions,
+	) -> Result<Self, ParseError> {
+		let mut parser = Parser::new(func_str, variables, definitions)?;
+		let expression = parser.expression()?;
+		match parser.peek() {
+			None => Ok(expression),
+			Some(_) => Err(parser.unexpected()),
+		}
+	}
+
+	/// Parses the equation `func_str` (which contains a single '='), returning `lhs - rhs`.
+	/// `variables` are the variables allowed to be used, out of 'x', 'y', and 'i' (the imaginary unit).
+	pub fn parse_equation(func_str: &str, variables: &[char]) -> Result<Self, ParseError> {
+		Self::parse_equation_using(func_str, variables, &Definitions::EMPTY)
+	}
+
+	/// Parses the equation `func_str` like [`Expression::parse_equation`], where the user functions in `definitions` can be called
+	pub fn parse_equation_using(
+		func_str: &str, variables: &[char], definitions: &Definitions,
+	) -> Result<Self, ParseError> {
+		let mut parser = Parser::new(func_str, variables, definitions)?;
+		let lhs = parser.expression()?;
+		if !parser.eat('=') {
+			return Err(match parser.peek() {
+				None => ParseError::new("equations need an '='", 0..func_str.len()),
+				Some(_) => parser.unexpected(),
+			});
+		}
+
+		let rhs = parser.expression()?;
+		match parser.peek() {
+			None => Ok(Self::Binary(Operator::Sub, Box::new(lhs), Box::new(rhs))),
+			Some(_) => Err(parser.unexpected()),
+		}
+	}
+
+	/// Evaluates the expression at (`x`, `y`). The imaginary unit isn't a real number, so expressions using it evaluate to NaN.
+	pub fn eval(&self, x: f64, y: f64) -> f64 { self.eval_in(x, y, &mut Vec::new()) }
+
+	/// Evaluates the expression at (`x`, `y`), where `indices` are the values of the indices of the series being evaluated
+	fn eval_in<'a>(&'a self, x: f64, y: f64, indices: &mut Vec<(&'a str, f64)>) -> f64 {
+		match self {
+			Self::Number(value) => *value,
+			Self::Variable(Variable::X) => x,
+			Self::Variable(Variable::Y) => y,
+			Self::ImaginaryUnit => f64::NAN,
+			Self::Parameter(name) => index_value(indices, name),
+			Self::Negate(inner) => -inner.eval_in(x, y, indices),
+			Self::Binary(operator, lhs, rhs) => {
+				let (lhs, rhs) = (lhs.eval_in(x, y, indices), rhs.eval_in(x, y, indices));
+				match operator {
+					Operator::Add => lhs + rhs,
+					Operator::Sub => lhs - rhs,
+					Operator::Mul => lhs * rhs,
+					Operator::Div => lhs / rhs,
+					Operator::Pow => lhs.powf(rhs),
+				}
+			}
+			Self::Function(function, argument) => function.apply(argument.eval_in(x, y, indices)),
+			Self::MultiFunction(function, args) => function.apply(
+				&args
+					.iter()
+					.map(|argument| argument.eval_in(x, y, indices))
+					.collect::<Vec<f64>>(),
+			),
+			Self::Series(series, index, args) => {
+				let from = args[0].eval_in(x, y, indices).floor();
+				let to = args[1].eval_in(x, y, indices).floor();
+				series.apply(from, to, |k| {
+					indices.push((index, k));
+					let term = args[2].eval_in(x, y, indices);
+					indices.pop();
+					term
+				})
+			}
+		}
+	}
+
+	/// Evaluates the expression with x = `z`
+	pub fn eval_complex(&self, z: Complex) -> Complex { self.eval_complex_in(z, &mut Vec::new()) }
+
+	/// Evaluates the expression with x = `z`, where `indices` are the values of the indices of the series being evaluated
+	fn eval_complex_in<'a>(&'a self, z: Complex, indices: &mut Vec<(&'a str, f64)>) -> Complex {
+		match self {
+			Self::Number(value) => Complex::from(*value),
+			Self::Variable(Variable::X) => z,
+			Self::Variable(Variable::Y) => Complex::new(f64::NAN, f64::NAN),
+			Self::ImaginaryUnit => Complex::I,
+			Self::Parameter(name) => match index_value(indices, name) {
+				value if value.is_nan() => Complex::new(f64::NAN, f64::NAN),
+				value => Complex::from(value),
+			},
+			Self::Negate(inner) => -inner.eval_complex_in(z, indices),
+			Self::Binary(operator, lhs, rhs) => {
+				let (lhs, rhs) = (
+					lhs.eval_complex_in(z, indices),
+					rhs.eval_complex_in(z, indices),
+				);
+				match operator {
+					Operator::Add => lhs + rhs,
+					Operator::Sub => lhs - rhs,
+					Operator::Mul => lhs * rhs,
+					Operator::Div => lhs / rhs,
+					Operator::Pow => lhs.pow(rhs),
+				}
+			}
+			Self::Function(function, argument) => {
+				function.apply_complex(argument.eval_complex_in(z, indices))
+			}
+			Self::MultiFunction(function, args) => function.apply_complex(
+				&args
+					.iter()
+					.map(|argument| argument.eval_complex_in(z, indices))
+					.collect::<Vec<Complex>>(),
+			),
+			// Bounds are rounded down to integers, so only their real parts are used
+			Self::Series(series, index, args) => {
+				let from = args[0].eval_complex_in(z, indices).re.floor();
+				let to = args[1].eval_complex_in(z, indices).re.floor();
+				series.apply(from, to, |k| {
+					indices.push((index, k));
+					let term = args[2].eval_complex_in(z, indices);
+					indices.pop();
+					term
+				})
+			}
+		}
+	}
+
+	/// Bounds the values of the expression for x within `x`, so that wherever it's defined its value is within the result (up to rounding).
+	/// Like [`Expression::eval`], expressions using y or the imaginary unit have no real values.
+	pub fn eval_interval(&self, x: Interval) -> Interval {
+		self.eval_interval_in(x, &mut Vec::new())
+	}
+
+	/// Bounds the values of the expression for x within `x`, where `indices` are the values of the indices of the series being evaluated
+	fn eval_interval_in<'a>(&'a self, x: Interval, indices: &mut Vec<(&'a str, f64)>) -> Interval {
+		match self {
+			Self::Number(value) => Interval::from(*value),
+			Self::Variable(Variable::X) => x,
+			Self::Variable(Variable::Y) | Self::ImaginaryUnit => Interval::EMPTY,
+			Self::Parameter(name) => Interval::from(index_value(indices, name)),
+			Self::Negate(inner) => -inner.eval_interval_in(x, indices),
+			Self::Binary(operator, lhs, rhs) => {
+				let (lhs, rhs) = (
+					lhs.eval_interval_in(x, indices),
+					rhs.eval_interval_in(x, indices),
+				);
+				match operator {
+					Operator::Add => lhs + rhs,
+					Operator::Sub => lhs - rhs,
+					Operator::Mul => lhs * rhs,
+					Operator::Div => lhs / rhs,
+					Operator::Pow => lhs.pow(rhs),
+				}
+			}
+			Self::Function(function, argument) => {
+				function.apply_interval(argument.eval_interval_in(x, indices))
+			}
+			Self::MultiFunction(function, args) => function.apply_interval(
+				&args
+					.iter()
+					.map(|argument| argument.eval_interval_in(x, indices))
+					.collect::<Vec<Interval>>(),
+			),
+			// Series are only bounded where the number of terms they have doesn't change
+			Self::Series(series, index, args) => {
+				let from = args[0].eval_interval_in(x, indices);
+				let to = args[1].eval_interval_in(x, indices);
+				match (from.lo.floor() == from.hi.floor()) && (to.lo.floor() == to.hi.floor()) {
+					true => series.apply(from.lo.floor(), to.lo.floor(), |k| {
+						indices.push((index, k));
+						let term = args[2].eval_interval_in(x, indices);
+						indices.pop();
+						term
+					}),
+					false if from.is_empty() | to.is_empty() => Interval::EMPTY,
+					false => Interval::ENTIRE,
+				}
+			}
+		}
+	}
+
+	/// If `variable` is used anywhere in the expression
+	pub fn uses(&self, variable: Variable) -> bool {
+		match self {
+			Self::Variable(used) => *used == variable,
+			Self::Number(_) | Self::ImaginaryUnit | Self::Parameter(_) => false,
+			Self::Negate(inner) | Self::Function(_, inner) => inner.uses(variable),
+			Self::Binary(_, lhs, rhs) => lhs.uses(variable) || rhs.uses(variable),
+			Self::MultiFunction(_, args) | Self::Series(_, _, args) => {
+				args.iter().any(|argument| argument.uses(variable))
+			}
+		}
+	}
+
+	/// Converts the expression's trigonometric functions to use `angle_unit`, which they're parsed as using radians
+	pub fn with_angle_unit(self, angle_unit: AngleUnit) -> Self {
+		match angle_unit {
+			AngleUnit::Radians => self,
+			AngleUnit::Degrees => self.in_degrees(),
+		}
+	}
+
+	/// Scales the arguments of trigonometric functions and the results of inverse trigonometric functions to be in degrees
+	fn in_degrees(self) -> Self {
+		match self {
+			Self::Negate(inner) => Self::Negate(Box::new(inner.in_degrees())),
+			Self::Binary(operator, lhs, rhs) => Self::Binary(
+				operator,
+				Box::new(lhs.in_degrees()),
+				Box::new(rhs.in_degrees()),
+			),
+			Self::Function(function, argument) => {
+				let argument = argument.in_degrees();
+				match function {
+					Function::Sin | Function::Cos | Function::Tan => Self::Function(
+						function,
+						Box::new(Self::mul(argument, Self::Number(PI / 180.0))),
+					),
+					Function::Asin | Function::Acos | Function::Atan => Self::mul(
+						Self::Function(function, Box::new(argument)),
+						Self::Number(180.0 / PI),
+					),
+					_ => Self::Function(function, Box::new(argument)),
+				}
+			}
+			Self::MultiFunction(function, args) => {
+				let function_in_degrees =
+					Self::MultiFunction(function, args.into_iter().map(Self::in_degrees).collect());
+				match function {
+					MultiFunction::Atan2 => {
+						Self::mul(function_in_degrees, Self::Number(180.0 / PI))
+					}
+					_ => function_in_degrees,
+				}
+			}
+			Self::Series(series, index, args) => Self::Series(
+				series,
+				index,
+				args.into_iter().map(Self::in_degrees).collect(),
+			),
+			Self::Number(_) | Self::Variable(_) | Self::ImaginaryUnit | Self::Parameter(_) => self,
+		}
+	}
+
+	/// Names of the parameters used in the expression, sorted and without duplicates
+	pub fn parameters(&self) -> Vec<String> {
+		let mut parameters = Vec::new();
+		self.collect_parameters(&mut parameters);
+		parameters.sort();
+		parameters.dedup();
+		parameters
+	}
+
+	fn collect_parameters(&self, parameters: &mut Vec<String>) {
+		match self {
+			Self::Parameter(name) => parameters.push(name.clone()),
+			Self::Number(_) | Self::Variable(_) | Self::ImaginaryUnit => {}
+			Self::Negate(inner) | Self::Function(_, inner) => inner.collect_parameters(parameters),
+			Self::Binary(_, lhs, rhs) => {
+				lhs.collect_parameters(parameters);
+				rhs.collect_parameters(parameters);
+			}
+			Self::MultiFunction(_, args) => args
+				.iter()
+				.for_each(|argument| argument.collect_parameters(parameters)),
+			// The index is only a parameter outside of the series
+			Self::Series(_, index, args) => {
+				args[0].collect_parameters(parameters);
+				args[1].collect_parameters(parameters);
+				let mut body_parameters = Vec::new();
+				args[2].collect_parameters(&mut body_parameters);
+				parameters.extend(body_parameters.into_iter().filter(|name| name != index));
+			}
+		}
+	}
+
+	/// Replaces parameters with their values in `values`, parameters without values being NaN
+	pub fn with_parameters(self, values: &BTreeMap<String, f64>) -> Self {
+		self.bind_parameters(values, &mut Vec::new())
+	}
+
+	/// Replaces parameters with their values in `values`, other than the indices of the series being replaced in (`indices`)
+	fn bind_parameters(self, values: &BTreeMap<String, f64>, indices: &mut Vec<String>) -> Self {
+		match self {
+			Self::Parameter(ref name) if indices.contains(name) => self,
+			Self::Parameter(name) => Self::Number(values.get(&name).copied().unwrap_or(f64::NAN)),
+			Self::Negate(inner) => Self::Negate(Box::new(inner.bind_parameters(values, indices))),
+			Self::Binary(operator, lhs, rhs) => Self::Binary(
+				operator,
+				Box::new(lhs.bind_parameters(values, indices)),
+				Box::new(rhs.bind_parameters(values, indices)),
+			),
+			Self::Function(function, argument) => Self::Function(
+				function,
+				Box::new(argument.bind_parameters(values, indices)),
+			),
+			Self::MultiFunction(function, args) => Self::MultiFunction(
+				function,
+				args.into_iter()
+					.map(|argument| argument.bind_parameters(values, indices))
+					.collect(),
+			),
+			Self::Series(series, index, args) => {
+				let [from, to, body]: [Self; 3] = args.try_into().unwrap();
+				let (from, to) = (
+					from.bind_parameters(values, indices),
+					to.bind_parameters(values, indices),
+				);
+				indices.push(index.clone());
+				let body = body.bind_parameters(values, indices);
+				indices.pop();
+				Self::Series(series, index, vec![from, to, body])
+			}
+			Self::Number(_) | Self::Variable(_) | Self::ImaginaryUnit => self,
+		}
+	}
+
+	/// Replaces x with `argument`, such as when calling a user function
+	fn substitute_x(self, argument: &Self) -> Self {
+		match self {
+			Self::Variable(Variable::X) => argument.clone(),
+			Self::Negate(inner) => Self::Negate(Box::new(inner.substitute_x(argument))),
+			Self::Binary(operator, lhs, rhs) => Self::Binary(
+				operator,
+				Box::new(lhs.substitute_x(argument)),
+				Box::new(rhs.substitute_x(argument)),
+			),
+			Self::Function(function, inner) => {
+				Self::Function(function, Box::new(inner.substitute_x(argument)))
+			}
+			Self::MultiFunction(function, args) => Self::MultiFunction(
+				function,
+				args.into_iter()
+					.map(|inner| inner.substitute_x(argument))
+					.collect(),
+			),
+			Self::Series(series, index, args) => Self::Series(
+				series,
+				index,
+				args.into_iter()
+					.map(|inner| inner.substitute_x(argument))
+					.collect(),
+			),
+			Self::Number(_) | Self::Variable(_) | Self::ImaginaryUnit | Self::Parameter(_) => self,
+		}
+	}
+
+	/// Symbolic derivative of the expression with respect to x (y is held constant)
+	pub fn derivative(&self) -> Self {
+		match self {
+			Self::Number(_)
+			| Self::ImaginaryUnit
+			| Self::Parameter(_)
+			| Self::Variable(Variable::Y) => Self::Number(0.0),
+			Self::Variable(Variable::X) => Self::Number(1.0),
+			Self::Negate(inner) => Self::neg(inner.derivative()),
+			Self::Binary(operator, lhs, rhs) => {
+				let (lhs, rhs) = (lhs.as_ref().clone(), rhs.as_ref().clone());
+				let (lhs_derivative, rhs_derivative) = (lhs.derivative(), rhs.derivative());
+				match operator {
+					Operator::Add => Self::add(lhs_derivative, rhs_derivative),
+					Operator::Sub => Self::sub(lhs_derivative, rhs_derivative),
+					Operator::Mul => Self::add(
+						Self::mul(lhs_derivative, rhs.clone()),
+						Self::mul(lhs, rhs_derivative),
+					),
+					Operator::Div => Self::div(
+						Self::sub(
+							Self::mul(lhs_derivative, rhs.clone()),
+							Self::mul(lhs, rhs_derivative),
+						),
+						Self::pow(rhs, Self::Number(2.0)),
+					),
+					// Power rule, for constant exponents
+					Operator::Pow if !rhs.uses(Variable::X) => Self::mul(
+						Self::mul(
+							rhs.clone(),
+							Self::pow(lhs, Self::sub(rhs, Self::Number(1.0))),
+						),
+						lhs_derivative,
+					),
+					// (u^v)' = u^v (v' ln(u) + v u' / u)
+					Operator::Pow => Self::mul(
+						Self::pow(lhs.clone(), rhs.clone()),
+						Self::add(
+							Self::mul(
+								rhs_derivative,
+								Self::Function(Function::Ln, Box::new(lhs.clone())),
+							),
+							Self::div(Self::mul(rhs, lhs_derivative), lhs),
+						),
+					),
+				}
+			}
+			// Chain rule
+			Self::Function(function, argument) => {
+				Self::mul(function.derivative(argument), argument.derivative())
+			}
+			Self::MultiFunction(function, args) => function.derivative(args),
+			Self::Series(series, index, args) => {
+				let body_derivative = args[2].derivative();
+				let sum = |body: Self| match body {
+					Self::Number(0.0) => body,
+					_ => Self::Series(
+						Series::Sum,
+						index.clone(),
+						vec![args[0].clone(), args[1].clone(), body],
+					),
+				};
+				match series {
+					Series::Sum => sum(body_derivative),
+					// (f(a) ... f(b))' = f(a) ... f(b) (f'(a)/f(a) + ... + f'(b)/f(b))
+					Series::Product => Self::mul(
+						self.clone(),
+						sum(Self::div(body_derivative, args[2].clone())),
+					),
+				}
+			}
+		}
+	}
+
+	/// Simplified form of the expression, where operations with 0 and 1 are removed, constant factors are moved to the front of products, and constant parts are evaluated if they come out as whole numbers (so ones such as π/2 stay exact)
+	pub fn simplified(&self) -> Self {
+		match self {
+			Self::Negate(inner) => Self::neg(inner.simplified()),
+			Self::Binary(operator, lhs, rhs) => {
+				let (lhs, rhs) = (lhs.simplified(), rhs.simplified());
+				if let (Self::Number(_), Self::Number(_)) = (&lhs, &rhs) {
+					let unevaluated = Self::Binary(*operator, Box::new(lhs), Box::new(rhs));
+					let value = unevaluated.eval(f64::NAN, f64::NAN);
+					return match value.fract() == 0.0 {
+						true => Self::Number(value),
+						false => unevaluated,
+					};
+				}
+
+				match (operator, lhs, rhs) {
+					(Operator::Add, lhs, Self::Negate(rhs)) => Self::sub(lhs, *rhs),
+					(Operator::Add, lhs, Self::Number(b)) if b < 0.0 => {
+						Self::sub(lhs, Self::Number(-b))
+					}
+					(Operator::Add, lhs, rhs) => Self::add(lhs, rhs),
+					(Operator::Sub, lhs, Self::Negate(rhs)) => Self::add(lhs, *rhs),
+					(Operator::Sub, lhs, Self::Number(b)) if b < 0.0 => {
+						Self::add(lhs, Self::Number(-b))
+					}
+					(Operator::Sub, lhs, rhs) => Self::sub(lhs, rhs),
+					(Operator::Mul, lhs, rhs) => Self::product(lhs, rhs),
+					(Operator::Div, lhs, rhs) => Self::div(lhs, rhs),
+					(Operator::Pow, lhs, rhs) => Self::pow(lhs, rhs),
+				}
+			}
+			Self::Function(function, argument) => match argument.simplified() {
+				Self::Number(value) if function.apply(value).fract() == 0.0 => {
+					Self::Number(function.apply(value))
+				}
+				argument => Self::Function(*function, Box::new(argument)),
+			},
+			Self::MultiFunction(function, args) => {
+				Self::MultiFunction(*function, args.iter().map(Self::simplified).collect())
+			}
+			Self::Series(series, index, args) => Self::Series(
+				*series,
+				index.clone(),
+				args.iter().map(Self::simplified).collect(),
+			),
+			_ => self.clone(),
+		}
+	}
+
+	/// `lhs * rhs` of simplified factors, with constant factors (and signs) moved to the front and collected if they multiply to a whole number
+	fn product(lhs: Self, rhs: Self) -> Self {
+		match (lhs, rhs) {
+			(lhs @ Self::Number(_), rhs @ Self::Number(_)) => Self::mul(lhs, rhs),
+			(lhs, Self::Number(b)) => Self::product(Self::Number(b), lhs),
+			(Self::Negate(lhs), rhs) => Self::neg(Self::product(*lhs, rhs)),
+			(lhs, Self::Negate(rhs)) => Self::neg(Self::product(lhs, *rhs)),
+			(Self::Number(-1.0), rhs) => Self::neg(rhs),
+			(Self::Number(a), Self::Binary(Operator::Mul, inner_lhs, inner_rhs)) => {
+				match *inner_lhs {
+					Self::Number(b) if (a * b).fract() == 0.0 => {
+						Self::mul(Self::Number(a * b), *inner_rhs)
+					}
+					inner_lhs => Self::mul(
+						Self::Number(a),
+						Self::Binary(Operator::Mul, Box::new(inner_lhs), inner_rhs),
+					),
+				}
+			}
+			(lhs, rhs) => Self::mul(lhs, rhs),
+		}
+	}
+
+	/// `lhs + rhs`, simplified where either is 0
+	fn add(lhs: Self, rhs: Self) -> Self {
+		match (&lhs, &rhs) {
+			(Self::Number(a), Self::Number(b)) => Self::Number(a + b),
+			(Self::Number(a), _) if *a == 0.0 => rhs,
+			(_, Self::Number(b)) if *b == 0.0 => lhs,
+			_ => Self::Binary(Operator::Add, Box::new(lhs), Box::new(rhs)),
+		}
+	}
+
+	/// `lhs - rhs`, simplified where either is 0
+	fn sub(lhs: Self, rhs: Self) -> Self {
+		match (&lhs, &rhs) {
+			(Self::Number(a), Self::Number(b)) => Self::Number(a - b),
+			(Self::Number(a), _) if *a == 0.0 => Self::neg(rhs),
+			(_, Self::Number(b)) if *b == 0.0 => lhs,
+			_ => Self::Binary(Operator::Sub, Box::new(lhs), Box::new(rhs)),
+		}
+	}
+
+	/// `lhs * rhs`, simplified where either is 0 or 1
+	fn mul(lhs: Self, rhs: Self) -> Self {
+		match (&lhs, &rhs) {
+			(Self::Number(a), Self::Number(b)) => Self::Number(a * b),
+			(Self::Number(a), _) | (_, Self::Number(a)) if *a == 0.0 => Self::Number(0.0),
+			(Self::Number(a), _) if *a == 1.0 => rhs,
+			(_, Self::Number(b)) if *b == 1.0 => lhs,
+			_ => Self::Binary(Operator::Mul, Box::new(lhs), Box::new(rhs)),
+		}
+	}
+
+	/// `lhs / rhs`, simplified where `lhs` is 0 or `rhs` is 1
+	fn div(lhs: Self, rhs: Self) -> Self {
+		match (&lhs, &rhs) {
+			(Self::Number(a), Self::Number(b)) => Self::Number(a / b),
+			(Self::Number(a), _) if *a == 0.0 => Self::Number(0.0),
+			(_, Self::Number(b)) if *b == 1.0 => lhs,
+			_ => Self::Binary(Operator::Div, Box::new(lhs), Box::new(rhs)),
+		}
+	}
+
+	/// `lhs ^ rhs`, simplified where `rhs` is 0 or 1
+	fn pow(lhs: Self, rhs: Self) -> Self {
+		match (&lhs, &rhs) {
+			(Self::Number(a), Self::Number(b)) => Self::Number(a.powf(*b)),
+			(_, Self::Number(b)) if *b == 0.0 => Self::Number(1.0),
+			(_, Self::Number(b)) if *b == 1.0 => lhs,
+			_ => Self::Binary(Operator::Pow, Box::new(lhs), Box::new(rhs)),
+		}
+	}
+
+	/// `-inner`, simplified where `inner` is a number or already negated
+	fn neg(inner: Self) -> Self {
+		match inner {
+			Self::Number(value) => Self::Number(-value),
+			Self::Negate(inner) => *inner,
+			_ => Self::Negate(Box::new(inner)),
+		}
+	}
+}
+
+/// Names of variables, which (alongside function names and constants) runs of letters are split into
+const VARIABLES: [&str; 5] = ["theta", "θ", "x", "y", "i"];
+
+/// Value of the innermost index called `name` in `indices`, or NaN if there isn't one (such as for parameters without values)
+fn index_value(indices: &[(&str, f64)], name: &str) -> f64 {
+	indices
+		.iter()
+		.rev()
+		.find(|(index, _)| *index == name)
+		.map(|(_, value)| *value)
+		.unwrap_or(f64::NAN)
+}
+
+/// Token of an expression
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+	Number(f64),
+
+	/// Constant or variable
+	Name(String),
+
+	/// Name that isn't a function, constant, or variable, but is called like a function
+	Unknown(String),
+
+	/// Letter that isn't a constant or variable, such as `a` in `a*sin(x)`
+	Parameter(String),
+
+	Function(Function),
+	MultiFunction(MultiFunction),
+	Series(Series),
+	Symbol(char),
+
+	/// Absolute value bar that opens `|x|`
+	OpenBar,
+
+	/// Absolute value bar that closes `|x|`
+	CloseBar,
+}
+
+impl Token {
+	/// If the token can end an operand, such as `2`, `x`, `)`, or `!`
+	fn ends_operand(&self) -> bool {
+		matches!(
+			self,
+			Self::Number(_)
+				| Self::Name(_)
+				| Self::Parameter(_)
+				| Self::Symbol(')' | '!')
+				| Self::CloseBar
+		)
+	}
+
+	/// If the token can start an operand, such as `2`, `x`, `sin`, or `(`
+	fn starts_operand(&self) -> bool {
+		matches!(
+			self,
+			Self::Number(_)
+				| Self::Name(_)
+				| Self::Unknown(_)
+				| Self::Parameter(_)
+				| Self::Function(_)
+				| Self::MultiFunction(_)
+				| Self::Series(_)
+				| Self::Symbol('(')
+				| Self::OpenBar
+		)
+	}
+}
+
+/// Splits `func_str` into tokens, skipping whitespace.
+/// Runs of letters are split into the longest function names, constants, and variables they start with (so `xsin` is `x` followed by `sin`), with other letters being parameters.
+/// Names are matched regardless of case (so `Sin(X)` is `sin(x)`), except for variables if `case_sensitive`.
+/// Absolute value bars close the innermost open bar when they follow an operand, and open a new one otherwise.
+fn tokenize(
+	func_str: &str, case_sensitive: bool,
+) -> Result<Vec<(Token, Range<usize>)>, ParseError> {
+	let mut tokens: Vec<(Token, Range<usize>)> = Vec::new();
+	let mut start = 0;
+
+	// Number of absolute value bars that haven't been closed
+	let mut open_bars: usize = 0;
+	while let Some(c) = func_str[start..].chars().next() {
+		let rest = &func_str[start..];
+
+		// Length of the run of characters at the start of `rest` matching `matches`
+		let run_len = |matches: fn(char) -> bool| -> usize {
+			rest.find(|c: char| !matches(c)).unwrap_or(rest.len())
+		};
+
+		let (token, len) = match c {
+			_ if c.is_whitespace() => {
+				start += c.len_utf8();
+				continue;
+			}
+			'0'..='9' | '.' => {
+				let mantissa_len = run_len(|c| c.is_ascii_digit() || (c == '.'));
+
+				// Scientific notation, such as `1e-3` or `2.5E6`, where the e is followed by the exponent's digits (rather than being Euler's number)
+				let exponent = rest[mantissa_len..]
+					.strip_prefix(['e', 'E'])
+					.map(|exponent| exponent.strip_prefix(['+', '-']).unwrap_or(exponent))
+					.filter(|digits| digits.starts_with(|c: char| c.is_ascii_digit()));
+				let len = match exponent {
+					Some(digits) => {
+						let digits_len = digits
+							.find(|c: char| !c.is_ascii_digit())
+							.unwrap_or(digits.len());
+						rest.len() - digits.len() + digits_len
+					}
+					None => mantissa_len,
+				};
+				let number = &rest[..len];
+				let value = number.parse::<f64>().map_err(|_| {
+					ParseError::new(format!("invalid number '{}'", number), start..(start + len))
+				})?;
+				(Token::Number(value), len)
+			}
+			_ if c.is_alphabetic() => {
+				let starts_with = |name: &str, ignore_case: bool| {
+					rest.get(..name.len())
+						.is_some_and(|start| match ignore_case {
+							true => start.eq_ignore_ascii_case(name),
+							false => start == name,
+						})
+				};
+				let longest = SUPPORTED_FUNCTIONS
+					.iter()
+					.chain(["log"].iter())
+					.chain(CONSTANTS.iter().flat_map(|constant| constant.names))
+					.filter(|name| starts_with(name, true))
+					.chain(
+						VARIABLES
+							.iter()
+							.filter(|name| starts_with(name, !case_sensitive)),
+					)
+					.max_by_key(|name| name.len());
+				match longest {
+					Some(name) => {
+						let token = Function::from_name(name)
+							.map(Token::Function)
+							.or_else(|| MultiFunction::from_name(name).map(Token::MultiFunction))
+							.or_else(|| Series::from_name(name).map(Token::Series))
+							.unwrap_or_else(|| Token::Name(name.to_string()));
+						(token, name.len())
+					}
+					None => {
+						let len = run_len(char::is_alphanumeric);
+						match rest[len..].trim_start().starts_with('(') {
+							// Unknown names called like functions are kept whole, so they can be reported
+							true => (Token::Unknown(rest[..len].to_owned()), len),
+							false => (Token::Parameter(c.to_string()), c.len_utf8()),
+						}
+					}
+				}
+			}
+			// `**` is another way of writing exponents
+			'*' if rest.starts_with("**") => (Token::Symbol('^'), 2),
+			'|' => {
+				let closes = (open_bars > 0)
+					&& tokens
+						.last()
+						.is_some_and(|(previous, _)| previous.ends_operand());
+				match closes {
+					true => {
+						open_bars -= 1;
+						(Token::CloseBar, 1)
+					}
+					false => {
+						open_bars += 1;
+						(Token::OpenBar, 1)
+					}
+				}
+			}
+			'+' | '-' | '*' | '/' | '%' | '^' | '!' | '(' | ')' | ',' | '=' => {
+				(Token::Symbol(c), c.len_utf8())
+			}
+			_ => {
+				return Err(ParseError::new(
+					format!("unexpected '{}'", c),
+					start..(start + c.len_utf8()),
+				));
+			}
+		};
+
+		// Juxtaposed operands are implicitly multiplied, such as in `2x`, `3sin(x)`, and `(x+1)(x-2)`
+		if token.starts_operand()
+			&& tokens
+				.last()
+				.is_some_and(|(previous, _)| previous.ends_operand())
+		{
+			tokens.push((Token::Symbol('*'), start..start));
+		}
+
+		tokens.push((token, start..(start + len)));
+		start += len;
+	}
+	Ok(tokens)
+}
+
+/// Recursive descent parser of [`Expression`]s
+struct Parser<'a> {
+	tokens: Vec<(Token, Range<usize>)>,
+
+	/// Index of the next token to be parsed
+	position: usize,
+
+	/// Input being parsed
+	func_str: &'a str,
+
+	/// Variables allowed to be used
+	variables: &'a [char],
+
+	/// User functions that can be called
+	definitions: &'a Definitions,
+}
+
+impl<'a> Parser<'a> {
+	fn new(
+		func_str: &'a str, variables: &'a [char], definitions: &'a Definitions,
+	) -> Result<Self, ParseError> {
+		Ok(Self {
+			tokens: tokenize(func_str, definitions.case_sensitive)?,
+			position: 0,
+			func_str,
+			variables,
+			definitions,
+		})
+	}
+
+	fn peek(&self) -> Option<&Token> { self.tokens.get(self.position).map(|(token, _)| token) }
+
+	/// Range of bytes of the next token, or the end of the input if there are none left
+	fn span(&self) -> Range<usize> {
+		match self.tokens.get(self.position) {
+			Some((_, span)) => span.clone(),
+			None => self.func_str.len()..self.func_str.len(),
+		}
+	}
+
+	/// Consumes the next token if it's the symbol `c`
+	fn eat(&mut self, c: char) -> bool {
+		let matches = self.peek() == Some(&Token::Symbol(c));
+		if matches {
+			self.position += 1;
+		}
+		matches
+	}
+
+	/// Error for the next token not fitting the grammar
+	fn unexpected(&self) -> ParseError {
+		let span = self.span();
+		match self.peek() {
+			Some(_) => ParseError::new(
+				format!("unexpected '{}'", &self.func_str[span.clone()]),
+				span,
+			),
+			None => ParseError::new("unexpected end of expression", span),
+		}
+	}
+
+	/// expression = term (('+' | '-') term)*
+	fn expression(&mut self) -> Result<Expression, ParseError> {
+		let mut lhs = self.term()?;
+		loop {
+			let operator = match self.peek() {
+				Some(Token::Symbol('+')) => Operator::Add,
+				Some(Token::Symbol('-')) => Operator::Sub,
+				_ => return Ok(lhs),
+			};
+			self.position += 1;
+			lhs = Expression::Binary(operator, Box::new(lhs), Box::new(self.term()?));
+		}
+	}
+
+	/// term = unary (('*' | '/' | '%') unary)*
+	fn term(&mut self) -> Result<Expression, ParseError> {
+		let mut lhs = self.unary()?;
+		loop {
+			let operator = match self.peek() {
+				Some(Token::Symbol('*')) => Operator::Mul,
+				Some(Token::Symbol('/')) => Operator::Div,
+				Some(Token::Symbol('%')) => {
+					self.position += 1;
+					lhs = Expression::MultiFunction(MultiFunction::Mod, vec![lhs, self.unary()?]);
+					continue;
+				}
+				_ => return Ok(lhs),
+			};
+			self.position += 1;
+			lhs = Expression::Binary(operator, Box::new(lhs), Box::new(self.unary()?));
+		}
+	}
+
+	/// unary = '-' unary | power
+	fn unary(&mut self) -> Result<Expression, ParseError> {
+		match self.eat('-') {
+			true => Ok(Expression::Negate(Box::new(self.unary()?))),
+			false => self.power(),
+		}
+	}
+
+	/// power = factorial ('^' unary)?, which is right associative
+	fn power(&mut self) -> Result<Expression, ParseError> {
+		let base = self.factorial()?;
+		match self.eat('^') {
+			true => Ok(Expression::Binary(
+				Operator::Pow,
+				Box::new(base),
+				Box::new(self.unary()?),
+			)),
+			false => Ok(base),
+		}
+	}
+
+	/// factorial = atom '!'*
+	fn factorial(&mut self) -> Result<Expression, ParseError> {
+		let mut operand = self.atom()?;
+		while self.eat('!') {
+			operand = Expression::Function(Function::Factorial, Box::new(operand));
+		}
+		Ok(operand)
+	}
+
+	/// atom = number | variable | constant | parameter | (function | user_function) '(' expression ')' | multi_function '(' expression (',' expression)* ')' | series '(' parameter (',' expression){3} ')' | '(' expression ')' | '|' expression '|'
+	fn atom(&mut self) -> Result<Expression, ParseError> {
+		let span = self.span();
+		let token = self.peek().cloned();
+		match token {
+			Some(Token::OpenBar) => {
+				self.position += 1;
+				let inner = self.expression()?;
+				match self.peek() {
+					Some(Token::CloseBar) => {
+						self.position += 1;
+						Ok(Expression::Function(Function::Abs, Box::new(inner)))
+					}
+					_ => Err(self.unexpected()),
+				}
+			}
+			Some(Token::Symbol('(')) => {
+				self.position += 1;
+				let inner = self.expression()?;
+				match self.eat(')') {
+					true => Ok(inner),
+					false => Err(self.unexpected()),
+				}
+			}
+			Some(Token::Number(value)) => {
+				self.position += 1;
+				Ok(Expression::Number(value))
+			}
+			Some(Token::Function(function)) => {
+				self.position += 1;
+				if !self.eat('(') {
+					return Err(self.unexpected());
+				}
+				let argument = self.expression()?;
+				match self.eat(')') {
+					true => Ok(Expression::Function(function, Box::new(argument))),
+					false => Err(self.unexpected()),
+				}
+			}
+			Some(Token::MultiFunction(function)) => {
+				self.position += 1;
+				if !self.eat('(') {
+					return Err(self.unexpected());
+				}
+				let mut args = vec![self.expression()?];
+				while self.eat(',') {
+					args.push(self.expression()?);
+				}
+				if !self.eat(')') {
+					return Err(self.unexpected());
+				}
+
+				match args.len() == function.arity() {
+					true => Ok(Expression::MultiFunction(function, args)),
+					false => Err(ParseError::new(
+						format!(
+							"{} takes {} arguments, not {}",
+							function.name(),
+							function.arity(),
+							args.len()
+						),
+						span.start..self.tokens[self.position - 1].1.end,
+					)),
+				}
+			}
+			Some(Token::Series(series)) => {
+				self.position += 1;
+				if !self.eat('(') {
+					return Err(self.unexpected());
+				}
+				let index = match self.peek() {
+					Some(Token::Parameter(index)) => index.clone(),
+					_ => {
+						return Err(ParseError::new(
+							format!("{}'s index needs to be a letter, such as n", series.name()),
+							self.span(),
+						))
+					}
+				};
+				self.position += 1;
+
+				// The bounds and then the body, each following a comma
+				let mut args = Vec::with_capacity(3);
+				for _ in 0..3 {
+					if !self.eat(',') {
+						return Err(self.unexpected());
+					}
+					args.push(self.expression()?);
+				}
+				match self.eat(')') {
+					true => Ok(Expression::Series(series, index, args)),
+					false => Err(self.unexpected()),
+				}
+			}
+			Some(Token::Name(name)) => {
+				self.position += 1;
+				if let Some(value) = constant_value(&name) {
+					return Ok(Expression::Number(value));
+				}
+
+				let (variable, c) = match name.as_str() {
+					// θ (used by polar functions) is the same variable as x
+					"x" | "theta" | "θ" => (Expression::Variable(Variable::X), 'x'),
+					"y" => (Expression::Variable(Variable::Y), 'y'),
+					_ => (Expression::ImaginaryUnit, 'i'),
+				};
+				match self.variables.contains(&c) {
+					true => Ok(variable),
+					false => Err(ParseError::new(format!("invalid variable: {}", name), span)),
+				}
+			}
+			Some(Token::Parameter(name)) => {
+				self.position += 1;
+				Ok(Expression::Parameter(name))
+			}
+			Some(Token::Unknown(name)) => match self.definitions.get(&name) {
+				Some(Ok(body)) => {
+					self.position += 1;
+					if !self.eat('(') {
+						return Err(self.unexpected());
+					}
+					let argument = self.expression()?;
+					match self.eat(')') {
+						true => Ok(body.clone().substitute_x(&argument)),
+						false => Err(self.unexpected()),
+					}
+				}
+				Some(Err(message)) => Err(ParseError::new(message.clone(), span)),
+				None => Err(ParseError::new(
+					format!("unknown function '{}'", name),
+					span,
+				)),
+			},
+			_ => Err(self.unexpected()),
+		}
+	}
+}
@@ -0,0 +1,155 @@
+use crate::constants::CONSTANTS;
+use crate::expression::{Expression, Function, MultiFunction, Operator, Variable};
+use crate::typeset::SCIENTIFIC_MIN;
+use std::fmt;
+
+// How tightly expressions written as text bind, where ones binding less tightly than their surroundings are parenthesized
+const SUM: u8 = 0;
+const PRODUCT: u8 = 1;
+const POWER: u8 = 2;
+const POSTFIX: u8 = 3;
+const ATOM: u8 = 4;
+
+/// Text of `value` which parses back to it, where constants are written by their name and very large or small numbers in scientific notation
+fn number(value: f64) -> String {
+	if value < 0.0 {
+		return format!("-{}", number(-value));
+	}
+
+	if let Some(constant) = CONSTANTS.iter().find(|constant| constant.value == value) {
+		return constant.names[0].to_owned();
+	}
+
+	match value.is_finite()
+		&& (value != 0.0)
+		&& !((1.0 / SCIENTIFIC_MIN)..SCIENTIFIC_MIN).contains(&value)
+	{
+		true => format!("{:e}", value),
+		false => value.to_string(),
+	}
+}
+
+/// Expression written as an operand of an operation binding as tightly as the precedence, being parenthesized if it binds less tightly
+struct Operand<'a>(&'a Expression, u8);
+
+impl fmt::Display for Operand<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let Self(expression, precedence) = self;
+		match expression.text_precedence() < *precedence {
+			true => write!(f, "({})", expression),
+			false => write!(f, "{}", expression),
+		}
+	}
+}
+
+impl Expression {
+	/// How tightly the expression written as text binds
+	fn text_precedence(&self) -> u8 {
+		match self {
+			Self::Number(value) if *value < 0.0 => SUM,
+			Self::Negate(_) | Self::Binary(Operator::Add | Operator::Sub, _, _) => SUM,
+			Self::Binary(Operator::Mul | Operator::Div, _, _)
+			| Self::MultiFunction(MultiFunction::Mod, _) => PRODUCT,
+			Self::Binary(Operator::Pow, _, _) => POWER,
+			Self::Function(Function::Factorial, _) => POSTFIX,
+			_ => ATOM,
+		}
+	}
+
+	/// If the product (such as 2x) is written without a multiplication sign
+	fn juxtaposed(lhs: &Self, rhs: &Self) -> bool {
+		let is_letter = |expression: &Self| {
+			matches!(
+				expression,
+				Self::Variable(_) | Self::ImaginaryUnit | Self::Parameter(_)
+			)
+		};
+		let is_plain_number = match lhs {
+			Self::Number(value) => number(*value)
+				.trim_start_matches('-')
+				.chars()
+				.all(|c| c.is_ascii_digit() | (c == '.')),
+			_ => false,
+		};
+
+		is_plain_number
+			&& match rhs {
+				Self::Binary(Operator::Pow, base, _) => is_letter(base),
+				Self::Function(Function::Factorial, _)
+				| Self::MultiFunction(MultiFunction::Mod, _) => false,
+				rhs => is_letter(rhs) | matches!(rhs, Self::Function(..) | Self::MultiFunction(..)),
+			}
+	}
+}
+
+/// Writes the expression the way it's entered, so it can be parsed again
+impl fmt::Display for Expression {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Number(value) => write!(f, "{}", number(*value)),
+			Self::Variable(Variable::X) => write!(f, "x"),
+			Self::Variable(Variable::Y) => write!(f, "y"),
+			Self::ImaginaryUnit => write!(f, "i"),
+			Self::Parameter(name) => write!(f, "{}", name),
+			Self::Negate(inner) => write!(f, "-{}", Operand(inner, PRODUCT)),
+			Self::Binary(Operator::Add, lhs, rhs) => {
+				write!(f, "{} + {}", Operand(lhs, SUM), Operand(rhs, PRODUCT))
+			}
+			Self::Binary(Operator::Sub, lhs, rhs) => {
+				write!(f, "{} - {}", Operand(lhs, SUM), Operand(rhs, PRODUCT))
+			}
+			Self::Binary(Operator::Mul, lhs, rhs) => {
+				// Negated factors on the left, such as in -2x, are parsed the same way without parentheses
+				let lhs_operand = match **lhs {
+					Self::Negate(_) | Self::Number(_) => Operand(lhs, SUM),
+					_ => Operand(lhs, PRODUCT),
+				};
+				let rhs_operand = match **rhs {
+					Self::MultiFunction(MultiFunction::Mod, _) => Operand(rhs, POWER),
+					_ => Operand(rhs, PRODUCT),
+				};
+				match Self::juxtaposed(lhs, rhs) {
+					true => write!(f, "{}{}", lhs_operand, rhs_operand),
+					false => write!(f, "{}*{}", lhs_operand, rhs_operand),
+				}
+			}
+			Self::Binary(Operator::Div, lhs, rhs) => {
+				write!(f, "{}/{}", Operand(lhs, PRODUCT), Operand(rhs, POWER))
+			}
+			Self::Binary(Operator::Pow, base, exponent) => {
+				write!(f, "{}^{}", Operand(base, POSTFIX), Operand(exponent, POWER))
+			}
+			Self::Function(Function::Factorial, argument) => {
+				write!(f, "{}!", Operand(argument, ATOM))
+			}
+			Self::Function(function, argument) => write!(f, "{}({})", function.name(), argument),
+			Self::MultiFunction(MultiFunction::Mod, args) => {
+				write!(
+					f,
+					"{} % {}",
+					Operand(&args[0], PRODUCT),
+					Operand(&args[1], POWER)
+				)
+			}
+			Self::MultiFunction(function, args) => {
+				write!(f, "{}(", function.name())?;
+				for (i, arg) in args.iter().enumerate() {
+					if i != 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{}", arg)?;
+				}
+				write!(f, ")")
+			}
+			Self::Series(series, index, args) => write!(
+				f,
+				"{}({}, {}, {}, {})",
+				series.name(),
+				index,
+				args[0],
+				args[1],
+				args[2]
+			),
+		}
+	}
+}
@@ -0,0 +1,9 @@
+// Also included by build.rs (to generate completions of the functions' names), so this can't depend on the rest of the crate
+
+/// Names of the functions that can be used in expressions, each of which has to be handled by the parser
+pub const SUPPORTED_FUNCTIONS: [&str; 38] = [
+	"abs", "signum", "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh", "asinh",
+	"acosh", "atanh", "floor", "round", "ceil", "trunc", "fract", "exp", "sqrt", "cbrt", "ln",
+	"log2", "log10", "min", "max", "clamp", "mod", "sum", "prod", "erf", "erfc", "gamma", "lgamma",
+	"besselj", "atan2", "hypot",
+];
@@ -0,0 +1,122 @@
+use crate::complex::Complex;
+use std::f64::consts::PI;
+
+/// Parameter g of the Lanczos approximation
+const LANCZOS_G: f64 = 7.0;
+
+/// Coefficients of the Lanczos approximation (for g = 7)
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+	0.999_999_999_999_809_9,
+	676.520_368_121_885_1,
+	-1_259.139_216_722_402_8,
+	771.323_428_777_653_1,
+	-176.615_029_162_140_6,
+	12.507_343_278_686_905,
+	-0.138_571_095_265_720_12,
+	9.984_369_578_019_572e-6,
+	1.505_632_735_149_311_6e-7,
+];
+
+/// Largest integer whose factorial is finite
+const MAX_FACTORIAL: f64 = 170.0;
+
+/// Natural logarithm of |Γ(x)|, alongside the sign of Γ(x)
+pub fn ln_gamma(x: f64) -> (f64, f64) {
+	// Reflection formula, Γ(x) Γ(1 - x) = π / sin(πx)
+	if x < 0.5 {
+		let sin = (PI * x).sin();
+		return ((PI / sin.abs()).ln() - ln_gamma(1.0 - x).0, sin.signum());
+	}
+
+	let x = x - 1.0;
+	let sum = LANCZOS_COEFFICIENTS[1..]
+		.iter()
+		.enumerate()
+		.fold(LANCZOS_COEFFICIENTS[0], |sum, (i, coefficient)| {
+			sum + (coefficient / (x + (i + 1) as f64))
+		});
+	let t = x + LANCZOS_G + 0.5;
+	(
+		(0.5 * (2.0 * PI).ln()) + ((x + 0.5) * t.ln()) - t + sum.ln(),
+		1.0,
+	)
+}
+
+/// Factorial x! of non-negative integers, and Γ(x + 1) of other real numbers
+pub fn factorial(x: f64) -> f64 {
+	if x.fract() == 0.0 {
+		return match x {
+			_ if x < 0.0 => f64::NAN,
+			_ if x > MAX_FACTORIAL => f64::INFINITY,
+			_ => (2..=(x as u32)).fold(1.0, |product, k| product * k as f64),
+		};
+	}
+
+	let (ln_gamma, sign) = ln_gamma(x + 1.0);
+	sign * ln_gamma.exp()
+}
+
+/// Γ(z + 1) of the complex number `z`
+pub fn complex_factorial(z: Complex) -> Complex {
+	if z.im == 0.0 {
+		return Complex::from(factorial(z.re));
+	}
+
+	// Reflection formula, Γ(z) Γ(1 - z) = π / sin(πz), where z here is `z + 1`
+	if z.re < -0.5 {
+		let pi = Complex::from(PI);
+		return pi / ((pi * (z + Complex::ONE)).sin() * complex_factorial(-z - Complex::ONE));
+	}
+
+	let sum = LANCZOS_COEFFICIENTS[1..].iter().enumerate().fold(
+		Complex::from(LANCZOS_COEFFICIENTS[0]),
+		|sum, (i, coefficient)| {
+			sum + (Complex::from(*coefficient) / (z + Complex::from((i + 1) as f64)))
+		},
+	);
+	let t = z + Complex::from(LANCZOS_G + 0.5);
+	let ln_gamma =
+		Complex::from(0.5 * (2.0 * PI).ln()) + ((z + Complex::from(0.5)) * t.ln()) - t + sum.ln();
+	ln_gamma.exp()
+}
+
+/// Digamma function ψ(x), the derivative of ln Γ(x)
+pub fn digamma(x: f64) -> f64 {
+	// Reflection formula, ψ(1 - x) - ψ(x) = π cot(πx)
+	if x < 0.0 {
+		return digamma(1.0 - x) - (PI / (PI * x).tan());
+	}
+
+	// Recurrence ψ(x) = ψ(x + 1) - 1/x, until the asymptotic series is accurate
+	let (mut x, mut result) = (x, 0.0);
+	while x < 10.0 {
+		result -= 1.0 / x;
+		x += 1.0;
+	}
+
+	let x2 = 1.0 / (x * x);
+	result + x.ln()
+		- (0.5 / x)
+		- (x2 * ((1.0 / 12.0) - (x2 * ((1.0 / 120.0) - (x2 * (1.0 / 252.0))))))
+}
+
+/// Trigamma function ψ₁(x), the derivative of ψ(x)
+pub fn trigamma(x: f64) -> f64 {
+	// Reflection formula, ψ₁(1 - x) + ψ₁(x) = π² / sin²(πx)
+	if x < 0.0 {
+		return (PI / (PI * x).sin()).powi(2) - trigamma(1.0 - x);
+	}
+
+	// Recurrence ψ₁(x) = ψ₁(x + 1) + 1/x², until the asymptotic series is accurate
+	let (mut x, mut result) = (x, 0.0);
+	while x < 10.0 {
+		result += 1.0 / (x * x);
+		x += 1.0;
+	}
+
+	let x2 = 1.0 / (x * x);
+	result
+		+ (1.0 / x)
+		+ (x2 / 2.0)
+		+ ((x2 / x) * ((1.0 / 6.0) - (x2 * ((1.0 / 30.0) - (x2 * (1.0 / 42.0))))))
+}
@@ -0,0 +1,254 @@
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Closed interval [lo, hi] of real numbers, used to bound the values of an expression over a range of x (up to floating point rounding).
+/// Intervals which nothing is in (such as the square roots of negative numbers) are empty, and have NaN bounds.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Interval {
+	pub lo: f64,
+	pub hi: f64,
+}
+
+impl Interval {
+	pub const EMPTY: Interval = Interval::new(f64::NAN, f64::NAN);
+	pub const ENTIRE: Interval = Interval::new(f64::NEG_INFINITY, f64::INFINITY);
+
+	pub const fn new(lo: f64, hi: f64) -> Self { Self { lo, hi } }
+
+	/// Interval between `a` and `b` (in either order), which is empty if either is NaN
+	fn between(a: f64, b: f64) -> Self {
+		match a.is_nan() | b.is_nan() {
+			true => Self::EMPTY,
+			false => Self::new(a.min(b), a.max(b)),
+		}
+	}
+
+	pub fn is_empty(self) -> bool { self.lo.is_nan() | self.hi.is_nan() }
+
+	/// If the interval only contains a single number
+	pub fn is_point(self) -> bool { self.lo == self.hi }
+
+	pub fn contains(self, x: f64) -> bool { (self.lo <= x) && (x <= self.hi) }
+
+	/// Smallest interval containing both intervals
+	pub fn hull(self, other: Self) -> Self {
+		match (self.is_empty(), other.is_empty()) {
+			(true, _) => other,
+			(_, true) => self,
+			_ => Self::new(self.lo.min(other.lo), self.hi.max(other.hi)),
+		}
+	}
+
+	/// Part of the interval between `lo` and `hi`
+	fn clip(self, lo: f64, hi: f64) -> Self {
+		let (lo, hi) = (self.lo.max(lo), self.hi.min(hi));
+		match self.is_empty() | (lo > hi) {
+			true => Self::EMPTY,
+			false => Self::new(lo, hi),
+		}
+	}
+
+	/// Applies the monotonic (either nondecreasing or nonincreasing) function `f`
+	pub(crate) fn monotonic(self, f: impl Fn(f64) -> f64) -> Self {
+		match self.is_empty() {
+			true => Self::EMPTY,
+			false => Self::between(f(self.lo), f(self.hi)),
+		}
+	}
+
+	/// Applies the function `f` that's monotonic over the real numbers at least `min`, which it's undefined below
+	pub(crate) fn monotonic_from(self, min: f64, f: impl Fn(f64) -> f64) -> Self {
+		self.clip(min, f64::INFINITY).monotonic(f)
+	}
+
+	/// Applies the function `f` that's monotonic over [-1, 1], which it's undefined outside of
+	pub(crate) fn monotonic_unit(self, f: impl Fn(f64) -> f64) -> Self {
+		self.clip(-1.0, 1.0).monotonic(f)
+	}
+
+	/// Applies the function `f` that's even and nondecreasing over the positive numbers (such as cosh)
+	pub(crate) fn even(self, f: impl Fn(f64) -> f64) -> Self { self.abs().monotonic(f) }
+
+	pub fn abs(self) -> Self {
+		match self.contains(0.0) {
+			true => Self::new(0.0, self.hi.max(-self.lo)),
+			false => self.monotonic(f64::abs),
+		}
+	}
+
+	/// Applies the sinusoid `f` with a period of 2π, which has maxima at `peak` + 2kπ and minima halfway between them
+	fn sinusoid(self, f: impl Fn(f64) -> f64, peak: f64) -> Self {
+		if self.is_empty() {
+			return Self::EMPTY;
+		}
+
+		if (self.hi - self.lo >= TAU) | self.lo.is_infinite() | self.hi.is_infinite() {
+			return Self::new(-1.0, 1.0);
+		}
+
+		let contains_next = |offset: f64| {
+			let next = offset + (((self.lo - offset) / TAU).ceil() * TAU);
+			next <= self.hi
+		};
+		let (lo, hi) = (f(self.lo), f(self.hi));
+		Self::new(
+			match contains_next(peak + PI) {
+				true => -1.0,
+				false => lo.min(hi),
+			},
+			match contains_next(peak) {
+				true => 1.0,
+				false => lo.max(hi),
+			},
+		)
+	}
+
+	pub fn sin(self) -> Self { self.sinusoid(f64::sin, FRAC_PI_2) }
+
+	pub fn cos(self) -> Self { self.sinusoid(f64::cos, 0.0) }
+
+	pub fn tan(self) -> Self {
+		// Each period is between poles at π/2 + kπ, which tan goes to ±∞ at
+		let next_pole = FRAC_PI_2 + (((self.lo - FRAC_PI_2) / PI).ceil() * PI);
+		match next_pole <= self.hi {
+			true => Self::ENTIRE,
+			false => self.monotonic(f64::tan),
+		}
+	}
+
+	/// Fractional part, with the sign of the number (like [`f64::fract`])
+	pub fn fract(self) -> Self {
+		// Jumps happen at integers, and it's increasing between them
+		match self.lo.ceil() <= self.hi.floor() {
+			true => Self::new(
+				match self.lo < 0.0 {
+					true => -1.0,
+					false => 0.0,
+				},
+				match self.hi > 0.0 {
+					true => 1.0,
+					false => 0.0,
+				},
+			),
+			false => self.monotonic(f64::fract),
+		}
+	}
+
+	/// `self` raised to the integer power `n`
+	fn powi(self, n: i32) -> Self {
+		match n {
+			0 => Self::from(1.0),
+			_ if n < 0 => Self::from(1.0) / self.powi(-n),
+			_ if n % 2 == 0 => self.even(|x| x.powi(n)),
+			_ => self.monotonic(|x| x.powi(n)),
+		}
+	}
+
+	/// `self` raised to the power `exponent`, where negative numbers are only raised to integer powers
+	pub fn pow(self, exponent: Self) -> Self {
+		if self.is_empty() | exponent.is_empty() {
+			return Self::EMPTY;
+		}
+
+		if exponent.is_point()
+			&& (exponent.lo.fract() == 0.0)
+			&& (exponent.lo.abs() <= i32::MAX as f64)
+		{
+			return self.powi(exponent.lo as i32);
+		}
+
+		// x^y = e^(y ln x), where ln x is increasing over the positive numbers
+		(exponent * self.monotonic_from(0.0, f64::ln)).monotonic(f64::exp)
+	}
+
+	pub fn min(self, other: Self) -> Self {
+		match self.is_empty() | other.is_empty() {
+			true => Self::EMPTY,
+			false => Self::new(self.lo.min(other.lo), self.hi.min(other.hi)),
+		}
+	}
+
+	pub fn max(self, other: Self) -> Self {
+		match self.is_empty() | other.is_empty() {
+			true => Self::EMPTY,
+			false => Self::new(self.lo.max(other.lo), self.hi.max(other.hi)),
+		}
+	}
+}
+
+/// Interval only containing `x`
+impl From<f64> for Interval {
+	fn from(x: f64) -> Self { Self::new(x, x) }
+}
+
+impl Add for Interval {
+	type Output = Self;
+
+	// ∞ - ∞ is NaN, which is unbounded in that direction
+	fn add(self, rhs: Self) -> Self {
+		match self.is_empty() | rhs.is_empty() {
+			true => Self::EMPTY,
+			false => Self::new(
+				(self.lo + rhs.lo).max(f64::NEG_INFINITY),
+				(self.hi + rhs.hi).min(f64::INFINITY),
+			),
+		}
+	}
+}
+
+impl Sub for Interval {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self { self + (-rhs) }
+}
+
+impl Mul for Interval {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self {
+		if self.is_empty() | rhs.is_empty() {
+			return Self::EMPTY;
+		}
+
+		// 0 * ∞ is NaN, where the products approaching it are all 0
+		let products = [
+			self.lo * rhs.lo,
+			self.lo * rhs.hi,
+			self.hi * rhs.lo,
+			self.hi * rhs.hi,
+		]
+		.map(|product| match product.is_nan() {
+			true => 0.0,
+			false => product,
+		});
+		Self::new(
+			products.into_iter().fold(f64::INFINITY, f64::min),
+			products.into_iter().fold(f64::NEG_INFINITY, f64::max),
+		)
+	}
+}
+
+impl Div for Interval {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self {
+		if self.is_empty() | rhs.is_empty() | (rhs == Self::from(0.0)) {
+			return Self::EMPTY;
+		}
+
+		// Dividing by numbers on both sides of 0 gives numbers approaching ±∞
+		let reciprocal = match (rhs.lo, rhs.hi) {
+			(lo, hi) if (lo < 0.0) && (hi > 0.0) => Self::ENTIRE,
+			(0.0, hi) => Self::new(1.0 / hi, f64::INFINITY),
+			(lo, 0.0) => Self::new(f64::NEG_INFINITY, 1.0 / lo),
+			(lo, hi) => Self::new(1.0 / hi, 1.0 / lo),
+		};
+		self * reciprocal
+	}
+}
+
+impl Neg for Interval {
+	type Output = Self;
+
+	fn neg(self) -> Self { Self::new(-self.hi, -self.lo) }
+}
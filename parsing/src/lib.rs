@@ -3,14 +3,35 @@
 #![feature(const_for)]
 mod autocomplete;
 mod autocomplete_hashmap;
+mod complex;
+mod constants;
+mod expression;
+mod formula;
+mod functions;
+mod gamma;
+mod interval;
 mod parsing;
+mod special;
 mod splitting;
 mod suggestions;
+mod typeset;
 
 pub use crate::{
 	autocomplete::{AutoComplete, Movement},
-	autocomplete_hashmap::compile_hashmap,
-	parsing::{process_func_str, BackingFunction, FlatExWrapper},
+	autocomplete_hashmap::{compile_hashmap, compile_hashmap_terms},
+	complex::Complex,
+	constants::{Constant, CONSTANTS},
+	expression::{AngleUnit, Definitions, Expression, ParseError, Variable},
+	functions::SUPPORTED_FUNCTIONS,
+	interval::Interval,
+	parsing::{
+		process_func_str, replace_variable, sanitize_func_str, BackingFunction, BivariateFunction,
+		ExpressionWrapper,
+	},
 	splitting::{split_function, split_function_chars, SplitType},
-	suggestions::{generate_hint, get_last_term, Hint, HINT_EMPTY, SUPPORTED_FUNCTIONS},
+	suggestions::{
+		closest_function, complete_terms, generate_hint, generate_hint_at, get_last_term,
+		unmatched_parens, Hint, HINT_EMPTY,
+	},
+	typeset::Typeset,
 };
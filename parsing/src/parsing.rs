@@ -1,82 +1,50 @@
-use exmex::prelude::*;
-use std::collections::HashMap;
+use crate::expression::{AngleUnit, Definitions, Expression, ParseError, Variable};
+use std::collections::{BTreeMap, HashMap};
 
+/// Function of x (or nothing), used for f(x) and its derivatives
 #[derive(Clone, PartialEq)]
-pub struct FlatExWrapper {
-	func: Option<FlatEx<f64>>,
-	func_str: Option<String>,
+pub struct ExpressionWrapper {
+	func: Option<Expression>,
 }
 
-impl FlatExWrapper {
-	const EMPTY: FlatExWrapper = FlatExWrapper {
-		func: None,
-		func_str: None,
-	};
+impl ExpressionWrapper {
+	const EMPTY: ExpressionWrapper = ExpressionWrapper { func: None };
 
 	#[inline]
-	const fn new(f: FlatEx<f64>) -> Self {
-		Self {
-			func: Some(f),
-			func_str: None,
-		}
-	}
+	const fn new(f: Expression) -> Self { Self { func: Some(f) } }
 
 	#[inline]
 	const fn is_none(&self) -> bool { self.func.is_none() }
 
 	#[inline]
-	pub fn eval(&self, x: &[f64]) -> f64 {
+	pub fn eval(&self, x: f64) -> f64 {
 		self.func
 			.as_ref()
-			.map(|f| f.eval(x).unwrap_or(f64::NAN))
+			.map(|f| f.eval(x, f64::NAN))
 			.unwrap_or(f64::NAN)
 	}
 
+	/// `n`th derivative of the function
 	#[inline]
-	fn partial(&self, x: usize) -> Self {
-		self.func
-			.as_ref()
-			.map(|f| f.clone().partial(x).map(Self::new).unwrap_or(Self::EMPTY))
-			.unwrap_or(Self::EMPTY)
-	}
-
-	#[inline]
-	fn get_string(&mut self) -> String {
-		match self.func_str {
-			Some(ref func_str) => func_str.clone(),
-			None => {
-				let calculated = self.func.as_ref().map(|f| f.unparse()).unwrap_or("");
-				self.func_str = Some(calculated.to_owned());
-				calculated.to_owned()
-			}
+	fn derivative(&self, n: usize) -> Self {
+		match self.func {
+			Some(ref f) => Self::new((0..n).fold(f.clone(), |f, _| f.derivative())),
+			None => Self::EMPTY,
 		}
 	}
-
-	#[inline]
-	fn partial_iter(&self, n: usize) -> Self {
-		self.func
-			.as_ref()
-			.map(|f| {
-				f.clone()
-					.partial_iter((0..=n).map(|_| 0))
-					.map(Self::new)
-					.unwrap_or(Self::EMPTY)
-			})
-			.unwrap_or(Self::EMPTY)
-	}
 }
 
-impl const Default for FlatExWrapper {
-	fn default() -> FlatExWrapper { FlatExWrapper::EMPTY }
+impl const Default for ExpressionWrapper {
+	fn default() -> ExpressionWrapper { ExpressionWrapper::EMPTY }
 }
 /// Function that includes f(x), f'(x), f'(x)'s string representation, and f''(x)
 #[derive(Clone, PartialEq)]
 pub struct BackingFunction {
 	/// f(x)
-	function: FlatExWrapper,
+	function: ExpressionWrapper,
 
 	/// Temporary cache for nth derivative
-	nth_derivative: HashMap<usize, FlatExWrapper>,
+	nth_derivative: HashMap<usize, ExpressionWrapper>,
 }
 
 impl Default for BackingFunction {
@@ -87,74 +55,146 @@ impl BackingFunction {
 	pub const fn is_none(&self) -> bool { self.function.is_none() }
 
 	/// Create new [`BackingFunction`] instance
-	pub fn new(func_str: &str) -> Result<Self, String> {
+	pub fn new(func_str: &str) -> Result<Self, ParseError> {
+		Self::new_using(func_str, &Definitions::EMPTY)
+	}
+
+	/// Create new [`BackingFunction`] instance, where the user functions in `definitions` can be called
+	pub fn new_using(func_str: &str, definitions: &Definitions) -> Result<Self, ParseError> {
 		if func_str.is_empty() {
 			return Ok(Self {
-				function: FlatExWrapper::EMPTY,
+				function: ExpressionWrapper::EMPTY,
 				nth_derivative: HashMap::new(),
 			});
 		}
 
-		let function = FlatExWrapper::new({
-			let parse_result = exmex::parse::<f64>(func_str);
-
-			match &parse_result {
-				Err(e) => return Err(e.to_string()),
-				Ok(ok_result) => {
-					let var_names = ok_result.var_names().to_vec();
-
-					if var_names != ["x"] {
-						let var_names_not_x: Vec<&String> = var_names
-							.iter()
-							.filter(|ele| ele != &"x")
-							.collect::<Vec<&String>>();
-
-						return Err(format!(
-							"Error: invalid variable{}",
-							match var_names_not_x.len() {
-								1 => String::from(": ") + var_names_not_x[0].as_str(),
-								_ => format!("s: {:?}", var_names_not_x),
-							}
-						));
-					}
-				}
-			}
-			unsafe { parse_result.unwrap_unchecked() }
-		});
+		let function = Expression::parse_using(func_str, &['x'], definitions)?;
+		if !function.uses(Variable::X) {
+			return Err(ParseError {
+				message: "functions need to use x".to_owned(),
+				span: 0..func_str.len(),
+			});
+		}
 
 		Ok(Self {
-			function,
+			function: ExpressionWrapper::new(function),
 
 			nth_derivative: HashMap::new(),
 		})
 	}
 
-	// TODO rewrite this logic, it's a mess
+	/// f(x), if there is one
+	pub fn expression(&self) -> Option<&Expression> { self.function.func.as_ref() }
+
+	/// Converts the function's trigonometric functions to use `angle_unit`
+	pub fn with_angle_unit(self, angle_unit: AngleUnit) -> Self {
+		Self {
+			function: ExpressionWrapper {
+				func: self.function.func.map(|f| f.with_angle_unit(angle_unit)),
+			},
+			nth_derivative: HashMap::new(),
+		}
+	}
+
+	/// Names of the parameters used by the function, sorted and without duplicates
+	pub fn parameters(&self) -> Vec<String> {
+		self.function
+			.func
+			.as_ref()
+			.map(|f| f.parameters())
+			.unwrap_or_default()
+	}
+
+	/// Replaces the function's parameters with their values in `values`
+	pub fn with_parameters(self, values: &BTreeMap<String, f64>) -> Self {
+		Self {
+			function: ExpressionWrapper {
+				func: self.function.func.map(|f| f.with_parameters(values)),
+			},
+			nth_derivative: HashMap::new(),
+		}
+	}
+
 	pub fn generate_derivative(&mut self, derivative: usize) {
 		if derivative == 0 {
 			return;
 		}
 
 		if !self.nth_derivative.contains_key(&derivative) {
-			let new_func = self.function.partial_iter(derivative);
-			self.nth_derivative.insert(derivative, new_func.clone());
+			let new_func = self.function.derivative(derivative);
+			self.nth_derivative.insert(derivative, new_func);
 		}
 	}
 
-	pub fn get_function_derivative(&self, derivative: usize) -> &FlatExWrapper {
+	pub fn get_function_derivative(&self, derivative: usize) -> &ExpressionWrapper {
 		if derivative == 0 {
 			return &self.function;
 		} else {
 			return self
 				.nth_derivative
 				.get(&derivative)
-				.unwrap_or(&FlatExWrapper::EMPTY);
+				.unwrap_or(&ExpressionWrapper::EMPTY);
 		}
 	}
 
 	pub fn get(&mut self, derivative: usize, x: f64) -> f64 {
-		self.get_function_derivative(derivative).eval(&[x])
+		self.get_function_derivative(derivative).eval(x)
+	}
+}
+
+/// Function F(x, y) of two variables, used for implicit equations and slope fields
+#[derive(Clone, PartialEq)]
+pub struct BivariateFunction {
+	/// F(x, y)
+	function: Expression,
+}
+
+impl BivariateFunction {
+	/// Create new [`BivariateFunction`] instance from the expression `func_str`
+	pub fn new(func_str: &str) -> Result<Self, ParseError> {
+		Self::new_using(func_str, &Definitions::EMPTY)
+	}
+
+	/// Create new [`BivariateFunction`] instance from the expression `func_str`, where the user functions in `definitions` can be called
+	pub fn new_using(func_str: &str, definitions: &Definitions) -> Result<Self, ParseError> {
+		Ok(Self {
+			function: Expression::parse_using(func_str, &['x', 'y'], definitions)?,
+		})
 	}
+
+	/// Create new [`BivariateFunction`] instance F(x, y) = lhs - rhs from the equation `func_str` (which contains a single '='), whose solutions are where F(x, y) = 0
+	pub fn new_implicit(func_str: &str) -> Result<Self, ParseError> {
+		Self::new_implicit_using(func_str, &Definitions::EMPTY)
+	}
+
+	/// Create new [`BivariateFunction`] instance from the equation `func_str` like [`BivariateFunction::new_implicit`], where the user functions in `definitions` can be called
+	pub fn new_implicit_using(
+		func_str: &str, definitions: &Definitions,
+	) -> Result<Self, ParseError> {
+		Ok(Self {
+			function: Expression::parse_equation_using(func_str, &['x', 'y'], definitions)?,
+		})
+	}
+
+	/// Converts the function's trigonometric functions to use `angle_unit`
+	pub fn with_angle_unit(self, angle_unit: AngleUnit) -> Self {
+		Self {
+			function: self.function.with_angle_unit(angle_unit),
+		}
+	}
+
+	/// Names of the parameters used by the function, sorted and without duplicates
+	pub fn parameters(&self) -> Vec<String> { self.function.parameters() }
+
+	/// Replaces the function's parameters with their values in `values`
+	pub fn with_parameters(self, values: &BTreeMap<String, f64>) -> Self {
+		Self {
+			function: self.function.with_parameters(values),
+		}
+	}
+
+	/// Evaluates F(`x`, `y`)
+	pub fn get(&self, x: f64, y: f64) -> f64 { self.function.eval(x, y) }
 }
 
 fn prettyify_function_str(func: &str) -> String {
@@ -173,14 +213,92 @@ fn prettyify_function_str(func: &str) -> String {
 #[inline]
 pub const fn is_variable(c: &char) -> bool {
 	let c = c.to_ascii_lowercase();
-	(c == 'x') | (c == 'e') | (c == 'π')
+	(c == 'x') | (c == 'y') | (c == 'e') | (c == 'i') | (c == 'π') | (c == 'θ')
+}
+
+/// Replaces the variable `from` with `to` where it isn't part of a longer word (such as the n in `sin`)
+/// Used for sequences a(n) and complex functions f(z), whose variables n and z are the same variable as x
+pub fn replace_variable(func_str: &str, from: char, to: char) -> String {
+	let chars: Vec<char> = func_str.chars().collect();
+	let is_letter = |i: Option<usize>| {
+		i.and_then(|i| chars.get(i))
+			.map(|c| c.is_alphabetic())
+			.unwrap_or(false)
+	};
+
+	chars
+		.iter()
+		.enumerate()
+		.map(
+			|(i, c)| match *c == from && !is_letter(i.checked_sub(1)) && !is_letter(Some(i + 1)) {
+				true => to,
+				false => *c,
+			},
+		)
+		.collect()
+}
+
+/// Characters found in math pasted from documents, and the ASCII the parser understands them as
+const UNICODE_REPLACEMENTS: [(char, &str); 15] = [
+	('−', "-"),
+	('–', "-"),
+	('—', "-"),
+	('‐', "-"),
+	('×', "*"),
+	('·', "*"),
+	('⋅', "*"),
+	('∙', "*"),
+	('÷', "/"),
+	('∕', "/"),
+	('π', "pi"),
+	('‘', "'"),
+	('’', "'"),
+	('“', "\""),
+	('”', "\""),
+];
+
+/// Digits written as superscripts (such as in x²), which are exponents
+const SUPERSCRIPTS: [char; 11] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹', '⁻'];
+
+/// Converts unicode math characters (such as − and ×, often pasted from documents) into the ASCII they're written as, such as `2×x²` into `2*x^2`
+pub fn sanitize_func_str(func_str: &str) -> String {
+	let mut sanitized = String::with_capacity(func_str.len());
+	let mut in_exponent = false;
+	for c in func_str.chars() {
+		// Runs of superscripts are a single exponent
+		if let Some(i) = SUPERSCRIPTS
+			.iter()
+			.position(|superscript| *superscript == c)
+		{
+			if !in_exponent {
+				sanitized.push('^');
+			}
+			in_exponent = true;
+			sanitized.push(match i {
+				10 => '-',
+				_ => char::from_digit(i as u32, 10).unwrap(),
+			});
+			continue;
+		}
+		in_exponent = false;
+
+		match UNICODE_REPLACEMENTS.iter().find(|(from, _)| *from == c) {
+			Some((_, to)) => sanitized.push_str(to),
+			None if c.is_whitespace() => sanitized.push(' '),
+			None => sanitized.push(c),
+		}
+	}
+	sanitized
 }
 
 /// Adds asterisks where needed in a function
+/// θ (used by polar functions) is the same variable as x
 pub fn process_func_str(function_in: &str) -> String {
 	if function_in.is_empty() {
 		return String::new();
 	}
 
-	crate::split_function(function_in, crate::SplitType::Multiplication).join("*")
+	crate::split_function(function_in, crate::SplitType::Multiplication)
+		.join("*")
+		.replace('θ', "x")
 }
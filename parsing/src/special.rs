@@ -0,0 +1,102 @@
+use std::f64::consts::{FRAC_PI_4, PI, TAU};
+
+/// Below this, erf is calculated by its Taylor series, and above it erfc is calculated by its continued fraction
+const ERF_SERIES_MAX: f64 = 2.5;
+
+/// Number of terms of the continued fraction of erfc, which is accurate to machine precision above [`ERF_SERIES_MAX`]
+const ERFC_FRACTION_TERMS: usize = 60;
+
+/// Above this (and the square of the order), Bessel functions are calculated by their asymptotic expansion
+const BESSEL_ASYMPTOTIC_MIN: f64 = 1000.0;
+
+/// Error function erf(x) = 2/√π ∫₀ˣ e^(-t²) dt
+pub fn erf(x: f64) -> f64 {
+	match x.abs() < ERF_SERIES_MAX {
+		true => erf_series(x),
+		false => x.signum() * (1.0 - erfc_fraction(x.abs())),
+	}
+}
+
+/// Complementary error function erfc(x) = 1 - erf(x), which is accurate where erf(x) is close to 1
+pub fn erfc(x: f64) -> f64 {
+	match x {
+		_ if x >= ERF_SERIES_MAX => erfc_fraction(x),
+		_ if x <= -ERF_SERIES_MAX => 2.0 - erfc_fraction(-x),
+		_ => 1.0 - erf_series(x),
+	}
+}
+
+/// Taylor series erf(x) = 2/√π (x - x³/3 + x⁵/10 - ...), summed until the terms are negligible
+fn erf_series(x: f64) -> f64 {
+	let (mut sum, mut power) = (x, x);
+	for n in 1.. {
+		power *= -(x * x) / n as f64;
+		let term = power / (2 * n + 1) as f64;
+		sum += term;
+		if term.abs() <= (f64::EPSILON * sum.abs()) {
+			break;
+		}
+	}
+	(2.0 / PI.sqrt()) * sum
+}
+
+/// Continued fraction erfc(x) = e^(-x²)/√π 1/(x + (1/2)/(x + 1/(x + (3/2)/(x + ...)))), for positive x
+fn erfc_fraction(x: f64) -> f64 {
+	let denominator = (1..=ERFC_FRACTION_TERMS)
+		.rev()
+		.fold(x, |denominator, k| x + ((k as f64 / 2.0) / denominator));
+	(-(x * x)).exp() / (PI.sqrt() * denominator)
+}
+
+/// Bessel function of the first kind Jₙ(x), for integer orders `n`
+pub fn bessel_j(n: f64, x: f64) -> f64 {
+	if (n.fract() != 0.0) | !x.is_finite() {
+		return f64::NAN;
+	}
+
+	if (x.abs() > BESSEL_ASYMPTOTIC_MIN) && (x.abs() > n * n) {
+		// Jₙ(-x) = (-1)ⁿ Jₙ(x)
+		let sign = match (x < 0.0) && (n % 2.0 != 0.0) {
+			true => -1.0,
+			false => 1.0,
+		};
+		return sign * bessel_j_asymptotic(n, x.abs());
+	}
+
+	// Jₙ(x) = 1/2π ∫ cos(nτ - x sin τ) dτ over a period, which the trapezoid rule converges on exponentially fast once there are enough more points than |x| + |n|
+	let points = (x.abs() + n.abs() + 32.0 + (8.0 * x.abs().cbrt())) as usize;
+	(0..points)
+		.map(|k| {
+			let tau = TAU * k as f64 / points as f64;
+			((n * tau) - (x * tau.sin())).cos()
+		})
+		.sum::<f64>()
+		/ points as f64
+}
+
+/// Hankel's asymptotic expansion of Jₙ(x) for large positive x, summed until its terms stop shrinking
+fn bessel_j_asymptotic(n: f64, x: f64) -> f64 {
+	let mu = 4.0 * n * n;
+	let (mut p, mut q) = (1.0, 0.0);
+	let mut term: f64 = 1.0;
+	for k in 1..30 {
+		let next = term * (mu - ((2 * k - 1) as f64).powi(2)) / (k as f64 * 8.0 * x);
+		if next.abs() >= term.abs() {
+			break;
+		}
+		term = next;
+
+		// Terms alternate between Q and P, each alternating in sign
+		let sign = match (k / 2) % 2 {
+			0 => 1.0,
+			_ => -1.0,
+		};
+		match k % 2 {
+			1 => q += sign * term,
+			_ => p += sign * term,
+		}
+	}
+
+	let chi = x - (n * PI / 2.0) - FRAC_PI_4;
+	(2.0 / (PI * x)).sqrt() * ((p * chi.cos()) - (q * chi.sin()))
+}
@@ -1,9 +1,11 @@
 use crate::parsing::is_variable;
+use crate::{CONSTANTS, SUPPORTED_FUNCTIONS};
 
 pub fn split_function(input: &str, split: SplitType) -> Vec<String> {
 	split_function_chars(
 		&input
 			.replace("pi", "π") // replace "pi" text with pi symbol
+			.replace("theta", "θ") // replace "theta" text with theta symbol
 			.replace("**", "^") // support alternate manner of expressing exponents
 			.replace("exp", "\u{1fc93}") // stop-gap solution to fix the `exp` function
 			.chars()
@@ -26,6 +28,7 @@ pub enum SplitType {
 struct BoolSlice {
 	closing_parens: bool,
 	open_parens: bool,
+	bar: bool,
 	number: bool,
 	letter: bool,
 	variable: bool,
@@ -34,14 +37,17 @@ struct BoolSlice {
 }
 
 impl BoolSlice {
-	const fn from_char(c: &char, prev_masked_num: bool, prev_masked_var: bool) -> Self {
+	const fn from_char(
+		c: &char, in_function_name: bool, prev_masked_num: bool, prev_masked_var: bool,
+	) -> Self {
 		let isnumber = c.is_ascii_digit();
-		let isvariable = is_variable(c);
+		let isvariable = is_variable(c) && !in_function_name;
 		Self {
 			closing_parens: *c == ')',
 			open_parens: *c == '(',
+			bar: *c == '|',
 			number: isnumber,
-			letter: c.is_ascii_alphabetic(),
+			letter: c.is_ascii_alphabetic() | in_function_name,
 			variable: isvariable,
 			masked_num: match isnumber {
 				true => prev_masked_num,
@@ -65,14 +71,15 @@ impl BoolSlice {
 		} else if other.masked_var && self.variable {
 			// If previous char was a masked variable, and current char is a variable, mask current char's variable status
 			self.masked_var = true;
-		} else if other.letter && !other.is_unmasked_variable() {
+		} else if (other.letter && !other.is_unmasked_variable()) | other.masked_num {
+			// Letters and numbers following part of a function name (such as the 2 in `log2`) are masked
 			self.masked_num = self.number;
 			self.masked_var = self.variable;
 		}
 	}
 
 	const fn splitable(&self, c: &char, other: &BoolSlice, split: &SplitType) -> bool {
-		if (*c == '*') | (matches!(split, &SplitType::Term) && other.open_parens) {
+		if (*c == '*') | (matches!(split, &SplitType::Term) && (other.open_parens | other.bar)) {
 			true
 		} else if other.closing_parens {
 			// Cases like `)x`, `)2`, and `)(`
@@ -101,6 +108,27 @@ impl BoolSlice {
 	}
 }
 
+/// Marks the characters that are part of function and constant names (or of the start of one at the end of `chars`, which is being typed), so the variables in names such as `exp` and `erf` aren't split off
+fn function_name_mask(chars: &[char]) -> Vec<bool> {
+	let mut mask = vec![false; chars.len()];
+	let mut i = 0;
+	while i < chars.len() {
+		let rest = &chars[i..];
+		let len = SUPPORTED_FUNCTIONS
+			.iter()
+			.chain(CONSTANTS.iter().flat_map(|constant| constant.names))
+			.map(|name| name.chars().collect::<Vec<char>>())
+			.filter(|name| name.len() > 1)
+			.filter(|name| rest.starts_with(name) | name.starts_with(rest))
+			.map(|name| name.len().min(rest.len()))
+			.max()
+			.unwrap_or(0);
+		mask[i..(i + len)].fill(true);
+		i += len.max(1);
+	}
+	mask
+}
+
 // Splits a function (which is represented as an array of characters) based off of the value of SplitType
 pub fn split_function_chars(chars: &[char], split: SplitType) -> Vec<String> {
 	// Catch some basic cases
@@ -113,15 +141,23 @@ pub fn split_function_chars(chars: &[char], split: SplitType) -> Vec<String> {
 	// Resulting split-up data
 	let mut data: Vec<String> = std::vec::from_elem(chars[0].to_string(), 1);
 
+	let in_function_name = function_name_mask(chars);
+
 	// Setup first char here
-	let mut prev_char: BoolSlice = BoolSlice::from_char(&chars[0], false, false);
+	let mut prev_char: BoolSlice =
+		BoolSlice::from_char(&chars[0], in_function_name[0], false, false);
 
 	let mut last = unsafe { data.last_mut().unwrap_unchecked() };
 
 	// Iterate through all chars excluding the first one
-	for c in chars.iter().skip(1) {
+	for (c, in_function_name) in chars.iter().zip(in_function_name).skip(1) {
 		// Set data about current character
-		let mut curr_c = BoolSlice::from_char(c, prev_char.masked_num, prev_char.masked_var);
+		let mut curr_c = BoolSlice::from_char(
+			c,
+			in_function_name,
+			prev_char.masked_num,
+			prev_char.masked_var,
+		);
 
 		curr_c.calculate_mask(&prev_char);
 
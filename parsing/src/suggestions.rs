@@ -1,7 +1,30 @@
-use crate::{split_function_chars, SplitType};
+use crate::{split_function_chars, SplitType, SUPPORTED_FUNCTIONS};
 
 pub const HINT_EMPTY: Hint = Hint::Single("x^2");
-const HINT_CLOSED_PARENS: Hint = Hint::Single(")");
+
+/// Closing parentheses for each number of open ones (up to the most that are closed at once)
+const CLOSING_PARENS: [&str; 6] = [")", "))", ")))", "))))", ")))))", "))))))"];
+static HINT_CLOSED_PARENS: [Hint; 6] = [
+	Hint::Single(CLOSING_PARENS[0]),
+	Hint::Single(CLOSING_PARENS[1]),
+	Hint::Single(CLOSING_PARENS[2]),
+	Hint::Single(CLOSING_PARENS[3]),
+	Hint::Single(CLOSING_PARENS[4]),
+	Hint::Single(CLOSING_PARENS[5]),
+];
+const HINT_CLOSED_BAR: Hint = Hint::Single("|");
+const HINT_VARIABLE: Hint = Hint::Single("x");
+const HINT_LOG: Hint = Hint::Many(&["2(", "10(", "("]);
+const HINT_OPERATORS: Hint = Hint::Many(&["+", "-", "*", "/", "^"]);
+static HINT_OPERATORS_PARENS: [Hint; 6] = [
+	Hint::Many(&[CLOSING_PARENS[0], "+", "-", "*", "/", "^"]),
+	Hint::Many(&[CLOSING_PARENS[1], "+", "-", "*", "/", "^"]),
+	Hint::Many(&[CLOSING_PARENS[2], "+", "-", "*", "/", "^"]),
+	Hint::Many(&[CLOSING_PARENS[3], "+", "-", "*", "/", "^"]),
+	Hint::Many(&[CLOSING_PARENS[4], "+", "-", "*", "/", "^"]),
+	Hint::Many(&[CLOSING_PARENS[5], "+", "-", "*", "/", "^"]),
+];
+const HINT_OPERATORS_BAR: Hint = Hint::Many(&["|", "+", "-", "*", "/", "^"]);
 
 /// Only enacts println if cfg(test) is enabled
 #[allow(unused_macros)]
@@ -12,39 +35,222 @@ macro_rules! test_print {
     };
 }
 
-/// Generate a hint based on the input `input`, returns an `Option<String>`
-pub fn generate_hint<'a>(input: &str) -> &'a Hint<'a> {
-	if input.is_empty() {
-		&HINT_EMPTY
-	} else {
-		let chars: Vec<char> = input.chars().collect::<Vec<char>>();
-
-		let key = get_last_term(&chars);
-		match key {
-			Some(key) => {
-				if let Some(hint) = COMPLETION_HASHMAP.get(&key) {
-					return hint;
-				}
+/// Tokens of the input that hints are based on
+#[derive(PartialEq, Debug)]
+enum HintToken {
+	Number,
+	/// Name of a function, constant, or variable, which can have digits after the first letter (such as `log2`)
+	Name(String),
+	Operator,
+	Comma,
+	OpenParen,
+	CloseParen,
+	Bar,
+	Factorial,
+	Other,
+}
+
+/// Splits `chars` into the tokens hints are based on, where names run until the next character that isn't a letter or digit (so `xsi` is a single name)
+fn hint_tokens(chars: &[char]) -> Vec<HintToken> {
+	let mut tokens = Vec::new();
+	let mut i = 0;
+	while i < chars.len() {
+		let c = chars[i];
+		let run = |allowed: fn(&char) -> bool| chars[i..].iter().take_while(|c| allowed(c)).count();
+		let (token, len) = match c {
+			_ if c.is_alphabetic() => {
+				let len = run(|c| c.is_alphanumeric());
+				(HintToken::Name(chars[i..(i + len)].iter().collect()), len)
 			}
-			None => {
-				return &Hint::None;
+			_ if c.is_ascii_digit() | (c == '.') => {
+				(HintToken::Number, run(|c| c.is_ascii_digit() | (*c == '.')))
 			}
-		}
+			'+' | '-' | '*' | '/' | '^' | '%' => (HintToken::Operator, 1),
+			',' => (HintToken::Comma, 1),
+			'(' => (HintToken::OpenParen, 1),
+			')' => (HintToken::CloseParen, 1),
+			'|' => (HintToken::Bar, 1),
+			'!' => (HintToken::Factorial, 1),
+			_ if c.is_whitespace() => {
+				i += 1;
+				continue;
+			}
+			_ => (HintToken::Other, 1),
+		};
+		tokens.push(token);
+		i += len;
+	}
+	tokens
+}
 
-		let mut open_parens: usize = 0;
-		let mut closed_parens: usize = 0;
-		chars.iter().for_each(|chr| match *chr {
-			'(' => open_parens += 1,
-			')' => closed_parens += 1,
+/// Generate a hint based on the input `input`, returns an `Option<String>`
+pub fn generate_hint<'a>(input: &str) -> &'a Hint<'a> {
+	hint_for(&input.chars().collect::<Vec<char>>(), true)
+}
+
+/// Generate a hint for the term ending at the character index `cursor` of `input`, to be inserted there
+pub fn generate_hint_at<'a>(input: &str, cursor: usize) -> &'a Hint<'a> {
+	let chars: Vec<char> = input.chars().collect::<Vec<char>>();
+	if cursor >= chars.len() {
+		return hint_for(&chars, true);
+	}
+
+	// Completing in the middle of a term (or right before a parenthesis) would mangle what's after the cursor
+	if chars[cursor].is_alphanumeric() | (chars[cursor] == '(') {
+		return &Hint::None;
+	}
+
+	hint_for(&chars[..cursor], false)
+}
+
+/// Name right before the character index `cursor` of `input`, which is being completed, being `None` if there isn't one
+pub(crate) fn name_before(input: &str, cursor: usize) -> Option<String> {
+	let chars: Vec<char> = input.chars().collect::<Vec<char>>();
+	let cursor = cursor.min(chars.len());
+
+	// Like built in names, these aren't completed in the middle of a term
+	if chars
+		.get(cursor)
+		.is_some_and(|c| c.is_alphanumeric() | (*c == '('))
+	{
+		return None;
+	}
+
+	match hint_tokens(&chars[..cursor]).pop() {
+		Some(HintToken::Name(name)) => Some(name),
+		_ => None,
+	}
+}
+
+/// Completions from `terms` (names only known at runtime, such as user defined functions) of the name before the character index `cursor` of `input`
+pub fn complete_terms(input: &str, cursor: usize, terms: &[String]) -> Vec<String> {
+	match name_before(input, cursor) {
+		Some(name) => terms
+			.iter()
+			.filter_map(|term| term.strip_prefix(name.as_str()))
+			.filter(|rest| !rest.is_empty())
+			.map(str::to_owned)
+			.collect(),
+		None => Vec::new(),
+	}
+}
+
+/// Hint based on the last token of `chars`, where closing parentheses, closing bars, and operators are only hinted `at_end` of the input
+fn hint_for<'a>(chars: &[char], at_end: bool) -> &'a Hint<'a> {
+	let tokens = hint_tokens(chars);
+
+	let bars = tokens
+		.iter()
+		.filter(|token| **token == HintToken::Bar)
+		.count();
+
+	// Every parenthesis that's still open is closed, and an odd number of absolute value bars leaves one open, such as in `|x`
+	let open_parens = unmatched_parens_in(chars).1.len().min(CLOSING_PARENS.len());
+	let bar_open = bars % 2 == 1;
+	let closing = match (open_parens, bar_open) {
+		(1.., _) => &HINT_CLOSED_PARENS[open_parens - 1],
+		(0, true) => &HINT_CLOSED_BAR,
+		(0, false) => &Hint::None,
+	};
+
+	match tokens.last() {
+		None if at_end => &HINT_EMPTY,
+		None => &Hint::None,
+		// Bases of logarithms, as `log` alone is the natural logarithm
+		Some(HintToken::Name(name)) if name == "log" => &HINT_LOG,
+		// Names are completed as a whole, so a function isn't suggested right after another name (such as the `si` in `xsi`)
+		Some(HintToken::Name(name)) => match COMPLETION_HASHMAP.get(name) {
+			Some(hint) => hint,
+			None if at_end => closing,
+			None => &Hint::None,
+		},
+		// Arguments (and the insides of absolute values) start with a variable
+		Some(HintToken::OpenParen) => &HINT_VARIABLE,
+		Some(HintToken::Bar) if bar_open => &HINT_VARIABLE,
+		_ if !at_end => &Hint::None,
+		Some(HintToken::Number) => match (open_parens, bar_open) {
+			(1.., _) => &HINT_OPERATORS_PARENS[open_parens - 1],
+			(0, true) => &HINT_OPERATORS_BAR,
+			(0, false) => &HINT_OPERATORS,
+		},
+		Some(HintToken::CloseParen | HintToken::Bar | HintToken::Factorial) => closing,
+		Some(HintToken::Operator | HintToken::Comma | HintToken::Other) => &Hint::None,
+	}
+}
+
+/// Indices in `chars` of the parentheses that aren't matched, being the `)`s that close nothing and the `(`s that are never closed
+fn unmatched_parens_in(chars: &[char]) -> (Vec<usize>, Vec<usize>) {
+	let mut stray = Vec::new();
+	let mut open = Vec::new();
+	for (i, c) in chars.iter().enumerate() {
+		match c {
+			'(' => open.push(i),
+			')' if open.pop().is_none() => stray.push(i),
 			_ => {}
-		});
+		}
+	}
+	(stray, open)
+}
+
+/// Byte indices of the parentheses in `input` that aren't matched (in order), so they can be pointed out
+pub fn unmatched_parens(input: &str) -> Vec<usize> {
+	let (indices, chars): (Vec<usize>, Vec<char>) = input.char_indices().unzip();
+	let (stray, open) = unmatched_parens_in(&chars);
+
+	// Stray `)`s all come before the `(`s that are never closed, as those would've closed them
+	stray.into_iter().chain(open).map(|i| indices[i]).collect()
+}
+
+/// Number of insertions, deletions, substitutions, and swaps of adjacent characters it takes to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+	let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
 
-		if open_parens > closed_parens {
-			return &HINT_CLOSED_PARENS;
+	// distances[i][j] is the distance between the first i chars of `a` and the first j of `b`
+	let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+	for i in 0..=a.len() {
+		for j in 0..=b.len() {
+			distances[i][j] = match (i, j) {
+				(0, _) => j,
+				(_, 0) => i,
+				_ => {
+					let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+					let edit = substitution
+						.min(distances[i - 1][j] + 1)
+						.min(distances[i][j - 1] + 1);
+					match (i > 1) && (j > 1) && (a[i - 1] == b[j - 2]) && (a[i - 2] == b[j - 1]) {
+						true => edit.min(distances[i - 2][j - 2] + 1),
+						false => edit,
+					}
+				}
+			};
 		}
+	}
+	distances[a.len()][b.len()]
+}
 
-		&Hint::None
+/// Supported function closest to the unknown function name `name`, to correct misspellings such as `sni` or `sqtr` (which are
+/// at most 1 edit away from `sin` and `sqrt`, or 2 for names longer than 4 characters)
+pub fn closest_function(name: &str) -> Option<&'static str> {
+	// `log` is also the natural logarithm
+	let functions = || SUPPORTED_FUNCTIONS.iter().chain(["log"].iter());
+
+	let name = name.to_ascii_lowercase();
+	if name.is_empty()
+		| !name.chars().all(char::is_alphanumeric)
+		| functions().any(|function| *function == name)
+	{
+		return None;
 	}
+
+	let max_edits = match name.chars().count() {
+		0..=4 => 1,
+		_ => 2,
+	};
+	functions()
+		.map(|function| (*function, edit_distance(&name, function)))
+		.filter(|(_, edits)| *edits <= max_edits)
+		.min_by_key(|(_, edits)| *edits)
+		.map(|(function, _)| function)
 }
 
 pub fn get_last_term(chars: &[char]) -> Option<String> {
@@ -0,0 +1,237 @@
+use crate::constants::CONSTANTS;
+use crate::expression::{Expression, Function, MultiFunction, Operator, Series, Variable};
+
+/// Layout of an [`Expression`] as typeset math, made of text and the structures around it
+#[derive(Clone, PartialEq, Debug)]
+pub enum Typeset {
+	/// Run of text, such as a number, variable, operator, or the name of a function
+	Text(String),
+
+	/// Typesets next to each other, vertically centered
+	Row(Vec<Typeset>),
+
+	/// Numerator over the denominator, separated by a fraction bar
+	Fraction(Box<Typeset>, Box<Typeset>),
+
+	/// Base and its superscript exponent
+	Power(Box<Typeset>, Box<Typeset>),
+
+	/// Radicand under a root sign, along with the index of the root (such as 3 for cube roots) if it isn't a square root
+	Root(Box<Typeset>, Option<Box<Typeset>>),
+
+	/// Opening delimiter, contents, and closing delimiter, with the delimiters (such as parentheses) stretching to the height of the contents
+	Delimited(char, Box<Typeset>, char),
+
+	/// Large operator (such as Σ), the limits below and above it, and its body
+	BigOperator(char, Box<Typeset>, Box<Typeset>, Box<Typeset>),
+}
+
+impl Typeset {
+	fn text(text: impl Into<String>) -> Self { Self::Text(text.into()) }
+
+	fn parenthesized(inner: Self) -> Self { Self::Delimited('(', Box::new(inner), ')') }
+}
+
+// How tightly typeset expressions bind, where ones binding less tightly than their surroundings are parenthesized
+const SERIES: u8 = 0;
+const SUM: u8 = 1;
+const PRODUCT: u8 = 2;
+const FRACTION: u8 = 3;
+const POWER: u8 = 4;
+const POSTFIX: u8 = 5;
+const ATOM: u8 = 6;
+
+/// Numbers at least this large (or smaller than its reciprocal) are written in scientific notation
+pub(crate) const SCIENTIFIC_MIN: f64 = 1e6;
+
+/// Typeset of `value`, where constants are written by their name and very large or small numbers in scientific notation
+fn number(value: f64) -> Typeset {
+	// Symbols (such as π) are preferred over the names of constants
+	if let Some(constant) = CONSTANTS.iter().find(|constant| constant.value == value) {
+		let name = constant
+			.names
+			.iter()
+			.find(|name| !name.is_ascii())
+			.unwrap_or(&constant.names[0]);
+		return Typeset::text(*name);
+	}
+
+	let magnitude = value.abs();
+	if value.is_finite()
+		&& (magnitude != 0.0)
+		&& !((1.0 / SCIENTIFIC_MIN)..SCIENTIFIC_MIN).contains(&magnitude)
+	{
+		let formatted = format!("{:e}", value);
+		let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+		let power = Typeset::Power(
+			Box::new(Typeset::text("10")),
+			Box::new(Typeset::text(exponent.replace('-', "−"))),
+		);
+		return match mantissa {
+			"1" => power,
+			_ => Typeset::Row(vec![
+				Typeset::text(format!("{}×", mantissa.replace('-', "−"))),
+				power,
+			]),
+		};
+	}
+
+	Typeset::text(value.to_string().replace('-', "−"))
+}
+
+impl Expression {
+	/// Typesets the expression, where x is written as `x_name` (such as θ for polar functions)
+	pub fn typeset(&self, x_name: &str) -> Typeset {
+		match self {
+			Self::Number(value) => number(*value),
+			Self::Variable(Variable::X) => Typeset::text(x_name),
+			Self::Variable(Variable::Y) => Typeset::text("y"),
+			Self::ImaginaryUnit => Typeset::text("i"),
+			Self::Parameter(name) => Typeset::text(name),
+			Self::Negate(inner) => Typeset::Row(vec![
+				Typeset::text("−"),
+				inner.typeset_operand(x_name, PRODUCT),
+			]),
+			Self::Binary(Operator::Div, numerator, denominator) => Typeset::Fraction(
+				Box::new(numerator.typeset(x_name)),
+				Box::new(denominator.typeset(x_name)),
+			),
+			Self::Binary(Operator::Pow, base, exponent) => Typeset::Power(
+				Box::new(base.typeset_operand(x_name, POSTFIX)),
+				Box::new(exponent.typeset(x_name)),
+			),
+			Self::Binary(Operator::Mul, lhs, rhs) => {
+				// Negated factors on the left, such as in -2x, don't change what the product means
+				let lhs = match **lhs {
+					Self::Negate(_) => lhs.typeset(x_name),
+					_ => lhs.typeset_operand(x_name, PRODUCT),
+				};
+				let rhs = rhs.typeset_operand(x_name, PRODUCT);
+				match self.implicit_multiplication() {
+					true => Typeset::Row(vec![lhs, rhs]),
+					false => Typeset::Row(vec![lhs, Typeset::text("·"), rhs]),
+				}
+			}
+			Self::Binary(operator, lhs, rhs) => Typeset::Row(vec![
+				lhs.typeset_operand(x_name, SUM),
+				Typeset::text(match operator {
+					Operator::Add => " + ",
+					_ => " − ",
+				}),
+				rhs.typeset_operand(x_name, PRODUCT),
+			]),
+			Self::Function(function, argument) => {
+				let inner = || Box::new(argument.typeset(x_name));
+				match function {
+					Function::Abs => Typeset::Delimited('|', inner(), '|'),
+					Function::Sqrt => Typeset::Root(inner(), None),
+					Function::Cbrt => Typeset::Root(inner(), Some(Box::new(Typeset::text("3")))),
+					Function::Exp => Typeset::Power(Box::new(Typeset::text("e")), inner()),
+					Function::Factorial => Typeset::Row(vec![
+						argument.typeset_operand(x_name, ATOM),
+						Typeset::text("!"),
+					]),
+					_ => Typeset::Row(vec![
+						Typeset::text(match function {
+							Function::Log2 => "log₂",
+							Function::Log10 => "log₁₀",
+							Function::Gamma => "Γ",
+							_ => function.name(),
+						}),
+						Typeset::Delimited('(', inner(), ')'),
+					]),
+				}
+			}
+			Self::MultiFunction(MultiFunction::Mod, args) => Typeset::Row(vec![
+				args[0].typeset_operand(x_name, POSTFIX),
+				Typeset::text(" mod "),
+				args[1].typeset_operand(x_name, POSTFIX),
+			]),
+			Self::MultiFunction(function, args) => {
+				let mut arguments = Vec::with_capacity((2 * args.len()) - 1);
+				for (i, arg) in args.iter().enumerate() {
+					if i != 0 {
+						arguments.push(Typeset::text(", "));
+					}
+					arguments.push(arg.typeset(x_name));
+				}
+				Typeset::Row(vec![
+					Typeset::text(function.name()),
+					Typeset::Delimited('(', Box::new(Typeset::Row(arguments)), ')'),
+				])
+			}
+			Self::Series(series, index, args) => Typeset::BigOperator(
+				match series {
+					Series::Sum => 'Σ',
+					Series::Product => 'Π',
+				},
+				Box::new(Typeset::Row(vec![
+					Typeset::text(index),
+					Typeset::text(" = "),
+					args[0].typeset(x_name),
+				])),
+				Box::new(args[1].typeset(x_name)),
+				Box::new(args[2].typeset_operand(x_name, PRODUCT)),
+			),
+		}
+	}
+
+	/// Typesets the expression as an operand of an operation binding as tightly as `precedence`, parenthesizing it if it binds less tightly
+	fn typeset_operand(&self, x_name: &str, precedence: u8) -> Typeset {
+		match self.precedence() < precedence {
+			true => Typeset::parenthesized(self.typeset(x_name)),
+			false => self.typeset(x_name),
+		}
+	}
+
+	/// How tightly the typeset expression binds
+	fn precedence(&self) -> u8 {
+		match self {
+			Self::Number(value) => match number(*value) {
+				_ if *value < 0.0 => SUM,
+				Typeset::Text(_) => ATOM,
+				// Scientific notation
+				_ => PRODUCT,
+			},
+			Self::Negate(_) | Self::Binary(Operator::Add | Operator::Sub, _, _) => SUM,
+			Self::Binary(Operator::Mul, _, _) | Self::MultiFunction(MultiFunction::Mod, _) => {
+				PRODUCT
+			}
+			Self::Binary(Operator::Div, _, _) => FRACTION,
+			Self::Binary(Operator::Pow, _, _) | Self::Function(Function::Exp, _) => POWER,
+			Self::Function(Function::Factorial, _) => POSTFIX,
+			// The body of a series extends to its right, so it's parenthesized wherever something could follow it
+			Self::Series(..) => SERIES,
+			_ => ATOM,
+		}
+	}
+
+	/// If the product (such as 2x) is written without a multiplication sign
+	fn implicit_multiplication(&self) -> bool {
+		let Self::Binary(Operator::Mul, lhs, rhs) = self else {
+			return false;
+		};
+
+		let is_letter = |expression: &Self| {
+			matches!(
+				expression,
+				Self::Variable(_) | Self::ImaginaryUnit | Self::Parameter(_)
+			)
+		};
+		let is_plain_number = match **lhs {
+			Self::Number(value) => matches!(
+				number(value),
+				Typeset::Text(text) if text.starts_with(|c: char| c.is_ascii_digit())
+			),
+			_ => false,
+		};
+
+		is_plain_number
+			&& match &**rhs {
+				Self::Function(Function::Factorial, _) => false,
+				Self::Binary(Operator::Pow, base, _) => is_letter(base),
+				Self::MultiFunction(MultiFunction::Mod, _) => false,
+				rhs => is_letter(rhs) | matches!(rhs, Self::Function(..) | Self::MultiFunction(..)),
+			}
+	}
+}
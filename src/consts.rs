@@ -28,6 +28,36 @@ const_assert!(DEFAULT_MAX_X > DEFAULT_MIN_X);
 /// Default number of integral boxes
 pub const DEFAULT_INTEGRAL_NUM: usize = 100;
 
+/// Default error tolerance used by adaptive integration
+pub const DEFAULT_INTEGRAL_TOLERANCE: f64 = 0.0001;
+
+const_assert!(DEFAULT_INTEGRAL_TOLERANCE > 0.0);
+
+/// Default tolerance roots are refined to
+pub const DEFAULT_ROOT_TOLERANCE: f64 = 1e-12;
+
+const_assert!(DEFAULT_ROOT_TOLERANCE > 0.0);
+
+/// Relative distance within which areas and roots are recognized as simple closed forms (such as π/2 or 2/3)
+pub const CLOSED_FORM_TOLERANCE: f64 = 1e-9;
+
+const_assert!(CLOSED_FORM_TOLERANCE > 0.0);
+
+/// Default number of random samples used by Monte Carlo integration
+pub const DEFAULT_MONTE_CARLO_SAMPLES: usize = 1000;
+
+/// Default value of parameters (such as `a` in `a*sin(x)`)
+pub const DEFAULT_PARAMETER: f64 = 1.0;
+
+/// Color of rectangles of Riemann sums with more area than the function has over their interval
+pub const OVERESTIMATE_COLOR: Color32 = Color32::from_rgb(213, 94, 0);
+
+/// Color of rectangles of Riemann sums with less area than the function has over their interval
+pub const UNDERESTIMATE_COLOR: Color32 = Color32::from_rgb(0, 114, 178);
+
+/// Color of the sum an integral is compared to, which stands out from the colors functions are assigned
+pub const COMPARISON_COLOR: Color32 = Color32::from_rgb(204, 121, 167);
+
 /// Colors used for plotting
 // Colors commented out are used elsewhere and are not included here for better user experience
 pub const COLORS: [Color32; 13] = [
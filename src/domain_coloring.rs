@@ -0,0 +1,119 @@
+use egui::{ColorImage, Context, TextureHandle, TextureOptions};
+use epaint::{Color32, Hsva};
+use parsing::Complex;
+use std::f64::consts::TAU;
+
+/// Domain coloring of a complex function f(z) over the plot, cached until the plot's bounds or width change
+#[derive(Clone)]
+pub struct DomainColoring {
+	/// Bounds (minimum and maximum corners) and pixel width of the plot the image was colored over
+	key: Option<([f64; 2], [f64; 2], usize)>,
+
+	/// Image that has been colored, but not yet uploaded to `texture`
+	image: Option<ColorImage>,
+
+	/// Texture of the image drawn on the plot
+	pub texture: Option<TextureHandle>,
+}
+
+impl const Default for DomainColoring {
+	fn default() -> DomainColoring {
+		DomainColoring {
+			key: None,
+			image: None,
+			texture: None,
+		}
+	}
+}
+
+impl DomainColoring {
+	/// Recolors the image if the plot's bounds (`min` and `max` corners) or `width` changed since it was last colored
+	pub fn update(
+		&mut self, f: &mut impl FnMut(Complex) -> Complex, min: [f64; 2], max: [f64; 2],
+		width: usize,
+	) {
+		/// Width (in screen pixels) of each pixel of the image
+		const PIXEL_SIZE: usize = 2;
+
+		/// Maximum number of rows of pixels, for plots much taller than they are wide
+		const MAX_ROWS: usize = 2048;
+
+		let key = Some((min, max, width));
+		if self.key == key {
+			return;
+		}
+		self.key = key;
+
+		let columns = (width / PIXEL_SIZE).max(1);
+		let rows = ((columns as f64) * (max[1] - min[1]) / (max[0] - min[0]))
+			.ceil()
+			.clamp(1.0, MAX_ROWS as f64) as usize;
+		let (dx, dy) = (
+			(max[0] - min[0]) / (columns as f64),
+			(max[1] - min[1]) / (rows as f64),
+		);
+
+		// Images start at the top left, so rows are colored from the top of the plot down
+		let pixels = (0..rows)
+			.flat_map(|j| (0..columns).map(move |i| (i, j)))
+			.map(|(i, j)| {
+				domain_color(f(Complex::new(
+					min[0] + (dx * (i as f64 + 0.5)),
+					max[1] - (dy * (j as f64 + 0.5)),
+				)))
+			})
+			.collect();
+		self.image = Some(ColorImage {
+			size: [columns, rows],
+			pixels,
+		});
+	}
+
+	/// Uploads the newly colored image (if there is one) to the texture drawn on the plot
+	pub fn load_texture(&mut self, ctx: &Context) {
+		if let Some(image) = self.image.take() {
+			match self.texture {
+				Some(ref mut texture) => texture.set(image, TextureOptions::NEAREST),
+				None => {
+					self.texture =
+						Some(ctx.load_texture("domain_coloring", image, TextureOptions::NEAREST))
+				}
+			}
+		}
+	}
+
+	/// Center and size of the area of the plot the image was colored over
+	pub fn bounds(&self) -> Option<([f64; 2], [f64; 2])> {
+		self.key.map(|(min, max, _)| {
+			(
+				[(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0],
+				[max[0] - min[0], max[1] - min[1]],
+			)
+		})
+	}
+
+	/// Invalidates the image
+	pub fn clear(&mut self) {
+		self.key = None;
+		self.image = None;
+	}
+}
+
+/// Color of the value `w` of a complex function: its hue is the argument of `w`, and its brightness cycles with each doubling of |w|.
+/// Undefined values are transparent, zeros are black, and infinite values are white.
+pub fn domain_color(w: Complex) -> Color32 {
+	if w.re.is_nan() | w.im.is_nan() {
+		return Color32::TRANSPARENT;
+	}
+
+	let abs = w.abs();
+	if abs == 0.0 {
+		return Color32::BLACK;
+	} else if abs.is_infinite() {
+		return Color32::WHITE;
+	}
+
+	let hue = (w.arg() / TAU).rem_euclid(1.0);
+	let brightness = 0.7 + (0.3 * abs.log2().rem_euclid(1.0));
+	Color32::from(Hsva::new(hue as f32, 0.9, brightness as f32, 1.0))
+}
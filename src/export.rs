@@ -0,0 +1,1073 @@
+use crate::{function_entry::CurveStyle, i18n::Translations};
+use egui::ColorImage;
+use egui_plot::{Bar, LineStyle, PlotBounds, PlotPoint};
+use emath::{pos2, vec2, Pos2, Rect, Vec2};
+use epaint::{text::Fonts, Color32, FontId, Stroke};
+use std::{cell::RefCell, fmt::Write, rc::Rc};
+
+/// Color behind the plot, the same as the central panel's
+const BACKGROUND: Color32 = Color32::from_gray(27);
+
+/// Color of the grid lines
+const GRID_COLOR: Color32 = Color32::from_gray(50);
+
+/// Color of the x and y axes
+const AXIS_COLOR: Color32 = Color32::from_gray(110);
+
+/// Color of the tick labels and the legend's text
+const TEXT_COLOR: Color32 = Color32::from_gray(200);
+
+/// Size (in points) of the text of the tick labels and the legend
+const FONT_SIZE: f32 = 12.0;
+
+/// Width (in points) of lines that aren't given one, the same as egui_plot's
+const LINE_WIDTH: f32 = 1.5;
+
+/// Opacity of the inside of filled areas and bars, which are outlined in their full color
+const FILL_ALPHA: f32 = 0.2;
+
+/// Distance (in points) aimed for between grid lines
+const GRID_SPACING: f32 = 80.0;
+
+/// Distance (in points) of the legend and tick labels from the edges of the image
+const MARGIN: f32 = 8.0;
+
+/// Width (in points) of the line drawn next to each function in the legend
+const SWATCH_WIDTH: f32 = 16.0;
+
+/// File formats the plot can be exported as
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ExportFormat {
+	Png,
+	Svg,
+
+	/// HTML page with the plot, along with the functions and their results (see [`report_html`])
+	Report,
+}
+
+impl ExportFormat {
+	/// Extension of files in this format
+	pub const fn extension(&self) -> &'static str {
+		match self {
+			ExportFormat::Png => "png",
+			ExportFormat::Svg => "svg",
+			ExportFormat::Report => "html",
+		}
+	}
+
+	/// MIME type of files in this format, which the browser is told when downloading them
+	pub const fn mime_type(&self) -> &'static str {
+		match self {
+			ExportFormat::Png => "image/png",
+			ExportFormat::Svg => "image/svg+xml",
+			ExportFormat::Report => "text/html",
+		}
+	}
+}
+
+/// Something drawn in an exported image, positioned in plot coordinates
+#[derive(Clone, Debug)]
+pub enum ExportShape {
+	/// Line through the points
+	Line {
+		points: Vec<PlotPoint>,
+		stroke: Stroke,
+		style: CurveStyle,
+	},
+
+	/// Area between the line through the points and the x-axis
+	Fill {
+		points: Vec<PlotPoint>,
+		color: Color32,
+	},
+
+	/// Circle around each point, `radius` being in points
+	Points {
+		points: Vec<PlotPoint>,
+		radius: f32,
+		color: Color32,
+	},
+
+	/// Rectangles between opposite corners, outlined in `color` and filled with a faint version of it
+	Rects {
+		rects: Vec<[PlotPoint; 2]>,
+		color: Color32,
+	},
+
+	/// Text with its bottom left corner at `position`, such as an annotation
+	Text {
+		position: PlotPoint,
+		text: String,
+		color: Color32,
+	},
+}
+
+impl ExportShape {
+	/// Solid line through `points`, as thick as egui_plot draws lines by default
+	pub fn line(points: Vec<PlotPoint>, color: Color32) -> ExportShape {
+		ExportShape::Line {
+			points,
+			stroke: Stroke::new(LINE_WIDTH, color),
+			style: CurveStyle::Solid,
+		}
+	}
+
+	/// Rectangles of `bars` as drawn in a [`egui_plot::BarChart`], each being `width` wide if it's given
+	pub fn bars(bars: &[Bar], width: Option<f64>, color: Color32) -> ExportShape {
+		ExportShape::Rects {
+			rects: bars
+				.iter()
+				.filter(|bar| bar.value.is_finite())
+				.map(|bar| {
+					let width = width.unwrap_or(bar.bar_width);
+					let base = bar.base_offset.unwrap_or(0.0);
+					[
+						PlotPoint::new(bar.argument - (width / 2.0), base),
+						PlotPoint::new(bar.argument + (width / 2.0), base + bar.value),
+					]
+				})
+				.collect(),
+			color,
+		}
+	}
+}
+
+/// Image of the plot that's drawn separately from egui, so it can be saved as a file at a higher resolution than it's shown at
+pub struct ExportImage {
+	/// Area of the plot shown in the image
+	bounds: PlotBounds,
+
+	/// Size of the image in points, which is the size of the plot on screen
+	size: Vec2,
+
+	/// Pixels per point of the image
+	scale: f32,
+
+	/// Shapes drawn in the image, from back to front
+	shapes: Vec<ExportShape>,
+
+	/// Text and color of each entry in the legend
+	legend: Vec<(String, Color32)>,
+}
+
+impl ExportImage {
+	/// Image of the area `bounds` of the plot, which is `size` points large on screen, with `scale` pixels per point
+	pub fn new(bounds: PlotBounds, size: Vec2, scale: f32) -> ExportImage {
+		ExportImage {
+			bounds,
+			size: size.max(Vec2::splat(1.0)),
+			scale: scale.max(0.1),
+			shapes: Vec::new(),
+			legend: Vec::new(),
+		}
+	}
+
+	/// Draws `shape` in front of the ones already added
+	pub fn push(&mut self, shape: ExportShape) { self.shapes.push(shape); }
+
+	/// Draws `bars` like [`ExportShape::bars`] does, each in its own color if it has one (such as rectangles colored by whether
+	/// they over or underestimate the area) and in `color` otherwise
+	pub fn push_bars(&mut self, bars: &[Bar], width: Option<f64>, color: Color32) {
+		let bar_color = |bar: &Bar| match bar.stroke.color == Color32::TRANSPARENT {
+			true => color,
+			false => bar.stroke.color,
+		};
+
+		// One shape of each color, in the order the colors first appear in
+		let mut colors: Vec<Color32> = Vec::new();
+		for bar in bars {
+			if !colors.contains(&bar_color(bar)) {
+				colors.push(bar_color(bar));
+			}
+		}
+		for color in colors {
+			let colored: Vec<Bar> = bars
+				.iter()
+				.filter(|bar| bar_color(bar) == color)
+				.cloned()
+				.collect();
+			self.push(ExportShape::bars(&colored, width, color));
+		}
+	}
+
+	/// Adds an entry to the legend
+	pub fn push_legend(&mut self, text: String, color: Color32) { self.legend.push((text, color)); }
+
+	/// Size of the image in pixels
+	pub fn pixel_size(&self) -> [usize; 2] {
+		[
+			(self.size.x * self.scale).round().max(1.0) as usize,
+			(self.size.y * self.scale).round().max(1.0) as usize,
+		]
+	}
+
+	/// Position (in points, from the top left) of `point` in the image. Points far outside of it are brought closer, but in the same direction.
+	fn to_screen(&self, point: PlotPoint) -> Pos2 {
+		let (min, max) = (self.bounds.min(), self.bounds.max());
+		let limit = 100.0 * self.size.x.max(self.size.y);
+		pos2(
+			(((point.x - min[0]) / (max[0] - min[0])) as f32 * self.size.x).clamp(-limit, limit),
+			(((max[1] - point.y) / (max[1] - min[1])) as f32 * self.size.y).clamp(-limit, limit),
+		)
+	}
+
+	/// Values of the grid lines across `min` to `max`, spaced 1, 2, or 5 times a power of 10 apart so that they're about
+	/// [`GRID_SPACING`] apart along `length` points
+	fn grid_lines(min: f64, max: f64, length: f32) -> Vec<f64> {
+		let target = (max - min) * (GRID_SPACING / length) as f64;
+		if !target.is_normal() {
+			return Vec::new();
+		}
+
+		let magnitude = 10.0_f64.powf(target.log10().floor());
+		let spacing = [1.0, 2.0, 5.0, 10.0]
+			.into_iter()
+			.map(|multiple| multiple * magnitude)
+			.find(|spacing| *spacing >= target)
+			.unwrap_or(10.0 * magnitude);
+
+		((min / spacing).ceil() as i64..=(max / spacing).floor() as i64)
+			.map(|i| (i as f64) * spacing)
+			.collect()
+	}
+
+	/// Segments (in points) of the grid lines and axes, each with its color, along with the tick labels and where their top left corners are
+	fn grid(&self) -> (Vec<([Pos2; 2], Color32)>, Vec<(Pos2, String)>) {
+		let (min, max) = (self.bounds.min(), self.bounds.max());
+		let mut lines = Vec::new();
+		let mut labels = Vec::new();
+
+		for x in Self::grid_lines(min[0], max[0], self.size.x) {
+			let screen_x = self.to_screen(PlotPoint::new(x, 0.0)).x;
+			let color = match x == 0.0 {
+				true => AXIS_COLOR,
+				false => GRID_COLOR,
+			};
+			lines.push(([pos2(screen_x, 0.0), pos2(screen_x, self.size.y)], color));
+			labels.push((
+				pos2(screen_x + 2.0, self.size.y - FONT_SIZE - MARGIN),
+				emath::round_to_decimals(x, 6).to_string(),
+			));
+		}
+
+		for y in Self::grid_lines(min[1], max[1], self.size.y) {
+			let screen_y = self.to_screen(PlotPoint::new(0.0, y)).y;
+			let color = match y == 0.0 {
+				true => AXIS_COLOR,
+				false => GRID_COLOR,
+			};
+			lines.push(([pos2(0.0, screen_y), pos2(self.size.x, screen_y)], color));
+			labels.push((
+				pos2(MARGIN, screen_y - FONT_SIZE - 2.0),
+				emath::round_to_decimals(y, 6).to_string(),
+			));
+		}
+
+		(lines, labels)
+	}
+
+	/// Area (in points) behind the legend, which is in the top right corner. Text is assumed to be about 0.6 times as wide as it is tall,
+	/// as its actual width isn't known when writing SVGs.
+	fn legend_rect(&self) -> Rect {
+		let longest = self
+			.legend
+			.iter()
+			.map(|(text, _)| text.chars().count())
+			.max()
+			.unwrap_or(0);
+		let size = vec2(
+			SWATCH_WIDTH + (3.0 * MARGIN / 2.0) + (longest as f32 * FONT_SIZE * 0.6),
+			(self.legend.len() as f32 * (FONT_SIZE + MARGIN / 2.0)) + (MARGIN / 2.0),
+		);
+		Rect::from_min_size(pos2(self.size.x - MARGIN - size.x, MARGIN), size)
+	}
+
+	/// Left end of the line next to the legend's `i`th entry, with its text to the right of it
+	fn legend_row(&self, i: usize) -> Pos2 {
+		let rect = self.legend_rect();
+		pos2(
+			rect.min.x + (MARGIN / 2.0),
+			rect.min.y
+				+ (MARGIN / 2.0)
+				+ (i as f32 * (FONT_SIZE + MARGIN / 2.0))
+				+ (FONT_SIZE / 2.0),
+		)
+	}
+
+	/// Writes the image as an SVG, which is scaled to its size in pixels
+	pub fn to_svg(&self) -> String {
+		let [width, height] = self.pixel_size();
+		let mut svg = format!(
+			"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"{}\">\n",
+			width, height, self.size.x, self.size.y, FONT_SIZE
+		);
+		let _ = writeln!(
+			svg,
+			"<rect width=\"100%\" height=\"100%\" {}/>",
+			svg_paint("fill", BACKGROUND, 1.0)
+		);
+
+		let (lines, labels) = self.grid();
+		for ([a, b], color) in lines {
+			let _ = writeln!(
+				svg,
+				"<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+				a.x,
+				a.y,
+				b.x,
+				b.y,
+				svg_paint("stroke", color, 1.0)
+			);
+		}
+
+		let axis_y = self.to_screen(PlotPoint::new(0.0, 0.0)).y;
+		for shape in self.shapes.iter() {
+			match shape {
+				ExportShape::Line {
+					points,
+					stroke,
+					style,
+				} => {
+					let dashes = match style.line_style() {
+						LineStyle::Solid => String::new(),
+						LineStyle::Dashed { length } => {
+							format!(" stroke-dasharray=\"{} {}\"", length, length)
+						}
+						// Dashes of no length, which are dots because of their round caps
+						LineStyle::Dotted { spacing } => {
+							format!(" stroke-dasharray=\"0 {}\"", spacing)
+						}
+					};
+					for segment in self.segments(points) {
+						let _ = writeln!(
+							svg,
+							"<polyline points=\"{}\" fill=\"none\" {} stroke-width=\"{}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"{}/>",
+							svg_points(&segment),
+							svg_paint("stroke", stroke.color, 1.0),
+							stroke.width,
+							dashes
+						);
+					}
+				}
+				ExportShape::Fill { points, color } => {
+					for segment in self.segments(points) {
+						let (first, last) = (segment[0], segment[segment.len() - 1]);
+						let outline: Vec<Pos2> = std::iter::once(pos2(first.x, axis_y))
+							.chain(segment)
+							.chain(std::iter::once(pos2(last.x, axis_y)))
+							.collect();
+						let _ = writeln!(
+							svg,
+							"<polygon points=\"{}\" {}/>",
+							svg_points(&outline),
+							svg_paint("fill", *color, FILL_ALPHA)
+						);
+					}
+				}
+				ExportShape::Points {
+					points,
+					radius,
+					color,
+				} => {
+					for point in self.segments(points).into_iter().flatten() {
+						let _ = writeln!(
+							svg,
+							"<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+							point.x,
+							point.y,
+							radius,
+							svg_paint("fill", *color, 1.0)
+						);
+					}
+				}
+				ExportShape::Rects { rects, color } => {
+					for [a, b] in rects {
+						let rect = Rect::from_two_pos(self.to_screen(*a), self.to_screen(*b));
+						let _ = writeln!(
+							svg,
+							"<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {} {}/>",
+							rect.min.x,
+							rect.min.y,
+							rect.width(),
+							rect.height(),
+							svg_paint("fill", *color, FILL_ALPHA),
+							svg_paint("stroke", *color, 1.0)
+						);
+					}
+				}
+				ExportShape::Text {
+					position,
+					text,
+					color,
+				} => {
+					let pos = self.to_screen(*position);
+					let _ = writeln!(
+						svg,
+						"<text x=\"{}\" y=\"{}\" dominant-baseline=\"hanging\" {}>{}</text>",
+						pos.x,
+						pos.y - FONT_SIZE,
+						svg_paint("fill", *color, 1.0),
+						svg_escape(text)
+					);
+				}
+			}
+		}
+
+		for (pos, label) in labels {
+			let _ = writeln!(
+				svg,
+				"<text x=\"{}\" y=\"{}\" dominant-baseline=\"hanging\" {}>{}</text>",
+				pos.x,
+				pos.y,
+				svg_paint("fill", TEXT_COLOR, 1.0),
+				svg_escape(&label)
+			);
+		}
+
+		if !self.legend.is_empty() {
+			let rect = self.legend_rect();
+			let _ = writeln!(
+				svg,
+				"<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"3\" {} {}/>",
+				rect.min.x,
+				rect.min.y,
+				rect.width(),
+				rect.height(),
+				svg_paint("fill", BACKGROUND, 0.8),
+				svg_paint("stroke", GRID_COLOR, 1.0)
+			);
+			for (i, (text, color)) in self.legend.iter().enumerate() {
+				let row = self.legend_row(i);
+				let _ = writeln!(
+					svg,
+					"<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {} stroke-width=\"2\"/>",
+					row.x,
+					row.y,
+					row.x + SWATCH_WIDTH,
+					row.y,
+					svg_paint("stroke", *color, 1.0)
+				);
+				let _ = writeln!(
+					svg,
+					"<text x=\"{}\" y=\"{}\" dominant-baseline=\"central\" {}>{}</text>",
+					row.x + SWATCH_WIDTH + (MARGIN / 2.0),
+					row.y,
+					svg_paint("fill", TEXT_COLOR, 1.0),
+					svg_escape(text)
+				);
+			}
+		}
+
+		svg.push_str("</svg>\n");
+		svg
+	}
+
+	/// Positions (in points) of `points` in the image, split into separate segments around the ones that aren't finite
+	fn segments(&self, points: &[PlotPoint]) -> Vec<Vec<Pos2>> {
+		points
+			.split(|point| !point.x.is_finite() | !point.y.is_finite())
+			.filter(|segment| !segment.is_empty())
+			.map(|segment| segment.iter().map(|point| self.to_screen(*point)).collect())
+			.collect()
+	}
+
+	/// Draws the image into pixels, with text laid out using `fonts`
+	pub fn rasterize(&self, fonts: &Fonts) -> ColorImage {
+		let mut canvas = Canvas {
+			image: ColorImage::new(self.pixel_size(), BACKGROUND),
+			scale: self.scale,
+		};
+
+		let (lines, labels) = self.grid();
+		for ([a, b], color) in lines {
+			canvas.segment(a, b, 1.0, color);
+		}
+
+		let axis_y = self.to_screen(PlotPoint::new(0.0, 0.0)).y;
+		for shape in self.shapes.iter() {
+			match shape {
+				ExportShape::Line {
+					points,
+					stroke,
+					style,
+				} => {
+					for segment in self.segments(points) {
+						canvas.path(&segment, *stroke, style.line_style());
+					}
+				}
+				ExportShape::Fill { points, color } => {
+					for segment in self.segments(points) {
+						canvas.fill_to(&segment, axis_y, *color);
+					}
+				}
+				ExportShape::Points {
+					points,
+					radius,
+					color,
+				} => {
+					for point in self.segments(points).into_iter().flatten() {
+						canvas.segment(point, point, 2.0 * radius, *color);
+					}
+				}
+				ExportShape::Rects { rects, color } => {
+					for [a, b] in rects {
+						let rect = Rect::from_two_pos(self.to_screen(*a), self.to_screen(*b));
+						canvas.rect(rect, *color, FILL_ALPHA);
+						canvas.outline(rect, *color);
+					}
+				}
+				ExportShape::Text {
+					position,
+					text,
+					color,
+				} => {
+					let pos = self.to_screen(*position);
+					canvas.text(fonts, pos - vec2(0.0, FONT_SIZE), text, *color);
+				}
+			}
+		}
+
+		for (pos, label) in labels {
+			canvas.text(fonts, pos, &label, TEXT_COLOR);
+		}
+
+		if !self.legend.is_empty() {
+			let rect = self.legend_rect();
+			canvas.rect(rect, BACKGROUND, 0.8);
+			canvas.outline(rect, GRID_COLOR);
+			for (i, (text, color)) in self.legend.iter().enumerate() {
+				let row = self.legend_row(i);
+				canvas.segment(row, row + vec2(SWATCH_WIDTH, 0.0), 2.0, *color);
+				canvas.text(
+					fonts,
+					row + vec2(SWATCH_WIDTH + (MARGIN / 2.0), -FONT_SIZE / 2.0),
+					text,
+					TEXT_COLOR,
+				);
+			}
+		}
+
+		canvas.image
+	}
+
+	/// Encodes the image as a PNG, with text laid out using `fonts`
+	pub fn to_png(&self, fonts: &Fonts) -> Vec<u8> { encode_png(&self.rasterize(fonts)) }
+}
+
+/// `name` attribute (such as `fill`) of an SVG element painted `color` at `opacity`, along with the attribute for its opacity
+fn svg_paint(name: &str, color: Color32, opacity: f32) -> String {
+	let [r, g, b, a] = color.to_srgba_unmultiplied();
+	format!(
+		"{}=\"#{:02x}{:02x}{:02x}\" {}-opacity=\"{}\"",
+		name,
+		r,
+		g,
+		b,
+		name,
+		(a as f32 / 255.0) * opacity
+	)
+}
+
+/// Points of an SVG polyline or polygon
+fn svg_points(points: &[Pos2]) -> String {
+	points
+		.iter()
+		.map(|point| format!("{},{}", point.x, point.y))
+		.collect::<Vec<String>>()
+		.join(" ")
+}
+
+/// Escapes the characters of `text` that can't be in an SVG's text as is
+fn svg_escape(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+}
+
+/// Function listed in an exported report
+#[derive(Clone, Debug)]
+pub struct ReportFunction {
+	/// Function as it was entered
+	pub expression: String,
+
+	/// Color it's drawn in
+	pub color: Color32,
+
+	/// How its integral was calculated (such as "Left, N = 100, from 0 to 1"), if it's integrated
+	pub method: Option<String>,
+
+	/// Name and (already formatted) value of each of its results, such as ("Root", "(1, 0)")
+	pub results: Vec<(String, String)>,
+}
+
+/// Writes a self-contained HTML report with the plot (the SVG `svg`), when it was made, and the expression, integration method, and
+/// results of each of `functions`, such as for attaching to homework, with its text in the language of `i18n`
+pub fn report_html(
+	svg: &str, timestamp: &str, functions: &[ReportFunction], i18n: &Translations,
+) -> String {
+	let title = svg_escape(i18n.get("report.title"));
+	let mut html = String::new();
+	let _ = write!(
+		html,
+		"<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n",
+		i18n.language().code(),
+		title
+	);
+	html.push_str("<style>body { font-family: sans-serif; max-width: 960px; margin: 2em auto; } svg { max-width: 100%; height: auto; } table { border-collapse: collapse; } th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }</style>\n");
+	let _ = writeln!(html, "</head>\n<body>\n<h1>{}</h1>", title);
+	let _ = writeln!(
+		html,
+		"<p>{}</p>",
+		svg_escape(&i18n.format("report.exported", timestamp))
+	);
+	html.push_str(svg);
+	html.push('\n');
+
+	for function in functions {
+		let [r, g, b, _] = function.color.to_srgba_unmultiplied();
+		let _ = writeln!(
+			html,
+			"<h2><span style=\"color: #{:02x}{:02x}{:02x}\">&#9632;</span> {}</h2>",
+			r,
+			g,
+			b,
+			svg_escape(&function.expression)
+		);
+		if let Some(ref method) = function.method {
+			let _ = writeln!(
+				html,
+				"<p>{}</p>",
+				svg_escape(&i18n.format("report.integrated", method))
+			);
+		}
+
+		if function.results.is_empty() {
+			let _ = writeln!(html, "<p>{}</p>", svg_escape(i18n.get("results.none")));
+			continue;
+		}
+		let _ = writeln!(
+			html,
+			"<table>\n<tr><th>{}</th><th>{}</th></tr>",
+			svg_escape(i18n.get("results.result")),
+			svg_escape(i18n.get("results.value"))
+		);
+		for (name, value) in function.results.iter() {
+			let _ = writeln!(
+				html,
+				"<tr><td>{}</td><td>{}</td></tr>",
+				svg_escape(name),
+				svg_escape(value)
+			);
+		}
+		html.push_str("</table>\n");
+	}
+
+	html.push_str("</body>\n</html>\n");
+	html
+}
+
+/// Current time, in seconds since the Unix epoch
+pub(crate) fn unix_time() -> u64 {
+	cfg_if::cfg_if! {
+		if #[cfg(target_arch = "wasm32")] {
+			(js_sys::Date::now() / 1000.0) as u64
+		} else {
+			std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.map_or(0, |duration| duration.as_secs())
+		}
+	}
+}
+
+/// Writes `seconds` since the Unix epoch as a date and time in UTC, such as "2024-03-01 14:05 UTC"
+pub fn utc_timestamp(seconds: u64) -> String {
+	let minutes = (seconds % 86400) / 60;
+
+	// Days are counted from March 1st of the year 0, so leap days are at the end of each year, in eras of 400 years that each have
+	// the same number of days
+	let days = seconds / 86400 + 719468;
+	let era = days / 146097;
+	let day_of_era = days % 146097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = match month_index < 10 {
+		true => month_index + 3,
+		false => month_index - 9,
+	};
+	let year = era * 400 + year_of_era + (month <= 2) as u64;
+
+	format!(
+		"{:04}-{:02}-{:02} {:02}:{:02} UTC",
+		year,
+		month,
+		day,
+		minutes / 60,
+		minutes % 60
+	)
+}
+
+/// Saves `bytes` as the file `name`, which the browser downloads on the web, and is written to the working directory otherwise
+pub(crate) fn save_file(name: &str, mime_type: &str, bytes: &[u8]) {
+	cfg_if::cfg_if! {
+		if #[cfg(target_arch = "wasm32")] {
+			use wasm_bindgen::JsCast;
+
+			// Downloaded by clicking a link to it
+			let download = || -> Option<()> {
+				let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+				let mut options = web_sys::BlobPropertyBag::new();
+				options.type_(mime_type);
+				let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).ok()?;
+				let url = web_sys::Url::create_object_url_with_blob(&blob).ok()?;
+
+				let link: web_sys::HtmlAnchorElement =
+					web_sys::window()?.document()?.create_element("a").ok()?.dyn_into().ok()?;
+				link.set_href(&url);
+				link.set_download(name);
+				link.click();
+				web_sys::Url::revoke_object_url(&url).ok()
+			};
+
+			if download().is_none() {
+				tracing::error!("Failed to download {}", name);
+			}
+		} else {
+			let _ = mime_type;
+			match std::fs::write(name, bytes) {
+				Ok(()) => tracing::info!("Saved {}", name),
+				Err(error) => tracing::error!("Failed to save {}: {}", name, error),
+			}
+		}
+	}
+}
+
+/// Pixels being drawn into, which shapes are positioned on in points
+struct Canvas {
+	image: ColorImage,
+
+	/// Pixels per point
+	scale: f32,
+}
+
+impl Canvas {
+	/// Blends `color` over the pixel at `x`, `y` (if it's in the image), by how much of the pixel is `covered` and the color's opacity
+	fn blend(&mut self, x: i64, y: i64, color: Color32, covered: f32) {
+		let [width, height] = self.image.size;
+		if (x < 0) | (y < 0) | (x >= width as i64) | (y >= height as i64) {
+			return;
+		}
+
+		let [r, g, b, a] = color.to_srgba_unmultiplied();
+		let alpha = (a as f32 / 255.0) * covered.clamp(0.0, 1.0);
+		let pixel = &mut self.image.pixels[(y as usize * width) + x as usize];
+		let mix =
+			|from: u8, to: u8| ((from as f32 * (1.0 - alpha)) + (to as f32 * alpha)).round() as u8;
+		*pixel = Color32::from_rgb(mix(pixel.r(), r), mix(pixel.g(), g), mix(pixel.b(), b));
+	}
+
+	/// Range of pixels (along one side of the image) that `min` to `max` (in pixels) touch, limited to the `size` of the image
+	fn pixel_range(min: f32, max: f32, size: usize) -> std::ops::Range<i64> {
+		(min.floor().max(0.0) as i64)..(max.ceil().min(size as f32) as i64)
+	}
+
+	/// Position in pixels of `pos`, which is in points
+	fn to_pixels(&self, pos: Pos2) -> Pos2 { (pos.to_vec2() * self.scale).to_pos2() }
+
+	/// Draws a line `width` points thick from `a` to `b` with round ends, which is a dot if they're the same
+	fn segment(&mut self, a: Pos2, b: Pos2, width: f32, color: Color32) {
+		let (a, b) = (self.to_pixels(a), self.to_pixels(b));
+		let radius = (width * self.scale / 2.0).max(0.5);
+		let [image_width, image_height] = self.image.size;
+
+		let ab = b - a;
+		let length_sq = ab.length_sq();
+		for y in Self::pixel_range(
+			a.y.min(b.y) - radius - 1.0,
+			a.y.max(b.y) + radius + 1.0,
+			image_height,
+		) {
+			for x in Self::pixel_range(
+				a.x.min(b.x) - radius - 1.0,
+				a.x.max(b.x) + radius + 1.0,
+				image_width,
+			) {
+				// Distance from the center of the pixel to the nearest point of the segment
+				let center = pos2(x as f32 + 0.5, y as f32 + 0.5);
+				let t = match length_sq > 0.0 {
+					true => ((center - a).dot(ab) / length_sq).clamp(0.0, 1.0),
+					false => 0.0,
+				};
+				let distance = center.distance(a + (ab * t));
+				if distance < radius + 0.5 {
+					self.blend(x, y, color, radius + 0.5 - distance);
+				}
+			}
+		}
+	}
+
+	/// Draws a line through `points` in `style`
+	fn path(&mut self, points: &[Pos2], stroke: Stroke, style: LineStyle) {
+		// Distance along the line of the start of each segment
+		let mut travelled = 0.0;
+		for pair in points.windows(2) {
+			let (a, b) = (pair[0], pair[1]);
+			let length = a.distance(b);
+			let along = |distance: f32| a.lerp(b, (distance / length).clamp(0.0, 1.0));
+
+			match style {
+				LineStyle::Solid => self.segment(a, b, stroke.width, stroke.color),
+				// Dashes `dash` long, as far apart as they are long, the `i`th one starting `2 * i * dash` along the line
+				LineStyle::Dashed { length: dash } => {
+					let mut i = (travelled / (2.0 * dash)).floor() as i64;
+					while (i as f32 * 2.0 * dash) < travelled + length {
+						let start = ((i as f32 * 2.0 * dash) - travelled).max(0.0);
+						let end = ((i as f32 * 2.0 * dash) + dash - travelled).min(length);
+						if start < end {
+							self.segment(along(start), along(end), stroke.width, stroke.color);
+						}
+						i += 1;
+					}
+				}
+				// Dots `spacing` apart, the `i`th one being `i * spacing` along the line
+				LineStyle::Dotted { spacing } => {
+					let mut i = (travelled / spacing).ceil() as i64;
+					while (i as f32 * spacing) < travelled + length {
+						let dot = along((i as f32 * spacing) - travelled);
+						self.segment(dot, dot, stroke.width, stroke.color);
+						i += 1;
+					}
+				}
+			}
+
+			travelled += length;
+		}
+	}
+
+	/// Fills the area between the line through `points` and the height `axis_y`, column by column of pixels
+	fn fill_to(&mut self, points: &[Pos2], axis_y: f32, color: Color32) {
+		let axis_y = axis_y * self.scale;
+		for pair in points.windows(2) {
+			let (a, b) = (self.to_pixels(pair[0]), self.to_pixels(pair[1]));
+			if a.x == b.x {
+				continue;
+			}
+
+			let (left, right) = (a.x.min(b.x), a.x.max(b.x));
+			for x in Self::pixel_range(left, right, self.image.size[0]) {
+				// Only the part of the column between `a` and `b` is filled, so neighboring segments don't overlap
+				let (start, end) = ((x as f32).max(left), (x as f32 + 1.0).min(right));
+				let y = a.y + ((b.y - a.y) * ((start + end) / 2.0 - a.x) / (b.x - a.x));
+				self.fill_pixels(
+					Rect::from_min_max(pos2(start, y.min(axis_y)), pos2(end, y.max(axis_y))),
+					color,
+					FILL_ALPHA,
+				);
+			}
+		}
+	}
+
+	/// Fills `rect` (in points) at `opacity`
+	fn rect(&mut self, rect: Rect, color: Color32, opacity: f32) {
+		self.fill_pixels(
+			Rect::from_min_max(self.to_pixels(rect.min), self.to_pixels(rect.max)),
+			color,
+			opacity,
+		);
+	}
+
+	/// Fills `rect` (in pixels) at `opacity`, blending pixels on its edges by how much of them it covers
+	fn fill_pixels(&mut self, rect: Rect, color: Color32, opacity: f32) {
+		let [width, height] = self.image.size;
+		for y in Self::pixel_range(rect.min.y, rect.max.y, height) {
+			let covered_y = (rect.max.y.min(y as f32 + 1.0) - rect.min.y.max(y as f32)).max(0.0);
+			for x in Self::pixel_range(rect.min.x, rect.max.x, width) {
+				let covered_x =
+					(rect.max.x.min(x as f32 + 1.0) - rect.min.x.max(x as f32)).max(0.0);
+				self.blend(x, y, color, covered_x * covered_y * opacity);
+			}
+		}
+	}
+
+	/// Outlines `rect` (in points) with a line one point thick
+	fn outline(&mut self, rect: Rect, color: Color32) {
+		let corners = [
+			rect.left_top(),
+			rect.right_top(),
+			rect.right_bottom(),
+			rect.left_bottom(),
+			rect.left_top(),
+		];
+		for pair in corners.windows(2) {
+			self.segment(pair[0], pair[1], 1.0, color);
+		}
+	}
+
+	/// Draws `text` with its top left corner at `pos` (in points), using the glyphs `fonts` rasterized into their atlas
+	fn text(&mut self, fonts: &Fonts, pos: Pos2, text: &str, color: Color32) {
+		// Laid out at the image's scale, so the glyphs are rasterized at least as finely as the image's pixels
+		let galley = fonts.layout_no_wrap(
+			text.to_owned(),
+			FontId::proportional(FONT_SIZE * self.scale),
+			color,
+		);
+		let atlas = fonts.image();
+		let pos = self.to_pixels(pos);
+		let [width, height] = self.image.size;
+
+		for glyph in galley.rows.iter().flat_map(|row| row.glyphs.iter()) {
+			let uv = glyph.uv_rect;
+			if uv.is_nothing() {
+				continue;
+			}
+
+			// Samples the nearest pixel of the glyph in the atlas to each pixel it covers
+			let rect = Rect::from_min_size(pos + glyph.pos.to_vec2() + uv.offset, uv.size);
+			let texels = vec2(
+				(uv.max[0] - uv.min[0]) as f32 / uv.size.x,
+				(uv.max[1] - uv.min[1]) as f32 / uv.size.y,
+			);
+			for y in Self::pixel_range(rect.min.y, rect.max.y, height) {
+				for x in Self::pixel_range(rect.min.x, rect.max.x, width) {
+					let u = uv.min[0] as usize
+						+ ((x as f32 + 0.5 - rect.min.x) * texels.x).max(0.0) as usize;
+					let v = uv.min[1] as usize
+						+ ((y as f32 + 0.5 - rect.min.y) * texels.y).max(0.0) as usize;
+					if let Some(coverage) = atlas.pixels.get((v * atlas.size[0]) + u) {
+						self.blend(x, y, color, *coverage);
+					}
+				}
+			}
+		}
+	}
+}
+
+/// CRC-32 of each byte, used to checksum the chunks of PNGs
+const CRC_TABLE: [u32; 256] = {
+	let mut table = [0; 256];
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = i as u32;
+		let mut bit = 0;
+		while bit < 8 {
+			crc = match crc & 1 {
+				1 => 0xEDB88320 ^ (crc >> 1),
+				_ => crc >> 1,
+			};
+			bit += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	table
+};
+
+/// Appends the PNG chunk `kind` containing `data` to `png`
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+	let crc = !kind.iter().chain(data.iter()).fold(!0_u32, |crc, byte| {
+		CRC_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+	});
+
+	png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+	png.extend_from_slice(kind);
+	png.extend_from_slice(data);
+	png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Encodes `image` as an opaque PNG. Its pixels are stored without being compressed, so no compression library is needed.
+pub fn encode_png(image: &ColorImage) -> Vec<u8> {
+	let [width, height] = image.size;
+
+	// Each row of pixels starts with the filter applied to it, which is none
+	let mut pixels = Vec::with_capacity(height * (1 + (3 * width)));
+	for row in image.pixels.chunks(width.max(1)) {
+		pixels.push(0);
+		for pixel in row {
+			pixels.extend_from_slice(&[pixel.r(), pixel.g(), pixel.b()]);
+		}
+	}
+
+	// Zlib stream of the pixels, being uncompressed deflate blocks followed by the Adler-32 checksum of the pixels
+	let mut data = vec![0x78, 0x01];
+	let mut blocks = pixels.chunks(u16::MAX as usize).peekable();
+	while let Some(block) = blocks.next() {
+		let length = block.len() as u16;
+		data.push(blocks.peek().is_none() as u8);
+		data.extend_from_slice(&length.to_le_bytes());
+		data.extend_from_slice(&(!length).to_le_bytes());
+		data.extend_from_slice(block);
+	}
+	let (a, b) = pixels.iter().fold((1_u32, 0_u32), |(a, b), byte| {
+		let a = (a + *byte as u32) % 65521;
+		(a, (b + a) % 65521)
+	});
+	data.extend_from_slice(&((b << 16) | a).to_be_bytes());
+
+	// 8 bits per channel of RGB pixels, which aren't interlaced
+	let mut header = Vec::with_capacity(13);
+	header.extend_from_slice(&(width as u32).to_be_bytes());
+	header.extend_from_slice(&(height as u32).to_be_bytes());
+	header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+	let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+	write_chunk(&mut png, b"IHDR", &header);
+	write_chunk(&mut png, b"IDAT", &data);
+	write_chunk(&mut png, b"IEND", &[]);
+	png
+}
+
+/// Opens a file, storing its contents in `opened` once they've been read (and repainting `ctx`). The browser asks which file to open on the web, and `name` is read from the working directory otherwise
+pub(crate) fn open_file(
+	ctx: &egui::Context, name: &str, accept: &str, opened: &Rc<RefCell<Option<Vec<u8>>>>,
+) {
+	cfg_if::cfg_if! {
+		if #[cfg(target_arch = "wasm32")] {
+			use wasm_bindgen::{closure::Closure, JsCast};
+
+			let _ = name;
+
+			// Picked with a file input, which is read once a file is chosen
+			let pick = || -> Option<()> {
+				let input: web_sys::HtmlInputElement =
+					web_sys::window()?.document()?.create_element("input").ok()?.dyn_into().ok()?;
+				input.set_type("file");
+				input.set_accept(accept);
+
+				let (ctx, opened, chosen) = (ctx.clone(), opened.clone(), input.clone());
+				let on_change = Closure::once(move || {
+					let Some(file) = chosen.files().and_then(|files| files.get(0)) else {
+						return;
+					};
+					wasm_bindgen_futures::spawn_local(async move {
+						match wasm_bindgen_futures::JsFuture::from(file.array_buffer()).await {
+							Ok(buffer) => {
+								*opened.borrow_mut() = Some(js_sys::Uint8Array::new(&buffer).to_vec());
+								ctx.request_repaint();
+							}
+							Err(_) => tracing::error!("Failed to read {}", file.name()),
+						}
+					});
+				});
+				input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+				on_change.forget();
+				input.click();
+				Some(())
+			};
+
+			if pick().is_none() {
+				tracing::error!("Failed to open a file picker");
+			}
+		} else {
+			let _ = accept;
+			match std::fs::read(name) {
+				Ok(bytes) => {
+					*opened.borrow_mut() = Some(bytes);
+					ctx.request_repaint();
+				}
+				Err(error) => tracing::error!("Failed to open {}: {}", name, error),
+			}
+		}
+	}
+}
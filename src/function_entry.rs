@@ -1,60 +1,555 @@
+use crate::consts::{
+	CLOSED_FORM_TOLERANCE, COMPARISON_COLOR, DEFAULT_INTEGRAL_NUM, DEFAULT_MAX_X, DEFAULT_MIN_X,
+	DEFAULT_PARAMETER, OVERESTIMATE_COLOR, UNDERESTIMATE_COLOR,
+};
+use crate::domain_coloring::DomainColoring;
+use crate::export::{save_file, ExportImage, ExportShape};
+use crate::i18n::Translations;
+use crate::implicit::{ContourPlot, ImplicitPlot};
 use crate::math_app::AppSettings;
-use crate::misc::{newtons_method_helper, step_helper, EguiHelper};
-use egui::{Checkbox, Context};
-use egui_plot::{Bar, BarChart, PlotPoint, PlotUi};
+use crate::misc::{
+	adaptive_simpson, closed_form, detect_period, find_roots, improper_integral, is_discontinuous,
+	newtons_method_helper, one_sided_limit, parse_csv_points, partial_sums, refine_root, romberg,
+	sequence, singular_integral, step_helper, to_csv, trapezoid_points, EguiHelper, Xorshift,
+};
+use crate::number_format::NumberFormat;
+use crate::ode::SlopeField;
+use crate::vector_field::{line_integrals, VectorField};
+use egui::{Checkbox, Context, DragValue};
+use egui_plot::{
+	Arrows, Bar, BarChart, LineStyle, MarkerShape, PlotImage, PlotPoint, PlotUi, Polygon, Text,
+};
+use itertools::Itertools;
 
 use epaint::Color32;
 use parsing::{generate_hint, AutoComplete};
-use parsing::{process_func_str, BackingFunction};
+use parsing::{
+	closest_function, replace_variable, AngleUnit, BackingFunction, BivariateFunction, Complex,
+	Definitions, Expression, Interval, ParseError, Typeset,
+};
 use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
+	collections::BTreeMap,
 	fmt::{self, Debug},
 	hash::{Hash, Hasher},
+	ops::Range,
 };
 
 /// Represents the possible variations of Riemann Sums
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, Serialize, Deserialize)]
 pub enum Riemann {
 	#[default]
 	Left,
 
 	Middle,
 	Right,
+	Trapezoid,
+
+	/// Darboux upper sum, uses the maximum of the function on each interval
+	Upper,
+
+	/// Darboux lower sum, uses the minimum of the function on each interval
+	Lower,
+
+	/// Adaptive Simpson's quadrature, subdivides until `integral_tolerance` is met
+	Adaptive,
+
+	/// Monte Carlo estimation using `monte_carlo_samples` random points
+	MonteCarlo,
+
+	/// Romberg extrapolation of the trapezoid rule, refining up to `integral_num` intervals
+	Romberg,
+}
+
+impl Riemann {
+	/// Every variation, in the order they're listed in
+	pub const ALL: [Riemann; 9] = [
+		Riemann::Left,
+		Riemann::Middle,
+		Riemann::Right,
+		Riemann::Trapezoid,
+		Riemann::Upper,
+		Riemann::Lower,
+		Riemann::Adaptive,
+		Riemann::MonteCarlo,
+		Riemann::Romberg,
+	];
+
+	/// Returns whether or not the sum is made up of `integral_num` evenly spaced intervals
+	pub const fn uses_intervals(&self) -> bool {
+		matches!(
+			self,
+			Riemann::Left
+				| Riemann::Middle
+				| Riemann::Right
+				| Riemann::Trapezoid
+				| Riemann::Upper
+				| Riemann::Lower
+		)
+	}
+
+	/// Key of the sum's name in the translations
+	pub const fn label(&self) -> &'static str {
+		match self {
+			Riemann::Left => "riemann.left",
+			Riemann::Middle => "riemann.middle",
+			Riemann::Right => "riemann.right",
+			Riemann::Trapezoid => "riemann.trapezoid",
+			Riemann::Upper => "riemann.upper",
+			Riemann::Lower => "riemann.lower",
+			Riemann::Adaptive => "riemann.adaptive",
+			Riemann::MonteCarlo => "riemann.monte_carlo",
+			Riemann::Romberg => "riemann.romberg",
+		}
+	}
 }
 
 impl fmt::Display for Riemann {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{:?}", self) }
 }
 
+/// Integration settings of a single function, which it's integrated with instead of the ones in [`AppSettings`]
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct IntegralOverride {
+	/// Lower bound of the integral (of θ for polar functions)
+	pub min_x: f64,
+
+	/// Upper bound of the integral (of θ for polar functions)
+	pub max_x: f64,
+
+	/// Number of intervals the integral is split into
+	pub num: usize,
+
+	pub riemann_sum: Riemann,
+}
+
+impl const Default for IntegralOverride {
+	fn default() -> Self {
+		Self {
+			min_x: DEFAULT_MIN_X,
+			max_x: DEFAULT_MAX_X,
+			num: DEFAULT_INTEGRAL_NUM,
+			riemann_sum: Riemann::Left,
+		}
+	}
+}
+
+impl Hash for IntegralOverride {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.min_x.to_bits().hash(state);
+		self.max_x.to_bits().hash(state);
+		self.num.hash(state);
+		self.riemann_sum.hash(state);
+	}
+}
+
+/// Represents the possible ways of reporting the area of an integral
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum AreaMode {
+	/// Net area, where area below the x-axis counts as negative
+	#[default]
+	Signed,
+
+	/// Total area, ∫|f(x)|
+	Absolute,
+
+	/// Area above and below the x-axis reported separately, along with the net area
+	Split,
+}
+
+impl AreaMode {
+	/// Every variation, in the order they're listed in
+	pub const ALL: [AreaMode; 3] = [AreaMode::Signed, AreaMode::Absolute, AreaMode::Split];
+
+	/// Key of the mode's name in the translations
+	pub const fn label(&self) -> &'static str {
+		match self {
+			AreaMode::Signed => "area_mode.signed",
+			AreaMode::Absolute => "area_mode.absolute",
+			AreaMode::Split => "area_mode.split",
+		}
+	}
+}
+
+impl fmt::Display for AreaMode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{:?}", self) }
+}
+
+/// Represents the possible ways a function's curve can be drawn
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, Serialize, Deserialize)]
+pub enum CurveStyle {
+	#[default]
+	Solid,
+
+	Dashed,
+	Dotted,
+}
+
+impl CurveStyle {
+	/// Style of the lines drawn with this
+	pub const fn line_style(&self) -> LineStyle {
+		match self {
+			CurveStyle::Solid => LineStyle::Solid,
+			CurveStyle::Dashed => LineStyle::Dashed { length: 10.0 },
+			CurveStyle::Dotted => LineStyle::Dotted { spacing: 10.0 },
+		}
+	}
+
+	/// Key of the style's name in the translations
+	pub const fn label(&self) -> &'static str {
+		match self {
+			CurveStyle::Solid => "curve_style.solid",
+			CurveStyle::Dashed => "curve_style.dashed",
+			CurveStyle::Dotted => "curve_style.dotted",
+		}
+	}
+}
+
+impl fmt::Display for CurveStyle {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{:?}", self) }
+}
+
+/// Lighter version of `color` (halfway to white), used for the derivative drawn alongside a function
+const fn lighter(color: Color32) -> Color32 {
+	Color32::from_rgb(
+		(color.r() / 2) + 128,
+		(color.g() / 2) + 128,
+		(color.b() / 2) + 128,
+	)
+}
+
+/// Darker version of `color` (halfway to black), used for the integral drawn alongside a function
+const fn darker(color: Color32) -> Color32 {
+	Color32::from_rgb(color.r() / 2, color.g() / 2, color.b() / 2)
+}
+
+/// Maximum number of terms of a sequence drawn with stems, beyond which only their points are drawn
+const MAX_STEMS: usize = 500;
+
+/// Classification of a critical point (where f'(x) = 0)
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Extremum {
+	Minimum,
+	Maximum,
+
+	/// Neither a minimum nor maximum, such as an inflection point
+	Neither,
+}
+
+impl Extremum {
+	/// Key of the classification's name in the translations
+	pub const fn label(&self) -> &'static str {
+		match self {
+			Extremum::Minimum => "results.minimum",
+			Extremum::Maximum => "results.maximum",
+			Extremum::Neither => "results.neither",
+		}
+	}
+}
+
+/// Functions as they were parsed, before their parameters were replaced with their values
+#[derive(Clone)]
+struct Unbound {
+	function: BackingFunction,
+	bivariate: Option<BivariateFunction>,
+	complex: Option<Expression>,
+	vector_q: Option<BivariateFunction>,
+}
+
+/// Rectangle of a Riemann sum, which is described in a tooltip while it's hovered over
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct RiemannRectangle {
+	/// Index of the rectangle, counting from the left
+	pub index: usize,
+
+	/// Left and right ends of its interval
+	pub interval: (f64, f64),
+
+	/// Height the function was sampled at
+	pub height: f64,
+
+	/// Area it adds to the sum, which is negative below the x-axis (or when the integral's bounds are reversed)
+	pub area: f64,
+
+	/// Sum of its area and the areas of the rectangles to the left of it
+	pub cumulative: f64,
+}
+
 /// `FunctionEntry` is a function that can calculate values, integrals, derivatives, etc etc
 #[derive(Clone)]
 pub struct FunctionEntry {
 	/// The `BackingFunction` instance that is used to generate `f(x)`, `f'(x)`, and `f''(x)`
 	function: BackingFunction,
 
+	/// F(x, y), used instead of `function` for implicit equations F(x, y) = 0 (when the function string contains '='), slope fields, and P(x, y) of vector fields
+	bivariate: Option<BivariateFunction>,
+
+	/// f(x) evaluated over complex numbers, used instead of `function` when graphing complex values or domain coloring
+	complex: Option<Expression>,
+
+	/// Q(x, y) of vector fields
+	vector_q: Option<BivariateFunction>,
+
+	/// Stores the string of Q(x, y) (that hasn't been processed via `process_func_str`)
+	pub q_func_str: String,
+
+	/// Error from parsing `q_func_str`
+	q_test_result: Option<String>,
+
+	/// Curve traced by the implicit equation over the plot
+	pub implicit_plot: ImplicitPlot,
+
+	/// Contour lines of the function over the plot
+	pub contour_data: ContourPlot,
+
+	/// Slope field of the differential equation over the plot
+	pub slope_field_data: SlopeField,
+
+	/// Arrows of the vector field over the plot
+	pub vector_field_data: VectorField,
+
+	/// Rectangle (minimum and maximum corners) the vector field's circulation and flux are calculated around
+	pub line_integral_rect: Option<([f64; 2], [f64; 2])>,
+
+	/// Circulation and flux of the vector field around `line_integral_rect`
+	pub line_integral_data: Option<(f64, f64)>,
+
 	/// Stores a function string (that hasn't been processed via `process_func_str`) to display to the user
 	pub raw_func_str: String,
 
 	/// If calculating/displayingintegrals are enabled
 	pub integral: bool,
 
+	/// Integration settings used instead of the global ones, if the function has its own
+	integral_override: Option<IntegralOverride>,
+
 	/// If displaying derivatives are enabled (note, they are still calculated for other purposes)
 	pub derivative: bool,
 
+	/// If displaying the running integral F(x) (starting at the lower integral bound) is enabled
+	pub antiderivative: bool,
+
 	pub nth_derviative: bool,
 
+	/// If displaying the Mean Value Theorem over the integral's bounds is enabled
+	pub mean_value: bool,
+
+	/// If displaying the curvature κ(x) is enabled
+	pub curvature: bool,
+
+	/// If the function is a polar function r(θ), drawn over the θ range instead of the x-axis
+	pub polar: bool,
+
+	/// If the function is the right hand side f(x, y) of the differential equation dy/dx = f(x, y), drawn as a slope field
+	pub slope_field: bool,
+
+	/// If the function is P(x, y) of the vector field (P(x, y), Q(x, y)), with Q(x, y) being `q_func_str`
+	pub vector_field: bool,
+
+	/// If the function f(x, y) is drawn as a contour plot
+	pub contour: bool,
+
+	/// If the real and imaginary parts of the function are graphed as two lines, rather than leaving gaps where it isn't real
+	pub complex_values: bool,
+
+	/// If the function is a function f(z) of a complex variable, drawn with domain coloring
+	pub domain_coloring: bool,
+
+	/// Real and imaginary parts of the function over the plot
+	pub complex_data: Option<(Vec<PlotPoint>, Vec<PlotPoint>)>,
+
+	/// Domain coloring of the function over the plot
+	pub domain_coloring_data: DomainColoring,
+
+	/// If the function is a sequence a(n), only defined at integers n (also written as x)
+	pub sequence: bool,
+
+	/// First index n of the sequence
+	pub sequence_start: i64,
+
+	/// If displaying the partial sums S(n) = a(`sequence_start`) + ... + a(n) of the sequence is enabled
+	pub partial_sums: bool,
+
+	/// Terms of the sequence within the plot's bounds
+	pub sequence_data: Vec<PlotPoint>,
+
+	/// If the entry is a series of (x, y) data points parsed from `data_csv`, rather than a function
+	pub data_series: bool,
+
+	/// CSV the data points are parsed from
+	pub data_csv: String,
+
+	/// Data points parsed from `data_csv`, sorted by x
+	pub data_points: Vec<PlotPoint>,
+
+	/// Error from parsing `data_csv`
+	data_test_result: Option<String>,
+
+	/// Partial sums of the sequence within the plot's bounds
+	pub partial_sum_data: Vec<PlotPoint>,
+
+	/// Number of levels drawn in the contour plot
+	pub contour_levels: usize,
+
+	/// If the contour lines are labeled with their levels
+	pub contour_labels: bool,
+
 	pub back_data: Vec<PlotPoint>,
+
+	/// Indices of `back_data` that are followed by a discontinuity
+	pub back_gaps: Vec<usize>,
+
+	/// If guaranteed bounds of the function over each column of pixels are drawn, which catches what sampling misses (such as the oscillations of sin(1/x) near 0)
+	pub rigorous: bool,
+
+	/// Bounds of the function over each column of pixels, as bars spanning them
+	pub rigorous_data: Vec<Bar>,
+
 	pub integral_data: Option<(Vec<Bar>, f64)>,
+
+	/// Arc length over the integral's bounds (`None` if the function has none), once [`FunctionEntry::results`] has needed it since
+	/// the integral changed
+	arc_length_data: Option<Option<f64>>,
+
+	/// Outline of the trapezoids used when integrating via [`Riemann::Trapezoid`]
+	pub trapezoid_data: Vec<PlotPoint>,
+
+	/// Number of subintervals used when integrating via [`Riemann::Adaptive`]
+	pub integral_subdivisions: Option<usize>,
+
+	/// Sample points (hits, misses) used when integrating via [`Riemann::MonteCarlo`]
+	pub monte_carlo_data: (Vec<PlotPoint>, Vec<PlotPoint>),
+
+	/// Standard error of the area estimated via [`Riemann::MonteCarlo`]
+	pub integral_std_error: Option<f64>,
+
+	/// Upper bound on the error of a [`Riemann::Left`], [`Riemann::Middle`], [`Riemann::Right`], or [`Riemann::Trapezoid`] sum
+	pub integral_error_bound: Option<f64>,
+
+	/// Table of estimates created when integrating via [`Riemann::Romberg`]
+	pub romberg_table: Vec<Vec<f64>>,
+
+	/// If a singularity (where the function is infinite) had to be integrated around
+	pub integral_singular: bool,
+
+	/// If the integral diverges due to a singularity
+	pub integral_diverges: bool,
+
+	/// Area above and below the x-axis (positive, negative), calculated when not using [`AreaMode::Signed`]
+	pub integral_parts: Option<(f64, f64)>,
+
+	/// Rectangles of the opposite Darboux sum, displayed alongside [`Riemann::Upper`] or [`Riemann::Lower`]
+	pub darboux_data: Vec<Bar>,
+
+	/// Lower and upper Darboux sums, the true integral lies between them
+	pub darboux_bounds: Option<(f64, f64)>,
+
+	/// Rectangles (or subintervals), outline of the trapezoids, and area of the integral calculated with
+	/// [`AppSettings::compare_sum`], which is compared with `integral_data`
+	pub comparison_data: Option<(Vec<Bar>, Vec<PlotPoint>, f64)>,
+
+	/// Area calculated with an increasing number of rectangles, up to `integral_num` (number of rectangles, area)
+	pub convergence_data: Vec<PlotPoint>,
+
+	/// x value to draw a tangent line at
+	pub tangent_x: Option<f64>,
+
+	/// Point of tangency and slope of the tangent line at `tangent_x`
+	pub tangent_data: Option<(PlotPoint, f64)>,
+
+	/// Secant line over the integral's bounds and the points where the function's tangent line is parallel to it
+	pub mean_value_data: Option<([PlotPoint; 2], Vec<PlotPoint>)>,
+
+	/// x value to draw the osculating circle at
+	pub osculating_x: Option<f64>,
+
+	/// Point the osculating circle at `osculating_x` touches the function, along with its center and radius
+	pub osculating_data: Option<(PlotPoint, PlotPoint, f64)>,
+
+	/// Fundamental period of the function (`None` if it isn't periodic), once [`FunctionEntry::period`] has found it
+	period: Option<Option<f64>>,
+
+	/// If the parameters changed on the last update, in which case the period isn't found until they stop changing
+	period_stale: bool,
+
+	/// x value to evaluate the limit at
+	pub limit_x: f64,
+
+	/// Left and right limits at `limit_x`
+	pub limit_data: Option<(f64, f64)>,
+
 	pub derivative_data: Vec<PlotPoint>,
-	pub extrema_data: Vec<PlotPoint>,
+
+	/// Indices of `derivative_data` that are followed by a discontinuity
+	pub derivative_gaps: Vec<usize>,
+
+	pub antiderivative_data: Vec<PlotPoint>,
+	/// Critical points along with whether they are a minimum, maximum, or neither
+	pub extrema_data: Vec<(PlotPoint, Extremum)>,
 	pub root_data: Vec<PlotPoint>,
 	nth_derivative_data: Option<Vec<PlotPoint>>,
 
+	/// Curvature κ(x) = |f''(x)| / (1 + f'(x)²)^(3/2)
+	pub curvature_data: Option<Vec<PlotPoint>>,
+
 	pub autocomplete: AutoComplete<'static>,
 
 	test_result: Option<String>,
+
+	/// Range of bytes of `raw_func_str` the error in `test_result` was caused by
+	error_span: Option<Range<usize>>,
+
+	/// Function the misspelled function name at `error_span` can be corrected to (such as `sin` for `sni`)
+	correction: Option<&'static str>,
+
 	curr_nth: usize,
 
+	/// Unit of the angles used by the function's trigonometric functions
+	angle_unit: AngleUnit,
+
+	/// Values the function's parameters (such as `a` in `a*sin(x)`) were replaced with
+	parameters: BTreeMap<String, f64>,
+
+	/// The functions before their parameters were replaced, so only the values have to be replaced when they change (such as while
+	/// they're animated) rather than parsing the function strings again
+	unbound: Unbound,
+
+	/// Names of the parameters used by the function string
+	function_parameters: Vec<String>,
+
+	/// Names of the parameters used by `q_func_str`
+	q_parameters: Vec<String>,
+
+	/// User functions the function was parsed with
+	definitions: Definitions,
+
+	/// Names of the user functions called by the function string
+	function_calls: Vec<String>,
+
+	/// Names of the user functions called by `q_func_str`
+	q_calls: Vec<String>,
+
+	/// Name and body of the user function defined by the function string (such as `f(x) = x^2 + 1`), if it's a definition
+	definition: Option<(String, String)>,
+
+	/// Typeset math of the parsed function string, shown so it can be checked that it was parsed as intended
+	typeset: Option<Typeset>,
+
+	/// Simplified formulas of the function and its derivative, shown as text alongside the derivative
+	formulas: Option<(String, String)>,
+
+	/// If the function is drawn on the plot, hiding it keeps its function string and everything calculated from it
+	pub visible: bool,
+
+	/// Color the function is drawn with, being the one given by its position in the list of functions if `None`
+	pub color: Option<Color32>,
+
+	/// Width of the function's curve
+	pub line_width: f32,
+
+	/// If the function's curve is solid, dashed, or dotted
+	pub curve_style: CurveStyle,
+
 	pub settings_opened: bool,
 }
 
@@ -62,8 +557,31 @@ impl Hash for FunctionEntry {
 	fn hash<H: Hasher>(&self, state: &mut H) {
 		self.raw_func_str.hash(state);
 		self.integral.hash(state);
+		self.integral_override.hash(state);
+		self.antiderivative.hash(state);
 		self.nth_derviative.hash(state);
+		self.mean_value.hash(state);
+		self.curvature.hash(state);
+		self.polar.hash(state);
+		self.slope_field.hash(state);
+		self.vector_field.hash(state);
+		self.q_func_str.hash(state);
+		self.contour.hash(state);
+		self.contour_levels.hash(state);
+		self.contour_labels.hash(state);
+		self.complex_values.hash(state);
+		self.domain_coloring.hash(state);
+		self.sequence.hash(state);
+		self.sequence_start.hash(state);
+		self.partial_sums.hash(state);
+		self.data_series.hash(state);
+		self.data_csv.hash(state);
+		self.rigorous.hash(state);
 		self.curr_nth.hash(state);
+		self.visible.hash(state);
+		self.color.hash(state);
+		self.line_width.to_bits().hash(state);
+		self.curve_style.hash(state);
 		self.settings_opened.hash(state);
 	}
 }
@@ -73,11 +591,37 @@ impl Serialize for FunctionEntry {
 	where
 		S: Serializer,
 	{
-		let mut s = serializer.serialize_struct("FunctionEntry", 4)?;
+		let mut s = serializer.serialize_struct("FunctionEntry", 30)?;
 		s.serialize_field("raw_func_str", &self.raw_func_str)?;
 		s.serialize_field("integral", &self.integral)?;
+		s.serialize_field("integral_override", &self.integral_override)?;
 		s.serialize_field("derivative", &self.derivative)?;
+		s.serialize_field("antiderivative", &self.antiderivative)?;
+		s.serialize_field("nth_derviative", &self.nth_derviative)?;
 		s.serialize_field("curr_nth", &self.curr_nth)?;
+		s.serialize_field("mean_value", &self.mean_value)?;
+		s.serialize_field("curvature", &self.curvature)?;
+		s.serialize_field("tangent_x", &self.tangent_x)?;
+		s.serialize_field("osculating_x", &self.osculating_x)?;
+		s.serialize_field("polar", &self.polar)?;
+		s.serialize_field("slope_field", &self.slope_field)?;
+		s.serialize_field("vector_field", &self.vector_field)?;
+		s.serialize_field("q_func_str", &self.q_func_str)?;
+		s.serialize_field("contour", &self.contour)?;
+		s.serialize_field("contour_levels", &self.contour_levels)?;
+		s.serialize_field("contour_labels", &self.contour_labels)?;
+		s.serialize_field("complex_values", &self.complex_values)?;
+		s.serialize_field("domain_coloring", &self.domain_coloring)?;
+		s.serialize_field("sequence", &self.sequence)?;
+		s.serialize_field("sequence_start", &self.sequence_start)?;
+		s.serialize_field("partial_sums", &self.partial_sums)?;
+		s.serialize_field("data_series", &self.data_series)?;
+		s.serialize_field("data_csv", &self.data_csv)?;
+		s.serialize_field("rigorous", &self.rigorous)?;
+		s.serialize_field("visible", &self.visible)?;
+		s.serialize_field("color", &self.color)?;
+		s.serialize_field("line_width", &self.line_width)?;
+		s.serialize_field("curve_style", &self.curve_style)?;
 
 		s.end()
 	}
@@ -88,31 +632,124 @@ impl<'de> Deserialize<'de> for FunctionEntry {
 	where
 		D: Deserializer<'de>,
 	{
+		/// Fields missing from older sessions are the defaults of a new entry
 		#[derive(Deserialize)]
+		#[serde(default)]
 		struct Helper {
 			raw_func_str: String,
 			integral: bool,
+			integral_override: Option<IntegralOverride>,
 			derivative: bool,
+			antiderivative: bool,
+			nth_derviative: bool,
 			curr_nth: usize,
+			mean_value: bool,
+			curvature: bool,
+			tangent_x: Option<f64>,
+			osculating_x: Option<f64>,
+			polar: bool,
+			slope_field: bool,
+			vector_field: bool,
+			q_func_str: String,
+			contour: bool,
+			contour_levels: usize,
+			contour_labels: bool,
+			complex_values: bool,
+			domain_coloring: bool,
+			sequence: bool,
+			sequence_start: i64,
+			partial_sums: bool,
+			data_series: bool,
+			data_csv: String,
+			rigorous: bool,
+			visible: bool,
+			color: Option<Color32>,
+			line_width: f32,
+			curve_style: CurveStyle,
+		}
+
+		impl Default for Helper {
+			fn default() -> Helper {
+				let entry = FunctionEntry::default();
+				Helper {
+					raw_func_str: entry.raw_func_str,
+					integral: entry.integral,
+					integral_override: entry.integral_override,
+					derivative: entry.derivative,
+					antiderivative: entry.antiderivative,
+					nth_derviative: entry.nth_derviative,
+					curr_nth: entry.curr_nth,
+					mean_value: entry.mean_value,
+					curvature: entry.curvature,
+					tangent_x: entry.tangent_x,
+					osculating_x: entry.osculating_x,
+					polar: entry.polar,
+					slope_field: entry.slope_field,
+					vector_field: entry.vector_field,
+					q_func_str: entry.q_func_str,
+					contour: entry.contour,
+					contour_levels: entry.contour_levels,
+					contour_labels: entry.contour_labels,
+					complex_values: entry.complex_values,
+					domain_coloring: entry.domain_coloring,
+					sequence: entry.sequence,
+					sequence_start: entry.sequence_start,
+					partial_sums: entry.partial_sums,
+					data_series: entry.data_series,
+					data_csv: entry.data_csv,
+					rigorous: entry.rigorous,
+					visible: entry.visible,
+					color: entry.color,
+					line_width: entry.line_width,
+					curve_style: entry.curve_style,
+				}
+			}
 		}
 
 		let helper = Helper::deserialize(deserializer)?;
 		let mut new_func_entry = FunctionEntry::default();
-		let gen_func = BackingFunction::new(&helper.raw_func_str);
-		match gen_func {
-			Ok(func) => new_func_entry.function = func,
-			Err(x) => new_func_entry.test_result = Some(x),
-		}
+
+		// How the function string is parsed depends on what it's graphed as, so that's set first. User functions it calls are
+		// resolved once the definitions are set.
+		new_func_entry.polar = helper.polar;
+		new_func_entry.slope_field = helper.slope_field;
+		new_func_entry.vector_field = helper.vector_field;
+		new_func_entry.contour = helper.contour;
+		new_func_entry.complex_values = helper.complex_values;
+		new_func_entry.domain_coloring = helper.domain_coloring;
+		new_func_entry.sequence = helper.sequence;
+		new_func_entry.update_string(&helper.raw_func_str);
+		new_func_entry.update_q_string(&helper.q_func_str);
+		new_func_entry.update_data(&helper.data_csv);
 
 		new_func_entry.autocomplete = AutoComplete {
 			i: 0,
 			hint: generate_hint(&helper.raw_func_str),
+			cursor: helper.raw_func_str.chars().count(),
 			string: helper.raw_func_str,
+			..AutoComplete::EMPTY
 		};
 
 		new_func_entry.integral = helper.integral;
+		new_func_entry.integral_override = helper.integral_override;
 		new_func_entry.derivative = helper.derivative;
+		new_func_entry.antiderivative = helper.antiderivative;
+		new_func_entry.nth_derviative = helper.nth_derviative;
 		new_func_entry.curr_nth = helper.curr_nth;
+		new_func_entry.mean_value = helper.mean_value;
+		new_func_entry.curvature = helper.curvature;
+		new_func_entry.tangent_x = helper.tangent_x;
+		new_func_entry.osculating_x = helper.osculating_x;
+		new_func_entry.contour_levels = helper.contour_levels;
+		new_func_entry.contour_labels = helper.contour_labels;
+		new_func_entry.sequence_start = helper.sequence_start;
+		new_func_entry.partial_sums = helper.partial_sums;
+		new_func_entry.data_series = helper.data_series;
+		new_func_entry.rigorous = helper.rigorous;
+		new_func_entry.visible = helper.visible;
+		new_func_entry.color = helper.color;
+		new_func_entry.line_width = helper.line_width;
+		new_func_entry.curve_style = helper.curve_style;
 
 		Ok(new_func_entry)
 	}
@@ -123,121 +760,1745 @@ impl const Default for FunctionEntry {
 	fn default() -> FunctionEntry {
 		FunctionEntry {
 			function: BackingFunction::default(),
+			bivariate: None,
+			complex: None,
+			implicit_plot: ImplicitPlot::default(),
+			contour_data: ContourPlot::default(),
+			slope_field_data: SlopeField::default(),
+			vector_q: None,
+			q_func_str: String::new(),
+			q_test_result: None,
+			vector_field_data: VectorField::default(),
+			line_integral_rect: None,
+			line_integral_data: None,
 			raw_func_str: String::new(),
 			integral: false,
+			integral_override: None,
 			derivative: false,
+			antiderivative: false,
 			nth_derviative: false,
+			mean_value: false,
+			curvature: false,
+			polar: false,
+			slope_field: false,
+			vector_field: false,
+			contour: false,
+			contour_levels: 10,
+			contour_labels: true,
+			complex_values: false,
+			domain_coloring: false,
+			complex_data: None,
+			domain_coloring_data: DomainColoring::default(),
+			sequence: false,
+			sequence_start: 1,
+			partial_sums: false,
+			sequence_data: Vec::new(),
+			data_series: false,
+			data_csv: String::new(),
+			data_points: Vec::new(),
+			data_test_result: None,
+			partial_sum_data: Vec::new(),
 			back_data: Vec::new(),
+			back_gaps: Vec::new(),
+			rigorous: false,
+			rigorous_data: Vec::new(),
 			integral_data: None,
+			arc_length_data: None,
+			trapezoid_data: Vec::new(),
+			integral_subdivisions: None,
+			monte_carlo_data: (Vec::new(), Vec::new()),
+			integral_std_error: None,
+			integral_error_bound: None,
+			romberg_table: Vec::new(),
+			integral_singular: false,
+			integral_diverges: false,
+			integral_parts: None,
+			darboux_data: Vec::new(),
+			darboux_bounds: None,
+			comparison_data: None,
+			convergence_data: Vec::new(),
+			tangent_x: None,
+			tangent_data: None,
+			mean_value_data: None,
+			osculating_x: None,
+			osculating_data: None,
+			period: None,
+			period_stale: false,
+			limit_x: 0.0,
+			limit_data: None,
 			derivative_data: Vec::new(),
+			derivative_gaps: Vec::new(),
+			antiderivative_data: Vec::new(),
 			extrema_data: Vec::new(),
 			root_data: Vec::new(),
 			nth_derivative_data: None,
+			curvature_data: None,
 			autocomplete: AutoComplete::EMPTY,
 			test_result: None,
+			error_span: None,
+			correction: None,
 			curr_nth: 3,
+			angle_unit: AngleUnit::Radians,
+			parameters: BTreeMap::new(),
+			unbound: Unbound {
+				function: BackingFunction::default(),
+				bivariate: None,
+				complex: None,
+				vector_q: None,
+			},
+			function_parameters: Vec::new(),
+			q_parameters: Vec::new(),
+			definitions: Definitions::EMPTY,
+			function_calls: Vec::new(),
+			q_calls: Vec::new(),
+			definition: None,
+			typeset: None,
+			formulas: None,
+			visible: true,
+			color: None,
+			line_width: 4.0,
+			curve_style: CurveStyle::Solid,
 			settings_opened: false,
 		}
 	}
 }
 
 impl FunctionEntry {
-	pub const fn is_some(&self) -> bool { !self.function.is_none() }
+	pub const fn is_some(&self) -> bool {
+		!self.function.is_none()
+			| self.bivariate.is_some()
+			| self.complex.is_some()
+			| self.data_series
+	}
 
-	pub fn settings_window(&mut self, ctx: &Context) {
+	/// Shows the window of the function's settings (if it's open), with its text in the language of `i18n` and its values in `number_format`
+	pub fn settings_window(
+		&mut self, ctx: &Context, i18n: &Translations, number_format: &NumberFormat,
+	) {
 		let mut invalidate_nth = false;
-		egui::Window::new(format!("Settings: {}", self.raw_func_str))
+		let mut invalidate_polar = false;
+		let mut reparse = false;
+		let mut data_csv = self.data_csv.clone();
+		let mut export_csv: Option<(&str, fn(&Self) -> String)> = None;
+		egui::Window::new(i18n.format("function_settings.title", &self.raw_func_str))
 			.open(&mut self.settings_opened)
 			.default_pos([200.0, 200.0])
 			.resizable(false)
 			.collapsible(false)
 			.show(ctx, |ui| {
+				invalidate_polar = ui
+					.checkbox(&mut self.polar, i18n.get("function_settings.polar"))
+					.on_hover_text(i18n.get("function_settings.polar.hover"))
+					.changed();
+
+				ui.checkbox(&mut self.rigorous, i18n.get("function_settings.rigorous"))
+					.on_hover_text(i18n.get("function_settings.rigorous.hover"));
+
+				reparse = ui
+					.checkbox(
+						&mut self.slope_field,
+						i18n.get("function_settings.slope_field"),
+					)
+					.on_hover_text(i18n.get("function_settings.slope_field.hover"))
+					.changed();
+
+				if self.slope_field {
+					let initial_conditions = &mut self.slope_field_data.initial_conditions;
+					let mut changed = false;
+					let mut remove_i: Option<usize> = None;
+					for (i, initial) in initial_conditions.iter_mut().enumerate() {
+						ui.horizontal(|ui| {
+							changed |= ui
+								.add(DragValue::new(&mut initial.x).speed(0.1).prefix("x₀: "))
+								.changed();
+							changed |= ui
+								.add(DragValue::new(&mut initial.y).speed(0.1).prefix("y₀: "))
+								.changed();
+							if ui
+								.button("✖")
+								.on_hover_text(
+									i18n.get("function_settings.initial_condition.remove"),
+								)
+								.clicked()
+							{
+								remove_i = Some(i);
+							}
+						});
+					}
+
+					if let Some(i) = remove_i {
+						initial_conditions.remove(i);
+						changed = true;
+					}
+
+					if ui
+						.button(i18n.get("function_settings.initial_condition.add"))
+						.on_hover_text(i18n.get("function_settings.initial_condition.add.hover"))
+						.clicked()
+					{
+						initial_conditions.push(PlotPoint::new(0.0, 0.0));
+						changed = true;
+					}
+
+					if changed {
+						self.slope_field_data.solutions.clear();
+					}
+				}
+
+				reparse |= ui
+					.checkbox(
+						&mut self.complex_values,
+						i18n.get("function_settings.complex"),
+					)
+					.on_hover_text(i18n.get("function_settings.complex.hover"))
+					.changed();
+
+				reparse |= ui
+					.checkbox(
+						&mut self.domain_coloring,
+						i18n.get("function_settings.domain_coloring"),
+					)
+					.on_hover_text(i18n.get("function_settings.domain_coloring.hover"))
+					.changed();
+
+				ui.checkbox(
+					&mut self.data_series,
+					i18n.get("function_settings.data_series"),
+				)
+				.on_hover_text(i18n.get("function_settings.data_series.hover"));
+
+				if self.data_series {
+					ui.add(
+						egui::TextEdit::multiline(&mut data_csv)
+							.hint_text(i18n.get("function_settings.data_series.hint"))
+							.desired_rows(4),
+					);
+				}
+
+				reparse |= ui
+					.checkbox(&mut self.sequence, i18n.get("function_settings.sequence"))
+					.on_hover_text(i18n.get("function_settings.sequence.hover"))
+					.changed();
+
+				if self.sequence {
+					ui.horizontal(|ui| {
+						ui.label(i18n.get("function_settings.sequence.start"));
+						if ui.add(DragValue::new(&mut self.sequence_start)).changed() {
+							self.sequence_data.clear();
+							self.partial_sum_data.clear();
+						}
+					});
+
+					ui.checkbox(
+						&mut self.partial_sums,
+						i18n.get("function_settings.partial_sums"),
+					)
+					.on_hover_text(i18n.get("function_settings.partial_sums.hover"));
+				}
+
+				reparse |= ui
+					.checkbox(&mut self.contour, i18n.get("function_settings.contour"))
+					.on_hover_text(i18n.get("function_settings.contour.hover"))
+					.changed();
+
+				if self.contour {
+					ui.add(
+						egui::Slider::new(&mut self.contour_levels, 1..=30)
+							.text(i18n.get("function_settings.contour.levels")),
+					);
+					ui.checkbox(
+						&mut self.contour_labels,
+						i18n.get("function_settings.contour.labels"),
+					);
+				}
+
+				reparse |= ui
+					.checkbox(
+						&mut self.vector_field,
+						i18n.get("function_settings.vector_field"),
+					)
+					.on_hover_text(i18n.get("function_settings.vector_field.hover"))
+					.changed();
+
+				if self.vector_field {
+					let mut line_integral = self.line_integral_rect.is_some();
+					let ([mut min_x, mut min_y], [mut max_x, mut max_y]) = self
+						.line_integral_rect
+						.unwrap_or(([-1.0, -1.0], [1.0, 1.0]));
+					ui.checkbox(
+						&mut line_integral,
+						i18n.get("function_settings.line_integral"),
+					)
+					.on_hover_text(i18n.get("function_settings.line_integral.hover"));
+					ui.add_enabled_ui(line_integral, |ui| {
+						ui.horizontal(|ui| {
+							ui.label("x:");
+							ui.add(DragValue::new(&mut min_x).speed(0.1));
+							ui.label(i18n.get("settings.view_range.to"));
+							ui.add(DragValue::new(&mut max_x).speed(0.1));
+						});
+						ui.horizontal(|ui| {
+							ui.label("y:");
+							ui.add(DragValue::new(&mut min_y).speed(0.1));
+							ui.label(i18n.get("settings.view_range.to"));
+							ui.add(DragValue::new(&mut max_y).speed(0.1));
+						});
+					});
+
+					let line_integral_rect =
+						line_integral.then_some(([min_x, min_y], [max_x, max_y]));
+					if line_integral_rect != self.line_integral_rect {
+						self.line_integral_rect = line_integral_rect;
+						self.line_integral_data = None;
+					}
+
+					if let Some((min, max)) = self.line_integral_rect
+						&& let (Some(p), Some(q)) = (&self.bivariate, &self.vector_q)
+					{
+						let (circulation, flux) =
+							*self.line_integral_data.get_or_insert_with(|| {
+								line_integrals(
+									&mut |x, y| p.get(x, y),
+									&mut |x, y| q.get(x, y),
+									min,
+									max,
+								)
+							});
+						ui.label(i18n.format(
+							"function_settings.circulation",
+							&number_format.format(circulation, i18n),
+						));
+						ui.label(
+							i18n.format(
+								"function_settings.flux",
+								&number_format.format(flux, i18n),
+							),
+						);
+					}
+				}
+
 				ui.add(Checkbox::new(
 					&mut self.nth_derviative,
-					"Display Nth Derivative",
+					i18n.get("function_settings.nth_derivative"),
 				));
 
 				if ui
-					.add(egui::Slider::new(&mut self.curr_nth, 3..=5).text("Nth Derivative"))
+					.add(
+						egui::Slider::new(&mut self.curr_nth, 3..=5)
+							.text(i18n.get("function_settings.nth_derivative.order")),
+					)
 					.changed()
 				{
 					invalidate_nth = true;
 				}
+
+				let mut tangent = self.tangent_x.is_some();
+				let mut tangent_x = self.tangent_x.unwrap_or(0.0);
+				ui.horizontal(|ui| {
+					ui.checkbox(&mut tangent, i18n.get("function_settings.tangent"));
+					ui.add_enabled(tangent, DragValue::new(&mut tangent_x).speed(0.1));
+				});
+				self.tangent_x = tangent.then_some(tangent_x);
+
+				ui.checkbox(&mut self.curvature, i18n.get("function_settings.curvature"));
+
+				let mut osculating = self.osculating_x.is_some();
+				let mut osculating_x = self.osculating_x.unwrap_or(0.0);
+				ui.horizontal(|ui| {
+					ui.checkbox(&mut osculating, i18n.get("function_settings.osculating"));
+					ui.add_enabled(osculating, DragValue::new(&mut osculating_x).speed(0.1));
+				});
+				self.osculating_x = osculating.then_some(osculating_x);
+
+				ui.checkbox(
+					&mut self.mean_value,
+					i18n.get("function_settings.mean_value"),
+				)
+				.on_hover_text(i18n.get("function_settings.mean_value.hover"));
+
+				ui.separator();
+				ui.horizontal(|ui| {
+					ui.label(i18n.get("function_settings.limit.at"));
+					if ui
+						.add(DragValue::new(&mut self.limit_x).speed(0.1))
+						.changed()
+					{
+						self.limit_data = None;
+					}
+				});
+
+				let (left, right) = match self.limit_data {
+					Some(limits) => limits,
+					None => {
+						let limits = limits(&mut self.function, self.limit_x);
+						self.limit_data = Some(limits);
+						limits
+					}
+				};
+				ui.label(i18n.format(
+					"function_settings.limit.left",
+					&number_format.format(left, i18n),
+				));
+				ui.label(i18n.format(
+					"function_settings.limit.right",
+					&number_format.format(right, i18n),
+				));
+				ui.label(match limits_agree(left, right) {
+					true => i18n.format(
+						"function_settings.limit",
+						&number_format.format((left + right) / 2.0, i18n),
+					),
+					false => i18n.get("function_settings.limit.none").to_owned(),
+				});
+
+				ui.separator();
+				ui.horizontal(|ui| {
+					ui.label(i18n.get("function_settings.export"));
+					let has_rectangles = self
+						.integral_data
+						.as_ref()
+						.is_some_and(|(bars, _)| !bars.is_empty());
+					for (name, file_name, enabled, csv, hover) in [
+						(
+							"function_settings.export.samples",
+							"samples.csv",
+							!self.back_data.is_empty(),
+							Self::samples_csv as fn(&Self) -> String,
+							"function_settings.export.samples.hover",
+						),
+						(
+							"function_settings.export.derivative",
+							"derivative.csv",
+							!self.derivative_data.is_empty(),
+							Self::derivative_csv,
+							"function_settings.export.derivative.hover",
+						),
+						(
+							"function_settings.export.rectangles",
+							"rectangles.csv",
+							has_rectangles,
+							Self::rectangles_csv,
+							"function_settings.export.rectangles.hover",
+						),
+					] {
+						if ui
+							.add_enabled(enabled, egui::Button::new(i18n.get(name)))
+							.on_hover_text(i18n.get(hover))
+							.clicked()
+						{
+							export_csv = Some((file_name, csv));
+						}
+					}
+				});
 			});
 
+		if let Some((file_name, csv)) = export_csv {
+			save_file(file_name, "text/csv", csv(self).as_bytes());
+		}
+
 		if invalidate_nth {
 			self.function.generate_derivative(self.curr_nth);
 			self.clear_nth();
 		}
+
+		if invalidate_polar {
+			self.invalidate_whole();
+		}
+
+		self.update_data(&data_csv);
+
+		// The function string is parsed differently depending on how it's graphed (such as for slope fields), so it's parsed again when `update_string` is next called
+		if reparse {
+			self.raw_func_str.clear();
+		}
 	}
 
-	/// Get function's cached test result
-	pub fn get_test_result(&self) -> &Option<String> { &self.test_result }
+	/// f(x) and f'(x) at each of `xs`, for the table of values.
+	/// Returns `None` if the function isn't a function of x, such as an implicit equation or data series.
+	pub fn table(&mut self, xs: &[f64]) -> Option<Vec<(f64, f64)>> {
+		if self.test_result.is_some()
+			| self.function.is_none()
+			| self.data_series
+			| self.bivariate.is_some()
+			| self.complex.is_some()
+		{
+			return None;
+		}
 
-	/// Update function string and test it
-	pub fn update_string(&mut self, raw_func_str: &str) {
-		if raw_func_str == self.raw_func_str {
-			return;
+		self.function.generate_derivative(1);
+		Some(
+			xs.iter()
+				.map(|x| (self.function.get(0, *x), self.function.get(1, *x)))
+				.collect(),
+		)
+	}
+
+	/// Length of the function's curve over the integral's bounds, ∫√(1 + f'(x)²) dx (or ∫√(r² + r'²) dθ over the θ range for polar functions).
+	/// Returns `None` if the function isn't a function of x, like [`FunctionEntry::table`].
+	pub fn arc_length(&mut self, settings: &AppSettings) -> Option<f64> {
+		/// Maximum error of the arc length
+		const TOLERANCE: f64 = 1e-9;
+
+		/// Maximum amount of times an interval can be split in half
+		const MAX_DEPTH: usize = 16;
+
+		if self.test_result.is_some()
+			| self.function.is_none()
+			| self.data_series
+			| self.sequence
+			| self.bivariate.is_some()
+			| self.complex.is_some()
+		{
+			return None;
 		}
 
-		self.raw_func_str = raw_func_str.to_owned();
-		let processed_func = process_func_str(raw_func_str);
-		let new_func_result = BackingFunction::new(&processed_func);
+		let settings = self.integral_settings(settings);
+		let (min_x, max_x) = (
+			settings.integral_min_x.min(settings.integral_max_x),
+			settings.integral_min_x.max(settings.integral_max_x),
+		);
+		self.function.generate_derivative(1);
+		let polar = self.polar;
+		let (length, _) = adaptive_simpson(
+			&mut |x| {
+				let dx = match polar {
+					true => self.function.get(0, x),
+					false => 1.0,
+				};
+				dx.hypot(self.function.get(1, x))
+			},
+			min_x,
+			max_x,
+			TOLERANCE,
+			MAX_DEPTH,
+		);
+		Some(length).filter(|length| length.is_finite())
+	}
 
-		match new_func_result {
-			Ok(new_function) => {
-				self.test_result = None;
-				self.function = new_function;
-				self.invalidate_whole();
+	/// Numbers shown in the results panel, each being the key of its name in the translations, its x value (if it has one), and
+	/// its value: the roots and extrema found within the plot (while finding them is enabled), then the integral's area and the
+	/// arc length over its bounds, which is only calculated again once the integral is
+	pub fn results(&mut self, settings: &AppSettings) -> Vec<(&'static str, Option<f64>, f64)> {
+		let mut results: Vec<(&'static str, Option<f64>, f64)> = self
+			.root_data
+			.iter()
+			.map(|point| ("results.root", Some(point.x), point.y))
+			.collect();
+		results.extend(
+			self.extrema_data
+				.iter()
+				.map(|(point, extremum)| (extremum.label(), Some(point.x), point.y)),
+		);
+
+		if let Some((_, area)) = self.integral_data {
+			results.push(("results.area", None, area));
+			if let Some((positive, negative)) = self.integral_parts {
+				results.push(("results.area_above", None, positive));
+				results.push(("results.area_below", None, negative));
 			}
-			Err(error) => {
-				self.test_result = Some(error);
+			if self.arc_length_data.is_none() {
+				self.arc_length_data = Some(self.arc_length(settings));
+			}
+			if let Some(Some(length)) = self.arc_length_data {
+				results.push(("results.arc_length", None, length));
 			}
 		}
+		results
 	}
 
-	/// Creates and does the math for creating all the rectangles under the graph
-	fn integral_rectangles(
-		&mut self, integral_min_x: f64, integral_max_x: f64, sum: Riemann, integral_num: usize,
-	) -> (Vec<(f64, f64)>, f64) {
-		let step = (integral_max_x - integral_min_x) / (integral_num as f64);
+	/// Sampled points of the function as CSV, with a column for x and one for y
+	pub fn samples_csv(&self) -> String {
+		to_csv(
+			["x", "y"],
+			self.back_data.iter().map(|point| [point.x, point.y]),
+		)
+	}
 
-		// let sum_func = self.get_sum_func(sum);
+	/// Sampled points of the function's derivative as CSV, with a column for x and one for f'(x)
+	pub fn derivative_csv(&self) -> String {
+		to_csv(
+			["x", "f'(x)"],
+			self.derivative_data.iter().map(|point| [point.x, point.y]),
+		)
+	}
 
-		let data2: Vec<(f64, f64)> = step_helper(integral_num, integral_min_x, step)
-			.into_iter()
-			.map(|x| {
-				let step_offset = step.copysign(x); // store the offset here so it doesn't have to be calculated multiple times
-				let x2: f64 = x + step_offset;
+	/// Rectangles of the integral's Riemann sum as CSV, with columns for the center x of each, its width, its height, and its area.
+	/// The integral is the sum of their areas, or the negative of it if its bounds are reversed.
+	pub fn rectangles_csv(&self) -> String {
+		let bars = self
+			.integral_data
+			.as_ref()
+			.map(|(bars, _)| bars.as_slice())
+			.unwrap_or_default();
+		to_csv(
+			["x", "width", "height", "area"],
+			bars.iter().map(|bar| {
+				[
+					bar.argument,
+					bar.bar_width,
+					bar.value,
+					bar.bar_width * bar.value,
+				]
+			}),
+		)
+	}
 
-				let (left_x, right_x) = match x.is_sign_positive() {
-					true => (x, x2),
-					false => (x2, x),
-				};
+	/// Uploads the domain coloring's image (if it was recolored) to be drawn, called after [`FunctionEntry::calculate`]
+	pub fn load_textures(&mut self, ctx: &Context) {
+		if self.domain_coloring {
+			self.domain_coloring_data.load_texture(ctx);
+		}
+	}
 
-				let y = match sum {
-					Riemann::Left => self.function.get(0, left_x),
-					Riemann::Right => self.function.get(0, right_x),
-					Riemann::Middle => {
-						(self.function.get(0, left_x) + self.function.get(0, right_x)) / 2.0
-					}
-				};
+	/// Adds a solution curve through `point` if the function is a slope field
+	pub fn add_initial_condition(&mut self, point: PlotPoint) {
+		if self.test_result.is_some() | !self.slope_field {
+			return;
+		}
 
-				(x + (step_offset / 2.0), y)
-			})
-			.filter(|(_, y)| y.is_finite())
-			.collect();
+		self.slope_field_data.initial_conditions.push(point);
+		self.slope_field_data.solutions.clear();
+	}
 
-		let area = data2.iter().map(move |(_, y)| y * step).sum();
+	/// Vertical distance between the function and `point`, used for clicking on functions in the plot.
+	/// Returns `None` if the function isn't defined at `point`, or is a polar function or sequence.
+	pub fn distance_to(&mut self, point: PlotPoint) -> Option<f64> {
+		if self.test_result.is_some() | self.function.is_none() | self.polar | self.sequence {
+			return None;
+		}
 
-		(data2, area)
+		let distance = (self.function.get(0, point.x) - point.y).abs();
+		distance.is_finite().then_some(distance)
 	}
 
-	/// Helps with processing newton's method depending on level of derivative
-	fn newtons_method_helper(
-		&mut self, threshold: f64, derivative_level: usize, range: &std::ops::Range<f64>,
-	) -> Vec<PlotPoint> {
-		self.function.generate_derivative(derivative_level);
+	/// Sampled point of the function's curve nearest to `pointer`, along with f'(x) there if the derivative is shown, used to trace the curve under the mouse.
+	/// `scale` is the size of a pixel along each axis, so points are compared by how far apart they are on the screen.
+	pub fn trace(&self, pointer: PlotPoint, scale: [f64; 2]) -> Option<(PlotPoint, Option<f64>)> {
+		if !self.visible | self.test_result.is_some() | self.function.is_none() | self.sequence {
+			return None;
+		}
+
+		let screen_distance = |point: &PlotPoint| {
+			((point.x - pointer.x) / scale[0]).hypot((point.y - pointer.y) / scale[1])
+		};
+		let (i, point) = self
+			.back_data
+			.iter()
+			.enumerate()
+			.filter(|(_, point)| point.y.is_finite())
+			.min_by(|(_, a), (_, b)| screen_distance(a).total_cmp(&screen_distance(b)))?;
+
+		// The derivative is sampled at the same x values, except for polar functions where it isn't calculated
+		let derivative = match self.derivative && !self.polar {
+			true => self.derivative_data.get(i).map(|point| point.y),
+			false => None,
+		};
+		Some((*point, derivative))
+	}
+
+	/// Rectangle of the Riemann sum under `pointer`, found among the bars that were calculated for it
+	pub fn rectangle_at(
+		&self, pointer: PlotPoint, settings: &AppSettings,
+	) -> Option<RiemannRectangle> {
+		if !self.integral | self.polar {
+			return None;
+		}
+
+		let settings = self.integral_settings(settings);
+		let (bars, _) = self.integral_data.as_ref()?;
+		let (index, bar) = bars.iter().enumerate().find(|(_, bar)| {
+			let half_width = bar.bar_width / 2.0;
+			bar.value.is_finite()
+				&& ((bar.argument - half_width)..=(bar.argument + half_width)).contains(&pointer.x)
+				&& (bar.value.min(0.0)..=bar.value.max(0.0)).contains(&pointer.y)
+		})?;
+
+		// Bars are always from left to right, integrating from right to left negates their areas
+		let sign = match settings.integral_min_x > settings.integral_max_x {
+			true => -1.0,
+			false => 1.0,
+		};
+		let area = |bar: &Bar| sign * bar.value * bar.bar_width;
+		Some(RiemannRectangle {
+			index,
+			interval: (
+				bar.argument - (bar.bar_width / 2.0),
+				bar.argument + (bar.bar_width / 2.0),
+			),
+			height: bar.value,
+			area: area(bar),
+			cumulative: bars[..=index]
+				.iter()
+				.filter(|bar| bar.value.is_finite())
+				.map(area)
+				.sum(),
+		})
+	}
+
+	/// Smallest and largest corners of the box around the function's drawn points, used to fit the plot to the functions.
+	/// The most extreme 1% of values on either side are left out, so points near asymptotes don't stretch the box.
+	pub fn extent(&self) -> Option<(PlotPoint, PlotPoint)> {
+		if !self.visible | self.test_result.is_some() {
+			return None;
+		}
+
+		let points: Vec<&PlotPoint> = self
+			.back_data
+			.iter()
+			.chain(self.sequence_data.iter())
+			.chain(self.data_points.iter())
+			.filter(|point| point.x.is_finite() && point.y.is_finite())
+			.collect();
+		if points.is_empty() {
+			return None;
+		}
+
+		let trimmed = |mut values: Vec<f64>| {
+			values.sort_by(|a, b| a.total_cmp(b));
+			let cut = values.len() / 100;
+			(values[cut], values[values.len() - 1 - cut])
+		};
+		let (min_x, max_x) = trimmed(points.iter().map(|point| point.x).collect());
+		let (min_y, max_y) = trimmed(points.iter().map(|point| point.y).collect());
+		Some((PlotPoint::new(min_x, min_y), PlotPoint::new(max_x, max_y)))
+	}
+
+	/// Finds the points where the function intersects `other` within the plot's bounds, refined to `root_tolerance`
+	pub fn intersections(&mut self, other: &mut Self, settings: &AppSettings) -> Vec<PlotPoint> {
+		if self.test_result.is_some()
+			| self.function.is_none()
+			| other.test_result.is_some()
+			| other.function.is_none()
+			| self.polar
+			| other.polar
+		{
+			return Vec::new();
+		}
+
+		self.function.generate_derivative(1);
+		other.function.generate_derivative(1);
+		find_roots(
+			&mut |x| {
+				(
+					self.function.get(0, x) - other.function.get(0, x),
+					self.function.get(1, x) - other.function.get(1, x),
+				)
+			},
+			settings.min_x,
+			settings.max_x,
+			settings.plot_width,
+			settings.root_tolerance,
+		)
+		.into_iter()
+		.map(|x| PlotPoint::new(x, self.function.get(0, x)))
+		.collect()
+	}
+
+	/// Finds the solutions of f(x) = `target` within the plot's bounds, refined to `root_tolerance`
+	pub fn solve(&mut self, target: f64, settings: &AppSettings) -> Vec<PlotPoint> {
+		if self.test_result.is_some() | self.function.is_none() | self.polar {
+			return Vec::new();
+		}
+
+		self.function.generate_derivative(1);
+		find_roots(
+			&mut |x| (self.function.get(0, x) - target, self.function.get(1, x)),
+			settings.min_x,
+			settings.max_x,
+			settings.plot_width,
+			settings.root_tolerance,
+		)
+		.into_iter()
+		.map(|x| PlotPoint::new(x, target))
+		.collect()
+	}
+
+	/// Get function's cached test result
+	pub fn get_test_result(&self) -> &Option<String> {
+		match self.test_result {
+			_ if self.data_series => &self.data_test_result,
+			None if self.vector_field => &self.q_test_result,
+			_ => &self.test_result,
+		}
+	}
+
+	/// Update the CSV the data points are parsed from and test it
+	pub fn update_data(&mut self, data_csv: &str) {
+		if data_csv == self.data_csv {
+			return;
+		}
+
+		self.data_csv = data_csv.to_owned();
+		match parse_csv_points(data_csv) {
+			Ok(mut points) => {
+				points.sort_by(|a, b| a.x.total_cmp(&b.x));
+				self.data_test_result = None;
+				self.data_points = points;
+			}
+			Err(error) => {
+				self.data_test_result = Some(error);
+			}
+		}
+	}
+
+	/// If the function is graphed as a function of x and y (rather than just x)
+	const fn is_bivariate(&self) -> bool { self.slope_field | self.vector_field | self.contour }
+
+	/// If the function is evaluated over complex numbers
+	const fn is_complex(&self) -> bool { self.complex_values | self.domain_coloring }
+
+	/// Update the string of Q(x, y) (used by vector fields) and test it
+	pub fn update_q_string(&mut self, q_func_str: &str) {
+		if q_func_str == self.q_func_str {
+			return;
+		}
+
+		self.q_func_str = q_func_str.to_owned();
+		self.q_parameters.clear();
+		self.q_calls = Definitions::calls(q_func_str);
+		match BivariateFunction::new_using(q_func_str, &self.definitions) {
+			Ok(new_function) => {
+				self.q_test_result = None;
+				self.q_parameters = self.track_parameters(new_function.parameters());
+				let new_function = new_function.with_angle_unit(self.angle_unit);
+				self.unbound.vector_q = Some(new_function.clone());
+				self.vector_q = Some(new_function.with_parameters(&self.parameters));
+				self.vector_field_data.clear();
+				self.line_integral_data = None;
+			}
+			Err(error) => {
+				self.q_test_result = Some(error.to_string());
+			}
+		}
+	}
+
+	/// Update function string and test it
+	pub fn update_string(&mut self, raw_func_str: &str) {
+		if raw_func_str == self.raw_func_str {
+			return;
+		}
+
+		self.raw_func_str = raw_func_str.to_owned();
+		// n and z (along with N and Z, unless variables are case sensitive) are replaced one for one, so errors' spans still line up with `raw_func_str`
+		let replace = |from: char| {
+			let replaced = replace_variable(raw_func_str, from, 'x');
+			match self.definitions.case_sensitive() {
+				true => replaced,
+				false => replace_variable(&replaced, from.to_ascii_uppercase(), 'x'),
+			}
+		};
+		let func_str = match self.sequence {
+			true => replace('n'),
+			false if self.domain_coloring => replace('z'),
+			false => raw_func_str.to_owned(),
+		};
+
+		self.error_span = None;
+		self.correction = None;
+		self.function_parameters.clear();
+
+		// Definitions of user functions (such as `f(x) = x^2 + 1`) are graphed as their body, which starts at `body_start`
+		let body_start = match Definitions::split(&func_str) {
+			Some((name, body_start)) => {
+				self.definition = Some((name, func_str[body_start..].to_owned()));
+				body_start
+			}
+			None => {
+				self.definition = None;
+				0
+			}
+		};
+		let func_str = &func_str[body_start..];
+		self.function_calls = Definitions::calls(func_str);
+		self.typeset = self.typeset_func_str(func_str);
+		self.formulas = None;
+
+		// Complex functions are evaluated over complex numbers, where i is the imaginary unit
+		if self.is_complex() {
+			match Expression::parse_using(func_str, &['x', 'i'], &self.definitions) {
+				Ok(new_function) => {
+					self.test_result = None;
+					self.function_parameters = self.track_parameters(new_function.parameters());
+					self.unbound.function = BackingFunction::default();
+					self.unbound.bivariate = None;
+					self.unbound.complex = Some(new_function.with_angle_unit(self.angle_unit));
+					self.bind_parameters();
+					self.period = Some(None);
+				}
+				Err(error) => self.set_error(error, body_start),
+			}
+			return;
+		}
+
+		// Slope fields, vector fields, and contour plots are of functions of x and y, and equations (such as x^2 + y^2 = 4) are graphed implicitly
+		if self.is_bivariate() | func_str.contains('=') {
+			let new_func_result = match self.is_bivariate() {
+				true => BivariateFunction::new_using(func_str, &self.definitions),
+				false => BivariateFunction::new_implicit_using(func_str, &self.definitions),
+			};
+
+			match new_func_result {
+				Ok(new_function) => {
+					self.test_result = None;
+					self.function_parameters = self.track_parameters(new_function.parameters());
+					self.unbound.function = BackingFunction::default();
+					self.unbound.bivariate = Some(new_function.with_angle_unit(self.angle_unit));
+					self.unbound.complex = None;
+					self.bind_parameters();
+					self.period = Some(None);
+				}
+				Err(error) => self.set_error(error, body_start),
+			}
+			return;
+		}
+
+		let new_func_result = BackingFunction::new_using(func_str, &self.definitions);
+
+		match new_func_result {
+			Ok(new_function) => {
+				self.test_result = None;
+				self.function_parameters = self.track_parameters(new_function.parameters());
+				self.formulas = match self.sequence {
+					true => None,
+					false => new_function.expression().map(|function| {
+						let function = function.clone().with_angle_unit(self.angle_unit).simplified();
+						let derivative = function.derivative().simplified();
+						(function.to_string(), derivative.to_string())
+					}),
+				};
+				self.unbound.function = new_function.with_angle_unit(self.angle_unit);
+				self.unbound.bivariate = None;
+				self.unbound.complex = None;
+				self.bind_parameters();
+				self.period = None;
+			}
+			Err(error) => self.set_error(error, body_start),
+		}
+	}
+
+	/// Sets the unit of the angles used by trigonometric functions, parsing the function strings again if it changed
+	pub fn set_angle_unit(&mut self, angle_unit: AngleUnit) {
+		if angle_unit == self.angle_unit {
+			return;
+		}
+
+		self.angle_unit = angle_unit;
+		self.reparse();
+	}
+
+	/// Names of the parameters used by the function (and Q(x, y) of vector fields)
+	pub fn parameter_names(&self) -> impl Iterator<Item = &String> {
+		self.function_parameters.iter().chain(self.q_parameters.iter())
+	}
+
+	/// Sets the values of the function's parameters, replacing them in the functions again if any of them changed. As finding the
+	/// period is slow, it isn't found again until the values stop changing (such as when a parameter's animation is paused).
+	pub fn set_parameters(&mut self, values: &BTreeMap<String, f64>) {
+		if self
+			.parameter_names()
+			.all(|name| values.get(name) == self.parameters.get(name))
+		{
+			self.period_stale = false;
+			return;
+		}
+
+		self.parameters = values.clone();
+		self.bind_parameters();
+		self.period = None;
+		self.period_stale = true;
+	}
+
+	/// Replaces the parameters of the functions as they were parsed with their values, forgetting everything calculated from the
+	/// previous functions
+	fn bind_parameters(&mut self) {
+		let parameters = &self.parameters;
+		self.function = self.unbound.function.clone().with_parameters(parameters);
+		self.bivariate = self
+			.unbound
+			.bivariate
+			.clone()
+			.map(|f| f.with_parameters(parameters));
+		self.complex = self
+			.unbound
+			.complex
+			.clone()
+			.map(|f| f.with_parameters(parameters));
+		self.vector_q = self
+			.unbound
+			.vector_q
+			.clone()
+			.map(|f| f.with_parameters(parameters));
+		self.invalidate_whole();
+	}
+
+	/// Name and body of the user function defined by the function string, if it's a definition
+	pub fn definition(&self) -> Option<&(String, String)> { self.definition.as_ref() }
+
+	/// Sets the user functions that can be called, parsing the function strings again if any of the ones they call (or how variables are matched) changed
+	pub fn set_definitions(&mut self, definitions: &Definitions) {
+		if (definitions.case_sensitive() == self.definitions.case_sensitive())
+			&& self
+				.function_calls
+				.iter()
+				.chain(self.q_calls.iter())
+				.all(|name| definitions.get(name) == self.definitions.get(name))
+		{
+			return;
+		}
+
+		self.definitions = definitions.clone();
+		self.reparse();
+	}
+
+	/// Gives the parameters `names` the default value if they don't have one, returning `names`
+	fn track_parameters(&mut self, names: Vec<String>) -> Vec<String> {
+		names.iter().for_each(|name| {
+			self.parameters
+				.entry(name.clone())
+				.or_insert(DEFAULT_PARAMETER);
+		});
+		names
+	}
+
+	/// Parses the function string and Q(x, y) again, such as after the definitions they can call change
+	fn reparse(&mut self) {
+		let raw_func_str = std::mem::take(&mut self.raw_func_str);
+		self.update_string(&raw_func_str);
+		let q_func_str = std::mem::take(&mut self.q_func_str);
+		self.update_q_string(&q_func_str);
+	}
+
+	/// Stores `error` from parsing the function string starting at `offset`, along with where in the string it was caused
+	fn set_error(&mut self, error: ParseError, offset: usize) {
+		let span = (error.span.start + offset)..(error.span.end + offset);
+		self.correction = self.raw_func_str.get(span.clone()).and_then(closest_function);
+		self.test_result = Some(error.to_string());
+		self.typeset = None;
+		self.error_span = Some(span);
+	}
+
+	/// Typesets `func_str` (the body of the definition, if the function string is one) the way it's parsed, being `None` if it can't be parsed
+	fn typeset_func_str(&self, func_str: &str) -> Option<Typeset> {
+		let variables: &[char] = match self.is_complex() {
+			true => &['x', 'i'],
+			false => &['x', 'y'],
+		};
+		let x_name = match self.sequence {
+			true => "n",
+			false if self.domain_coloring => "z",
+			false if self.polar => "θ",
+			false => "x",
+		};
+		let typeset_side = |side: &str| {
+			Expression::parse_using(side, variables, &self.definitions)
+				.ok()
+				.map(|expression| expression.typeset(x_name))
+		};
+
+		let typeset = match func_str.split_once('=') {
+			Some((lhs, rhs)) => Typeset::Row(vec![
+				typeset_side(lhs)?,
+				Typeset::Text(String::from(" = ")),
+				typeset_side(rhs)?,
+			]),
+			None => typeset_side(func_str)?,
+		};
+		Some(match &self.definition {
+			Some((name, _)) => Typeset::Row(vec![
+				Typeset::Text(format!("{}({}) = ", name, x_name)),
+				typeset,
+			]),
+			None => typeset,
+		})
+	}
+
+	/// Typeset math of the parsed function string, if it could be parsed
+	pub fn typeset(&self) -> Option<&Typeset> { self.typeset.as_ref() }
+
+	/// Simplified formulas of the function and its derivative, if it's a function of x
+	pub fn formulas(&self) -> Option<(&str, &str)> {
+		self.formulas
+			.as_ref()
+			.map(|(function, derivative)| (function.as_str(), derivative.as_str()))
+	}
+
+	/// Range of bytes of the function string that caused the error parsing it, if there is one
+	pub fn error_span(&self) -> Option<Range<usize>> {
+		self.test_result.as_ref().and(self.error_span.clone())
+	}
+
+	/// Range of bytes of the function string that's a misspelled function name, along with the name it can be corrected to
+	pub fn correction(&self) -> Option<(Range<usize>, &'static str)> {
+		self.error_span().zip(self.correction)
+	}
+
+	/// Fundamental period of the function, `None` if it isn't periodic (or its parameters are changing). As detecting it is slow,
+	/// it's only found the first time it's needed after the function changes, rather than as it's typed.
+	pub fn period(&mut self) -> Option<f64> {
+		if self.period_stale {
+			return None;
+		}
+
+		if self.period.is_none() {
+			self.period = Some(self.detect_period());
+		}
+		self.period.flatten()
+	}
+
+	/// Detects the fundamental period of the function, returning `None` if it isn't periodic (or is a sequence)
+	fn detect_period(&mut self) -> Option<f64> {
+		/// Largest period that is searched for
+		const MAX_PERIOD: f64 = 100.0;
+
+		if self.function.is_none() | self.sequence {
+			return None;
+		}
+		detect_period(&mut |x| self.function.get(0, x), MAX_PERIOD)
+	}
+
+	/// Value that is integrated at `x`, which is f(x) or ½r(θ)² (the polar area formula) for polar functions
+	fn integrand(&mut self, x: f64) -> f64 {
+		let y = self.function.get(0, x);
+		match self.polar {
+			true => 0.5 * y * y,
+			false => y,
+		}
+	}
+
+	/// Settings the function is integrated with, polar functions being integrated over the θ range instead of the integral's bounds.
+	/// The function's own integration settings are used instead of the global ones if it has them.
+	fn integral_settings(&self, settings: &AppSettings) -> AppSettings {
+		let settings = match self.polar {
+			true => AppSettings {
+				integral_min_x: settings.theta_min,
+				integral_max_x: settings.theta_max,
+				integral_changed: settings.integral_changed | settings.theta_changed,
+				..*settings
+			},
+			false => *settings,
+		};
+
+		match self.integral_override {
+			Some(integral_override) => AppSettings {
+				integral_min_x: integral_override.min_x,
+				integral_max_x: integral_override.max_x,
+				integral_num: integral_override.num,
+				riemann_sum: integral_override.riemann_sum,
+				..settings
+			},
+			None => settings,
+		}
+	}
+
+	/// Bounds, Riemann sum, and number of intervals the area was last calculated with, along with the area, `None` if it wasn't
+	pub fn area_computation(
+		&self, settings: &AppSettings,
+	) -> Option<(f64, f64, Riemann, usize, f64)> {
+		let (_, area) = self.integral_data.as_ref()?;
+		let settings = self.integral_settings(settings);
+		Some((
+			settings.integral_min_x,
+			settings.integral_max_x,
+			settings.riemann_sum,
+			settings.integral_num,
+			*area,
+		))
+	}
+
+	/// Integration settings the function uses instead of the global ones, if it has its own
+	pub const fn integral_override(&self) -> Option<IntegralOverride> { self.integral_override }
+
+	/// Sets the function's own integration settings (`None` using the global ones), integrating it again if they changed
+	pub fn set_integral_override(&mut self, integral_override: Option<IntegralOverride>) {
+		if self.integral_override != integral_override {
+			self.integral_override = integral_override;
+			self.clear_integral();
+		}
+	}
+
+	/// Samples the polar function r(θ) over the θ range, returning the points (r cos θ, r sin θ)
+	fn polar_points(&mut self, settings: &AppSettings) -> Vec<PlotPoint> {
+		let step = (settings.theta_max - settings.theta_min) / (settings.plot_width as f64);
+		step_helper(settings.plot_width + 1, settings.theta_min, step)
+			.into_iter()
+			.map(|theta| {
+				let r = self.function.get(0, theta);
+				PlotPoint::new(r * theta.cos(), r * theta.sin())
+			})
+			.collect()
+	}
+
+	/// Creates and does the math for creating all the rectangles under the graph
+	/// Each rectangle is marked with whether or not it contains a singularity that had to be integrated around
+	/// Also returns the outline of the trapezoids if `sum` is [`Riemann::Trapezoid`]
+	/// The returned area is NaN if the integral diverges, or if `sum` isn't made up of intervals (see [`Riemann::uses_intervals`]),
+	/// in which case there aren't any rectangles
+	fn integral_rectangles(
+		&mut self, integral_min_x: f64, integral_max_x: f64, sum: Riemann, integral_num: usize,
+	) -> (Vec<(f64, f64, bool)>, Vec<PlotPoint>, f64) {
+		/// Tolerance used when integrating around singularities
+		const SINGULAR_TOLERANCE: f64 = 1e-8;
+
+		/// Number of pieces each interval is split into when searching for the maximum/minimum of a Darboux sum
+		const DARBOUX_SAMPLES: usize = 16;
+
+		if !sum.uses_intervals() {
+			return (Vec::new(), Vec::new(), f64::NAN);
+		}
+
+		// Integrating from right to left is the negative of integrating from left to right
+		if integral_min_x > integral_max_x {
+			let (data, trapezoids, area) =
+				self.integral_rectangles(integral_max_x, integral_min_x, sum, integral_num);
+			return (data, trapezoids, -area);
+		}
+
+		let step = (integral_max_x - integral_min_x) / (integral_num as f64);
+
+		let mut trapezoids: Vec<PlotPoint> = Vec::new();
+		let mut diverges = false;
+
+		let data2: Vec<(f64, f64, bool)> = step_helper(integral_num, integral_min_x, step)
+			.into_iter()
+			.enumerate()
+			.map(|(i, x)| {
+				// Tiles [integral_min_x, integral_max_x] from left to right, the last interval ending exactly on the upper bound
+				let (left_x, right_x) = match i + 1 == integral_num {
+					true => (x, integral_max_x),
+					false => (x, x + step),
+				};
+				let middle_x = (left_x + right_x) / 2.0;
+
+				let y = match sum {
+					Riemann::Left => self.integrand(left_x),
+					Riemann::Right => self.integrand(right_x),
+					Riemann::Middle => (self.integrand(left_x) + self.integrand(right_x)) / 2.0,
+					Riemann::Trapezoid => {
+						let (left_y, right_y) = (self.integrand(left_x), self.integrand(right_x));
+
+						if left_y.is_finite() && right_y.is_finite() {
+							// Trace the trapezoid's outline down to the x-axis so it can be filled as one line
+							trapezoids.extend([
+								PlotPoint::new(left_x, 0.0),
+								PlotPoint::new(left_x, left_y),
+								PlotPoint::new(right_x, right_y),
+								PlotPoint::new(right_x, 0.0),
+							]);
+						}
+
+						(left_y + right_y) / 2.0
+					}
+					Riemann::Upper | Riemann::Lower => {
+						// Sample across the interval to find the maximum/minimum of the function
+						let samples = (0..=DARBOUX_SAMPLES).map(|i| {
+							self.integrand(
+								left_x + (((right_x - left_x) * i as f64) / DARBOUX_SAMPLES as f64),
+							)
+						});
+
+						match sum {
+							Riemann::Upper => samples.fold(f64::NAN, f64::max),
+							_ => samples.fold(f64::NAN, f64::min),
+						}
+					}
+					// Returned early above
+					Riemann::Adaptive | Riemann::MonteCarlo | Riemann::Romberg => f64::NAN,
+				};
+
+				// The function blows up somewhere on this interval, so integrate around it more carefully
+				if y.is_infinite() {
+					match singular_integral(
+						&mut |x| self.integrand(x),
+						left_x,
+						right_x,
+						SINGULAR_TOLERANCE,
+					) {
+						Some(area) => {
+							let height = area / (right_x - left_x);
+
+							if sum == Riemann::Trapezoid {
+								trapezoids.extend([
+									PlotPoint::new(left_x, 0.0),
+									PlotPoint::new(left_x, height),
+									PlotPoint::new(right_x, height),
+									PlotPoint::new(right_x, 0.0),
+								]);
+							}
+
+							return (middle_x, height, true);
+						}
+						None => diverges = true,
+					}
+				}
+
+				(middle_x, y, false)
+			})
+			.filter(|(_, y, _)| y.is_finite())
+			.collect();
+
+		let area = match diverges {
+			true => f64::NAN,
+			false => data2.iter().map(move |(_, y, _)| y * step).sum(),
+		};
+
+		(data2, trapezoids, area)
+	}
+
+	/// Calculates the standard error bound of a Riemann sum, using the largest |f'| (Left/Right) or |f''| (Middle/Trapezoid) sampled across the bounds.
+	/// Returns `None` for sums without a standard bound, or if the derivative isn't finite across the bounds.
+	fn integral_error_bound(&mut self, settings: &AppSettings) -> Option<f64> {
+		/// Number of pieces the bounds are split into when searching for the maximum of the derivative
+		const SAMPLES: usize = 1000;
+
+		let (derivative, divisor) = match settings.riemann_sum {
+			// |E| <= max|f'| (b - a)^2 / (2n)
+			Riemann::Left | Riemann::Right => (1, 2.0),
+			// |E| <= max|f''| (b - a)^3 / (12n^2), Middle averages the endpoints so it shares the Trapezoid's bound
+			Riemann::Middle | Riemann::Trapezoid => (2, 12.0),
+			_ => return None,
+		};
+
+		let (min_x, max_x) = settings.integral_bounds();
+		self.function.generate_derivative(derivative);
+		let max_derivative = step_helper(SAMPLES + 1, min_x, (max_x - min_x) / SAMPLES as f64)
+			.into_iter()
+			.map(|x| self.function.get(derivative, x).abs())
+			.fold(0.0, f64::max);
+
+		let width = max_x - min_x;
+		let num = settings.integral_num as f64;
+		let bound = max_derivative * width.powi(derivative as i32 + 1)
+			/ (divisor * num.powi(derivative as i32));
+
+		bound.is_finite().then_some(bound)
+	}
+
+	/// Color of the rectangle centered on `x` that's `width` wide and `height` tall, by whether it has more or less area than the
+	/// function over its interval, `None` if they're the same
+	fn estimate_color(&mut self, x: f64, height: f64, width: f64) -> Option<Color32> {
+		/// Relative difference between the areas below which they're considered the same
+		const TOLERANCE: f64 = 1e-9;
+
+		/// Maximum amount of times the interval can be split in half when integrating over it
+		const MAX_DEPTH: usize = 8;
+
+		let (area, _) = adaptive_simpson(
+			&mut |x| self.integrand(x),
+			x - (width / 2.0),
+			x + (width / 2.0),
+			TOLERANCE * width,
+			MAX_DEPTH,
+		);
+		let difference = (height * width) - area;
+		if !difference.is_finite() | (difference.abs() <= TOLERANCE * (1.0 + area.abs())) {
+			return None;
+		}
+
+		match difference > 0.0 {
+			true => Some(OVERESTIMATE_COLOR),
+			false => Some(UNDERESTIMATE_COLOR),
+		}
+	}
+
+	/// Table of estimates of Romberg integration, whose last row evaluates (at most) `integral_num` intervals
+	fn romberg_integral(&mut self, settings: &AppSettings) -> Vec<Vec<f64>> {
+		/// Maximum number of rows in the table (the last row evaluating 2^19 intervals)
+		const MAX_LEVELS: usize = 20;
+
+		let levels = (usize::BITS - settings.integral_num.leading_zeros()) as usize;
+		romberg(
+			&mut |x| self.integrand(x),
+			settings.integral_min_x,
+			settings.integral_max_x,
+			levels.min(MAX_LEVELS),
+		)
+	}
+
+	/// Integrates with `riemann_sum` instead of the sum the function is integrated with, so the two can be compared. Returns the
+	/// rectangles (or subintervals) it's made up of, the outline of its trapezoids, and its area.
+	fn comparison_integral(
+		&mut self, settings: &AppSettings, riemann_sum: Riemann,
+	) -> (Vec<Bar>, Vec<PlotPoint>, f64) {
+		match riemann_sum {
+			Riemann::Adaptive => {
+				let (bars, area) = self.adaptive_integral(
+					settings.integral_min_x,
+					settings.integral_max_x,
+					settings.integral_tolerance,
+				);
+				(bars, Vec::new(), area)
+			}
+			Riemann::Romberg => {
+				let area = self
+					.romberg_integral(settings)
+					.last()
+					.and_then(|row| row.last())
+					.cloned()
+					.unwrap_or(f64::NAN);
+				(Vec::new(), Vec::new(), area)
+			}
+			// Monte Carlo estimates are random, so they aren't compared (and would replace the sample points that are shown)
+			Riemann::MonteCarlo => (Vec::new(), Vec::new(), f64::NAN),
+			_ => {
+				let step = (settings.integral_max_x - settings.integral_min_x).abs()
+					/ (settings.integral_num as f64);
+				let (data, trapezoids, area) = self.integral_rectangles(
+					settings.integral_min_x,
+					settings.integral_max_x,
+					riemann_sum,
+					settings.integral_num,
+				);
+				let bars = data
+					.into_iter()
+					.map(|(x, y, _)| Bar::new(x, y).width(step))
+					.collect();
+				(bars, trapezoids, area)
+			}
+		}
+	}
+
+	/// Integrates using adaptive Simpson's quadrature, returning bars representing each subinterval used
+	fn adaptive_integral(
+		&mut self, integral_min_x: f64, integral_max_x: f64, tolerance: f64,
+	) -> (Vec<Bar>, f64) {
+		/// Maximum amount of times an interval can be split in half
+		const MAX_DEPTH: usize = 16;
+
+		// Integrating from right to left is the negative of integrating from left to right
+		if integral_min_x > integral_max_x {
+			let (bars, area) = self.adaptive_integral(integral_max_x, integral_min_x, tolerance);
+			return (bars, -area);
+		}
+
+		let (area, intervals) = adaptive_simpson(
+			&mut |x| self.integrand(x),
+			integral_min_x,
+			integral_max_x,
+			tolerance,
+			MAX_DEPTH,
+		);
+
+		let bars = intervals
+			.into_iter()
+			.filter(|(_, _, area)| area.is_finite())
+			.map(|(left_x, right_x, area)| {
+				let width = right_x - left_x;
+				Bar::new((left_x + right_x) / 2.0, area / width).width(width)
+			})
+			.collect();
+
+		(bars, area)
+	}
+
+	/// Estimates the integral by scattering `samples` random points over the box bounding the curve.
+	/// Points between the curve and the x-axis count towards the area (negatively if below the x-axis).
+	/// Returns the estimated area and its standard error.
+	fn monte_carlo_integral(
+		&mut self, integral_min_x: f64, integral_max_x: f64, samples: usize,
+	) -> (f64, f64) {
+		// Integrating from right to left is the negative of integrating from left to right
+		if integral_min_x > integral_max_x {
+			let (area, std_error) =
+				self.monte_carlo_integral(integral_max_x, integral_min_x, samples);
+			return (-area, std_error);
+		}
+
+		let mut rng = Xorshift::from_entropy();
+
+		let samples: Vec<(f64, f64)> = (0..samples)
+			.map(|_| {
+				let x = rng.range_f64(integral_min_x, integral_max_x);
+				(x, self.integrand(x))
+			})
+			.filter(|(_, y)| y.is_finite())
+			.collect();
+
+		if samples.is_empty() {
+			self.monte_carlo_data = (Vec::new(), Vec::new());
+			return (f64::NAN, f64::NAN);
+		}
+
+		// Bounding box of the curve (always including the x-axis)
+		let (min_y, max_y) = samples
+			.iter()
+			.fold((0.0_f64, 0.0_f64), |(min, max), (_, y)| {
+				(min.min(*y), max.max(*y))
+			});
+		let box_area = (integral_max_x - integral_min_x) * (max_y - min_y);
+
+		let mut hits = Vec::new();
+		let mut misses = Vec::new();
+		let scores: Vec<f64> = samples
+			.into_iter()
+			.map(|(x, fx)| {
+				let y = rng.range_f64(min_y, max_y);
+				let score = if (0.0 < y) && (y <= fx) {
+					1.0
+				} else if (fx <= y) && (y < 0.0) {
+					-1.0
+				} else {
+					0.0
+				};
+
+				match score == 0.0 {
+					true => misses.push(PlotPoint::new(x, y)),
+					false => hits.push(PlotPoint::new(x, y)),
+				}
+
+				score
+			})
+			.collect();
+
+		let n = scores.len() as f64;
+		let mean = scores.iter().sum::<f64>() / n;
+		let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+
+		self.monte_carlo_data = (hits, misses);
+		(box_area * mean, box_area * (variance / n).sqrt())
+	}
+
+	/// Splits the integral into the area above and below the x-axis, returned as (positive, negative).
+	/// Rectangles are summed (and colored by which side of the x-axis they are on) when they exist,
+	/// otherwise the positive and negative parts of the function are integrated separately.
+	fn integral_parts(&mut self, settings: &AppSettings) -> (f64, f64) {
+		/// Maximum amount of times an interval can be split in half
+		const MAX_DEPTH: usize = 16;
+
+		let (min_x, max_x) = settings.integral_bounds();
+		let (positive, negative) = match self.integral_data {
+			Some((ref mut bars, _)) if !bars.is_empty() => {
+				bars.iter_mut()
+					.fold((0.0, 0.0), |(positive, negative), bar| {
+						let area = bar.value * bar.bar_width;
+						let color = match area.is_sign_negative() {
+							true => Color32::RED,
+							false => Color32::GREEN,
+						};
+
+						// Rectangles already highlighted (such as around a singularity) keep their colors
+						if bar.fill == Color32::TRANSPARENT {
+							bar.fill = color.linear_multiply(0.2);
+							bar.stroke = egui::Stroke::new(1.0, color);
+						}
+
+						match area.is_sign_negative() {
+							true => (positive, negative + area),
+							false => (positive + area, negative),
+						}
+					})
+			}
+			_ => (
+				improper_integral(
+					&mut |x| self.integrand(x).max(0.0),
+					min_x,
+					max_x,
+					settings.integral_tolerance,
+					MAX_DEPTH,
+				),
+				improper_integral(
+					&mut |x| self.integrand(x).min(0.0),
+					min_x,
+					max_x,
+					settings.integral_tolerance,
+					MAX_DEPTH,
+				),
+			),
+		};
+
+		// When integrating from right to left, the area above the x-axis counts negatively (and vice versa)
+		match settings.is_reversed() {
+			true => (-negative, -positive),
+			false => (positive, negative),
+		}
+	}
+
+	/// Calculates the area with an increasing number of rectangles (from 2 up to `integral_num`), showing how the sum converges.
+	/// Only done for integrals that use rectangles, and cached until the integral changes.
+	pub fn calculate_convergence(&mut self, settings: &AppSettings) {
+		/// Maximum amount of sums calculated, spread evenly between 2 and `integral_num` rectangles
+		const MAX_POINTS: usize = 200;
+
+		let settings = &self.integral_settings(settings);
+		if !self.integral
+			| !self.convergence_data.is_empty()
+			| !settings.riemann_sum.uses_intervals()
+			| settings.is_improper()
+			| (settings.integral_num < 2)
+		{
+			return;
+		}
+
+		let points = MAX_POINTS.min(settings.integral_num - 1);
+		self.convergence_data = (0..points)
+			.map(|i| 2 + ((settings.integral_num - 2) * i) / (points - 1).max(1))
+			.dedup()
+			.map(|num| {
+				let area = self
+					.integral_rectangles(
+						settings.integral_min_x,
+						settings.integral_max_x,
+						settings.riemann_sum,
+						num,
+					)
+					.2;
+				PlotPoint::new(num as f64, area)
+			})
+			.collect();
+	}
+
+	/// Creates the running integral F(x) = ∫ f(t) dt starting at the lower integral bound.
+	/// Accumulates the integral's rectangles, or the plot's samples for methods which don't create rectangles.
+	fn antiderivative_points(&mut self, settings: &AppSettings) -> Vec<PlotPoint> {
+		let bars = match self.integral_data {
+			Some((ref bars, _)) => bars,
+			None => return Vec::new(),
+		};
+
+		if !bars.is_empty() {
+			let mut intervals: Vec<(f64, f64, f64)> = bars
+				.iter()
+				.map(|bar| {
+					let half_width = bar.bar_width / 2.0;
+					(
+						bar.argument - half_width,
+						bar.argument + half_width,
+						bar.value,
+					)
+				})
+				.collect();
+			intervals.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+			// When integrating from right to left, F(x) starts at the rightmost rectangle instead
+			let mut running = match settings.is_reversed() {
+				true => -intervals
+					.iter()
+					.map(|(left_x, right_x, y)| y * (right_x - left_x))
+					.sum::<f64>(),
+				false => 0.0,
+			};
+			return std::iter::once(PlotPoint::new(intervals[0].0, running))
+				.chain(intervals.into_iter().map(|(left_x, right_x, y)| {
+					running += y * (right_x - left_x);
+					PlotPoint::new(right_x, running)
+				}))
+				.collect();
+		}
+
+		/// Maximum amount of times an interval can be split in half
+		const MAX_DEPTH: usize = 16;
+
+		let (min_x, max_x) = settings.integral_bounds();
+		let samples: Vec<PlotPoint> = self
+			.back_data
+			.iter()
+			.filter(|point| (min_x..=max_x).contains(&point.x) && point.y.is_finite())
+			.cloned()
+			.collect();
+
+		let Some(first) = samples.first() else {
+			return Vec::new();
+		};
+
+		// Integrate up to the first visible sample, as the lower bound may be offscreen
+		let mut running = improper_integral(
+			&mut |x| self.function.get(0, x),
+			settings.integral_min_x,
+			first.x,
+			settings.integral_tolerance,
+			MAX_DEPTH,
+		);
+
+		std::iter::once(PlotPoint::new(first.x, running))
+			.chain(samples.iter().tuple_windows().map(|(prev, curr)| {
+				running += (curr.x - prev.x) * (prev.y + curr.y) / 2.0;
+				PlotPoint::new(curr.x, running)
+			}))
+			.collect()
+	}
+
+	/// Finds the indices of `data` (samples of the `derivative`th derivative) that are followed by a discontinuity.
+	/// Only steep sections (changing more than `resolution` between samples) are checked.
+	fn find_gaps(&mut self, derivative: usize, data: &[PlotPoint], resolution: f64) -> Vec<usize> {
+		/// Number of times a steep interval is halved when checking for a discontinuity
+		const ITERATIONS: usize = 20;
+
+		data.iter()
+			.tuple_windows()
+			.enumerate()
+			.filter(|(_, (prev, curr))| (curr.y - prev.y).abs() > resolution)
+			.filter(|(_, (prev, curr))| {
+				is_discontinuous(
+					&mut |x| self.function.get(derivative, x),
+					prev.x,
+					curr.x,
+					ITERATIONS,
+				)
+			})
+			.map(|(i, _)| i)
+			.collect()
+	}
+
+	/// Classifies the critical point at `x` using the second derivative test.
+	/// Falls back to checking if f'(x) changes sign `h` away from `x` when f''(x) is (nearly) 0.
+	fn classify_extremum(&mut self, x: f64, h: f64) -> Extremum {
+		/// Second derivatives smaller than this are treated as 0
+		const EPSILON: f64 = 1e-10;
+
+		self.function.generate_derivative(2);
+		let second_derivative = self.function.get(2, x);
+		if second_derivative > EPSILON {
+			return Extremum::Minimum;
+		} else if second_derivative < -EPSILON {
+			return Extremum::Maximum;
+		}
+
+		match (self.function.get(1, x - h), self.function.get(1, x + h)) {
+			(left, right) if (left < 0.0) && (right > 0.0) => Extremum::Minimum,
+			(left, right) if (left > 0.0) && (right < 0.0) => Extremum::Maximum,
+			_ => Extremum::Neither,
+		}
+	}
+
+	/// Curvature of the function at `x`, along with the first and second derivatives it was calculated from.
+	/// Requires the first and second derivatives to have been generated.
+	fn curvature_at(&mut self, x: f64) -> (f64, f64, f64) {
+		let first = self.function.get(1, x);
+		let second = self.function.get(2, x);
+		let curvature = second.abs() / (1.0 + (first * first)).powf(1.5);
+		(curvature, first, second)
+	}
+
+	/// Secant line of the function over [`a`, `b`], and the points in between where f'(c) equals its slope.
+	/// The Mean Value Theorem guarantees at least one such point if the function is differentiable over [`a`, `b`].
+	fn mean_value_points(
+		&mut self, a: f64, b: f64, settings: &AppSettings,
+	) -> ([PlotPoint; 2], Vec<PlotPoint>) {
+		let start = PlotPoint::new(a, self.function.get(0, a));
+		let end = PlotPoint::new(b, self.function.get(0, b));
+		let slope = (end.y - start.y) / (b - a);
+
+		self.function.generate_derivative(1);
+		self.function.generate_derivative(2);
+		let points = find_roots(
+			&mut |x| (self.function.get(1, x) - slope, self.function.get(2, x)),
+			a,
+			b,
+			settings.plot_width,
+			settings.root_tolerance,
+		)
+		.into_iter()
+		.filter(|x| (a < *x) && (*x < b))
+		.map(|x| PlotPoint::new(x, self.function.get(0, x)))
+		.collect();
+
+		([start, end], points)
+	}
+
+	/// Helps with processing newton's method depending on level of derivative
+	fn newtons_method_helper(
+		&mut self, threshold: f64, derivative_level: usize, range: &std::ops::Range<f64>,
+	) -> Vec<PlotPoint> {
+		self.function.generate_derivative(derivative_level);
 		self.function.generate_derivative(derivative_level + 1);
 		let newtons_method_output: Vec<f64> = match derivative_level {
 			0 => newtons_method_helper(
@@ -268,16 +2529,124 @@ impl FunctionEntry {
 		&mut self, width_changed: bool, min_max_changed: bool, did_zoom: bool,
 		settings: AppSettings,
 	) {
-		if self.test_result.is_some() | self.function.is_none() {
+		// Data series are only parsed, there's nothing to calculate
+		if self.get_test_result().is_some() | self.data_series {
+			return;
+		}
+
+		if let Some(ref bivariate) = self.bivariate {
+			let (min, max) = (
+				[settings.min_x, settings.min_y],
+				[settings.max_x, settings.max_y],
+			);
+			match self.slope_field {
+				_ if self.vector_field => {
+					if let Some(ref q) = self.vector_q {
+						self.vector_field_data.update(
+							&mut |x, y| bivariate.get(x, y),
+							&mut |x, y| q.get(x, y),
+							min,
+							max,
+							settings.plot_width,
+						);
+					}
+				}
+				true => self.slope_field_data.update(
+					&mut |x, y| bivariate.get(x, y),
+					min,
+					max,
+					settings.plot_width,
+				),
+				false if self.contour => self.contour_data.update(
+					&mut |x, y| bivariate.get(x, y),
+					min,
+					max,
+					settings.plot_width,
+					self.contour_levels,
+				),
+				false => self.implicit_plot.update(
+					&mut |x, y| bivariate.get(x, y),
+					min,
+					max,
+					settings.plot_width,
+				),
+			}
+			return;
+		}
+
+		if let Some(ref complex) = self.complex {
+			if self.domain_coloring {
+				self.domain_coloring_data.update(
+					&mut |z| complex.eval_complex(z),
+					[settings.min_x, settings.min_y],
+					[settings.max_x, settings.max_y],
+					settings.plot_width,
+				);
+				return;
+			}
+
+			if width_changed | min_max_changed | did_zoom {
+				self.complex_data = None;
+			}
+
+			if self.complex_data.is_none() {
+				let resolution = (settings.max_x - settings.min_x) / (settings.plot_width as f64);
+				let values: Vec<(f64, Complex)> =
+					step_helper(settings.plot_width + 1, settings.min_x, resolution)
+						.into_iter()
+						.map(|x| (x, complex.eval_complex(Complex::from(x))))
+						.collect();
+
+				// Only the parts which are finite are drawn
+				let part = |f: fn(Complex) -> f64| -> Vec<PlotPoint> {
+					values
+						.iter()
+						.map(|(x, value)| PlotPoint::new(*x, f(*value)))
+						.filter(|point| point.y.is_finite())
+						.collect()
+				};
+				self.complex_data = Some((part(|value| value.re), part(|value| value.im)));
+			}
+			return;
+		}
+
+		if self.function.is_none() {
+			return;
+		}
+
+		// Sequences are only defined at integers, so nothing else is calculated from them
+		if self.sequence {
+			if min_max_changed {
+				self.clear_sequence();
+			}
+
+			if self.sequence_data.is_empty() {
+				self.sequence_data = sequence(
+					&mut |n| self.function.get(0, n),
+					self.sequence_start,
+					settings.min_x,
+					settings.max_x,
+				);
+			}
+
+			if self.partial_sums && self.partial_sum_data.is_empty() {
+				self.partial_sum_data = partial_sums(
+					&mut |n| self.function.get(0, n),
+					self.sequence_start,
+					settings.min_x,
+					settings.max_x,
+				);
+			}
 			return;
 		}
 
+		let settings = self.integral_settings(&settings);
 		let resolution = (settings.max_x - settings.min_x) / (settings.plot_width as f64);
 		debug_assert!(resolution > 0.0);
 		let resolution_iter = step_helper(settings.plot_width + 1, settings.min_x, resolution);
 
 		// Makes sure proper arguments are passed when integral is enabled
-		if self.integral && settings.integral_changed {
+		if (self.integral | self.antiderivative) && settings.integral_changed {
 			self.clear_integral();
 		}
 
@@ -285,9 +2654,16 @@ impl FunctionEntry {
 			self.clear_back();
 			self.clear_derivative();
 			self.clear_nth();
+			self.clear_curvature();
+		}
+
+		if self.polar && settings.theta_changed {
+			self.clear_back();
 		}
 
-		if self.back_data.is_empty() {
+		if self.polar && self.back_data.is_empty() {
+			self.back_data = self.polar_points(&settings);
+		} else if self.back_data.is_empty() {
 			let data: Vec<PlotPoint> = resolution_iter
 				.clone()
 				.into_iter()
@@ -295,10 +2671,35 @@ impl FunctionEntry {
 				.collect();
 			debug_assert_eq!(data.len(), settings.plot_width + 1);
 
+			self.back_gaps = self.find_gaps(0, &data, resolution);
 			self.back_data = data;
 		}
 
-		if self.derivative_data.is_empty() {
+		// Bounds are over columns of x, so they aren't calculated for polar functions
+		if self.rigorous
+			&& !self.polar
+			&& self.rigorous_data.is_empty()
+			&& let Some(function) = self.function.expression()
+		{
+			self.rigorous_data = resolution_iter[..settings.plot_width]
+				.iter()
+				.map(|&x| (x, function.eval_interval(Interval::new(x, x + resolution))))
+				.filter(|(_, bounds)| !bounds.is_empty())
+				.map(|(x, bounds)| {
+					// Unbounded columns are cut off at the edges of the plot
+					(x, bounds.lo.max(settings.min_y), bounds.hi.min(settings.max_y))
+				})
+				.filter(|(_, lo, hi)| lo <= hi)
+				.map(|(x, lo, hi)| {
+					Bar::new(x + (resolution / 2.0), hi - lo)
+						.base_offset(lo)
+						.width(resolution)
+				})
+				.collect();
+		}
+
+		// Derivatives (and everything calculated from them) are in terms of x, so they aren't calculated for polar functions
+		if !self.polar && self.derivative_data.is_empty() {
 			self.function.generate_derivative(1);
 			let data: Vec<PlotPoint> = resolution_iter
 				.clone()
@@ -306,11 +2707,13 @@ impl FunctionEntry {
 				.map(|x| PlotPoint::new(x, self.function.get(1, x)))
 				.collect();
 			debug_assert_eq!(data.len(), settings.plot_width + 1);
+			self.derivative_gaps = self.find_gaps(1, &data, resolution);
 			self.derivative_data = data;
 		}
 
-		if self.nth_derviative && self.nth_derivative_data.is_none() {
+		if self.nth_derviative && !self.polar && self.nth_derivative_data.is_none() {
 			let data: Vec<PlotPoint> = resolution_iter
+				.clone()
 				.into_iter()
 				.map(|x| PlotPoint::new(x, self.function.get(self.curr_nth, x)))
 				.collect();
@@ -318,22 +2721,196 @@ impl FunctionEntry {
 			self.nth_derivative_data = Some(data);
 		}
 
-		if self.integral {
-			if self.integral_data.is_none() {
-				let (data, area) = self.integral_rectangles(
-					settings.integral_min_x,
-					settings.integral_max_x,
-					settings.riemann_sum,
-					settings.integral_num,
-				);
+		if !self.curvature | self.polar {
+			self.clear_curvature();
+		} else if self.curvature_data.is_none() {
+			self.function.generate_derivative(1);
+			self.function.generate_derivative(2);
+			let data: Vec<PlotPoint> = resolution_iter
+				.into_iter()
+				.map(|x| PlotPoint::new(x, self.curvature_at(x).0))
+				.collect();
+			debug_assert_eq!(data.len(), settings.plot_width + 1);
+			self.curvature_data = Some(data);
+		}
+
+		// The running integral is built from the integral's data, so calculate it if either are enabled
+		if self.integral | self.antiderivative {
+			if self.integral_data.is_none() && settings.is_improper() {
+				/// Maximum amount of times an interval can be split in half
+				const MAX_DEPTH: usize = 16;
+
+				// Rectangles can't be made over an infinite range, so only the area is calculated
+				let area = improper_integral(
+					&mut |x| self.integrand(x),
+					settings.integral_min_x,
+					settings.integral_max_x,
+					settings.integral_tolerance,
+					MAX_DEPTH,
+				);
+				self.integral_data = Some((Vec::new(), area));
+			} else if self.integral_data.is_none() {
+				match settings.riemann_sum {
+					Riemann::Adaptive => {
+						let (bars, area) = self.adaptive_integral(
+							settings.integral_min_x,
+							settings.integral_max_x,
+							settings.integral_tolerance,
+						);
+						self.integral_subdivisions = Some(bars.len());
+						self.integral_data = Some((bars, area));
+					}
+					Riemann::MonteCarlo => {
+						let (area, std_error) = self.monte_carlo_integral(
+							settings.integral_min_x,
+							settings.integral_max_x,
+							settings.monte_carlo_samples,
+						);
+						self.integral_std_error = Some(std_error);
+						self.integral_data = Some((Vec::new(), area));
+					}
+					Riemann::Romberg => {
+						let table = self.romberg_integral(&settings);
+						let area = table
+							.last()
+							.and_then(|row| row.last())
+							.cloned()
+							.unwrap_or(f64::NAN);
+						self.romberg_table = table;
+						self.integral_data = Some((Vec::new(), area));
+					}
+					_ => {
+						let step = (settings.integral_max_x - settings.integral_min_x).abs()
+							/ (settings.integral_num as f64);
+						let (data, trapezoids, area) = self.integral_rectangles(
+							settings.integral_min_x,
+							settings.integral_max_x,
+							settings.riemann_sum,
+							settings.integral_num,
+						);
+						self.integral_singular = data.iter().any(|(_, _, singular)| *singular);
+						self.integral_diverges = area.is_nan();
+						// The error bound is of f(x), not of the polar area formula
+						self.integral_error_bound = match self.integral_singular | self.polar {
+							false => self.integral_error_bound(&settings),
+							true => None,
+						};
+
+						let bars = data
+							.into_iter()
+							.map(|(x, y, singular)| {
+								let color = match singular {
+									// Highlight rectangles that were integrated around a singularity
+									true => Some(Color32::RED),
+									false if settings.estimate_colors && !self.polar => {
+										self.estimate_color(x, y, step)
+									}
+									false => None,
+								};
+								match color {
+									Some(color) => Bar::new(x, y)
+										.width(step)
+										.fill(color.linear_multiply(0.2))
+										.stroke(egui::Stroke::new(1.0, color)),
+									None => Bar::new(x, y).width(step),
+								}
+							})
+							.collect();
+						self.integral_data = Some((bars, area));
+						self.trapezoid_data = trapezoids;
+
+						// Calculate the opposite Darboux sum as well, so the integral can be shown between the two
+						let opposite = match settings.riemann_sum {
+							Riemann::Upper => Some(Riemann::Lower),
+							Riemann::Lower => Some(Riemann::Upper),
+							_ => None,
+						};
+
+						if let Some(opposite) = opposite {
+							let (opposite_data, _, opposite_area) = self.integral_rectangles(
+								settings.integral_min_x,
+								settings.integral_max_x,
+								opposite,
+								settings.integral_num,
+							);
+
+							self.darboux_data = opposite_data
+								.into_iter()
+								.map(|(x, y, _)| Bar::new(x, y).width(step))
+								.collect();
+							// Reversed bounds negate both sums, which swaps which one is smaller
+							self.darboux_bounds =
+								Some((area.min(opposite_area), area.max(opposite_area)));
+						}
+					}
+				}
+			}
+
+			if settings.area_mode != AreaMode::Signed && self.integral_parts.is_none() {
+				self.integral_parts = Some(self.integral_parts(&settings));
+			}
+
+			// The integral is also calculated with the sum it's compared to
+			if self.integral
+				&& !settings.is_improper()
+				&& self.comparison_data.is_none()
+				&& let Some(compare_sum) = settings.compare_sum
+				&& (compare_sum != settings.riemann_sum)
+			{
+				self.comparison_data = Some(self.comparison_integral(&settings, compare_sum));
+			}
+		} else {
+			self.clear_integral();
+		}
+
+		if self.polar {
+			return;
+		}
+
+		if self.antiderivative && self.antiderivative_data.is_empty() {
+			self.antiderivative_data = self.antiderivative_points(&settings);
+		}
+
+		// Recalculate the tangent line if the point of tangency moved
+		match (self.tangent_x, self.tangent_data) {
+			(Some(x), Some((point, _))) if point.x == x => {}
+			(Some(x), _) => {
+				self.function.generate_derivative(1);
+				self.tangent_data = Some((
+					PlotPoint::new(x, self.function.get(0, x)),
+					self.function.get(1, x),
+				));
+			}
+			(None, _) => self.tangent_data = None,
+		}
 
-				self.integral_data = Some((
-					data.into_iter().map(|(x, y)| Bar::new(x, y)).collect(),
-					area,
+		// Recalculate the osculating circle if the point it touches moved.
+		// The circle's center lies along the normal line, 1/κ away from the function
+		match (self.osculating_x, self.osculating_data) {
+			(Some(x), Some((point, ..))) if point.x == x => {}
+			(Some(x), _) => {
+				self.function.generate_derivative(1);
+				self.function.generate_derivative(2);
+				let (curvature, first, second) = self.curvature_at(x);
+				let y = self.function.get(0, x);
+				let normal = (1.0 + (first * first)) / second;
+				self.osculating_data = Some((
+					PlotPoint::new(x, y),
+					PlotPoint::new(x - (first * normal), y + normal),
+					curvature.recip(),
 				));
 			}
-		} else {
-			self.clear_integral();
+			(None, _) => self.osculating_data = None,
+		}
+
+		// Recalculate the Mean Value Theorem's secant line if the integral's bounds moved
+		let (a, b) = settings.integral_bounds();
+		match self.mean_value_data {
+			_ if !self.mean_value | settings.is_improper() | (a == b) => {
+				self.mean_value_data = None
+			}
+			Some(([start, end], _)) if (start.x == a) && (end.x == b) => {}
+			_ => self.mean_value_data = Some(self.mean_value_points(a, b, &settings)),
 		}
 
 		let threshold: f64 = resolution / 2.0;
@@ -341,71 +2918,331 @@ impl FunctionEntry {
 
 		// Calculates extrema
 		if settings.do_extrema && (min_max_changed | self.extrema_data.is_empty()) {
-			self.extrema_data = self.newtons_method_helper(threshold, 1, &x_range);
+			self.extrema_data = self
+				.newtons_method_helper(threshold, 1, &x_range)
+				.into_iter()
+				.map(|point| (point, self.classify_extremum(point.x, resolution)))
+				.collect();
 		}
 
 		// Calculates roots
-		if settings.do_roots && (min_max_changed | self.root_data.is_empty()) {
-			self.root_data = self.newtons_method_helper(threshold, 0, &x_range);
+		if settings.do_roots
+			&& (min_max_changed | settings.roots_changed | self.root_data.is_empty())
+		{
+			// Roots are only found to the plot's resolution, so polish them to `root_tolerance`
+			self.root_data = self
+				.newtons_method_helper(threshold, 0, &x_range)
+				.into_iter()
+				.map(|point| {
+					let x = refine_root(
+						&mut |x| (self.function.get(0, x), self.function.get(1, x)),
+						point.x,
+						point.x - resolution,
+						point.x + resolution,
+						settings.root_tolerance,
+					)
+					.unwrap_or(point.x);
+					PlotPoint::new(x, self.function.get(0, x))
+				})
+				.collect();
 		}
 	}
 
-	/// Displays the function's output on PlotUI `plot_ui` with settings `settings`.
-	/// Returns an `Option<f64>` of the calculated integral.
+	/// Displays the function's output on PlotUI `plot_ui` with settings `settings`, in `default_color` unless it has its own color.
+	/// Returns an `Option<String>` of the calculated integral. Names and labels are in the language of `i18n`.
 	pub fn display(
-		&self, plot_ui: &mut PlotUi, settings: &AppSettings, main_plot_color: Color32,
-	) -> Option<f64> {
-		if self.test_result.is_some() | self.function.is_none() {
+		&self, plot_ui: &mut PlotUi, settings: &AppSettings, default_color: Color32,
+		i18n: &Translations,
+	) -> Option<String> {
+		if self.get_test_result().is_some() | !self.visible {
+			return None;
+		}
+
+		let main_plot_color = self.color.unwrap_or(default_color);
+		let main_stroke = egui::Stroke::new(self.line_width, main_plot_color);
+		let integral_color = darker(main_plot_color);
+
+		if self.data_series {
+			plot_ui.points(
+				self.data_points
+					.clone()
+					.to_points()
+					.color(main_plot_color)
+					.radius(4.0)
+					.name(i18n.get("plot.data")),
+			);
+
+			if !self.integral {
+				return None;
+			}
+
+			// Integrated via the trapezoid rule, which is the area under the lines connecting the points
+			plot_ui.line(self.data_points.clone().to_line().color(integral_color));
+			let area = trapezoid_points(
+				&self.data_points,
+				settings.integral_min_x,
+				settings.integral_max_x,
+			);
+			return Some(settings.number_format.format(area, i18n));
+		}
+
+		// Vector fields, slope fields, and implicit equations are only drawn, nothing else is calculated from them
+		if self.bivariate.is_some() {
+			if self.vector_field {
+				plot_ui.arrows(
+					Arrows::new(
+						self.vector_field_data.origins.clone().to_values(),
+						self.vector_field_data.tips.clone().to_values(),
+					)
+					.color(main_plot_color),
+				);
+
+				if let Some(([min_x, min_y], [max_x, max_y])) = self.line_integral_rect {
+					plot_ui.line(
+						vec![
+							PlotPoint::new(min_x, min_y),
+							PlotPoint::new(max_x, min_y),
+							PlotPoint::new(max_x, max_y),
+							PlotPoint::new(min_x, max_y),
+							PlotPoint::new(min_x, min_y),
+						]
+						.to_line()
+						.color(Color32::GOLD)
+						.style(LineStyle::dashed_loose())
+						.name(i18n.get("plot.line_integral_path")),
+					);
+				}
+			} else if self.slope_field {
+				plot_ui.arrows(
+					Arrows::new(
+						self.slope_field_data.origins.clone().to_values(),
+						self.slope_field_data.tips.clone().to_values(),
+					)
+					.tip_length(6.0)
+					.color(main_plot_color),
+				);
+
+				for solution in self.slope_field_data.solutions.iter() {
+					plot_ui.line(
+						solution
+							.clone()
+							.to_line()
+							.color(Color32::GOLD)
+							.width(3.0)
+							.name(i18n.get("plot.solution")),
+					);
+				}
+
+				plot_ui.points(
+					self.slope_field_data
+						.initial_conditions
+						.clone()
+						.to_points()
+						.color(Color32::GOLD)
+						.radius(5.0)
+						.name(i18n.get("plot.initial_condition")),
+				);
+			} else if self.contour {
+				for (level, curves) in self.contour_data.levels.iter() {
+					let level = settings.number_format.format(*level, i18n);
+
+					// Named so that hovering over a contour line shows its level
+					for curve in curves.iter() {
+						plot_ui.line(
+							curve
+								.clone()
+								.to_line()
+								.color(main_plot_color)
+								.width(2.0)
+								.name(format!("z = {}", level)),
+						);
+					}
+
+					// Labels the longest part of the contour line in its middle
+					if self.contour_labels
+						&& let Some(curve) = curves.iter().max_by_key(|curve| curve.len())
+					{
+						plot_ui.text(
+							Text::new(curve[curve.len() / 2], level.clone()).color(main_plot_color),
+						);
+					}
+				}
+			}
+
+			for curve in self.implicit_plot.curves.iter() {
+				plot_ui.line(
+					curve
+						.clone()
+						.to_line()
+						.stroke(main_stroke)
+						.style(self.curve_style.line_style()),
+				);
+			}
+			return None;
+		}
+
+		if self.complex.is_some() {
+			if self.domain_coloring {
+				if let Some(ref texture) = self.domain_coloring_data.texture
+					&& let Some((center, size)) = self.domain_coloring_data.bounds()
+				{
+					plot_ui.image(PlotImage::new(
+						texture.id(),
+						PlotPoint::new(center[0], center[1]),
+						[size[0] as f32, size[1] as f32],
+					));
+				}
+			} else if let Some((ref re_data, ref im_data)) = self.complex_data {
+				plot_ui.line(
+					re_data
+						.clone()
+						.to_line()
+						.color(main_plot_color)
+						.name(i18n.get("plot.real")),
+				);
+				plot_ui.line(
+					im_data
+						.clone()
+						.to_line()
+						.color(main_plot_color)
+						.style(LineStyle::dashed_loose())
+						.name(i18n.get("plot.imaginary")),
+				);
+			}
+			return None;
+		}
+
+		if self.function.is_none() {
+			return None;
+		}
+
+		if self.sequence {
+			if self.sequence_data.len() <= MAX_STEMS {
+				plot_ui.bar_chart(
+					BarChart::new(
+						self.sequence_data
+							.iter()
+							.map(|point| Bar::new(point.x, point.y).width(0.05))
+							.collect(),
+					)
+					.color(main_plot_color),
+				);
+			}
+
+			plot_ui.points(
+				self.sequence_data
+					.clone()
+					.to_points()
+					.color(main_plot_color)
+					.radius(4.0)
+					.name(i18n.get("plot.sequence")),
+			);
+
+			if self.partial_sums {
+				plot_ui.points(
+					self.partial_sum_data
+						.clone()
+						.to_points()
+						.color(Color32::GOLD)
+						.radius(4.0)
+						.name(i18n.get("plot.partial_sums")),
+				);
+			}
 			return None;
 		}
 
-		let integral_step =
-			(settings.integral_max_x - settings.integral_min_x) / (settings.integral_num as f64);
-		debug_assert!(integral_step > 0.0);
+		let settings = &self.integral_settings(settings);
+		let integral_step = (settings.integral_max_x - settings.integral_min_x).abs()
+			/ (settings.integral_num as f64);
+		debug_assert!(integral_step >= 0.0);
 
 		let step = (settings.max_x - settings.min_x) / (settings.plot_width as f64);
 		debug_assert!(step > 0.0);
 
+		// Plot the bounds of the function beneath it
+		if self.rigorous && !self.rigorous_data.is_empty() {
+			plot_ui.bar_chart(
+				BarChart::new(self.rigorous_data.clone())
+					.color(main_plot_color)
+					.name(i18n.get("plot.bounds")),
+			);
+		}
+
 		// Plot back data
 		if !self.back_data.is_empty() {
-			if self.integral && (step >= integral_step) {
+			if self.integral
+				&& !self.polar
+				&& (settings.is_improper()
+					| (settings.riemann_sum.uses_intervals() && (step >= integral_step)))
+			{
+				let (min_x, max_x) = settings.integral_bounds();
 				plot_ui.line(
 					self.back_data
 						.iter()
-						.filter(|value| {
-							(value.x > settings.integral_min_x)
-								&& (settings.integral_max_x > value.x)
-						})
+						.filter(|value| (value.x > min_x) && (max_x > value.x))
 						.cloned()
 						.collect::<Vec<PlotPoint>>()
 						.to_line()
 						.stroke(epaint::Stroke::NONE)
-						.color(Color32::from_rgb(4, 4, 255))
+						.color(integral_color)
 						.fill(0.0),
 				);
 			}
-			plot_ui.line(
-				self.back_data
-					.clone()
-					.to_line()
-					.stroke(egui::Stroke::new(4.0, main_plot_color)),
-			);
+			// Drawn in segments so there are gaps at discontinuities
+			for segment in self.back_data.clone().to_segments(&self.back_gaps) {
+				plot_ui.line(
+					segment
+						.to_line()
+						.stroke(main_stroke)
+						.style(self.curve_style.line_style()),
+				);
+			}
 		}
 
 		// Plot derivative data
 		if self.derivative && !self.derivative_data.is_empty() {
-			plot_ui.line(self.derivative_data.clone().to_line().color(Color32::GREEN));
+			for segment in self
+				.derivative_data
+				.clone()
+				.to_segments(&self.derivative_gaps)
+			{
+				plot_ui.line(
+					segment
+						.to_line()
+						.stroke(egui::Stroke::new(
+							self.line_width / 2.0,
+							lighter(main_plot_color),
+						))
+						.style(self.curve_style.line_style()),
+				);
+			}
 		}
 
-		// Plot extrema points
+		// Plot extrema points, named by their classification so it's shown when hovered over
 		if settings.do_extrema && !self.extrema_data.is_empty() {
-			plot_ui.points(
-				self.extrema_data
-					.clone()
-					.to_points()
-					.color(Color32::YELLOW)
-					.radius(5.0), // Radius of points of Extrema
-			);
+			for (kind, shape) in [
+				(Extremum::Minimum, MarkerShape::Down),
+				(Extremum::Maximum, MarkerShape::Up),
+				(Extremum::Neither, MarkerShape::Circle),
+			] {
+				let points: Vec<PlotPoint> = self
+					.extrema_data
+					.iter()
+					.filter(|(_, extremum)| *extremum == kind)
+					.map(|(point, _)| *point)
+					.collect();
+
+				if !points.is_empty() {
+					plot_ui.points(
+						points
+							.to_points()
+							.color(Color32::YELLOW)
+							.shape(shape)
+							.name(i18n.get(kind.label()))
+							.radius(5.0), // Radius of points of Extrema
+					);
+				}
+			}
 		}
 
 		// Plot roots points
@@ -425,26 +3262,543 @@ impl FunctionEntry {
 			plot_ui.line(nth_derviative.clone().to_line().color(Color32::DARK_RED));
 		}
 
+		// Plot the tangent line across the plot, along with its equation
+		if let Some((point, slope)) = self.tangent_data
+			&& point.y.is_finite()
+			&& slope.is_finite()
+		{
+			let tangent = |x: f64| PlotPoint::new(x, point.y + (slope * (x - point.x)));
+			let intercept = point.y - (slope * point.x);
+
+			plot_ui.line(
+				vec![tangent(settings.min_x), tangent(settings.max_x)]
+					.to_line()
+					.color(Color32::from_rgb(255, 165, 0)),
+			);
+			plot_ui.points(
+				vec![point]
+					.to_points()
+					.color(Color32::from_rgb(255, 165, 0))
+					.radius(5.0),
+			);
+			plot_ui.text(
+				Text::new(
+					point,
+					format!(
+						"y = {}x {} {}",
+						settings.number_format.format(slope, i18n),
+						match intercept.is_sign_negative() {
+							true => '-',
+							false => '+',
+						},
+						settings.number_format.format(intercept.abs(), i18n)
+					),
+				)
+				.color(Color32::from_rgb(255, 165, 0))
+				.anchor(emath::Align2::LEFT_BOTTOM),
+			);
+		}
+
+		if self.curvature
+			&& let Some(ref curvature_data) = self.curvature_data
+		{
+			plot_ui.line(
+				curvature_data
+					.clone()
+					.to_line()
+					.color(Color32::DARK_GREEN)
+					.name(i18n.get("plot.curvature")),
+			);
+		}
+
+		// Plot the osculating circle, which is undefined where the function is straight (κ = 0)
+		if let Some((point, center, radius)) = self.osculating_data
+			&& center.x.is_finite()
+			&& center.y.is_finite()
+			&& radius.is_finite()
+		{
+			/// Number of points the osculating circle is drawn with
+			const CIRCLE_POINTS: usize = 128;
+
+			plot_ui.line(
+				(0..=CIRCLE_POINTS)
+					.map(|i| (i as f64) * std::f64::consts::TAU / (CIRCLE_POINTS as f64))
+					.map(|theta| {
+						PlotPoint::new(
+							center.x + (radius * theta.cos()),
+							center.y + (radius * theta.sin()),
+						)
+					})
+					.collect::<Vec<PlotPoint>>()
+					.to_line()
+					.color(Color32::KHAKI)
+					.name(i18n.get("plot.osculating_circle")),
+			);
+			plot_ui.points(
+				vec![point, center]
+					.to_points()
+					.color(Color32::KHAKI)
+					.radius(5.0),
+			);
+		}
+
+		// Plot the secant line over the integral's bounds, and the parallel tangent lines
+		if let Some(([start, end], ref points)) = self.mean_value_data
+			&& start.y.is_finite()
+			&& end.y.is_finite()
+		{
+			let slope = (end.y - start.y) / (end.x - start.x);
+			let extent = (settings.max_x - settings.min_x) / 8.0;
+
+			plot_ui.line(
+				vec![start, end]
+					.to_line()
+					.color(Color32::LIGHT_RED)
+					.name(i18n.get("plot.secant_line")),
+			);
+			points.iter().for_each(|point| {
+				let tangent = |x: f64| PlotPoint::new(x, point.y + (slope * (x - point.x)));
+				plot_ui.line(
+					vec![tangent(point.x - extent), tangent(point.x + extent)]
+						.to_line()
+						.color(Color32::LIGHT_RED)
+						.style(LineStyle::dashed_loose()),
+				);
+			});
+			plot_ui.points(
+				points
+					.clone()
+					.to_points()
+					.color(Color32::LIGHT_RED)
+					.name(i18n.get("plot.mean_value"))
+					.radius(5.0),
+			);
+		}
+
+		// Plot the running integral
+		if self.antiderivative && !self.antiderivative_data.is_empty() {
+			plot_ui.line(
+				self.antiderivative_data
+					.clone()
+					.to_line()
+					.stroke(egui::Stroke::new(
+						self.line_width / 2.0,
+						darker(main_plot_color),
+					))
+					.style(self.curve_style.line_style()),
+			);
+		}
+
 		// Plot integral data
 		match &self.integral_data {
-			Some(integral_data) => {
-				if integral_step > step {
-					plot_ui.bar_chart(
-						BarChart::new(integral_data.0.clone())
-							.color(Color32::BLUE)
-							.width(integral_step),
+			Some(integral_data) if self.integral => {
+				match settings.riemann_sum {
+					// Each rectangle of ½r(θ)² is the area of a sector of the circle with radius r(θ)
+					_ if self.polar => {
+						/// Number of points each sector's arc is drawn with
+						const ARC_POINTS: usize = 8;
+
+						integral_data
+							.0
+							.iter()
+							.filter(|bar| bar.value.is_finite())
+							.for_each(|bar| {
+								let radius = (2.0 * bar.value.abs()).sqrt();
+								let start = bar.argument - (bar.bar_width / 2.0);
+								let sector: Vec<PlotPoint> =
+									std::iter::once(PlotPoint::new(0.0, 0.0))
+										.chain((0..=ARC_POINTS).map(|i| {
+											let theta = start
+												+ ((bar.bar_width * i as f64) / ARC_POINTS as f64);
+											PlotPoint::new(
+												radius * theta.cos(),
+												radius * theta.sin(),
+											)
+										}))
+										.collect();
+								plot_ui.polygon(
+									Polygon::new(sector.to_values())
+										.fill_color(integral_color.linear_multiply(0.2))
+										.stroke(egui::Stroke::new(1.0, integral_color)),
+								);
+							});
+					}
+					// Each bar has its own width, so they are always displayed
+					Riemann::Adaptive => {
+						plot_ui.bar_chart(
+							BarChart::new(integral_data.0.clone()).color(integral_color),
+						);
+					}
+					Riemann::MonteCarlo => {
+						plot_ui.points(
+							self.monte_carlo_data
+								.0
+								.clone()
+								.to_points()
+								.color(integral_color)
+								.radius(1.5),
+						);
+						plot_ui.points(
+							self.monte_carlo_data
+								.1
+								.clone()
+								.to_points()
+								.color(Color32::GRAY)
+								.radius(1.5),
+						);
+					}
+					Riemann::Trapezoid if integral_step > step => {
+						plot_ui.line(
+							self.trapezoid_data
+								.clone()
+								.to_line()
+								.color(integral_color)
+								.fill(0.0),
+						);
+					}
+					Riemann::Upper | Riemann::Lower if integral_step > step => {
+						plot_ui.bar_chart(
+							BarChart::new(self.darboux_data.clone())
+								.color(Color32::GRAY)
+								.width(integral_step),
+						);
+						plot_ui.bar_chart(
+							BarChart::new(integral_data.0.clone())
+								.color(integral_color)
+								.width(integral_step),
+						);
+					}
+					_ if integral_step > step => {
+						plot_ui.bar_chart(
+							BarChart::new(integral_data.0.clone())
+								.color(integral_color)
+								.width(integral_step),
+						);
+					}
+					_ => {}
+				}
+
+				// The sum it's compared to is drawn over it in another color
+				if let Some((ref bars, ref trapezoids, _)) = self.comparison_data
+					&& !self.polar
+				{
+					match settings.compare_sum {
+						Some(Riemann::Adaptive) => {
+							plot_ui.bar_chart(BarChart::new(bars.clone()).color(COMPARISON_COLOR));
+						}
+						Some(Riemann::Trapezoid) if integral_step > step => {
+							plot_ui.line(
+								trapezoids
+									.clone()
+									.to_line()
+									.color(COMPARISON_COLOR)
+									.style(LineStyle::dashed_loose()),
+							);
+						}
+						_ if integral_step > step => {
+							plot_ui.bar_chart(
+								BarChart::new(bars.clone())
+									.color(COMPARISON_COLOR)
+									.width(integral_step),
+							);
+						}
+						_ => {}
+					}
+				}
+
+				// Hovering over a rectangle that's drawn shows its interval, height, and area, along with the sum up to it
+				let rectangles_drawn = !self.polar
+					&& match settings.riemann_sum {
+						Riemann::Adaptive => true,
+						Riemann::MonteCarlo => false,
+						_ => integral_step > step,
+					};
+				if rectangles_drawn
+					&& let Some(pointer) = plot_ui.pointer_coordinate()
+					&& let Some(rectangle) = self.rectangle_at(pointer, settings)
+				{
+					let round = |value: f64| settings.number_format.format(value, i18n);
+					egui::show_tooltip_at_pointer(
+						plot_ui.ctx(),
+						plot_ui.response().id.with("riemann_rectangle"),
+						|ui| {
+							ui.label(i18n.format_all(
+								"plot.rectangle",
+								&[
+									(rectangle.index + 1).to_string(),
+									round(rectangle.interval.0),
+									round(rectangle.interval.1),
+									round(rectangle.height),
+									round(rectangle.area),
+									round(rectangle.cumulative),
+								],
+							));
+						},
 					);
 				}
 
-				// return value rounded to 8 decimal places
-				Some(emath::round_to_decimals(integral_data.1, 8))
+				if self.integral_diverges {
+					return Some(i18n.get("plot.diverges").to_owned());
+				}
+
+				// return value written in the number format of the settings
+				let round = |value: f64| settings.number_format.format(value, i18n);
+				// Along with the simple closed form (such as π/2) it's close to, if it's close to one
+				let round_exact = |value: f64| match closed_form(value, CLOSED_FORM_TOLERANCE) {
+					Some(form) => format!("{} ({}?)", round(value), form),
+					None => round(value),
+				};
+				let area = match (settings.area_mode, self.integral_parts, self.darboux_bounds) {
+					(AreaMode::Absolute, Some((positive, negative)), _) => {
+						round_exact(positive - negative)
+					}
+					(AreaMode::Split, Some((positive, negative)), _) => format!(
+						"{} - {} = {}",
+						round(positive),
+						round(-negative),
+						round(integral_data.1)
+					),
+					(_, _, Some((lower, upper))) => {
+						format!("{} ≤ ∫ ≤ {}", round(lower), round(upper))
+					}
+					_ => round_exact(integral_data.1),
+				};
+				Some(
+					// Both are an uncertainty of the area
+					match (
+						self.integral_subdivisions,
+						self.integral_std_error.or(self.integral_error_bound),
+					) {
+						(Some(subdivisions), _) => {
+							i18n.format_all("plot.subdivisions", &[area, subdivisions.to_string()])
+						}
+						(None, Some(std_error)) => {
+							format!("{} ± {}", area, round(std_error))
+						}
+						(None, None) => area,
+					},
+				)
+			}
+			_ => None,
+		}
+	}
+
+	/// Adds the curves, bars, and points [`FunctionEntry::display`] draws to the exported `image`, along with the function's entry in its legend.
+	/// Fields, contours, domain colorings, and the polar integral's sectors aren't exported.
+	pub fn export(
+		&self, image: &mut ExportImage, settings: &AppSettings, default_color: Color32,
+		i18n: &Translations,
+	) {
+		if self.get_test_result().is_some() | !self.visible | self.raw_func_str.trim().is_empty() {
+			return;
+		}
+
+		let main_plot_color = self.color.unwrap_or(default_color);
+		let main_stroke = egui::Stroke::new(self.line_width, main_plot_color);
+		let integral_color = darker(main_plot_color);
+		let curve = |points: Vec<PlotPoint>, stroke: egui::Stroke| ExportShape::Line {
+			points,
+			stroke,
+			style: self.curve_style,
+		};
+		let points = |points: Vec<PlotPoint>, radius: f32, color: Color32| ExportShape::Points {
+			points,
+			radius,
+			color,
+		};
+
+		if self.data_series {
+			image.push_legend(i18n.get("plot.data").to_owned(), main_plot_color);
+			image.push(points(self.data_points.clone(), 4.0, main_plot_color));
+			if self.integral {
+				image.push(ExportShape::line(self.data_points.clone(), integral_color));
+			}
+			return;
+		}
+
+		image.push_legend(self.raw_func_str.clone(), main_plot_color);
+
+		if self.bivariate.is_some() {
+			for implicit_curve in self.implicit_plot.curves.iter() {
+				image.push(curve(implicit_curve.clone(), main_stroke));
+			}
+			return;
+		}
+
+		if self.complex.is_some() {
+			if !self.domain_coloring
+				&& let Some((ref re_data, ref im_data)) = self.complex_data
+			{
+				image.push(ExportShape::line(re_data.clone(), main_plot_color));
+				image.push(ExportShape::Line {
+					points: im_data.clone(),
+					stroke: egui::Stroke::new(1.5, main_plot_color),
+					style: CurveStyle::Dashed,
+				});
+			}
+			return;
+		}
+
+		if self.function.is_none() {
+			return;
+		}
+
+		if self.sequence {
+			if self.sequence_data.len() <= MAX_STEMS {
+				let stems: Vec<Bar> = self
+					.sequence_data
+					.iter()
+					.map(|point| Bar::new(point.x, point.y))
+					.collect();
+				image.push(ExportShape::bars(&stems, Some(0.05), main_plot_color));
+			}
+
+			image.push(points(self.sequence_data.clone(), 4.0, main_plot_color));
+			if self.partial_sums {
+				image.push(points(self.partial_sum_data.clone(), 4.0, Color32::GOLD));
+			}
+			return;
+		}
+
+		let settings = &self.integral_settings(settings);
+		let integral_step = (settings.integral_max_x - settings.integral_min_x).abs()
+			/ (settings.integral_num as f64);
+		let step = (settings.max_x - settings.min_x) / (settings.plot_width as f64);
+
+		if self.rigorous && !self.rigorous_data.is_empty() {
+			image.push(ExportShape::bars(
+				&self.rigorous_data,
+				None,
+				main_plot_color,
+			));
+		}
+
+		if !self.back_data.is_empty() {
+			if self.integral
+				&& !self.polar
+				&& (settings.is_improper()
+					| (settings.riemann_sum.uses_intervals() && (step >= integral_step)))
+			{
+				let (min_x, max_x) = settings.integral_bounds();
+				image.push(ExportShape::Fill {
+					points: self
+						.back_data
+						.iter()
+						.filter(|value| (value.x > min_x) && (max_x > value.x))
+						.cloned()
+						.collect(),
+					color: integral_color,
+				});
+			}
+			for segment in self.back_data.clone().to_segments(&self.back_gaps) {
+				image.push(curve(segment, main_stroke));
+			}
+		}
+
+		if self.derivative && !self.derivative_data.is_empty() {
+			for segment in self
+				.derivative_data
+				.clone()
+				.to_segments(&self.derivative_gaps)
+			{
+				image.push(curve(
+					segment,
+					egui::Stroke::new(self.line_width / 2.0, lighter(main_plot_color)),
+				));
+			}
+		}
+
+		if settings.do_extrema && !self.extrema_data.is_empty() {
+			image.push(points(
+				self.extrema_data.iter().map(|(point, _)| *point).collect(),
+				5.0,
+				Color32::YELLOW,
+			));
+		}
+
+		if settings.do_roots && !self.root_data.is_empty() {
+			image.push(points(self.root_data.clone(), 5.0, Color32::LIGHT_BLUE));
+		}
+
+		if self.antiderivative && !self.antiderivative_data.is_empty() {
+			image.push(curve(
+				self.antiderivative_data.clone(),
+				egui::Stroke::new(self.line_width / 2.0, darker(main_plot_color)),
+			));
+		}
+
+		if let Some(ref integral_data) = self.integral_data
+			&& self.integral
+			&& !self.polar
+		{
+			match settings.riemann_sum {
+				Riemann::Adaptive => {
+					image.push_bars(&integral_data.0, None, integral_color);
+				}
+				Riemann::MonteCarlo => {
+					image.push(points(self.monte_carlo_data.0.clone(), 1.5, integral_color));
+					image.push(points(self.monte_carlo_data.1.clone(), 1.5, Color32::GRAY));
+				}
+				Riemann::Trapezoid if integral_step > step => {
+					image.push(ExportShape::Fill {
+						points: self.trapezoid_data.clone(),
+						color: integral_color,
+					});
+					image.push(ExportShape::line(
+						self.trapezoid_data.clone(),
+						integral_color,
+					));
+				}
+				Riemann::Upper | Riemann::Lower if integral_step > step => {
+					image.push(ExportShape::bars(
+						&self.darboux_data,
+						Some(integral_step),
+						Color32::GRAY,
+					));
+					image.push_bars(&integral_data.0, Some(integral_step), integral_color);
+				}
+				_ if integral_step > step => {
+					image.push_bars(&integral_data.0, Some(integral_step), integral_color);
+				}
+				_ => {}
+			}
+
+			if let Some((ref bars, ref trapezoids, _)) = self.comparison_data {
+				match settings.compare_sum {
+					Some(Riemann::Adaptive) => {
+						image.push(ExportShape::bars(bars, None, COMPARISON_COLOR));
+					}
+					Some(Riemann::Trapezoid) if integral_step > step => {
+						image.push(ExportShape::line(trapezoids.clone(), COMPARISON_COLOR));
+					}
+					_ if integral_step > step => {
+						image.push(ExportShape::bars(
+							bars,
+							Some(integral_step),
+							COMPARISON_COLOR,
+						));
+					}
+					_ => {}
+				}
 			}
-			None => None,
 		}
 	}
 
 	/// Invalidate entire cache
 	fn invalidate_whole(&mut self) {
+		self.complex_data = None;
+		self.domain_coloring_data.clear();
+		self.implicit_plot.clear();
+		self.contour_data.clear();
+		self.clear_sequence();
+		self.slope_field_data.clear();
+		self.vector_field_data.clear();
+		self.line_integral_data = None;
+		self.limit_data = None;
+		self.tangent_data = None;
+		self.osculating_data = None;
+		self.mean_value_data = None;
+		self.clear_curvature();
 		self.clear_back();
 		self.clear_integral();
 		self.clear_derivative();
@@ -453,22 +3807,61 @@ impl FunctionEntry {
 		self.clear_roots();
 	}
 
+	/// Returns the left and right limits of the function at `x`
+	pub fn limit(&mut self, x: f64) -> (f64, f64) { limits(&mut self.function, x) }
+
+	/// Invalidate sequence data
+	#[inline]
+	fn clear_sequence(&mut self) {
+		self.sequence_data.clear();
+		self.partial_sum_data.clear();
+	}
+
 	/// Invalidate `back` data
 	#[inline]
-	fn clear_back(&mut self) { self.back_data.clear(); }
+	fn clear_back(&mut self) {
+		self.back_data.clear();
+		self.back_gaps.clear();
+		self.rigorous_data.clear();
+		self.antiderivative_data.clear();
+	}
 
 	/// Invalidate Integral data
 	#[inline]
-	fn clear_integral(&mut self) { self.integral_data = None; }
+	fn clear_integral(&mut self) {
+		self.integral_data = None;
+		self.arc_length_data = None;
+		self.trapezoid_data.clear();
+		self.integral_subdivisions = None;
+		self.monte_carlo_data = (Vec::new(), Vec::new());
+		self.integral_std_error = None;
+		self.integral_error_bound = None;
+		self.romberg_table.clear();
+		self.integral_singular = false;
+		self.integral_diverges = false;
+		self.integral_parts = None;
+		self.darboux_data.clear();
+		self.darboux_bounds = None;
+		self.comparison_data = None;
+		self.convergence_data.clear();
+		self.antiderivative_data.clear();
+	}
 
 	/// Invalidate Derivative data
 	#[inline]
-	fn clear_derivative(&mut self) { self.derivative_data.clear(); }
+	fn clear_derivative(&mut self) {
+		self.derivative_data.clear();
+		self.derivative_gaps.clear();
+	}
 
 	/// Invalidates `n`th derivative data
 	#[inline]
 	fn clear_nth(&mut self) { self.nth_derivative_data = None }
 
+	/// Invalidates curvature data
+	#[inline]
+	fn clear_curvature(&mut self) { self.curvature_data = None }
+
 	/// Invalidate extrema data
 	#[inline]
 	fn clear_extrema(&mut self) { self.extrema_data.clear() }
@@ -477,3 +3870,19 @@ impl FunctionEntry {
 	#[inline]
 	fn clear_roots(&mut self) { self.root_data.clear() }
 }
+
+/// Left and right limits of `function` at `x`
+fn limits(function: &mut BackingFunction, x: f64) -> (f64, f64) {
+	(
+		one_sided_limit(&mut |x| function.get(0, x), x, -1.0),
+		one_sided_limit(&mut |x| function.get(0, x), x, 1.0),
+	)
+}
+
+/// Whether the left and right limits are close enough to be considered the same
+fn limits_agree(left: f64, right: f64) -> bool {
+	if left.is_infinite() || right.is_infinite() {
+		return left == right;
+	}
+	(left - right).abs() <= 1e-6 * left.abs().max(1.0)
+}
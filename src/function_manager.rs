@@ -1,21 +1,97 @@
 use crate::{
 	consts::COLORS,
-	function_entry::FunctionEntry,
-	misc::{create_id, get_u64_id, random_u64},
+	function_entry::{CurveStyle, FunctionEntry, IntegralOverride, Riemann},
+	i18n::Translations,
+	math_app::AppSettings,
+	misc::{create_id, get_u64_id, parse_number, random_u64},
+	number_format::NumberFormat,
+	theme::Palette,
+	typeset::typeset_label,
 	widgets::widgets_ontop,
 };
-use egui::{Button, Id, Key, Modifiers, TextEdit, WidgetText};
+use egui::{
+	text::LayoutJob, Button, ComboBox, CursorIcon, DragValue, Id, Key, Modifiers, Response, Sense,
+	TextEdit, TextFormat, WidgetInfo, WidgetText, WidgetType,
+};
+use egui_plot::PlotPoint;
 use emath::vec2;
-use parsing::Movement;
+use epaint::{Color32, Rect, Stroke};
+use parsing::{sanitize_func_str, unmatched_parens, AngleUnit, Definitions, Movement};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::hash_map::DefaultHasher;
+use std::collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet};
 use std::hash::{Hash, Hasher};
-use std::ops::BitXorAssign;
+use std::ops::{BitXorAssign, Range};
+
+/// Converts unicode math characters in `string` (the contents of the text box `id`) into the ASCII they're written as, keeping the cursor after the same character
+fn sanitize_text_box(ctx: &egui::Context, id: Id, string: &mut String) {
+	let sanitized = sanitize_func_str(string);
+	if sanitized == *string {
+		return;
+	}
+
+	// Characters are only replaced by at least as many, so the cursor moves forward by however many were added
+	if let Some(mut state) = TextEdit::load_state(ctx, id)
+		&& let Some(range) = state.ccursor_range()
+	{
+		let added = sanitized.chars().count() - string.chars().count();
+		let ccursor = egui::text::CCursor::new(range.primary.index + added);
+		state.set_ccursor_range(Some(egui::text::CCursorRange::one(ccursor)));
+		TextEdit::store_state(ctx, id, state);
+	}
+	*string = sanitized;
+}
+
+/// Range of bytes of `string` to underline for the error caused by `span`.
+/// Errors at a single position (such as the end of the string) underline the character after it, or the last character if there isn't one.
+fn underlined_range(string: &str, span: Range<usize>) -> Option<Range<usize>> {
+	if !span.is_empty() {
+		return string.get(span.clone()).map(|_| span);
+	}
+
+	match string.get(span.start..)?.chars().next() {
+		Some(c) => Some(span.start..(span.start + c.len_utf8())),
+		None => string
+			.chars()
+			.next_back()
+			.map(|c| (string.len() - c.len_utf8())..string.len()),
+	}
+}
+
+/// Color of the underline under parentheses that aren't matched, which is only a warning (unlike the red underline of errors)
+const UNMATCHED_PARENS_COLOR: Color32 = Color32::from_rgb(255, 165, 0);
+
+/// Most function strings kept in the history
+const MAX_HISTORY: usize = 50;
+
+/// Id of the popup listing multiple autocomplete hints, which doesn't need to have a number in it as there should only be 1 autocomplete popup in the entire gui
+// hashed "autocomplete_popup"
+const POPUP_ID: Id = create_id(7574801616484505465);
 
 type Functions = Vec<(Id, FunctionEntry)>;
 pub struct FunctionManager {
 	functions: Functions,
+
+	/// Period of the function the user asked to integrate over one period of
+	integrate_period: Option<f64>,
+
+	/// Function strings that have been entered, the most recent last
+	history: Vec<String>,
+
+	/// If `history` is saved along with the functions, rather than only lasting the session
+	persist_history: bool,
+
+	/// Number of times each completion (such as `sin(`) has been accepted, which autocomplete candidates are ranked by
+	completion_usage: BTreeMap<String, usize>,
+
+	/// Index of the function being dragged by its handle, to move it elsewhere in the list
+	dragged: Option<usize>,
+
+	/// Colors functions are assigned (unless they're given one)
+	palette: Palette,
+
+	/// Id of the text box that was last typed in, which the on-screen keypad types into
+	typing: Option<Id>,
 }
 
 impl Default for FunctionManager {
@@ -25,7 +101,16 @@ impl Default for FunctionManager {
 			create_id(11414819524356497634), // Random number here to avoid call to crate::misc::random_u64()
 			FunctionEntry::default(),
 		));
-		Self { functions: vec }
+		Self {
+			functions: vec,
+			integrate_period: None,
+			history: Vec::new(),
+			persist_history: false,
+			completion_usage: BTreeMap::new(),
+			dragged: None,
+			palette: Palette::Default,
+			typing: None,
+		}
 	}
 }
 
@@ -34,7 +119,7 @@ impl Serialize for FunctionManager {
 	where
 		S: Serializer,
 	{
-		let mut s = serializer.serialize_struct("FunctionManager", 1)?;
+		let mut s = serializer.serialize_struct("FunctionManager", 4)?;
 		s.serialize_field(
 			"data",
 			&self
@@ -43,6 +128,15 @@ impl Serialize for FunctionManager {
 				.map(|(id, func)| (get_u64_id(*id), func.clone()))
 				.collect::<Vec<(u64, FunctionEntry)>>(),
 		)?;
+		s.serialize_field(
+			"history",
+			match self.persist_history {
+				true => self.history.as_slice(),
+				false => &[],
+			},
+		)?;
+		s.serialize_field("persist_history", &self.persist_history)?;
+		s.serialize_field("completion_usage", &self.completion_usage)?;
 		s.end()
 	}
 }
@@ -53,17 +147,30 @@ impl<'de> Deserialize<'de> for FunctionManager {
 		D: Deserializer<'de>,
 	{
 		#[derive(Deserialize)]
-		struct Helper(Vec<(u64, FunctionEntry)>);
+		struct Helper {
+			data: Vec<(u64, FunctionEntry)>,
+			history: Vec<String>,
+			persist_history: bool,
+			#[serde(default)]
+			completion_usage: BTreeMap<String, usize>,
+		}
 
 		let helper = Helper::deserialize(deserializer)?;
 
 		Ok(FunctionManager {
 			functions: helper
-				.0
+				.data
 				.iter()
 				.cloned()
 				.map(|(id, func)| (create_id(id), func))
 				.collect::<Vec<(Id, FunctionEntry)>>(),
+			integrate_period: None,
+			history: helper.history,
+			persist_history: helper.persist_history,
+			completion_usage: helper.completion_usage,
+			dragged: None,
+			palette: Palette::Default,
+			typing: None,
 		})
 	}
 }
@@ -73,107 +180,310 @@ fn button_area_button<'a>(text: impl Into<WidgetText>) -> Button<'a> {
 	Button::new(text).frame(false)
 }
 
+/// Shows `text` when hovering over a button of the button area, which is also what screen readers announce it as (rather than its symbol, such as ✖)
+fn button_area_hover(response: Response, text: &str) -> Response {
+	response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, text));
+	response.on_hover_text(text)
+}
+
 impl FunctionManager {
 	#[inline]
 	fn get_hash(&self) -> u64 {
 		let mut hasher = DefaultHasher::new();
 		self.functions.hash(&mut hasher);
+		self.history.hash(&mut hasher);
+		self.persist_history.hash(&mut hasher);
+		self.completion_usage.hash(&mut hasher);
 		hasher.finish()
 	}
 
-	/// Displays function entries alongside returning whether or not functions have been modified
-	pub fn display_entries(&mut self, ui: &mut egui::Ui) -> bool {
+	/// Displays function entries (with their text in the language of `i18n` and their values in `number_format`) alongside returning whether or not functions have been modified
+	pub fn display_entries(
+		&mut self, ui: &mut egui::Ui, i18n: &Translations, number_format: &NumberFormat,
+	) -> bool {
 		let initial_hash = self.get_hash();
 
 		let can_remove = self.functions.len() > 1;
+		let can_add = COLORS.len() > self.functions.len();
 
 		let available_width = ui.available_width();
 		let mut remove_i: Option<usize> = None;
+		let mut duplicate_i: Option<usize> = None;
+		let mut rows: Vec<Rect> = Vec::with_capacity(self.functions.len());
+		let mut integrate_period: Option<f64> = None;
+		let mut entered: Vec<String> = Vec::new();
 		let target_size = vec2(available_width, crate::consts::FONT_SIZE);
+
+		// User functions and parameters are completed along with the built in names
+		let terms: BTreeSet<String> = self
+			.definition_sources()
+			.into_keys()
+			.map(|name| name + "(")
+			.chain(
+				self.functions
+					.iter()
+					.flat_map(|(_, function)| function.parameter_names().cloned()),
+			)
+			.collect();
+
+		let text_boxes: Vec<Id> = self.functions.iter().map(|(id, _)| *id).collect();
 		for (i, (te_id, function)) in self.functions.iter_mut().map(|(a, b)| (*a, b)).enumerate() {
 			let mut new_string = function.autocomplete.string.clone();
-			function.update_string(&new_string);
+
+			// A function can't call itself, so it isn't completed in its own definition
+			let own_definition = function.definition().map(|(name, _)| format!("{}(", name));
+			function.autocomplete.set_terms(
+				terms
+					.iter()
+					.filter(|term| Some(*term) != own_definition.as_ref())
+					.cloned()
+					.collect(),
+			);
+			function.autocomplete.set_usage(&self.completion_usage);
 
 			let mut movement: Movement = Movement::default();
 
-			let size_multiplier = vec2(1.0, {
-				let had_focus = ui.memory(|x| x.has_focus(te_id));
-				(ui.ctx().animate_bool(te_id, had_focus) * 1.5) + 1.0
-			});
+			let had_focus = ui.memory(|x| x.has_focus(te_id));
+
+			// Stays open while the buttons below the text box are moved through with the keyboard, after Tab moves out of it
+			let buttons_focused = (self.typing == Some(te_id))
+				&& ui
+					.memory(|x| x.focus())
+					.is_some_and(|id| !text_boxes.contains(&id));
+			let size_multiplier = vec2(
+				1.0,
+				(ui.ctx().animate_bool(te_id, had_focus | buttons_focused) * 1.5) + 1.0,
+			);
+
+			// Moving through multiple hints (with Tab and Shift+Tab, or the arrows) is handled before the text box is,
+			// consuming the keys so they don't move its cursor or reach anything else (such as the plot)
+			if had_focus
+				&& (function.autocomplete.candidates().len() > 1)
+				&& function.correction().is_none()
+			{
+				let (down, up) = ui.input_mut(|x| {
+					(
+						x.consume_key(Modifiers::NONE, Key::Tab)
+							| x.consume_key(Modifiers::NONE, Key::ArrowDown),
+						x.consume_key(Modifiers::SHIFT, Key::Tab)
+							| x.consume_key(Modifiers::NONE, Key::ArrowUp),
+					)
+				});
+				if down {
+					movement = Movement::Down;
+				} else if up {
+					movement = Movement::Up;
+				}
+			}
+
+			// Underlines the part of the function string that caused the error parsing it, along with the parentheses that aren't matched
+			let error_span = function.error_span();
+			let mut layouter = |ui: &egui::Ui, string: &str, wrap_width: f32| {
+				let font_id = egui::FontSelection::default().resolve(ui.style());
+				let color = ui.visuals().widgets.inactive.text_color();
+				let mut job = LayoutJob::default();
+				job.wrap.max_width = wrap_width;
+
+				let error_range = error_span
+					.clone()
+					.and_then(|span| underlined_range(string, span));
+				let unmatched = unmatched_parens(string);
+				let underline_at = |i: usize| {
+					if error_range.as_ref().is_some_and(|range| range.contains(&i)) {
+						egui::Stroke::new(2.0, Color32::RED)
+					} else if unmatched.contains(&i) {
+						egui::Stroke::new(2.0, UNMATCHED_PARENS_COLOR)
+					} else {
+						egui::Stroke::NONE
+					}
+				};
+
+				// Characters are added in runs that are underlined the same way
+				let mut run_start = 0;
+				for i in string
+					.char_indices()
+					.map(|(i, _)| i)
+					.skip(1)
+					.chain(std::iter::once(string.len()))
+				{
+					if (i == string.len()) | (underline_at(i) != underline_at(run_start)) {
+						let format = TextFormat {
+							underline: underline_at(run_start),
+							..TextFormat::simple(font_id.clone(), color)
+						};
+						job.append(&string[run_start..i], 0.0, format);
+						run_start = i;
+					}
+				}
+				ui.fonts(|fonts| fonts.layout_job(job))
+			};
 
 			let re = ui.add_sized(
 				target_size * size_multiplier,
 				egui::TextEdit::singleline(&mut new_string)
 					.hint_forward(true) // Make the hint appear after the last text in the textbox
-					.lock_focus(true)
+					// Tab moves to the next widget (such as the buttons below), unless there's a hint or correction for it to accept
+					.lock_focus(
+						!function.autocomplete.current_hint().is_empty()
+							| function.correction().is_some(),
+					)
 					.id(te_id) // Set widget's id to `te_id`
+					.layouter(&mut layouter)
 					.hint_text(
-						// If there's a single hint, go ahead and apply the hint here, if not, set the hint to an empty string
-						function.autocomplete.hint.single().unwrap_or(""),
+						// Shows the hint that would be completed, which is the selected one if there are multiple, unless it would be inserted before the end
+						match function.autocomplete.cursor_at_end() {
+							true => function.autocomplete.current_hint(),
+							false => "",
+						},
 					),
 			);
+			rows.push(re.rect);
+			if re.has_focus() {
+				self.typing = Some(te_id);
+			}
 
-			// Only keep valid chars
+			// Only keep valid chars, after converting the ones pasted from documents (such as −) into ones that are
+			sanitize_text_box(ui.ctx(), re.id, &mut new_string);
 			new_string.retain(crate::misc::is_valid_char);
 
+			// Clicking on the autocomplete popup takes focus from the text box, which would hide the popup before the click is handled
+			let clicked_popup = re.lost_focus()
+				&& ui.memory(|x| x.is_popup_open(POPUP_ID))
+				&& ui
+					.input(|x| x.pointer.interact_pos())
+					.zip(ui.memory(|x| x.area_rect(POPUP_ID)))
+					.is_some_and(|(pos, rect)| rect.contains(pos));
+			if clicked_popup {
+				ui.memory_mut(|x| x.request_focus(te_id));
+			}
+
+			// Function strings are added to the history once they're done being entered, if they're valid
+			if re.lost_focus()
+				&& !clicked_popup
+				&& !new_string.is_empty()
+				&& (new_string == function.raw_func_str)
+				&& function.get_test_result().is_none()
+			{
+				entered.push(new_string.clone());
+			}
+
+			// Opened from the history button, and closed by clicking anywhere (after recalling what was clicked on)
+			let history_popup = te_id.with("history");
+			egui::popup_below_widget(ui, history_popup, &re, |ui| {
+				for func_str in self.history.iter().rev() {
+					if ui.selectable_label(false, func_str).clicked() {
+						function.autocomplete.update_string(func_str);
+					}
+				}
+
+				ui.separator();
+				ui.checkbox(
+					&mut self.persist_history,
+					i18n.get("function.history.remember"),
+				)
+				.on_hover_text(i18n.get("function.history.remember.hover"));
+			});
+
 			// If not fully open, return here as buttons cannot yet be displayed, therefore the user is inable to mark it for deletion
-			let animate_bool = ui.ctx().animate_bool(te_id, re.has_focus());
+			let animate_bool = ui
+				.ctx()
+				.animate_bool(te_id, re.has_focus() | clicked_popup | buttons_focused);
 			if animate_bool == 1.0 {
-				function.autocomplete.update_string(&new_string);
+				// Hints are for the term before the cursor, and are inserted there
+				let cursor = TextEdit::load_state(ui.ctx(), te_id)
+					.and_then(|state| state.ccursor_range())
+					.map_or(new_string.chars().count(), |range| range.primary.index);
+
+				// Typing `)` right before one that's already there (such as one added when completing a function) skips over it
+				if function.autocomplete.skips_closing_parens(&new_string, cursor) {
+					new_string = function.autocomplete.string.clone();
+				}
+				function.autocomplete.update_string_at(&new_string, cursor);
+				let at_end = function.autocomplete.cursor_at_end();
 
-				if function.autocomplete.hint.is_some() {
-					// only register up and down arrow movements if hint is type `Hint::Many`
-					if !function.autocomplete.hint.is_single() {
-						let (arrow_down, arrow_up) = ui.input(|x| {
-							(x.key_pressed(Key::ArrowDown), x.key_pressed(Key::ArrowUp))
-						});
-						if arrow_down {
-							movement = Movement::Down;
-						} else if arrow_up {
-							movement = Movement::Up;
-						}
+				if let Some((range, correction)) = function.correction() {
+					// Misspelled function names (such as `sni` in `sni(x)`) are offered to be corrected instead of hints, which Enter or Tab accepts
+					let accepted = ui.input_mut(|x| {
+						x.consume_key(Modifiers::NONE, Key::Enter)
+							| x.consume_key(Modifiers::NONE, Key::Tab)
+					});
+
+					let mut clicked = false;
+					egui::popup_below_widget(ui, POPUP_ID, &re, |ui| {
+						clicked = ui
+							.selectable_label(
+								true,
+								i18n.format("function.did_you_mean", correction),
+							)
+							.clicked();
+					});
+
+					if accepted | clicked {
+						function.autocomplete.apply_correction(range, correction);
+						movement = Movement::Complete;
+					} else {
+						ui.memory_mut(|x| x.open_popup(POPUP_ID));
 					}
+				} else if !function.autocomplete.candidates().is_empty() {
+					// Tab only completes single hints, as it moves through multiple ones
+					let is_single = function.autocomplete.candidates().len() == 1;
 
 					// Put here so these key presses don't interact with other elements
 					let movement_complete_action = ui.input_mut(|x| {
 						x.consume_key(Modifiers::NONE, Key::Enter)
-							| x.consume_key(Modifiers::NONE, Key::Tab)
-							| x.key_pressed(Key::ArrowRight)
+							| (is_single && x.consume_key(Modifiers::NONE, Key::Tab))
+							| (at_end && x.key_pressed(Key::ArrowRight))
 					});
 
 					if movement_complete_action {
 						movement = Movement::Complete;
+						let completion = function
+							.autocomplete
+							.completion(function.autocomplete.current_hint());
+						*self.completion_usage.entry(completion).or_default() += 1;
 					}
 
 					// Register movement and apply proper changes
 					function.autocomplete.register_movement(&movement);
 
-					if movement != Movement::Complete
-						&& let Some(hints) = function.autocomplete.hint.many()
-					{
-						// Doesn't need to have a number in id as there should only be 1 autocomplete popup in the entire gui
-
-						// hashed "autocomplete_popup"
-						const POPUP_ID: Id = create_id(7574801616484505465);
+					// Single hints are listed as well when they'd be inserted before the end, as the text box only shows them after it
+					let hints: Vec<String> = function
+						.autocomplete
+						.candidates()
+						.into_iter()
+						.map(str::to_owned)
+						.collect();
+					let listed = (hints.len() > 1) | (!at_end && !hints.is_empty());
 
+					if (movement != Movement::Complete) && listed {
 						let mut clicked = false;
 
 						egui::popup_below_widget(ui, POPUP_ID, &re, |ui| {
-							hints.iter().enumerate().for_each(|(i, candidate)| {
-								if ui
-									.selectable_label(i == function.autocomplete.i, *candidate)
-									.clicked()
-								{
-									clicked = true;
-									function.autocomplete.i = i;
-								}
-							});
+							egui::ScrollArea::vertical()
+								.max_height(crate::consts::FONT_SIZE * 10.0)
+								.show(ui, |ui| {
+									hints.iter().enumerate().for_each(|(i, candidate)| {
+										let selected = i == function.autocomplete.i;
+										let label = ui.selectable_label(selected, candidate);
+
+										// Keep the selected candidate in view when moving through them with the keyboard
+										if selected && !movement.is_none() {
+											label.scroll_to_me(None);
+										}
+
+										if label.clicked() {
+											clicked = true;
+											function.autocomplete.i = i;
+										}
+									});
+								});
 						});
 
 						if clicked {
-							function
-								.autocomplete
-								.apply_hint(hints[function.autocomplete.i]);
+							let hint = &hints[function.autocomplete.i];
+							let completion = function.autocomplete.completion(hint);
+							*self.completion_usage.entry(completion).or_default() += 1;
+							function.autocomplete.apply_hint(hint);
 
 							// Don't need this here as it simply won't be display next frame
 							// ui.memory_mut().close_popup();
@@ -183,15 +493,15 @@ impl FunctionManager {
 							ui.memory_mut(|x| x.open_popup(POPUP_ID));
 						}
 					}
+				}
 
-					// Push cursor to end if needed
-					if movement == Movement::Complete {
-						let mut state =
-							unsafe { TextEdit::load_state(ui.ctx(), te_id).unwrap_unchecked() };
-						let ccursor = egui::text::CCursor::new(function.autocomplete.string.len());
-						state.set_ccursor_range(Some(egui::text::CCursorRange::one(ccursor)));
-						TextEdit::store_state(ui.ctx(), te_id, state);
-					}
+				// Push cursor to after the completed hint (or in place after a correction) if needed
+				if movement == Movement::Complete {
+					let mut state =
+						unsafe { TextEdit::load_state(ui.ctx(), te_id).unwrap_unchecked() };
+					let ccursor = egui::text::CCursor::new(function.autocomplete.cursor);
+					state.set_ccursor_range(Some(egui::text::CCursorRange::one(ccursor)));
+					TextEdit::store_state(ui.ctx(), te_id, state);
 				}
 
 				/// The y offset multiplier of the `buttons_area` area
@@ -200,51 +510,288 @@ impl FunctionManager {
 
 				widgets_ontop(ui, create_id(i as u64), &re, Y_OFFSET, |ui| {
 					ui.horizontal(|ui| {
+						// Handle that's dragged up or down to move the function
+						let handle = button_area_hover(
+							ui.add_enabled(
+								can_remove,
+								button_area_button("☰").sense(Sense::drag()),
+							),
+							i18n.get("function.reorder"),
+						);
+						if handle.drag_started() {
+							self.dragged = Some(i);
+						}
+						if handle.hovered() {
+							ui.ctx().set_cursor_icon(CursorIcon::Grab);
+						}
+
 						// There's more than 1 function! Functions can now be deleted
-						if ui
-							.add_enabled(can_remove, button_area_button("✖"))
-							.on_hover_text("Delete Function")
-							.clicked()
+						if button_area_hover(
+							ui.add_enabled(can_remove, button_area_button("✖")),
+							i18n.get("function.delete"),
+						)
+						.clicked()
 						{
 							remove_i = Some(i);
 						}
 
+						// Copies the function (along with its settings) into a new entry below it
+						if button_area_hover(
+							ui.add_enabled(can_add, button_area_button("📋")),
+							i18n.get("function.duplicate"),
+						)
+						.clicked()
+						{
+							duplicate_i = Some(i);
+						}
+
+						// Shows previously entered function strings below the text box, clicking one replaces the function string with it
+						if button_area_hover(
+							ui.add_enabled(!self.history.is_empty(), button_area_button("🕘")),
+							i18n.get("function.history"),
+						)
+						.clicked()
+						{
+							ui.memory_mut(|x| x.toggle_popup(history_popup));
+						}
+
 						ui.add_enabled_ui(function.is_some(), |ui| {
+							// Toggle the function being drawn, without removing it
+							function.visible.bitxor_assign(
+								button_area_hover(
+									ui.add(button_area_button(match function.visible {
+										true => "👁",
+										false => "⊘",
+									})),
+									i18n.get(match function.visible {
+										true => "function.hide",
+										false => "function.show",
+									}),
+								)
+								.clicked(),
+							);
+
 							// Toggle integral being enabled or not
 							function.integral.bitxor_assign(
-								ui.add(button_area_button("∫"))
-									.on_hover_text(match function.integral {
-										true => "Don't integrate",
-										false => "Integrate",
-									})
-									.clicked(),
+								button_area_hover(
+									ui.add(button_area_button("∫")),
+									i18n.get(match function.integral {
+										true => "function.integrate.disable",
+										false => "function.integrate",
+									}),
+								)
+								.clicked(),
 							);
 
+							// Toggle integrating with the function's own bounds, interval count, and sum instead of the global ones
+							let has_override = function.integral_override().is_some();
+							if button_area_hover(
+								ui.add(button_area_button("[a, b]")),
+								i18n.get(match has_override {
+									true => "function.integral_override.disable",
+									false => "function.integral_override",
+								}),
+							)
+							.clicked()
+							{
+								function.set_integral_override(match has_override {
+									true => None,
+									false => Some(IntegralOverride::default()),
+								});
+							}
+
 							// Toggle showing the derivative (even though it's already calculated this option just toggles if it's displayed or not)
 							function.derivative.bitxor_assign(
-								ui.add(button_area_button("d/dx"))
-									.on_hover_text(match function.derivative {
-										true => "Don't Differentiate",
-										false => "Differentiate",
-									})
-									.clicked(),
+								button_area_hover(
+									ui.add(button_area_button("d/dx")),
+									i18n.get(match function.derivative {
+										true => "function.differentiate.disable",
+										false => "function.differentiate",
+									}),
+								)
+								.clicked(),
+							);
+
+							// Toggle showing the running integral F(x)
+							function.antiderivative.bitxor_assign(
+								button_area_hover(
+									ui.add(button_area_button("F(x)")),
+									i18n.get(match function.antiderivative {
+										true => "function.antiderivative.hide",
+										false => "function.antiderivative.show",
+									}),
+								)
+								.clicked(),
 							);
 
 							// Toggle showing the settings window
 							function.settings_opened.bitxor_assign(
-								ui.add(button_area_button("⚙"))
-									.on_hover_text(match function.settings_opened {
-										true => "Close Settings",
-										false => "Open Settings",
-									})
-									.clicked(),
+								button_area_hover(
+									ui.add(button_area_button("⚙")),
+									i18n.get(match function.settings_opened {
+										true => "function.settings.close",
+										false => "function.settings.open",
+									}),
+								)
+								.clicked(),
 							);
+
+							// How the function's curve is drawn
+							let mut color = function.color.unwrap_or(self.palette.color(i));
+							if egui::color_picker::color_edit_button_srgba(
+								ui,
+								&mut color,
+								egui::color_picker::Alpha::Opaque,
+							)
+							.on_hover_text(i18n.get("function.color"))
+							.changed()
+							{
+								function.color = Some(color);
+							}
+
+							ui.add(
+								egui::Slider::new(&mut function.line_width, 0.5..=10.0)
+									.show_value(false),
+							)
+							.on_hover_text(i18n.get("function.line_width"));
+
+							ComboBox::from_id_source((te_id, "curve_style"))
+								.selected_text(i18n.get(function.curve_style.label()))
+								.width(crate::consts::FONT_SIZE * 4.0)
+								.show_ui(ui, |ui| {
+									for curve_style in
+										[CurveStyle::Solid, CurveStyle::Dashed, CurveStyle::Dotted]
+									{
+										ui.selectable_value(
+											&mut function.curve_style,
+											curve_style,
+											i18n.get(curve_style.label()),
+										);
+									}
+								});
+
+							// Detected period of the function, clicking it integrates over one period
+							if let Some(period) = function.period()
+								&& ui
+									.add(button_area_button(format!(
+										"T = {}",
+										number_format.format(period, i18n)
+									)))
+									.on_hover_text(i18n.get("function.period.hover"))
+									.clicked()
+							{
+								integrate_period = Some(period);
+							}
 						});
 					});
 				});
 			}
 
-			function.settings_window(ui.ctx());
+			// Typeset math of the function, so it can be checked that it was parsed as intended
+			if let Some(typeset) = function.typeset() {
+				typeset_label(ui, typeset);
+			}
+
+			// Formulas of the function and its derivative, which can be selected and copied
+			if function.derivative && let Some((formula, derivative)) = function.formulas() {
+				for (name, mut formula) in [("f(x) =", formula), ("f'(x) =", derivative)] {
+					ui.horizontal(|ui| {
+						ui.label(name);
+						// Text edits of a `&str` can't be edited
+						ui.add(TextEdit::singleline(&mut formula).desired_width(f32::INFINITY));
+					});
+				}
+			}
+
+			// The function's own integration settings, if it has them
+			if (function.integral | function.antiderivative)
+				&& let Some(mut integral_override) = function.integral_override()
+			{
+				ui.horizontal(|ui| {
+					ui.label(i18n.get("side_panel.integral"));
+					ui.add(
+						DragValue::new(&mut integral_override.min_x).custom_parser(parse_number),
+					);
+					ui.label(",");
+					ui.add(
+						DragValue::new(&mut integral_override.max_x).custom_parser(parse_number),
+					);
+					ui.label("]");
+					ui.add(
+						DragValue::new(&mut integral_override.num)
+							.clamp_range(1..=500000)
+							.prefix(format!("{} ", i18n.get("side_panel.intervals")))
+							.custom_parser(parse_number),
+					);
+					ComboBox::from_id_source((te_id, "riemann_sum"))
+						.selected_text(i18n.get(integral_override.riemann_sum.label()))
+						.show_ui(ui, |ui| {
+							for riemann_sum in Riemann::ALL {
+								ui.selectable_value(
+									&mut integral_override.riemann_sum,
+									riemann_sum,
+									i18n.get(riemann_sum.label()),
+								);
+							}
+						});
+				});
+				function.set_integral_override(Some(integral_override));
+			}
+
+			// Vector fields have a second expression, Q(x, y), entered below the function
+			if function.vector_field {
+				let mut q_string = function.q_func_str.clone();
+				let q_re = ui.add_sized(
+					target_size,
+					TextEdit::singleline(&mut q_string)
+						.id_source((te_id, "q"))
+						.hint_text(i18n.get("function.q_hint")),
+				);
+				sanitize_text_box(ui.ctx(), q_re.id, &mut q_string);
+				q_string.retain(crate::misc::is_valid_char);
+				function.update_q_string(&q_string);
+			}
+
+			function.settings_window(ui.ctx(), i18n, number_format);
+		}
+
+		// The function being dragged is moved to where the pointer is (marked with a line) when it's dropped
+		if let Some(dragged) = self.dragged.filter(|dragged| *dragged < rows.len())
+			&& let Some(pointer) = ui.ctx().pointer_latest_pos()
+		{
+			ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+
+			// Index the function is moved to, among the other functions
+			let others: Vec<Rect> = rows
+				.iter()
+				.enumerate()
+				.filter(|(j, _)| *j != dragged)
+				.map(|(_, rect)| *rect)
+				.collect();
+			let target = others
+				.iter()
+				.filter(|rect| rect.center().y < pointer.y)
+				.count();
+
+			let y = match others.get(target) {
+				Some(rect) => rect.top(),
+				None => others
+					.last()
+					.map_or(rows[dragged].bottom(), |rect| rect.bottom()),
+			} - ui.spacing().item_spacing.y / 2.0;
+			ui.painter().hline(
+				rows[dragged].x_range(),
+				y,
+				Stroke::new(2.0, ui.visuals().selection.bg_fill),
+			);
+
+			if ui.input(|x| x.pointer.any_released()) {
+				let function = self.functions.remove(dragged);
+				self.functions.insert(target, function);
+				self.dragged = None;
+			}
+		} else {
+			self.dragged = None;
 		}
 
 		// Remove function if the user requests it
@@ -252,11 +799,44 @@ impl FunctionManager {
 			self.functions.remove(remove_i_unwrap);
 		}
 
+		// Duplicate function if the user requests it
+		if let Some(duplicate_i_unwrap) = duplicate_i {
+			let mut duplicate = self.functions[duplicate_i_unwrap].1.clone();
+			duplicate.settings_opened = false;
+			self.functions.insert(
+				duplicate_i_unwrap + 1,
+				(
+					create_id(random_u64().expect("unable to generate random id")),
+					duplicate,
+				),
+			);
+		}
+
+		if integrate_period.is_some() {
+			self.integrate_period = integrate_period;
+		}
+
+		entered
+			.iter()
+			.for_each(|func_str| self.remember(func_str));
+
 		let final_hash = self.get_hash();
 
 		initial_hash != final_hash
 	}
 
+	/// Adds `func_str` to the end of the history, moving it there if it was already in it
+	fn remember(&mut self, func_str: &str) {
+		self.history.retain(|previous| previous != func_str);
+		self.history.push(func_str.to_owned());
+		if self.history.len() > MAX_HISTORY {
+			self.history.remove(0);
+		}
+	}
+
+	/// Takes the period of the function the user asked to integrate over one period of
+	pub fn take_integrate_period(&mut self) -> Option<f64> { self.integrate_period.take() }
+
 	/// Create and push new empty function entry
 	pub fn push_empty(&mut self) {
 		self.functions.push((
@@ -265,14 +845,153 @@ impl FunctionManager {
 		));
 	}
 
+	/// Create and push new data series entry, with its points parsed from `csv`
+	pub fn push_data_series(&mut self, csv: &str) {
+		let mut entry = FunctionEntry::default();
+		entry.data_series = true;
+		entry.update_data(csv);
+		self.functions.push((
+			create_id(random_u64().expect("unable to generate random id")),
+			entry,
+		));
+	}
+
 	/// Detect if any functions are using integrals
 	pub fn any_using_integral(&self) -> bool {
-		self.functions.iter().any(|(_, func)| func.integral)
+		self.functions
+			.iter()
+			.any(|(_, func)| func.integral | func.antiderivative)
+	}
+
+	/// Detect if any functions are polar functions
+	pub fn any_polar(&self) -> bool { self.functions.iter().any(|(_, func)| func.polar) }
+
+	/// Inserts `text` at the cursor of the function that was last typed in (or the first function), such as from the on-screen keypad
+	pub fn insert_text(&mut self, ctx: &egui::Context, text: &str) {
+		self.edit_typing(ctx, |string, cursor| {
+			let i = string
+				.char_indices()
+				.nth(cursor)
+				.map_or(string.len(), |(i, _)| i);
+			string.insert_str(i, text);
+			cursor + text.chars().count()
+		});
+	}
+
+	/// Removes the character before the cursor of the function that was last typed in (or the first function)
+	pub fn delete_char(&mut self, ctx: &egui::Context) {
+		self.edit_typing(ctx, |string, cursor| {
+			if let Some((i, _)) = cursor
+				.checked_sub(1)
+				.and_then(|i| string.char_indices().nth(i))
+			{
+				string.remove(i);
+				return cursor - 1;
+			}
+			cursor
+		});
+	}
+
+	/// Edits the string of the function that was last typed in (or the first function) with `edit`, which is given the cursor and returns where it's moved to.
+	/// The text box keeps its focus, so it doesn't need to be tapped on again between keys.
+	fn edit_typing(&mut self, ctx: &egui::Context, edit: impl FnOnce(&mut String, usize) -> usize) {
+		let i = self
+			.functions
+			.iter()
+			.position(|(id, _)| Some(*id) == self.typing)
+			.unwrap_or(0);
+		let Some((te_id, function)) = self.functions.get_mut(i) else {
+			return;
+		};
+
+		let mut string = function.autocomplete.string.clone();
+		let mut state = TextEdit::load_state(ctx, *te_id).unwrap_or_default();
+		let cursor = state
+			.ccursor_range()
+			.map_or(string.chars().count(), |range| range.primary.index);
+
+		let cursor = edit(&mut string, cursor);
+		function.autocomplete.update_string(&string);
+
+		state.set_ccursor_range(Some(egui::text::CCursorRange::one(
+			egui::text::CCursor::new(cursor),
+		)));
+		TextEdit::store_state(ctx, *te_id, state);
+		ctx.memory_mut(|x| x.request_focus(*te_id));
+	}
+
+	/// Sets the colors functions are assigned (unless they're given one)
+	pub fn set_palette(&mut self, palette: Palette) { self.palette = palette; }
+
+	/// Sets the unit of the angles used by every function's trigonometric functions
+	pub fn set_angle_unit(&mut self, angle_unit: AngleUnit) {
+		self.functions
+			.iter_mut()
+			.for_each(|(_, func)| func.set_angle_unit(angle_unit));
+	}
+
+	/// Names of the parameters used by any function
+	pub fn parameter_names(&self) -> BTreeSet<String> {
+		self.functions
+			.iter()
+			.flat_map(|(_, func)| func.parameter_names().cloned())
+			.collect()
+	}
+
+	/// Sets the values of every function's parameters
+	pub fn set_parameters(&mut self, values: &BTreeMap<String, f64>) {
+		self.functions
+			.iter_mut()
+			.for_each(|(_, func)| func.set_parameters(values));
+	}
+
+	/// Bodies of the user functions defined by the entries by name, the first entry defining a name taking precedence
+	pub fn definition_sources(&self) -> BTreeMap<String, String> {
+		let mut sources = BTreeMap::new();
+		self.functions
+			.iter()
+			.filter_map(|(_, func)| func.definition())
+			.for_each(|(name, body)| {
+				sources.entry(name.clone()).or_insert_with(|| body.clone());
+			});
+		sources
+	}
+
+	/// Parses the text entered for every function (if it changed), which happens even while the entries aren't shown, returning
+	/// whether any of them changed
+	pub fn update_strings(&mut self) -> bool {
+		let mut changed = false;
+		for (_, func) in self.functions.iter_mut() {
+			let new_string = func.autocomplete.string.clone();
+			changed |= new_string != func.raw_func_str;
+			func.update_string(&new_string);
+		}
+		changed
+	}
+
+	/// Sets the user functions every function can call
+	pub fn set_definitions(&mut self, definitions: &Definitions) {
+		self.functions
+			.iter_mut()
+			.for_each(|(_, func)| func.set_definitions(definitions));
+	}
+
+	/// Finds the intersections of the `a`th and `b`th functions
+	pub fn intersections(&mut self, a: usize, b: usize, settings: &AppSettings) -> Vec<PlotPoint> {
+		if (a == b) | (a.max(b) >= self.functions.len()) {
+			return Vec::new();
+		}
+
+		let (left, right) = self.functions.split_at_mut(a.max(b));
+		left[a.min(b)].1.intersections(&mut right[0].1, settings)
 	}
 
 	#[inline]
 	pub fn len(&self) -> usize { self.functions.len() }
 
+	#[inline]
+	pub fn is_empty(&self) -> bool { self.functions.is_empty() }
+
 	#[inline]
 	pub fn get_entries_mut(&mut self) -> &mut Functions { &mut self.functions }
 
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, io::Read};
+
+/// Language the UI's text is shown in
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, Serialize, Deserialize)]
+pub enum Language {
+	#[default]
+	English,
+	Spanish,
+}
+
+impl Language {
+	/// Every language, in the order they're listed in
+	pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+	/// Code of the language, which its file in `assets/i18n` is named after
+	pub const fn code(&self) -> &'static str {
+		match self {
+			Language::English => "en",
+			Language::Spanish => "es",
+		}
+	}
+
+	/// JSON of the language's text, keyed by what it's for, which `build.rs` compresses into its own file for each language
+	fn source(&self) -> Result<String, String> {
+		let mut compressed: &[u8] = match self {
+			Language::English => include_bytes!(concat!(env!("OUT_DIR"), "/i18n_en.json.zst")),
+			Language::Spanish => include_bytes!(concat!(env!("OUT_DIR"), "/i18n_es.json.zst")),
+		};
+
+		let mut source = String::new();
+		ruzstd::StreamingDecoder::new(&mut compressed)
+			.map_err(|error| error.to_string())?
+			.read_to_string(&mut source)
+			.map_err(|error| error.to_string())?;
+		Ok(source)
+	}
+}
+
+/// Shown in the language itself, so it can be found without knowing the current one
+impl fmt::Display for Language {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Language::English => write!(f, "English"),
+			Language::Spanish => write!(f, "Español"),
+		}
+	}
+}
+
+/// Keys of the errors other errors are written inside of, which are matched first so those inside them aren't mistaken for the
+/// whole error
+const WRAPPING_ERRORS: [&str; 2] = ["error.at", "error.definition"];
+
+/// Text of the UI in a language, which falls back to English for anything that isn't translated
+pub struct Translations {
+	/// Language of `strings`
+	language: Language,
+
+	/// Text of the language
+	strings: BTreeMap<String, String>,
+
+	/// Text in English
+	fallback: BTreeMap<String, String>,
+}
+
+impl Translations {
+	/// Loads the text of `language`
+	pub fn new(language: Language) -> Translations {
+		let parse = |language: Language| -> BTreeMap<String, String> {
+			language
+				.source()
+				.and_then(|source| serde_json::from_str(&source).map_err(|error| error.to_string()))
+				.unwrap_or_else(|error| {
+					tracing::error!("Invalid translation for {}: {}", language, error);
+					BTreeMap::new()
+				})
+		};
+
+		Self {
+			language,
+			strings: match language {
+				Language::English => BTreeMap::new(),
+				_ => parse(language),
+			},
+			fallback: parse(Language::English),
+		}
+	}
+
+	/// Language the text is in
+	pub const fn language(&self) -> Language { self.language }
+
+	/// Text of `key`, which is given back if there's no text for it at all
+	pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+		self.strings
+			.get(key)
+			.or_else(|| self.fallback.get(key))
+			.map(|text| text.as_str())
+			.unwrap_or(key)
+	}
+
+	/// Text of `key`, with `{}` replaced by `value`
+	pub fn format(&self, key: &str, value: &str) -> String { self.get(key).replace("{}", value) }
+
+	/// Text of `key`, with each `{}` replaced by the next of `values`
+	pub fn format_all(&self, key: &str, values: &[impl AsRef<str>]) -> String {
+		let mut text = String::new();
+		for (i, part) in self.get(key).split("{}").enumerate() {
+			if i > 0 {
+				text.push_str(values.get(i - 1).map_or("", |value| value.as_ref()));
+			}
+			text.push_str(part);
+		}
+		text
+	}
+
+	/// `error` (written in English, such as by the parser) in the language, found by matching it against the English text of the
+	/// keys starting with `error.`. The values filled into it are translated the same way, and errors that don't match are given
+	/// back as they are.
+	pub fn error(&self, error: &str) -> String {
+		if self.language == Language::English {
+			return error.to_owned();
+		}
+
+		WRAPPING_ERRORS
+			.iter()
+			.filter_map(|key| self.fallback.get_key_value(*key))
+			.chain(self.fallback.iter().filter(|(key, _)| {
+				key.starts_with("error.") & !WRAPPING_ERRORS.contains(&key.as_str())
+			}))
+			.find_map(|(key, english)| Some((key, fill_ins(english, error)?)))
+			.map(|(key, values)| {
+				let values: Vec<String> =
+					values.into_iter().map(|value| self.error(value)).collect();
+				self.format_all(key, &values)
+			})
+			.unwrap_or_else(|| error.to_owned())
+	}
+
+	/// Keys the language has text for
+	pub fn keys(&self) -> impl Iterator<Item = &str> {
+		let strings = match self.language {
+			Language::English => &self.fallback,
+			_ => &self.strings,
+		};
+		strings.keys().map(|key| key.as_str())
+	}
+}
+
+/// Values that fill in each `{}` of `template` to make `text`, if it can be made from it
+fn fill_ins<'a>(template: &str, text: &'a str) -> Option<Vec<&'a str>> {
+	let mut parts = template.split("{}");
+	let mut rest = text.strip_prefix(parts.next()?)?;
+	let parts: Vec<&str> = parts.collect();
+
+	let mut values = Vec::with_capacity(parts.len());
+	for (i, part) in parts.iter().enumerate() {
+		// The last value goes up to the end of the text, the others up to the first time the text after them appears
+		let end = match i == (parts.len() - 1) {
+			true => rest.strip_suffix(part)?.len(),
+			false => rest.find(part)?,
+		};
+		values.push(&rest[..end]);
+		rest = &rest[(end + part.len())..];
+	}
+	rest.is_empty().then_some(values)
+}
@@ -0,0 +1,297 @@
+use egui_plot::PlotPoint;
+use std::collections::HashMap;
+
+/// Width (in pixels) of each cell of the grid F(x, y) is sampled on
+const CELL_SIZE: usize = 4;
+
+/// Maximum number of rows of cells, for plots much taller than they are wide
+const MAX_ROWS: usize = 1024;
+
+/// Number of cells ([columns, rows]) F(x, y) is sampled on over the plot between the corners `min` and `max`, which is `width` pixels wide
+fn plot_cells(min: [f64; 2], max: [f64; 2], width: usize) -> [usize; 2] {
+	let columns = (width / CELL_SIZE).max(1);
+	let rows = ((columns as f64) * (max[1] - min[1]) / (max[0] - min[0]))
+		.ceil()
+		.clamp(1.0, MAX_ROWS as f64) as usize;
+	[columns, rows]
+}
+
+/// Curve F(x, y) = 0 traced over the plot, cached until the plot's bounds or width change
+#[derive(Clone)]
+pub struct ImplicitPlot {
+	/// Bounds (minimum and maximum corners) and pixel width of the plot the curve was traced over
+	key: Option<([f64; 2], [f64; 2], usize)>,
+
+	/// Polylines making up the curve
+	pub curves: Vec<Vec<PlotPoint>>,
+}
+
+impl const Default for ImplicitPlot {
+	fn default() -> ImplicitPlot {
+		ImplicitPlot {
+			key: None,
+			curves: Vec::new(),
+		}
+	}
+}
+
+impl ImplicitPlot {
+	/// Retraces the curve F(x, y) = 0 if the plot's bounds (`min` and `max` corners) or `width` changed since it was last traced
+	pub fn update(
+		&mut self, f: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2], max: [f64; 2], width: usize,
+	) {
+		let key = Some((min, max, width));
+		if self.key == key {
+			return;
+		}
+		self.key = key;
+
+		self.curves = marching_squares(f, min, max, plot_cells(min, max, width));
+	}
+
+	/// Invalidates the traced curve
+	pub fn clear(&mut self) {
+		self.key = None;
+		self.curves.clear();
+	}
+}
+
+/// Contour lines F(x, y) = c of evenly spaced levels c traced over the plot, cached until the plot's bounds or width, or the number of levels change
+#[derive(Clone)]
+pub struct ContourPlot {
+	/// Bounds (minimum and maximum corners) and pixel width of the plot, and number of levels, the contour lines were traced with
+	key: Option<([f64; 2], [f64; 2], usize, usize)>,
+
+	/// Each level c, alongside the polylines making up its contour line
+	pub levels: Vec<(f64, Vec<Vec<PlotPoint>>)>,
+}
+
+impl const Default for ContourPlot {
+	fn default() -> ContourPlot {
+		ContourPlot {
+			key: None,
+			levels: Vec::new(),
+		}
+	}
+}
+
+impl ContourPlot {
+	/// Retraces the contour lines if the plot's bounds (`min` and `max` corners), `width`, or `level_count` changed since they were last traced
+	pub fn update(
+		&mut self, f: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2], max: [f64; 2], width: usize,
+		level_count: usize,
+	) {
+		let key = Some((min, max, width, level_count));
+		if self.key == key {
+			return;
+		}
+		self.key = key;
+
+		let grid = SampledGrid::new(f, min, max, plot_cells(min, max, width));
+		self.levels = grid
+			.levels(level_count)
+			.into_iter()
+			.map(|level| (level, grid.contour(level)))
+			.collect();
+	}
+
+	/// Invalidates the traced contour lines
+	pub fn clear(&mut self) {
+		self.key = None;
+		self.levels.clear();
+	}
+}
+
+/// Values of F(x, y) sampled on a grid over a rectangle, which curves F(x, y) = c are traced along
+pub struct SampledGrid {
+	/// Minimum corner of the rectangle
+	min: [f64; 2],
+
+	/// Width and height of each cell
+	step: [f64; 2],
+
+	/// Number of cells ([columns, rows])
+	cells: [usize; 2],
+
+	/// Values of F at the corners of the cells, row by row
+	values: Vec<f64>,
+}
+
+impl SampledGrid {
+	/// Samples F over the rectangle between the corners `min` and `max`, on a grid of `cells` ([columns, rows]) cells
+	pub fn new(
+		f: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2], max: [f64; 2], cells: [usize; 2],
+	) -> Self {
+		let [columns, rows] = cells;
+		let step = [
+			(max[0] - min[0]) / (columns as f64),
+			(max[1] - min[1]) / (rows as f64),
+		];
+
+		let mut grid = Self {
+			min,
+			step,
+			cells,
+			values: Vec::with_capacity((columns + 1) * (rows + 1)),
+		};
+		for j in 0..=rows {
+			for i in 0..=columns {
+				let (x, y) = grid.point(i, j);
+				grid.values.push(f(x, y));
+			}
+		}
+		grid
+	}
+
+	/// Coordinates of the `i`th column and `j`th row of grid points
+	fn point(&self, i: usize, j: usize) -> (f64, f64) {
+		(
+			self.min[0] + (self.step[0] * i as f64),
+			self.min[1] + (self.step[1] * j as f64),
+		)
+	}
+
+	/// Value of F at the `i`th column and `j`th row of grid points
+	fn value(&self, i: usize, j: usize) -> f64 { self.values[(j * (self.cells[0] + 1)) + i] }
+
+	/// Returns `count` levels evenly spaced between (and excluding) the smallest and largest values of F on the grid, or none if F isn't defined anywhere on it
+	pub fn levels(&self, count: usize) -> Vec<f64> {
+		let (smallest, largest) = self.values.iter().filter(|value| value.is_finite()).fold(
+			(f64::INFINITY, f64::NEG_INFINITY),
+			|(smallest, largest), value| (smallest.min(*value), largest.max(*value)),
+		);
+		if smallest > largest {
+			return Vec::new();
+		}
+
+		let spacing = (largest - smallest) / ((count + 1) as f64);
+		(1..=count)
+			.map(|k| smallest + (spacing * k as f64))
+			.collect()
+	}
+
+	/// Traces the curve F(x, y) = `level` using marching squares, linearly interpolating the curve across each cell's edges.
+	/// Returns the curve as polylines, joining the line segments of neighboring cells.
+	pub fn contour(&self, level: f64) -> Vec<Vec<PlotPoint>> {
+		let [columns, rows] = self.cells;
+		let value = |i: usize, j: usize| self.value(i, j) - level;
+
+		// Edges are identified by the grid point they start at, and whether they go right (even) or up (odd)
+		let horizontal = |i: usize, j: usize| 2 * ((j * (columns + 1)) + i);
+		let vertical = |i: usize, j: usize| horizontal(i, j) + 1;
+
+		let mut segments: Vec<(usize, usize)> = Vec::new();
+		for j in 0..rows {
+			for i in 0..columns {
+				let corners = [
+					value(i, j),
+					value(i + 1, j),
+					value(i + 1, j + 1),
+					value(i, j + 1),
+				];
+				if corners.iter().any(|corner| !corner.is_finite()) {
+					continue;
+				}
+
+				let (bottom, right, top, left) = (
+					horizontal(i, j),
+					vertical(i + 1, j),
+					horizontal(i, j + 1),
+					vertical(i, j),
+				);
+
+				// Which corners (bottom left, bottom right, top right, top left) are positive
+				let case = corners
+					.iter()
+					.enumerate()
+					.fold(0, |case, (k, corner)| case | (((*corner > 0.0) as u8) << k));
+
+				// Saddle points are resolved by the value in the center of the cell
+				let center_positive = (corners.iter().sum::<f64>() / 4.0) > 0.0;
+				match case {
+					1 | 14 => segments.push((left, bottom)),
+					2 | 13 => segments.push((bottom, right)),
+					3 | 12 => segments.push((left, right)),
+					4 | 11 => segments.push((right, top)),
+					6 | 9 => segments.push((bottom, top)),
+					7 | 8 => segments.push((left, top)),
+					5 | 10 => {
+						if (case == 5) == center_positive {
+							segments.extend([(bottom, right), (left, top)]);
+						} else {
+							segments.extend([(left, bottom), (right, top)]);
+						}
+					}
+					_ => {}
+				}
+			}
+		}
+
+		// Point along `edge` where F crosses `level`
+		let edge_point = |edge: usize| -> PlotPoint {
+			let start = edge / 2;
+			let (i, j) = (start % (columns + 1), start / (columns + 1));
+			let (end_i, end_j) = match edge % 2 {
+				0 => (i + 1, j),
+				_ => (i, j + 1),
+			};
+
+			let (start_value, end_value) = (value(i, j), value(end_i, end_j));
+			let t = start_value / (start_value - end_value);
+			let ((x1, y1), (x2, y2)) = (self.point(i, j), self.point(end_i, end_j));
+			PlotPoint::new(x1 + (t * (x2 - x1)), y1 + (t * (y2 - y1)))
+		};
+
+		// Join segments sharing an edge into polylines
+		let mut adjacent: HashMap<usize, Vec<usize>> = HashMap::new();
+		for (k, (a, b)) in segments.iter().enumerate() {
+			adjacent.entry(*a).or_default().push(k);
+			adjacent.entry(*b).or_default().push(k);
+		}
+
+		let mut used = vec![false; segments.len()];
+		let mut curves: Vec<Vec<PlotPoint>> = Vec::new();
+		for start in 0..segments.len() {
+			if used[start] {
+				continue;
+			}
+			used[start] = true;
+
+			// Follows the curve from `edge` until it ends (or loops back around)
+			let mut follow = |mut edge: usize| -> Vec<usize> {
+				let mut edges = Vec::new();
+				while let Some(&k) = adjacent[&edge].iter().find(|k| !used[**k]) {
+					used[k] = true;
+					let (a, b) = segments[k];
+					edge = if a == edge { b } else { a };
+					edges.push(edge);
+				}
+				edges
+			};
+
+			let (a, b) = segments[start];
+			let forwards = follow(b);
+			let backwards = follow(a);
+			curves.push(
+				backwards
+					.into_iter()
+					.rev()
+					.chain([a, b])
+					.chain(forwards)
+					.map(edge_point)
+					.collect(),
+			);
+		}
+
+		curves
+	}
+}
+
+/// Traces the curve F(x, y) = 0 over the rectangle between the corners `min` and `max` using marching squares.
+/// F is sampled on a grid of `cells` ([columns, rows]) cells, and the curve is linearly interpolated across each cell's edges.
+/// The function returns the curve as polylines, joining the line segments of neighboring cells.
+pub fn marching_squares(
+	f: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2], max: [f64; 2], cells: [usize; 2],
+) -> Vec<Vec<PlotPoint>> {
+	SampledGrid::new(f, min, max, cells).contour(0.0)
+}
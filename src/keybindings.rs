@@ -0,0 +1,95 @@
+use egui::Key;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Something the app does when a key is pressed, which the key can be changed for in the Settings window
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+pub enum Action {
+	/// Shows or hides the side panel
+	TogglePanel,
+
+	/// Adds a new function
+	AddFunction,
+
+	/// Opens or closes the Help window
+	ToggleHelp,
+
+	/// Switches between the dark and light themes
+	ToggleDarkMode,
+
+	/// Goes back to the default view
+	ResetView,
+
+	/// Fits the view to the visible functions
+	FitView,
+}
+
+impl Action {
+	/// Every action, in the order they're listed in
+	pub const ALL: [Action; 6] = [
+		Action::TogglePanel,
+		Action::AddFunction,
+		Action::ToggleHelp,
+		Action::ToggleDarkMode,
+		Action::ResetView,
+		Action::FitView,
+	];
+
+	/// Key the action is bound to unless it's changed
+	pub const fn default_key(&self) -> Key {
+		match self {
+			Action::TogglePanel => Key::H,
+			Action::AddFunction => Key::N,
+			Action::ToggleHelp => Key::F1,
+			Action::ToggleDarkMode => Key::D,
+			Action::ResetView => Key::R,
+			Action::FitView => Key::F,
+		}
+	}
+
+	/// Key of the action's name in the translations
+	pub const fn label(&self) -> &'static str {
+		match self {
+			Action::TogglePanel => "settings.keys.side_panel",
+			Action::AddFunction => "settings.keys.add_function",
+			Action::ToggleHelp => "settings.keys.help",
+			Action::ToggleDarkMode => "settings.keys.dark_mode",
+			Action::ResetView => "settings.keys.reset_view",
+			Action::FitView => "settings.keys.fit_view",
+		}
+	}
+}
+
+/// Keys the actions are bound to, those that haven't been changed are bound to their default key (so actions that are added later get theirs)
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Keybindings(BTreeMap<Action, Key>);
+
+impl Keybindings {
+	/// Every action bound to its default key
+	pub const fn new() -> Keybindings { Self(BTreeMap::new()) }
+
+	/// Key `action` is bound to
+	pub fn get(&self, action: Action) -> Key {
+		self.0.get(&action).copied().unwrap_or(action.default_key())
+	}
+
+	/// Binds `action` to `key`
+	pub fn set(&mut self, action: Action, key: Key) {
+		match key == action.default_key() {
+			true => self.0.remove(&action),
+			false => self.0.insert(action, key),
+		};
+	}
+
+	/// Actions that are bound to the same key as another action, only the first of which is done when it's pressed
+	pub fn conflicts(&self) -> BTreeSet<Action> {
+		Action::ALL
+			.into_iter()
+			.filter(|action| {
+				Action::ALL
+					.iter()
+					.any(|other| (other != action) && (self.get(*other) == self.get(*action)))
+			})
+			.collect()
+	}
+}
@@ -13,21 +13,51 @@
 extern crate static_assertions;
 
 mod consts;
+mod domain_coloring;
+mod export;
 mod function_entry;
 mod function_manager;
+mod i18n;
+mod implicit;
+mod keybindings;
 mod math_app;
 mod misc;
+mod number_format;
+mod ode;
+mod sweep;
+mod theme;
+mod typeset;
 mod unicode_helper;
+mod vector_field;
 mod widgets;
 
 pub use crate::{
-	function_entry::{FunctionEntry, Riemann},
+	domain_coloring::{domain_color, DomainColoring},
+	export::{
+		encode_png, report_html, utc_timestamp, ExportFormat, ExportImage, ExportShape,
+		ReportFunction,
+	},
+	function_entry::{
+		AreaMode, CurveStyle, Extremum, FunctionEntry, IntegralOverride, Riemann, RiemannRectangle,
+	},
+	i18n::{Language, Translations},
+	implicit::{marching_squares, ContourPlot, ImplicitPlot, SampledGrid},
+	keybindings::{Action, Keybindings},
 	math_app::AppSettings,
 	misc::{
-		hashed_storage_create, hashed_storage_read, newtons_method, option_vec_printer,
-		step_helper, EguiHelper, HashBytes,
+		adaptive_simpson, brent_method, closed_form, decode_fragment, detect_period,
+		encode_fragment, find_roots, hashed_storage_create, hashed_storage_read, improper_integral,
+		is_discontinuous, newtons_method, one_sided_limit, option_vec_printer, parse_csv_points,
+		parse_number, partial_sums, pi_grid_spacer, pi_label, query_flag, refine_root, romberg,
+		sequence, singular_integral, snap_bounds, step_helper, to_csv, trapezoid_points,
+		EguiHelper, HashBytes, Xorshift,
 	},
+	number_format::{NumberFormat, Precision},
+	ode::{ode_solution, rk4, slope_field, SlopeField},
+	sweep::ParameterSweep,
+	theme::{Palette, Theme},
 	unicode_helper::{to_chars_array, to_unicode_hash},
+	vector_field::{line_integrals, vector_field, VectorField},
 };
 
 cfg_if::cfg_if! {
@@ -1,24 +1,131 @@
 use crate::{
-	consts::{build, BUILD_INFO, COLORS, DEFAULT_INTEGRAL_NUM, DEFAULT_MAX_X, DEFAULT_MIN_X},
-	function_entry::Riemann,
+	consts::{
+		build, BUILD_INFO, CLOSED_FORM_TOLERANCE, COLORS, COMPARISON_COLOR, DEFAULT_INTEGRAL_NUM,
+		DEFAULT_INTEGRAL_TOLERANCE, DEFAULT_MAX_X, DEFAULT_MIN_X, DEFAULT_MONTE_CARLO_SAMPLES,
+		DEFAULT_PARAMETER, DEFAULT_ROOT_TOLERANCE, OVERESTIMATE_COLOR, UNDERESTIMATE_COLOR,
+	},
+	export::{
+		open_file, report_html, save_file, unix_time, utc_timestamp, ExportFormat, ExportImage,
+		ExportShape, ReportFunction,
+	},
+	function_entry::{AreaMode, Riemann},
 	function_manager::FunctionManager,
-	misc::option_vec_printer,
+	i18n::{Language, Translations},
+	keybindings::{Action, Keybindings},
+	misc::{
+		closed_form, decode_fragment, encode_fragment, option_vec_printer, parse_number,
+		pi_grid_spacer, pi_label, snap_bounds, step_helper, EguiHelper,
+	},
+	number_format::{NumberFormat, Precision},
+	sweep::ParameterSweep,
+	theme::{Palette, Theme},
 };
 use eframe::App;
 use egui::{
-	style::Margin, Button, CentralPanel, Color32, ComboBox, Context, DragValue, Frame, Key, Layout,
-	SidePanel, TopBottomPanel, Ui, Vec2, Window,
+	style::Margin, Button, CentralPanel, Color32, ComboBox, Context, DragValue, FontId, Frame,
+	Grid, Key, KeyboardShortcut, Layout, Modifiers, RichText, SidePanel, Slider, TextEdit,
+	TextStyle, TopBottomPanel, Ui, Vec2, Window,
 };
-use egui_plot::Plot;
+use egui_plot::{LineStyle, Plot, PlotBounds, PlotPoint, PlotUi, Text, VLine};
 
 use emath::{Align, Align2};
 use epaint::Rounding;
 use instant::Instant;
 use itertools::Itertools;
-use std::{io::Read, ops::BitXorAssign};
+use parsing::{AngleUnit, Definitions, CONSTANTS};
+use serde::{Deserialize, Serialize};
+use std::{
+	cell::RefCell,
+	cmp::Ordering,
+	collections::{BTreeMap, BTreeSet},
+	io::Read,
+	ops::{BitXorAssign, RangeInclusive},
+	rc::Rc,
+};
+
+/// Range of `ui_scale` and `font_scale`
+const SCALE_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+
+/// Amount Ctrl+= and Ctrl+- change the UI's scale by
+const SCALE_STEP: f32 = 0.1;
+
+/// Fraction of the plot's view the arrow keys move it by
+const PAN_STEP: f64 = 0.1;
+
+/// Factor + and - zoom the plot by
+const ZOOM_STEP: f32 = 1.25;
+
+/// Smallest height of buttons (and other widgets) on touchscreens, so they're large enough to tap
+const TOUCH_SIZE: f32 = 32.0;
+
+/// Width of the window (in points) below which the side panel becomes a sheet along the bottom
+const NARROW_WIDTH: f32 = 600.0;
+
+/// Longest time (in seconds) a parameter's animation moves along in a frame, so it doesn't jump after a frame that took long
+const MAX_SWEEP_DT: f32 = 0.1;
+
+/// Time (in seconds) the number of intervals takes to increase to the one that was set when it's animated
+const INTERVAL_ANIMATION_DURATION: f32 = 4.0;
+
+/// Frames per second of animations that are recorded, which each frame being exported is slower than
+const RECORD_FPS: f32 = 30.0;
+
+/// Size of the keys of the on-screen keypad
+const KEY_SIZE: Vec2 = Vec2::new(48.0, 36.0);
+
+/// Keys of the on-screen keypad, as their labels and the text they type
+const KEYPAD: [[(&str, &str); 7]; 4] = [
+	[
+		("7", "7"),
+		("8", "8"),
+		("9", "9"),
+		("/", "/"),
+		("(", "("),
+		(")", ")"),
+		("^", "^"),
+	],
+	[
+		("4", "4"),
+		("5", "5"),
+		("6", "6"),
+		("*", "*"),
+		("x", "x"),
+		("π", "pi"),
+		("sqrt", "sqrt("),
+	],
+	[
+		("1", "1"),
+		("2", "2"),
+		("3", "3"),
+		("-", "-"),
+		("sin", "sin("),
+		("cos", "cos("),
+		("tan", "tan("),
+	],
+	[
+		("0", "0"),
+		(".", "."),
+		(",", ","),
+		("+", "+"),
+		("ln", "ln("),
+		("e", "e"),
+		("abs", "abs("),
+	],
+];
+
+/// Name of the file sessions are saved as (and loaded from, outside of the web)
+const SESSION_FILE: &str = "session.json";
+
+/// Address share links open outside of the web, where the app is hosted
+#[cfg(not(target_arch = "wasm32"))]
+const SHARE_URL: &str = "https://titaniumtown.github.io/";
+
+/// Flag in the URL's query that opens the app as a read-only figure, such as in an iframe on a course page
+const EMBED_FLAG: &str = "embed";
 
 /// Stores current settings/state of [`MathApp`]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppSettings {
 	/// Stores the type of Rienmann sum that should be calculated
 	pub riemann_sum: Riemann,
@@ -35,20 +142,98 @@ pub struct AppSettings {
 	/// Maximum x bound of plot
 	pub max_x: f64,
 
+	/// Minimum y bound of plot
+	pub min_y: f64,
+
+	/// Maximum y bound of plot
+	pub max_y: f64,
+
 	/// Stores whether or not integral settings have changed
+	#[serde(skip)]
 	pub integral_changed: bool,
 
 	/// Number of rectangles used to calculate integral
 	pub integral_num: usize,
 
+	/// Error tolerance used when integrating via [`Riemann::Adaptive`]
+	pub integral_tolerance: f64,
+
+	/// Number of random samples used when integrating via [`Riemann::MonteCarlo`]
+	pub monte_carlo_samples: usize,
+
+	/// How the area of integrals are reported
+	pub area_mode: AreaMode,
+
 	/// Stores whether or not displaying extrema is enabled
 	pub do_extrema: bool,
 
 	/// Stores whether or not displaying roots is enabled
 	pub do_roots: bool,
 
+	/// Stores whether or not the curves under the mouse are traced, marking the nearest point of each
+	pub do_trace: bool,
+
+	/// If rectangles of Riemann sums are colored by whether they over or underestimate the area of their interval
+	pub estimate_colors: bool,
+
+	/// Sum the integral is also calculated with, so the two can be compared, if any
+	pub compare_sum: Option<Riemann>,
+
+	/// Tolerance roots are refined to
+	pub root_tolerance: f64,
+
+	/// Stores whether or not root settings have changed
+	#[serde(skip)]
+	pub roots_changed: bool,
+
+	/// Minimum θ that polar functions are drawn and integrated from
+	pub theta_min: f64,
+
+	/// Maximum θ that polar functions are drawn and integrated to
+	pub theta_max: f64,
+
+	/// Stores whether or not the θ range has changed
+	#[serde(skip)]
+	pub theta_changed: bool,
+
+	/// Unit of the angles trigonometric functions take and return
+	#[serde(with = "AngleUnitDef")]
+	pub angle_unit: AngleUnit,
+
+	/// If the x-axis is labeled in degrees (while using [`AngleUnit::Degrees`])
+	pub degree_ticks: bool,
+
+	/// If the x-axis is labeled in fractions of π, with its grid lines placed at them (while using [`AngleUnit::Radians`])
+	pub pi_ticks: bool,
+
+	/// If variables are only matched when written in the same case, so uppercase letters (such as X) are parameters
+	pub case_sensitive: bool,
+
 	/// Stores current plot pixel width
+	#[serde(skip)]
 	pub plot_width: usize,
+
+	/// How areas and other results are written, which is set from the preferences
+	#[serde(skip)]
+	pub number_format: NumberFormat,
+}
+
+impl AppSettings {
+	/// Returns whether or not either integral bound is infinite
+	pub fn is_improper(&self) -> bool {
+		self.integral_min_x.is_infinite() | self.integral_max_x.is_infinite()
+	}
+
+	/// Returns whether or not the integral's bounds are reversed (integrating from right to left)
+	pub fn is_reversed(&self) -> bool { self.integral_min_x > self.integral_max_x }
+
+	/// Returns the integral's bounds ordered from left to right
+	pub fn integral_bounds(&self) -> (f64, f64) {
+		match self.is_reversed() {
+			true => (self.integral_max_x, self.integral_min_x),
+			false => (self.integral_min_x, self.integral_max_x),
+		}
+	}
 }
 
 impl const Default for AppSettings {
@@ -60,16 +245,131 @@ impl const Default for AppSettings {
 			integral_max_x: DEFAULT_MAX_X,
 			min_x: 0.0,
 			max_x: 0.0,
+			min_y: 0.0,
+			max_y: 0.0,
 			integral_changed: true,
 			integral_num: DEFAULT_INTEGRAL_NUM,
+			integral_tolerance: DEFAULT_INTEGRAL_TOLERANCE,
+			monte_carlo_samples: DEFAULT_MONTE_CARLO_SAMPLES,
+			area_mode: AreaMode::Signed,
 			do_extrema: true,
 			do_roots: true,
+			do_trace: false,
+			estimate_colors: false,
+			compare_sum: None,
+			root_tolerance: DEFAULT_ROOT_TOLERANCE,
+			roots_changed: false,
+			theta_min: 0.0,
+			theta_max: std::f64::consts::TAU,
+			theta_changed: false,
+			angle_unit: AngleUnit::Radians,
+			degree_ticks: true,
+			pi_ticks: false,
+			case_sensitive: false,
 			plot_width: 0,
+			number_format: NumberFormat::default(),
+		}
+	}
+}
+
+/// Mirror of [`AngleUnit`] so it can be (de)serialized as part of [`AppSettings`]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "AngleUnit")]
+enum AngleUnitDef {
+	Radians,
+	Degrees,
+}
+
+/// Defaults of new workspaces, along with other preferences, which are set in the Settings window
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct Preferences {
+	/// Type of Riemann sum new workspaces start with
+	riemann_sum: Riemann,
+
+	/// Number of intervals new workspaces start with
+	integral_num: usize,
+
+	/// Minimum x and y of the view the plot is reset to
+	view_min: f64,
+
+	/// Maximum x and y of the view the plot is reset to
+	view_max: f64,
+
+	/// Colors of the app and the functions
+	theme: Theme,
+
+	/// How areas and other results are written
+	number_format: NumberFormat,
+
+	/// Keys that toggle the side panel, reset the plot's view, and so on
+	keys: Keybindings,
+
+	/// Size of everything, relative to the display's own scale
+	ui_scale: f32,
+
+	/// Size of text (including the plot's labels), relative to the default size
+	font_scale: f32,
+
+	/// Language the UI's text is shown in
+	language: Language,
+}
+
+impl const Default for Preferences {
+	fn default() -> Preferences {
+		Self {
+			riemann_sum: Riemann::default(),
+			integral_num: DEFAULT_INTEGRAL_NUM,
+			view_min: DEFAULT_MIN_X,
+			view_max: DEFAULT_MAX_X,
+			theme: Theme::default(),
+			number_format: NumberFormat::default(),
+			keys: Keybindings::new(),
+			ui_scale: 1.0,
+			font_scale: 1.0,
+			language: Language::English,
+		}
+	}
+}
+
+impl Preferences {
+	/// Settings new workspaces start with
+	fn settings(&self) -> AppSettings {
+		AppSettings {
+			riemann_sum: self.riemann_sum,
+			integral_num: self.integral_num,
+			number_format: self.number_format,
+			..AppSettings::default()
+		}
+	}
+
+	/// Scales the UI and its text by `ui_scale` and `font_scale`, with larger buttons if `touch` is true, if they aren't already
+	fn apply_style(&self, ctx: &Context, touch: bool) {
+		let pixels_per_point = ctx.native_pixels_per_point().unwrap_or(1.0) * self.ui_scale;
+		if ctx.pixels_per_point() != pixels_per_point {
+			ctx.set_pixels_per_point(pixels_per_point);
+		}
+
+		let text_styles: BTreeMap<TextStyle, FontId> = egui::Style::default()
+			.text_styles
+			.into_iter()
+			.map(|(style, font)| (style, FontId::new(font.size * self.font_scale, font.family)))
+			.collect();
+		let mut style = (*ctx.style()).clone();
+		style.text_styles = text_styles;
+		style.spacing.interact_size.y = match touch {
+			true => TOUCH_SIZE,
+			false => egui::style::Spacing::default().interact_size.y,
+		};
+		if *ctx.style() != style {
+			ctx.set_style(style);
 		}
 	}
 }
 
 /// Used to store the opened of windows/widgets
+#[derive(Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct Opened {
 	/// Help window
 	pub help: bool,
@@ -82,6 +382,24 @@ struct Opened {
 
 	/// Welcome introduction
 	pub welcome: bool,
+
+	/// Convergence plot of the integral
+	pub convergence: bool,
+
+	/// History of the areas calculated
+	pub area_history: bool,
+
+	/// Table of values of the functions
+	pub table: bool,
+
+	/// Numerical results (roots, extrema, and areas) of the functions
+	pub results: bool,
+
+	/// Settings window, where the preferences are set
+	pub settings: bool,
+
+	/// On-screen keypad for entering functions
+	pub keypad: bool,
 }
 
 impl const Default for Opened {
@@ -91,10 +409,165 @@ impl const Default for Opened {
 			info: false,
 			side_panel: true,
 			welcome: true,
+			convergence: false,
+			area_history: false,
+			table: false,
+			results: false,
+			settings: false,
+			keypad: false,
+		}
+	}
+}
+
+/// Color of annotations, which can be seen on both the dark and light themes (and the background of exported images)
+const ANNOTATION_COLOR: Color32 = Color32::from_rgb(255, 160, 40);
+
+/// Radius (in points) of the point drawn at a pinned annotation
+const ANNOTATION_RADIUS: f32 = 4.0;
+
+/// Text label placed on the plot, such as "local max here", which can be dragged to move it
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Annotation {
+	/// Position of the bottom left corner of the text
+	x: f64,
+	y: f64,
+
+	/// Text shown on the plot, nothing is shown for pinned annotations without any
+	text: String,
+
+	/// If a point is drawn at its position, marking it
+	pinned: bool,
+}
+
+impl Annotation {
+	/// Position on the plot
+	fn position(&self) -> PlotPoint { PlotPoint::new(self.x, self.y) }
+}
+
+/// Maximum number of areas kept in the history, the oldest are forgotten once there are more
+const MAX_AREA_HISTORY: usize = 1000;
+
+/// Area that was calculated, along with what it was calculated with, kept in the history of areas
+#[derive(PartialEq, Debug, Clone)]
+struct AreaRecord {
+	/// Function string of the function that was integrated
+	function: String,
+
+	/// Bounds of the integral (of θ for polar functions)
+	min_x: f64,
+	max_x: f64,
+
+	riemann_sum: Riemann,
+
+	/// Number of intervals the integral was split into
+	intervals: usize,
+
+	area: f64,
+}
+
+/// History of areas as CSV, with a column for each field of [`AreaRecord`]
+fn area_history_csv(history: &[AreaRecord]) -> String {
+	let mut csv = String::from("function,min_x,max_x,method,intervals,area\n");
+	for record in history {
+		// The function string is quoted, as it can contain commas
+		csv.push_str(&format!(
+			"\"{}\",{},{},{},{},{}\n",
+			record.function.replace('"', "\"\""),
+			record.min_x,
+			record.max_x,
+			record.riemann_sum,
+			record.intervals,
+			record.area
+		));
+	}
+	csv
+}
+
+/// `value` written in `format` (in the language of `i18n`), along with the simple closed form (such as π/2) it's close to, if it's
+/// close to one
+fn format_result(format: &NumberFormat, value: f64, i18n: &Translations) -> String {
+	match closed_form(value, CLOSED_FORM_TOLERANCE) {
+		Some(form) => format!("{} ({}?)", format.format(value, i18n), form),
+		None => format.format(value, i18n),
+	}
+}
+
+/// Number of intervals increasing from 1 to the number that was set, showing how the Riemann sum converges
+#[derive(Debug, Copy, Clone)]
+struct IntervalAnimation {
+	/// Number of intervals it ends at, which is set again once it's done
+	target: usize,
+
+	/// How far along it is, from 0 to 1
+	progress: f32,
+
+	/// If each frame is exported as an image, which are a fixed time apart (rather than the time the frames took)
+	record: bool,
+
+	/// Number of frames that have been exported
+	frame: usize,
+}
+
+impl IntervalAnimation {
+	/// Number of intervals at this point of the animation, which grows exponentially so that the small numbers (where the sum
+	/// changes the most) aren't passed by too quickly
+	fn intervals(&self) -> usize {
+		((self.target as f32).powf(self.progress.min(1.0)).round() as usize)
+			.clamp(1, self.target.max(1))
+	}
+}
+
+/// Independent set of functions and settings, which is switched to with its tab
+#[derive(Serialize, Deserialize)]
+struct Workspace<F> {
+	/// Name shown on its tab
+	name: String,
+
+	/// Settings, including the plot's view and the integral's bounds
+	settings: AppSettings,
+
+	/// Values of the parameters set with sliders
+	parameters: BTreeMap<String, f64>,
+
+	/// The functions, either borrowed from [`MathApp`] when saving or owned when loading
+	functions: F,
+
+	/// Text labels and pinned points on the plot, which are missing from sessions saved before they were added
+	#[serde(default)]
+	annotations: Vec<Annotation>,
+}
+
+impl Workspace<FunctionManager> {
+	/// Creates a workspace named `name` with the default functions, and the settings new workspaces start with
+	fn new(name: String, preferences: &Preferences) -> Self {
+		Self {
+			name,
+			settings: preferences.settings(),
+			parameters: BTreeMap::new(),
+			functions: FunctionManager::default(),
+			annotations: Vec::new(),
 		}
 	}
 }
 
+/// Everything saved to (and loaded from) a session file, so a prepared set of functions can be reused
+#[derive(Serialize, Deserialize)]
+struct Session<F> {
+	/// Which windows and panels are open
+	opened: Opened,
+
+	/// Defaults and other preferences, which are missing from sessions saved before they were added
+	#[serde(default)]
+	preferences: Preferences,
+
+	/// Index of the workspace that's shown
+	workspace: usize,
+
+	/// Every workspace, in the order of their tabs
+	workspaces: Vec<Workspace<F>>,
+}
+
 /// The actual application
 pub struct MathApp {
 	/// Stores vector of functions
@@ -108,6 +581,143 @@ pub struct MathApp {
 
 	/// Stores settings (pretty self-explanatory)
 	settings: AppSettings,
+
+	/// Pair of functions to find the intersections of
+	intersections: Option<(usize, usize)>,
+
+	/// Intersections of the pair of functions, `None` if they need to be recalculated
+	intersection_data: Option<Vec<PlotPoint>>,
+
+	/// Function to solve f(x) = `solve_target` for
+	solve: Option<usize>,
+
+	/// Value `c` to solve f(x) = c for
+	solve_target: f64,
+
+	/// Solutions of f(x) = `solve_target`, `None` if they need to be recalculated
+	solve_data: Option<Vec<PlotPoint>>,
+
+	/// Values of the parameters used by functions (such as `a` in `a*sin(x)`), set with sliders
+	parameters: BTreeMap<String, f64>,
+
+	/// Range and speed each parameter is animated at, along with whether it's playing
+	sweeps: BTreeMap<String, ParameterSweep>,
+
+	/// Bodies of the user functions defined by entries (such as `f(x) = x^2 + 1`), by name
+	definition_sources: BTreeMap<String, String>,
+
+	/// User functions parsed from `definition_sources`, which every entry can call
+	definitions: Definitions,
+
+	/// Integral bound the mouse was over last frame, the plot isn't dragged while over one so that the bound is dragged instead
+	hovered_bound: Option<IntegralBound>,
+
+	/// Integral bound being dragged on the plot
+	dragged_bound: Option<IntegralBound>,
+
+	/// Text labels and pinned points on the plot
+	annotations: Vec<Annotation>,
+
+	/// Annotation the mouse was over last frame, the plot isn't dragged while over one so that the annotation is dragged instead
+	hovered_annotation: Option<usize>,
+
+	/// Annotation being dragged on the plot
+	dragged_annotation: Option<usize>,
+
+	/// Areas calculated this session, oldest first, which are listed in the Area History window
+	area_history: Vec<AreaRecord>,
+
+	/// Function whose roots or extrema the integral's bounds are snapped to
+	snap_function: usize,
+
+	/// Change to the plot's view that was asked for, which is made the next time the plot is shown
+	view_request: Option<ViewRequest>,
+
+	/// If a unit on the x-axis is kept as long as a unit on the y-axis
+	lock_aspect: bool,
+
+	/// Format the plot is exported as the next time it's shown
+	export_request: Option<ExportFormat>,
+
+	/// Resolution of exported images, as a multiple of the size of the plot on screen
+	export_scale: f32,
+
+	/// Name (without its extension) of the image exported the next time the plot is shown, `plot` if `None`
+	export_name: Option<String>,
+
+	/// Number of intervals being animated, if it is
+	interval_animation: Option<IntervalAnimation>,
+
+	/// If the frames of animations of the number of intervals are exported
+	record_animation: bool,
+
+	/// First and last values of x in the table of values
+	table_range: (f64, f64),
+
+	/// Distance between the values of x in the table of values
+	table_step: f64,
+
+	/// Functions shown in the table of values
+	table_functions: BTreeSet<usize>,
+
+	/// Defaults and other preferences set in the Settings window
+	preferences: Preferences,
+
+	/// Text of the UI in the language that's chosen
+	i18n: Translations,
+
+	/// Every workspace, the one shown has its functions, settings, and parameters moved into [`MathApp`] while it's shown
+	workspaces: Vec<Workspace<FunctionManager>>,
+
+	/// Index of the workspace that's shown
+	workspace: usize,
+
+	/// Contents of a session file that was opened, which is loaded the next time the app is updated
+	opened_session: Rc<RefCell<Option<Vec<u8>>>>,
+
+	/// Reason the last session file couldn't be loaded
+	session_error: Option<String>,
+
+	/// If a touchscreen has been used, which makes buttons larger
+	touch: bool,
+
+	/// If the app was opened with [`EMBED_FLAG`], in which case only the plot of the functions in its link is shown (which can be
+	/// moved around, but not edited) and its state isn't saved
+	embedded: bool,
+
+	/// When the app's state was last saved to local storage
+	#[cfg(target_arch = "wasm32")]
+	last_saved: Instant,
+
+	/// App's state as it was last saved to local storage, so it's only written again once it's changed
+	#[cfg(target_arch = "wasm32")]
+	saved_state: String,
+}
+
+/// Change to the plot's view, made with the buttons in the top bar (or their keys) and the side panel
+#[derive(PartialEq, Clone, Copy)]
+enum ViewRequest {
+	/// Go back to the default window around the origin
+	Reset,
+
+	/// Frame every visible function
+	Fit,
+
+	/// Show the given range of each axis
+	Set(PlotBounds),
+
+	/// Move the view by the given fraction of its width and height
+	Pan(f64, f64),
+
+	/// Zoom in by the given factor around the center of the view, or out if it's less than 1
+	Zoom(f32),
+}
+
+/// Bound of the integral, drawn on the plot as a vertical line that can be dragged
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum IntegralBound {
+	Min,
+	Max,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -124,7 +734,75 @@ fn get_localstorage() -> web_sys::Storage {
 #[cfg(target_arch = "wasm32")]
 const DATA_NAME: &str = "YTBN-DECOMPRESSED";
 #[cfg(target_arch = "wasm32")]
-const FUNC_NAME: &str = "YTBN-FUNCTIONS";
+const STATE_NAME: &str = "YTBN-STATE";
+
+/// Shortest time between saving the app's state to local storage
+#[cfg(target_arch = "wasm32")]
+const SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Draws a grid of circles around the origin and rays from it, used alongside polar functions
+fn polar_grid(plot_ui: &mut PlotUi) {
+	/// Number of rays, evenly spaced around the origin
+	const RAYS: usize = 12;
+
+	/// Number of points each circle is drawn with
+	const CIRCLE_POINTS: usize = 128;
+
+	let bounds = plot_ui.plot_bounds();
+	let (min, max) = (bounds.min(), bounds.max());
+	let color = Color32::from_gray(60);
+
+	// Only the circles that pass through the plot are drawn
+	let nearest = (0.0_f64.clamp(min[0], max[0])).hypot(0.0_f64.clamp(min[1], max[1]));
+	let farthest = (min[0].abs().max(max[0].abs())).hypot(min[1].abs().max(max[1].abs()));
+
+	// Space the circles 1, 2, or 5 times a power of 10 apart, aiming for about 10 across the plot
+	let target = (max[0] - min[0]) / 10.0;
+	let magnitude = 10.0_f64.powf(target.log10().floor());
+	let spacing = [1.0, 2.0, 5.0, 10.0]
+		.into_iter()
+		.map(|multiple| multiple * magnitude)
+		.find(|spacing| *spacing >= target)
+		.unwrap_or(10.0 * magnitude);
+
+	((nearest / spacing).floor() as usize..=(farthest / spacing).ceil() as usize)
+		.filter(|&i| i > 0)
+		.for_each(|i| {
+			let radius = (i as f64) * spacing;
+			plot_ui.line(
+				(0..=CIRCLE_POINTS)
+					.map(|j| (j as f64) * std::f64::consts::TAU / (CIRCLE_POINTS as f64))
+					.map(|theta| PlotPoint::new(radius * theta.cos(), radius * theta.sin()))
+					.collect::<Vec<PlotPoint>>()
+					.to_line()
+					.color(color),
+			);
+		});
+
+	(0..RAYS).for_each(|i| {
+		let theta = (i as f64) * std::f64::consts::TAU / (RAYS as f64);
+		plot_ui.line(
+			vec![
+				PlotPoint::new(0.0, 0.0),
+				PlotPoint::new(farthest * theta.cos(), farthest * theta.sin()),
+			]
+			.to_line()
+			.color(color),
+		);
+	});
+}
+
+/// ComboBox for selecting one of `len` functions, storing the selected function's index in `i`
+fn function_selector(ui: &mut Ui, i18n: &Translations, id: &str, i: &mut usize, len: usize) {
+	*i = (*i).min(len.saturating_sub(1));
+	ComboBox::from_id_source(id)
+		.selected_text(i18n.format("function.number", &i.to_string()))
+		.show_ui(ui, |ui| {
+			(0..len).for_each(|j| {
+				ui.selectable_value(i, j, i18n.format("function.number", &j.to_string()));
+			});
+		});
+}
 
 impl MathApp {
 	#[allow(dead_code)] // This is used lol
@@ -158,26 +836,6 @@ impl MathApp {
 						None
 					}
 				}
-
-				fn load_functions() -> Option<FunctionManager> {
-					let data = get_localstorage().get_item(FUNC_NAME).ok()??;
-					if crate::misc::HASH_LENGTH >= data.len() {
-						return None;
-					}
-
-					// TODO: stabilize FunctionManager serialize so it can persist across builds
-					let (commit, func_data) = crate::misc::hashed_storage_read(&data)?;
-
-
-					if commit == unsafe { std::mem::transmute::<&str, &[u8]>(build::SHORT_COMMIT) } {
-						tracing::info!("Reading previous function data");
-						let function_manager: FunctionManager = bincode::deserialize(&func_data).ok()?;
-						return Some(function_manager);
-					} else {
-						None
-					}
-				}
-
 			}
 		}
 
@@ -234,198 +892,2115 @@ impl MathApp {
 
 		tracing::info!("Initialized! Took: {:?}", start.elapsed());
 
-		Self {
-			#[cfg(target_arch = "wasm32")]
-			functions: load_functions().unwrap_or_default(),
-
-			#[cfg(not(target_arch = "wasm32"))]
+		let i18n = Translations::new(Language::English);
+		#[allow(unused_mut)]
+		let mut app = Self {
 			functions: FunctionManager::default(),
-
 			last_info: (None, None),
 			opened: Opened::default(),
 			settings: AppSettings::default(),
-		}
-	}
+			intersections: None,
+			intersection_data: None,
+			solve: None,
+			solve_target: 0.0,
+			solve_data: None,
+			parameters: BTreeMap::new(),
+			sweeps: BTreeMap::new(),
+			definition_sources: BTreeMap::new(),
+			definitions: Definitions::EMPTY,
+			hovered_bound: None,
+			dragged_bound: None,
+			annotations: Vec::new(),
+			hovered_annotation: None,
+			dragged_annotation: None,
+			area_history: Vec::new(),
+			snap_function: 0,
+			view_request: None,
+			lock_aspect: true,
+			export_request: None,
+			export_scale: 2.0,
+			export_name: None,
+			interval_animation: None,
+			record_animation: false,
+			table_range: (-5.0, 5.0),
+			table_step: 1.0,
+			table_functions: BTreeSet::from([0]),
+			preferences: Preferences::default(),
+			workspaces: vec![Workspace::new(
+				i18n.format("workspace.name", "1"),
+				&Preferences::default(),
+			)],
+			i18n,
+			workspace: 0,
+			opened_session: Rc::new(RefCell::new(None)),
+			session_error: None,
+			touch: false,
+			embedded: false,
 
-	/// Creates SidePanel which contains configuration options
-	fn side_panel(&mut self, ctx: &Context) {
-		// Side Panel which contains vital options to the operation of the application
-		// (such as adding functions and other options)
-		SidePanel::left("side_panel")
-			.resizable(false)
-			.show(ctx, |ui| {
-				let any_using_integral = self.functions.any_using_integral();
-				let prev_sum = self.settings.riemann_sum;
-				// ComboBox for selecting what Riemann sum type to use
-				ui.add_enabled_ui(any_using_integral, |ui| {
-					let spacing_mut = ui.spacing_mut();
+			#[cfg(target_arch = "wasm32")]
+			last_saved: Instant::now(),
 
-					spacing_mut.item_spacing.x = 1.0;
-					spacing_mut.interact_size *= 0.5;
-					ComboBox::from_label("Riemann Sum")
-						.selected_text(self.settings.riemann_sum.to_string())
-						.show_ui(ui, |ui| {
-							ui.selectable_value(
-								&mut self.settings.riemann_sum,
-								Riemann::Left,
-								"Left",
-							);
-							ui.selectable_value(
-								&mut self.settings.riemann_sum,
-								Riemann::Middle,
-								"Middle",
-							);
-							ui.selectable_value(
-								&mut self.settings.riemann_sum,
-								Riemann::Right,
-								"Right",
-							);
-						});
+			#[cfg(target_arch = "wasm32")]
+			saved_state: String::new(),
+		};
 
-					let riemann_changed = prev_sum != self.settings.riemann_sum;
+		#[cfg(target_arch = "wasm32")]
+		{
+			app.embedded = get_window()
+				.location()
+				.search()
+				.is_ok_and(|search| crate::misc::query_flag(&search, EMBED_FLAG));
+		}
 
-					let min_x_old = self.settings.integral_min_x;
-					let max_x_old = self.settings.integral_max_x;
+		// Restore the state saved before the page was last closed, the defaults are used if it can't be read. Embedded figures
+		// only show the functions in their link instead.
+		#[cfg(target_arch = "wasm32")]
+		if !app.embedded
+			&& let Some(state) = get_localstorage().get_item(STATE_NAME).ok().flatten()
+		{
+			tracing::info!("Reading previous state");
+			match app.load_session(state.as_bytes()) {
+				Ok(()) => app.saved_state = state,
+				Err(error) => tracing::error!("Failed to read previous state: {}", error),
+			}
+		}
 
-					let (min_x_changed, max_x_changed) = ui
-						.horizontal(|ui: &mut Ui| {
-							// let spacing_mut = ui.spacing_mut();
+		// The state in a share link's fragment is loaded over the one that was saved
+		#[cfg(target_arch = "wasm32")]
+		if let Some(hash) = get_window()
+			.location()
+			.hash()
+			.ok()
+			.filter(|hash| hash.len() > 1)
+		{
+			match decode_fragment(&hash[1..]).map(|state| app.load_session(&state)) {
+				Some(Ok(())) => tracing::info!("Loaded shared state"),
+				_ => tracing::error!("Failed to read shared state"),
+			}
 
-							// spacing_mut.item_spacing = Vec2::new(1.0, 0.0);
-							// spacing_mut.interact_size *= 0.5;
+			// Removed so reloading the page doesn't go back to the shared state, which embedded figures keep showing
+			if !app.embedded {
+				let _ = get_window().location().set_hash("");
+			}
+		}
 
-							ui.label("Integral: [");
-							let min_x_changed = ui
-								.add(DragValue::new(&mut self.settings.integral_min_x))
-								.changed();
-							ui.label(",");
-							let max_x_changed = ui
-								.add(DragValue::new(&mut self.settings.integral_max_x))
-								.changed();
-							ui.label("]");
-							(min_x_changed, max_x_changed)
-						})
-						.inner;
-
-					// Checks integral bounds, and if they are invalid, fix them
-					if self.settings.integral_min_x >= self.settings.integral_max_x {
-						if max_x_changed {
-							self.settings.integral_max_x = max_x_old;
-						} else if min_x_changed {
-							self.settings.integral_min_x = min_x_old;
-						} else {
-							// No clue how this would happen, but just in case
-							self.settings.integral_min_x = DEFAULT_MIN_X;
-							self.settings.integral_max_x = DEFAULT_MAX_X;
-						}
-					}
+		// Embedded figures don't have any panels or windows
+		if app.embedded {
+			app.opened = Opened {
+				side_panel: false,
+				welcome: false,
+				..Opened::default()
+			};
+		}
 
-					// Number of Rectangles for Riemann sum
-					let integral_num_changed = ui
-						.horizontal(|ui| {
-							let spacing_mut = ui.spacing_mut();
+		cc.egui_ctx.set_visuals(app.preferences.theme.visuals());
 
-							spacing_mut.item_spacing.x = 1.5;
-							ui.label("Interval:");
-							ui.add(DragValue::new(&mut self.settings.integral_num))
-								.changed()
-						})
-						.inner;
+		// Ctrl+= and Ctrl+- change the scale in the Settings window instead
+		cc.egui_ctx
+			.options_mut(|options| options.zoom_with_keyboard = false);
+		app
+	}
 
-					if integral_num_changed {
-						self.settings.integral_num = self.settings.integral_num.clamp(0, 500000);
-					}
+	/// Current workspaces and open windows, as they're saved
+	fn session(&self) -> Session<&FunctionManager> {
+		Session {
+			opened: self.opened,
+			preferences: self.preferences.clone(),
+			workspace: self.workspace,
+			workspaces: self
+				.workspaces
+				.iter()
+				.enumerate()
+				.map(|(i, workspace)| match i == self.workspace {
+					true => Workspace {
+						name: workspace.name.clone(),
+						settings: self.shown_settings(),
+						parameters: self.parameters.clone(),
+						functions: &self.functions,
+						annotations: self.annotations.clone(),
+					},
+					false => Workspace {
+						name: workspace.name.clone(),
+						settings: workspace.settings,
+						parameters: workspace.parameters.clone(),
+						functions: &workspace.functions,
+						annotations: workspace.annotations.clone(),
+					},
+				})
+				.collect(),
+		}
+	}
 
-					self.settings.integral_changed = any_using_integral
-						&& (max_x_changed | min_x_changed | integral_num_changed | riemann_changed);
-				});
+	/// Settings being shown, with the number of intervals that was set rather than the one it's at while it's animated
+	fn shown_settings(&self) -> AppSettings {
+		match self.interval_animation {
+			Some(animation) => AppSettings {
+				integral_num: animation.target,
+				..self.settings
+			},
+			None => self.settings,
+		}
+	}
 
-				ui.horizontal(|ui| {
-					self.settings.do_extrema.bitxor_assign(
-						ui.add(Button::new("Extrema"))
-							.on_hover_text(match self.settings.do_extrema {
-								true => "Disable Displaying Extrema",
-								false => "Display Extrema",
-							})
-							.clicked(),
-					);
+	/// Moves the functions, settings, parameters, and annotations being shown back into their workspace
+	fn store_workspace(&mut self) {
+		let settings = self.shown_settings();
+		let workspace = &mut self.workspaces[self.workspace];
+		workspace.settings = settings;
+		workspace.parameters = std::mem::take(&mut self.parameters);
+		workspace.functions = std::mem::take(&mut self.functions);
+		workspace.annotations = std::mem::take(&mut self.annotations);
+	}
 
-					self.settings.do_roots.bitxor_assign(
-						ui.add(Button::new("Roots"))
-							.on_hover_text(match self.settings.do_roots {
-								true => "Disable Displaying Roots",
-								false => "Display Roots",
-							})
-							.clicked(),
-					);
-				});
+	/// Shows the workspace `i`, moving its functions, settings, parameters, and annotations out of it (replacing those being shown, which should be stored first)
+	fn show_workspace(&mut self, i: usize) {
+		let workspace = &mut self.workspaces[i];
+		self.settings = AppSettings {
+			integral_changed: true,
+			plot_width: self.settings.plot_width,
+			..workspace.settings
+		};
+		self.parameters = std::mem::take(&mut workspace.parameters);
+		self.functions = std::mem::take(&mut workspace.functions);
+		self.annotations = std::mem::take(&mut workspace.annotations);
+		self.hovered_annotation = None;
+		self.dragged_annotation = None;
+		self.sweeps
+			.values_mut()
+			.for_each(|sweep| sweep.playing = false);
+		self.interval_animation = None;
+		self.workspace = i;
+
+		// The workspace's view is shown again (if it was ever shown)
+		let bounds = PlotBounds::from_min_max(
+			[self.settings.min_x, self.settings.min_y],
+			[self.settings.max_x, self.settings.max_y],
+		);
+		self.view_request = match bounds.is_valid() {
+			true => Some(ViewRequest::Set(bounds)),
+			false => Some(ViewRequest::Reset),
+		};
 
-				if self.functions.display_entries(ui) {
-					#[cfg(target_arch = "wasm32")]
-					{
-						tracing::info!("Saving function data");
-						use crate::misc::{hashed_storage_create, HashBytes};
-						let hash: HashBytes =
-							unsafe { std::mem::transmute::<&str, HashBytes>(build::SHORT_COMMIT) };
-						let saved_data = hashed_storage_create(
-							hash,
-							&bincode::serialize(&self.functions)
-								.expect("unable to deserialize functions"),
-						);
-						// tracing::info!("Bytes: {}", saved_data.len());
-						get_localstorage()
-							.set_item(FUNC_NAME, &saved_data)
-							.expect("failed to set local function storage");
-					}
-				}
+		self.clear_results();
+	}
 
-				// Only render if there's enough space
-				if ui.available_height() > crate::consts::FONT_SIZE {
-					ui.with_layout(Layout::bottom_up(Align::Min), |ui| {
-						// Contents put in reverse order from bottom to top due to the 'buttom_up' layout
+	/// Removes the workspace `i` (as long as it isn't the only one), if it was shown the one after it is shown instead
+	fn close_workspace(&mut self, i: usize) {
+		if self.workspaces.len() < 2 {
+			return;
+		}
 
-						// Hyperlink to project's github
-						ui.hyperlink_to(
-							"I'm Open Source!",
-							"https://github.com/Titaniumtown/YTBN-Graphing-Software",
-						);
-					});
-				}
-			});
+		self.workspaces.remove(i);
+		match i.cmp(&self.workspace) {
+			Ordering::Less => self.workspace -= 1,
+			Ordering::Equal => self.show_workspace(i.min(self.workspaces.len() - 1)),
+			Ordering::Greater => {}
+		}
 	}
-}
 
-impl App for MathApp {
-	/// Called each time the UI needs repainting.
-	fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
-		// start timer
-		let start = if self.opened.info {
-			Some(instant::Instant::now())
-		} else {
-			// if disabled, clear the stored formatted time
-			self.last_info.1 = None;
+	/// Saves the workspaces and open windows as a JSON session file
+	fn save_session(&self) {
+		match serde_json::to_vec_pretty(&self.session()) {
+			Ok(bytes) => save_file(SESSION_FILE, "application/json", &bytes),
+			Err(error) => tracing::error!("Failed to save session: {}", error),
+		}
+	}
 
-			None
+	/// Link to the app that opens with the current workspaces, which are stored (compressed) in its fragment. If `embedded`, the
+	/// link opens them as a read-only figure.
+	fn share_link(&self, embedded: bool) -> Option<String> {
+		let state = serde_json::to_vec(&self.session()).ok()?;
+
+		#[cfg(target_arch = "wasm32")]
+		let url = {
+			let location = get_window().location();
+			format!("{}{}", location.origin().ok()?, location.pathname().ok()?)
 		};
 
-		// If keyboard input isn't being grabbed, check for key combos
-		if !ctx.wants_keyboard_input() {
-			// If `H` key is pressed, toggle Side Panel
-			self.opened
-				.side_panel
-				.bitxor_assign(ctx.input_mut(|x| x.consume_key(egui::Modifiers::NONE, Key::H)));
+		#[cfg(not(target_arch = "wasm32"))]
+		let url = SHARE_URL;
+
+		let query = match embedded {
+			true => format!("?{}=1", EMBED_FLAG),
+			false => String::new(),
+		};
+		Some(format!("{}{}#{}", url, query, encode_fragment(&state)))
+	}
+
+	/// Replaces the workspaces and open windows with those of the JSON session file `bytes`
+	fn load_session(&mut self, bytes: &[u8]) -> Result<(), serde_json::Error> {
+		let session: Session<FunctionManager> = serde_json::from_slice(bytes)?;
+		if session.workspaces.is_empty() {
+			return Err(serde::de::Error::invalid_length(
+				0,
+				&"at least one workspace",
+			));
 		}
 
-		// Creates Top bar that contains some general options
-		TopBottomPanel::top("top_bar").show(ctx, |ui| {
-			ui.horizontal(|ui| {
-				// Button in top bar to toggle showing the side panel
+		// Every workspace needs a function, which the side panel's function selectors (among others) assume
+		if session
+			.workspaces
+			.iter()
+			.any(|workspace| workspace.functions.is_empty())
+		{
+			return Err(serde::de::Error::invalid_length(
+				0,
+				&"at least one function in every workspace",
+			));
+		}
+
+		self.opened = session.opened;
+		self.preferences = session.preferences;
+		self.workspaces = session.workspaces;
+		self.show_workspace(session.workspace.min(self.workspaces.len() - 1));
+		Ok(())
+	}
+
+	/// Goes back to the single workspace the app starts with
+	fn reset(&mut self) {
+		self.opened = Opened {
+			welcome: false,
+			..Opened::default()
+		};
+		self.workspaces = vec![Workspace::new(
+			self.i18n.format("workspace.name", "1"),
+			&self.preferences,
+		)];
+		self.show_workspace(0);
+	}
+
+	/// Forgets the intersections and solutions found, which may belong to functions that were replaced
+	fn clear_results(&mut self) {
+		self.intersections = None;
+		self.intersection_data = None;
+		self.solve = None;
+		self.solve_data = None;
+		self.session_error = None;
+	}
+
+	/// Saves the app's state to local storage (if it's changed since it was last saved), so it's restored when the page is reloaded
+	#[cfg(target_arch = "wasm32")]
+	fn save_state(&mut self) {
+		let state = match serde_json::to_string(&self.session()) {
+			Ok(state) => state,
+			Err(error) => {
+				tracing::error!("Failed to save state: {}", error);
+				return;
+			}
+		};
+
+		if state != self.saved_state {
+			tracing::info!("Saving state");
+			if get_localstorage().set_item(STATE_NAME, &state).is_err() {
+				tracing::error!("Failed to set local state storage");
+			}
+			self.saved_state = state;
+		}
+	}
+
+	/// Bounds around every visible function's drawn points, with some room around them
+	fn functions_extent(&self) -> Option<PlotBounds> {
+		/// Fraction of the size of the bounds added as room on each side
+		const MARGIN: f64 = 0.05;
+
+		let (min, max) = self
+			.functions
+			.get_entries()
+			.iter()
+			.filter_map(|(_, function)| function.extent())
+			.reduce(|(min_a, max_a), (min_b, max_b)| {
+				(
+					PlotPoint::new(min_a.x.min(min_b.x), min_a.y.min(min_b.y)),
+					PlotPoint::new(max_a.x.max(max_b.x), max_a.y.max(max_b.y)),
+				)
+			})?;
+
+		// Flat extents (such as of constant functions) are given some room as well
+		let margin_x = ((max.x - min.x) * MARGIN).max(1.0);
+		let margin_y = ((max.y - min.y) * MARGIN).max(1.0);
+		Some(PlotBounds::from_min_max(
+			[min.x - margin_x, min.y - margin_y],
+			[max.x + margin_x, max.y + margin_y],
+		))
+	}
+
+	/// Draws the integral's bounds on the plot as vertical lines, moving the one being dragged to the mouse
+	fn drag_integral_bounds(&mut self, plot_ui: &mut PlotUi) {
+		/// Distance (in pixels) the mouse can be from a bound and still grab it
+		const GRAB_DISTANCE: f64 = 6.0;
+
+		let pointer = plot_ui.pointer_coordinate();
+		let tolerance = GRAB_DISTANCE * plot_ui.transform().dvalue_dpos()[0].abs();
+		let bounds = [
+			(IntegralBound::Min, self.settings.integral_min_x),
+			(IntegralBound::Max, self.settings.integral_max_x),
+		];
+
+		// Infinite bounds aren't drawn, so they can't be grabbed, and nothing can be grabbed in embedded figures
+		self.hovered_bound = pointer.filter(|_| !self.embedded).and_then(|pointer| {
+			bounds
+				.into_iter()
+				.filter(|(_, x)| x.is_finite())
+				.map(|(bound, x)| (bound, (x - pointer.x).abs()))
+				.filter(|(_, distance)| *distance <= tolerance)
+				.min_by(|a, b| a.1.total_cmp(&b.1))
+				.map(|(bound, _)| bound)
+		});
+
+		let response = plot_ui.response();
+		if response.drag_started() {
+			self.dragged_bound = self.hovered_bound;
+		} else if !response.dragged() {
+			self.dragged_bound = None;
+		}
+
+		if let Some(bound) = self.dragged_bound
+			&& let Some(pointer) = pointer
+		{
+			match bound {
+				IntegralBound::Min => self.settings.integral_min_x = pointer.x,
+				IntegralBound::Max => self.settings.integral_max_x = pointer.x,
+			}
+			self.settings.integral_changed = true;
+		}
+
+		let bounds = [
+			(IntegralBound::Min, self.settings.integral_min_x),
+			(IntegralBound::Max, self.settings.integral_max_x),
+		];
+		for (bound, x) in bounds.into_iter().filter(|(_, x)| x.is_finite()) {
+			let grabbed = (Some(bound) == self.hovered_bound) | (Some(bound) == self.dragged_bound);
+			plot_ui.vline(
+				VLine::new(x)
+					.color(Color32::GRAY)
+					.width(match grabbed {
+						true => 3.0,
+						false => 1.5,
+					})
+					.style(LineStyle::dashed_loose())
+					.name(self.i18n.get(match bound {
+						IntegralBound::Min => "plot.lower_bound",
+						IntegralBound::Max => "plot.upper_bound",
+					})),
+			);
+		}
+	}
+
+	/// Adds the area of each function to the history if it was calculated again with different settings (or to a different area)
+	/// since it was last added
+	fn record_areas(&mut self) {
+		for (_, function) in self.functions.get_entries() {
+			let Some((min_x, max_x, riemann_sum, intervals, area)) =
+				function.area_computation(&self.settings)
+			else {
+				continue;
+			};
+			if !area.is_finite() {
+				continue;
+			}
+
+			let record = AreaRecord {
+				function: function.raw_func_str.clone(),
+				min_x,
+				max_x,
+				riemann_sum,
+				intervals,
+				area,
+			};
+			let previous = self
+				.area_history
+				.iter()
+				.rev()
+				.find(|previous| previous.function == record.function);
+			if previous != Some(&record) {
+				self.area_history.push(record);
+			}
+		}
+
+		if self.area_history.len() > MAX_AREA_HISTORY {
+			self.area_history
+				.drain(..(self.area_history.len() - MAX_AREA_HISTORY));
+		}
+	}
+
+	/// Expression, integration method, and results of each visible function, as they're listed in an exported report
+	fn report_functions(&mut self) -> Vec<ReportFunction> {
+		let settings = self.settings;
+		let theme = self.preferences.theme;
+		let i18n = &self.i18n;
+		let format_value = |value: f64| format_result(&settings.number_format, value, i18n);
+
+		self.functions
+			.get_entries_mut()
+			.iter_mut()
+			.enumerate()
+			.filter(|(_, (_, function))| function.visible & !function.raw_func_str.is_empty())
+			.map(|(i, (_, function))| {
+				let method = function.area_computation(&settings).map(
+					|(min_x, max_x, riemann_sum, intervals, _)| {
+						i18n.format_all(
+							"report.method",
+							&[
+								i18n.get(riemann_sum.label()).to_owned(),
+								intervals.to_string(),
+								format_value(min_x),
+								format_value(max_x),
+							],
+						)
+					},
+				);
+				let results = function
+					.results(&settings)
+					.into_iter()
+					.map(|(name, x, value)| match x {
+						Some(x) => (
+							i18n.get(name).to_owned(),
+							format!("({}, {})", format_value(x), format_value(value)),
+						),
+						None => (i18n.get(name).to_owned(), format_value(value)),
+					})
+					.collect();
+
+				ReportFunction {
+					expression: function.raw_func_str.clone(),
+					color: function.color.unwrap_or(theme.color(i)),
+					method,
+					results,
+				}
+			})
+			.collect()
+	}
+
+	/// Draws the annotations on the plot, moving the one being dragged to the mouse. An integral bound being dragged is kept, rather
+	/// than also dragging an annotation under it.
+	fn drag_annotations(&mut self, plot_ui: &mut PlotUi) {
+		/// Distance (in pixels) the mouse can be from an annotation's position and still grab it
+		const GRAB_DISTANCE: f64 = 8.0;
+
+		let pointer = plot_ui.pointer_coordinate();
+		let scale = plot_ui.transform().dvalue_dpos().map(f64::abs);
+
+		// The nearest one is grabbed when annotations overlap, and none can be grabbed in embedded figures
+		self.hovered_annotation = pointer.filter(|_| !self.embedded).and_then(|pointer| {
+			self.annotations
+				.iter()
+				.map(|annotation| {
+					((annotation.x - pointer.x) / scale[0])
+						.hypot((annotation.y - pointer.y) / scale[1])
+				})
+				.enumerate()
+				.filter(|(_, distance)| *distance <= GRAB_DISTANCE)
+				.min_by(|a, b| a.1.total_cmp(&b.1))
+				.map(|(i, _)| i)
+		});
+
+		let response = plot_ui.response();
+		if response.drag_started() {
+			self.dragged_annotation = self
+				.hovered_annotation
+				.filter(|_| self.dragged_bound.is_none());
+		} else if !response.dragged() {
+			self.dragged_annotation = None;
+		}
+
+		if let Some(i) = self.dragged_annotation
+			&& let Some(pointer) = pointer
+			&& let Some(annotation) = self.annotations.get_mut(i)
+		{
+			annotation.x = pointer.x;
+			annotation.y = pointer.y;
+		}
+
+		for (i, annotation) in self.annotations.iter().enumerate() {
+			let grabbed =
+				(Some(i) == self.hovered_annotation) | (Some(i) == self.dragged_annotation);
+			if annotation.pinned {
+				plot_ui.points(
+					vec![annotation.position()]
+						.to_points()
+						.color(ANNOTATION_COLOR)
+						.radius(ANNOTATION_RADIUS)
+						.highlight(grabbed),
+				);
+			}
+			if !annotation.text.is_empty() {
+				plot_ui.text(
+					Text::new(annotation.position(), annotation.text.clone())
+						.color(ANNOTATION_COLOR)
+						.anchor(Align2::LEFT_BOTTOM)
+						.highlight(grabbed),
+				);
+			}
+		}
+	}
+
+	/// Window where the defaults of new workspaces and other preferences are set
+	fn settings_window(&mut self, ctx: &Context) {
+		let mut theme_changed = false;
+		let i18n = &self.i18n;
+		Window::new(i18n.get("settings.title"))
+			.id(egui::Id::new("settings"))
+			.open(&mut self.opened.settings)
+			.default_pos([200.0, 200.0])
+			.resizable(false)
+			.collapsible(false)
+			.show(ctx, |ui| {
+				let preferences = &mut self.preferences;
+				ui.label(i18n.get("settings.new_workspaces"));
+				Grid::new("workspace_defaults")
+					.num_columns(2)
+					.show(ui, |ui| {
+						ui.label(i18n.get("settings.riemann_sum"));
+						ComboBox::from_id_source("default_riemann_sum")
+							.selected_text(i18n.get(preferences.riemann_sum.label()))
+							.show_ui(ui, |ui| {
+								for riemann_sum in Riemann::ALL {
+									ui.selectable_value(
+										&mut preferences.riemann_sum,
+										riemann_sum,
+										i18n.get(riemann_sum.label()),
+									);
+								}
+							});
+						ui.end_row();
+
+						ui.label(i18n.get("settings.interval"));
+						ui.add(
+							DragValue::new(&mut preferences.integral_num)
+								.clamp_range(1..=500000)
+								.custom_parser(parse_number),
+						);
+						ui.end_row();
+					});
+
+				ui.separator();
+				Grid::new("preferences").num_columns(2).show(ui, |ui| {
+					ui.label(i18n.get("settings.view_range"));
+					ui.horizontal(|ui| {
+						let (min, max) = (preferences.view_min, preferences.view_max);
+						ui.add(
+							DragValue::new(&mut preferences.view_min)
+								.clamp_range(f64::MIN..=(max - 1.0))
+								.custom_parser(parse_number),
+						);
+						ui.label(i18n.get("settings.view_range.to"));
+						ui.add(
+							DragValue::new(&mut preferences.view_max)
+								.clamp_range((min + 1.0)..=f64::MAX)
+								.custom_parser(parse_number),
+						);
+					})
+					.response
+					.on_hover_text(i18n.get("settings.view_range.hover"));
+					ui.end_row();
+
+					ui.label(i18n.get("settings.language"));
+					ComboBox::from_id_source("language")
+						.selected_text(preferences.language.to_string())
+						.show_ui(ui, |ui| {
+							for language in Language::ALL {
+								ui.selectable_value(
+									&mut preferences.language,
+									language,
+									language.to_string(),
+								);
+							}
+						});
+					ui.end_row();
+
+					let theme = &mut preferences.theme;
+					ui.label(i18n.get("settings.theme"));
+					ui.horizontal(|ui| {
+						theme_changed |= ui
+							.selectable_value(
+								&mut theme.dark_mode,
+								true,
+								i18n.get("settings.theme.dark"),
+							)
+							.changed();
+						theme_changed |= ui
+							.selectable_value(
+								&mut theme.dark_mode,
+								false,
+								i18n.get("settings.theme.light"),
+							)
+							.changed();
+					});
+					ui.end_row();
+
+					ui.label(i18n.get("settings.palette"));
+					ComboBox::from_id_source("palette")
+						.selected_text(i18n.get(theme.palette.label()))
+						.show_ui(ui, |ui| {
+							for palette in Palette::ALL {
+								ui.selectable_value(
+									&mut theme.palette,
+									palette,
+									i18n.get(palette.label()),
+								);
+							}
+						})
+						.response
+						.on_hover_text(i18n.get("settings.palette.hover"));
+					ui.end_row();
+
+					// The plot's colors are the theme's unless they're checked
+					let background = ui.visuals().extreme_bg_color;
+					let grid_color = ui.visuals().widgets.open.fg_stroke.color;
+					for (name, color, default) in [
+						(
+							"settings.plot_background",
+							&mut theme.plot_background,
+							background,
+						),
+						("settings.grid_lines", &mut theme.grid_color, grid_color),
+					] {
+						ui.label(i18n.get(name));
+						ui.horizontal(|ui| {
+							let mut custom = color.is_some();
+							if ui.checkbox(&mut custom, "").changed() {
+								*color = custom.then_some(default);
+							}
+							if let Some(color) = color {
+								egui::color_picker::color_edit_button_srgba(
+									ui,
+									color,
+									egui::color_picker::Alpha::Opaque,
+								);
+							}
+						});
+						ui.end_row();
+					}
+
+					let number_format = &mut preferences.number_format;
+					ui.label(i18n.get("settings.precision"));
+					ui.horizontal(|ui| {
+						ui.add(
+							DragValue::new(&mut number_format.digits)
+								.clamp_range(0..=NumberFormat::MAX_DIGITS),
+						)
+						.on_hover_text(i18n.get("settings.precision.hover"));
+						ComboBox::from_id_source("precision")
+							.selected_text(i18n.get(match number_format.precision {
+								Precision::DecimalPlaces => "settings.precision.decimals",
+								Precision::SignificantFigures => "settings.precision.significant",
+							}))
+							.show_ui(ui, |ui| {
+								for (precision, name) in [
+									(Precision::DecimalPlaces, "settings.precision.decimals"),
+									(
+										Precision::SignificantFigures,
+										"settings.precision.significant",
+									),
+								] {
+									ui.selectable_value(
+										&mut number_format.precision,
+										precision,
+										i18n.get(name),
+									);
+								}
+							});
+					});
+					ui.end_row();
+
+					ui.label(i18n.get("settings.scientific"));
+					ui.add(
+						DragValue::new(&mut number_format.scientific_exponent)
+							.clamp_range(1..=300)
+							.prefix("10^"),
+					)
+					.on_hover_text(i18n.get("settings.scientific.hover"));
+					ui.end_row();
+
+					ui.label(i18n.get("settings.thousands"));
+					ui.checkbox(&mut number_format.thousands_separators, "")
+						.on_hover_text(i18n.get("settings.thousands.hover"));
+					ui.end_row();
+
+					// How a number is written with the format that's set
+					ui.label(i18n.get("settings.number_preview"));
+					ui.label(number_format.format(-1234567.891234, i18n));
+					ui.end_row();
+
+					ui.label(i18n.get("settings.ui_scale"));
+					ui.add(
+						Slider::new(&mut preferences.ui_scale, SCALE_RANGE)
+							.step_by(SCALE_STEP as f64)
+							.fixed_decimals(1),
+					)
+					.on_hover_text(i18n.get("settings.ui_scale.hover"));
+					ui.end_row();
+
+					ui.label(i18n.get("settings.font_size"));
+					ui.add(
+						Slider::new(&mut preferences.font_scale, SCALE_RANGE)
+							.step_by(SCALE_STEP as f64)
+							.fixed_decimals(1),
+					)
+					.on_hover_text(i18n.get("settings.font_size.hover"));
+					ui.end_row();
+				});
+
+				ui.separator();
+				ui.label(i18n.get("settings.keys"));
+				let conflicts = preferences.keys.conflicts();
+				Grid::new("keys").num_columns(2).show(ui, |ui| {
+					for action in Action::ALL {
+						// Keys bound to more than one action only do the first
+						let label = i18n.get(action.label());
+						match conflicts.contains(&action) {
+							true => ui
+								.colored_label(Color32::RED, label)
+								.on_hover_text(i18n.get("settings.keys.conflict")),
+							false => ui.label(label),
+						};
+
+						let mut key = preferences.keys.get(action);
+						ComboBox::from_id_source(action)
+							.selected_text(key.name())
+							.show_ui(ui, |ui| {
+								// Only keys that are a single character (such as letters and numbers), or function keys
+								Key::ALL
+									.iter()
+									.filter(|option| {
+										(option.name().chars().count() == 1)
+											| option
+												.name()
+												.strip_prefix('F')
+												.is_some_and(|n| n.parse::<u8>().is_ok())
+									})
+									.for_each(|option| {
+										ui.selectable_value(&mut key, *option, option.name());
+									});
+							});
+						preferences.keys.set(action, key);
+						ui.end_row();
+					}
+				});
+
+				ui.separator();
+				if ui.button(i18n.get("settings.restore_defaults")).clicked() {
+					// The language is kept, so the window can still be read
+					*preferences = Preferences {
+						language: preferences.language,
+						..Preferences::default()
+					};
+					theme_changed = true;
+				}
+			});
+
+		if theme_changed {
+			ctx.set_visuals(self.preferences.theme.visuals());
+		}
+		self.settings.number_format = self.preferences.number_format;
+	}
+
+	/// Panel with an on-screen keypad, which types into the function that was last typed in, for entering functions without a physical keyboard
+	fn keypad_panel(&mut self, ctx: &Context) {
+		TopBottomPanel::bottom("keypad").show(ctx, |ui| {
+			ui.horizontal(|ui| {
+				Grid::new("keypad_keys").show(ui, |ui| {
+					for row in KEYPAD {
+						for (label, text) in row {
+							if ui.add(Button::new(label).min_size(KEY_SIZE)).clicked() {
+								self.functions.insert_text(ctx, text);
+							}
+						}
+						ui.end_row();
+					}
+				});
+
+				if ui
+					.add(Button::new(self.i18n.get("keypad.delete")).min_size(KEY_SIZE))
+					.clicked()
+				{
+					self.functions.delete_char(ctx);
+				}
+			});
+		});
+	}
+
+	/// Panel with a table of x, f(x), and f'(x) of each selected function, over a range of values of x
+	fn table_panel(&mut self, ctx: &Context) {
+		/// Maximum number of rows, so a small step doesn't evaluate the functions too many times
+		const MAX_ROWS: usize = 1000;
+
+		SidePanel::right("table_panel").show(ctx, |ui| {
+			Grid::new("table_range").show(ui, |ui| {
+				ui.label(self.i18n.get("table.from"));
+				ui.add(
+					DragValue::new(&mut self.table_range.0)
+						.speed(0.1)
+						.custom_parser(parse_number),
+				);
+				ui.end_row();
+
+				ui.label(self.i18n.get("table.to"));
+				ui.add(
+					DragValue::new(&mut self.table_range.1)
+						.speed(0.1)
+						.custom_parser(parse_number),
+				);
+				ui.end_row();
+
+				ui.label(self.i18n.get("table.step"));
+				ui.add(
+					DragValue::new(&mut self.table_step)
+						.speed(0.01)
+						.clamp_range(1e-6..=f64::MAX)
+						.custom_parser(parse_number),
+				);
+				ui.end_row();
+			});
+
+			ui.horizontal_wrapped(|ui| {
+				(0..self.functions.len()).for_each(|i| {
+					let mut selected = self.table_functions.contains(&i);
+					if ui.checkbox(&mut selected, format!("#{}", i)).changed() {
+						match selected {
+							true => self.table_functions.insert(i),
+							false => self.table_functions.remove(&i),
+						};
+					}
+				});
+			});
+			ui.separator();
+
+			// The last value is included even if it's slightly off from a multiple of the step
+			let (start, end) = self.table_range;
+			let rows = match end >= start {
+				true => ((((end - start) / self.table_step) + 1e-9).floor() as usize) + 1,
+				false => 0,
+			};
+			let xs = step_helper(rows.min(MAX_ROWS), start, self.table_step);
+
+			let format_value = |value: f64| self.settings.number_format.format(value, &self.i18n);
+
+			egui::ScrollArea::vertical().show(ui, |ui| {
+				for (i, (_, function)) in self.functions.get_entries_mut().iter_mut().enumerate() {
+					if !self.table_functions.contains(&i) {
+						continue;
+					}
+
+					ui.label(self.i18n.format_all(
+						"function.number_expression",
+						&[i.to_string(), function.raw_func_str.clone()],
+					));
+					let Some(values) = function.table(&xs) else {
+						ui.label(self.i18n.get("table.not_function"));
+						continue;
+					};
+
+					Grid::new(("table", i)).striped(true).show(ui, |ui| {
+						ui.label("x");
+						ui.label("f(x)");
+						ui.label("f'(x)");
+						ui.end_row();
+
+						for (x, (y, derivative)) in xs.iter().zip(values) {
+							ui.label(format_value(*x));
+							ui.label(format_value(y));
+							ui.label(format_value(derivative));
+							ui.end_row();
+						}
+					});
+				}
+			});
+		});
+	}
+
+	/// Creates the panel listing the roots, extrema, area, and arc length of each function as numbers, which can be copied or
+	/// exported as CSV
+	fn results_panel(&mut self, ctx: &Context) {
+		let settings = self.settings;
+		let i18n = &self.i18n;
+		let results: Vec<(usize, String, Vec<(&str, Option<f64>, f64)>)> = self
+			.functions
+			.get_entries_mut()
+			.iter_mut()
+			.enumerate()
+			.map(|(i, (_, function))| {
+				(
+					i,
+					function.raw_func_str.clone(),
+					function.results(&settings),
+				)
+			})
+			.collect();
+
+		let format_value = |value: f64| format_result(&settings.number_format, value, i18n);
+
+		// Results of a function as lines of text, for copying them
+		let to_text = |rows: &[(&str, Option<f64>, f64)]| -> String {
+			rows.iter()
+				.map(|(name, x, value)| match x {
+					Some(x) => format!(
+						"{}: ({}, {})\n",
+						i18n.get(name),
+						format_value(*x),
+						format_value(*value)
+					),
+					None => format!("{}: {}\n", i18n.get(name), format_value(*value)),
+				})
+				.collect()
+		};
+		let heading = |i: usize, func_str: &str| {
+			i18n.format_all(
+				"function.number_expression",
+				&[i.to_string().as_str(), func_str],
+			)
+		};
+
+		SidePanel::right("results_panel").show(ctx, |ui| {
+			ui.horizontal(|ui| {
+				if ui
+					.button(i18n.get("results.copy_all"))
+					.on_hover_text(i18n.get("results.copy_all.hover"))
+					.clicked()
+				{
+					let text = results
+						.iter()
+						.map(|(i, func_str, rows)| {
+							format!("{}\n{}", heading(*i, func_str), to_text(rows))
+						})
+						.join("\n");
+					ui.output_mut(|output| output.copied_text = text);
+				}
+
+				if ui
+					.button(i18n.get("results.export"))
+					.on_hover_text(i18n.get("results.export.hover"))
+					.clicked()
+				{
+					let mut csv = String::from("function,result,x,value\n");
+					let format_csv = |value: f64| match value.is_finite() {
+						true => value.to_string(),
+						false => String::new(),
+					};
+					for (i, _, rows) in results.iter() {
+						for (name, x, value) in rows {
+							csv.push_str(&format!(
+								"{},{},{},{}\n",
+								i,
+								i18n.get(name),
+								x.map(format_csv).unwrap_or_default(),
+								format_csv(*value)
+							));
+						}
+					}
+					save_file("results.csv", "text/csv", csv.as_bytes());
+				}
+			});
+
+			if !self.settings.do_roots | !self.settings.do_extrema {
+				ui.label(i18n.get("results.displayed_only"));
+			}
+			ui.separator();
+
+			egui::ScrollArea::vertical().show(ui, |ui| {
+				for (i, func_str, rows) in results.iter() {
+					ui.horizontal(|ui| {
+						ui.label(heading(*i, func_str));
+						if ui
+							.small_button(i18n.get("results.copy"))
+							.on_hover_text(i18n.get("results.copy.hover"))
+							.clicked()
+						{
+							let text = to_text(rows);
+							ui.output_mut(|output| output.copied_text = text);
+						}
+					});
+
+					if rows.is_empty() {
+						ui.label(i18n.get("results.none"));
+						continue;
+					}
+
+					Grid::new(("results", i)).striped(true).show(ui, |ui| {
+						ui.label(i18n.get("results.result"));
+						ui.label("x");
+						ui.label(i18n.get("results.value"));
+						ui.end_row();
+
+						for (name, x, value) in rows {
+							ui.label(i18n.get(name));
+							ui.label(x.map(format_value).unwrap_or_default());
+							ui.label(format_value(*value));
+							ui.end_row();
+						}
+					});
+				}
+			});
+		});
+	}
+
+	/// Creates the side panel, which contains configuration options along with the functions.
+	/// On narrow screens (such as phones) it's a sheet along the bottom instead, so the plot can use the full width.
+	fn side_panel(&mut self, ctx: &Context) {
+		match ctx.screen_rect().width() < NARROW_WIDTH {
+			true => {
+				TopBottomPanel::bottom("side_panel_sheet")
+					.resizable(true)
+					.default_height(ctx.screen_rect().height() / 3.0)
+					.max_height(ctx.screen_rect().height() * 2.0 / 3.0)
+					.show(ctx, |ui| {
+						egui::ScrollArea::vertical()
+							.show(ui, |ui| self.side_panel_contents(ui, true))
+					});
+			}
+			false => {
+				SidePanel::left("side_panel")
+					.resizable(false)
+					.show(ctx, |ui| self.side_panel_contents(ui, false));
+			}
+		}
+	}
+
+	/// Contents of the side panel, where `narrow` is if it's shown as a sheet along the bottom
+	fn side_panel_contents(&mut self, ui: &mut Ui, narrow: bool) {
+		// Side Panel which contains vital options to the operation of the application
+		// (such as adding functions and other options)
+		let any_using_integral = self.functions.any_using_integral();
+		let prev_sum = self.settings.riemann_sum;
+		let prev_area_mode = self.settings.area_mode;
+		// ComboBox for selecting what Riemann sum type to use
+		ui.add_enabled_ui(any_using_integral, |ui| {
+			let spacing_mut = ui.spacing_mut();
+
+			spacing_mut.item_spacing.x = 1.0;
+			spacing_mut.interact_size *= 0.5;
+			ComboBox::from_label(self.i18n.get("side_panel.riemann_sum"))
+				.selected_text(self.i18n.get(self.settings.riemann_sum.label()))
+				.show_ui(ui, |ui| {
+					for riemann_sum in Riemann::ALL {
+						ui.selectable_value(
+							&mut self.settings.riemann_sum,
+							riemann_sum,
+							self.i18n.get(riemann_sum.label()),
+						);
+					}
+				});
+
+			// ComboBox for selecting how the area is reported
+			ComboBox::from_label(self.i18n.get("side_panel.area"))
+				.selected_text(self.i18n.get(self.settings.area_mode.label()))
+				.show_ui(ui, |ui| {
+					for area_mode in AreaMode::ALL {
+						ui.selectable_value(
+							&mut self.settings.area_mode,
+							area_mode,
+							self.i18n.get(area_mode.label()),
+						);
+					}
+				});
+
+			let riemann_changed = prev_sum != self.settings.riemann_sum;
+			let area_mode_changed = prev_area_mode != self.settings.area_mode;
+
+			let (min_x_changed, max_x_changed) = ui
+				.horizontal(|ui: &mut Ui| {
+					// let spacing_mut = ui.spacing_mut();
+
+					// spacing_mut.item_spacing = Vec2::new(1.0, 0.0);
+					// spacing_mut.interact_size *= 0.5;
+
+					ui.label(self.i18n.get("side_panel.integral"));
+					// Bounds may be reversed, which negates the integral
+					let min_x_changed = match self.settings.integral_min_x.is_infinite() {
+						true => {
+							ui.label("-∞");
+							false
+						}
+						false => ui
+							.add(
+								DragValue::new(&mut self.settings.integral_min_x)
+									.custom_parser(parse_number),
+							)
+							.changed(),
+					};
+					ui.label(",");
+					let max_x_changed = match self.settings.integral_max_x.is_infinite() {
+						true => {
+							ui.label("∞");
+							false
+						}
+						false => ui
+							.add(
+								DragValue::new(&mut self.settings.integral_max_x)
+									.custom_parser(parse_number),
+							)
+							.changed(),
+					};
+					ui.label("]");
+					(min_x_changed, max_x_changed)
+				})
+				.inner;
+
+			// Allows for either bound to be infinite
+			let infinite_changed = ui
+				.horizontal(|ui| {
+					let mut min_infinite = self.settings.integral_min_x.is_infinite();
+					let mut max_infinite = self.settings.integral_max_x.is_infinite();
+
+					let min_infinite_changed = ui.checkbox(&mut min_infinite, "-∞").changed();
+					let max_infinite_changed = ui.checkbox(&mut max_infinite, "∞").changed();
+
+					if min_infinite_changed {
+						self.settings.integral_min_x = match min_infinite {
+							true => f64::NEG_INFINITY,
+							false => DEFAULT_MIN_X.min(self.settings.integral_max_x - 1.0),
+						};
+					}
+
+					if max_infinite_changed {
+						self.settings.integral_max_x = match max_infinite {
+							true => f64::INFINITY,
+							false => DEFAULT_MAX_X.max(self.settings.integral_min_x + 1.0),
+						};
+					}
+
+					min_infinite_changed | max_infinite_changed
+				})
+				.inner;
+
+			// Number of Rectangles for Riemann sum
+			let integral_num_changed = ui
+				.horizontal(|ui| {
+					let spacing_mut = ui.spacing_mut();
+
+					spacing_mut.item_spacing.x = 1.5;
+					ui.label(self.i18n.get("side_panel.intervals"));
+					ui.add_enabled(
+						self.interval_animation.is_none(),
+						DragValue::new(&mut self.settings.integral_num).custom_parser(parse_number),
+					)
+					.changed()
+				})
+				.inner;
+
+			// Animates the number of intervals increasing up to the one that was set, stopping it goes back to that number
+			let animation_changed = self.settings.riemann_sum.uses_intervals()
+				&& ui
+					.horizontal(|ui| {
+						let clicked = ui
+							.button(self.i18n.get(match self.interval_animation {
+								Some(_) => "side_panel.animate.stop",
+								None => "side_panel.animate",
+							}))
+							.on_hover_text(self.i18n.get("side_panel.animate.hover"))
+							.clicked();
+						if clicked {
+							match self.interval_animation.take() {
+								Some(animation) => self.settings.integral_num = animation.target,
+								None => {
+									self.interval_animation = Some(IntervalAnimation {
+										target: self.settings.integral_num,
+										progress: 0.0,
+										record: self.record_animation,
+										frame: 0,
+									})
+								}
+							}
+						}
+
+						ui.checkbox(
+							&mut self.record_animation,
+							self.i18n.get("side_panel.record"),
+						)
+						.on_hover_text(self.i18n.get("side_panel.record.hover"));
+						clicked
+					})
+					.inner;
+
+			// Colors rectangles by whether they over or underestimate the area of their interval, along with a legend of the colors
+			let estimate_colors_changed = self.settings.riemann_sum.uses_intervals()
+				&& (self.settings.riemann_sum != Riemann::Trapezoid)
+				&& ui
+					.horizontal(|ui| {
+						let changed = ui
+							.checkbox(
+								&mut self.settings.estimate_colors,
+								self.i18n.get("side_panel.estimate_colors"),
+							)
+							.on_hover_text(self.i18n.get("side_panel.estimate_colors.hover"))
+							.changed();
+						if self.settings.estimate_colors {
+							ui.colored_label(
+								OVERESTIMATE_COLOR,
+								self.i18n.get("side_panel.estimate_colors.over"),
+							);
+							ui.colored_label(
+								UNDERESTIMATE_COLOR,
+								self.i18n.get("side_panel.estimate_colors.under"),
+							);
+						}
+						changed
+					})
+					.inner;
+
+			// Sum the integral is compared to, which is drawn over it in another color. Monte Carlo estimates are random, so they
+			// aren't compared.
+			let prev_compare_sum = self.settings.compare_sum;
+			ComboBox::from_label(self.i18n.get("side_panel.compare"))
+				.selected_text(match self.settings.compare_sum {
+					Some(compare_sum) => {
+						RichText::new(self.i18n.get(compare_sum.label())).color(COMPARISON_COLOR)
+					}
+					None => RichText::new(self.i18n.get("side_panel.compare.none")),
+				})
+				.show_ui(ui, |ui| {
+					ui.selectable_value(
+						&mut self.settings.compare_sum,
+						None,
+						self.i18n.get("side_panel.compare.none"),
+					);
+					for riemann_sum in Riemann::ALL
+						.into_iter()
+						.filter(|riemann_sum| *riemann_sum != Riemann::MonteCarlo)
+					{
+						ui.selectable_value(
+							&mut self.settings.compare_sum,
+							Some(riemann_sum),
+							self.i18n.get(riemann_sum.label()),
+						);
+					}
+				})
+				.response
+				.on_hover_text(self.i18n.get("side_panel.compare.hover"));
+			let compare_sum_changed = self.settings.compare_sum != prev_compare_sum;
+
+			if integral_num_changed {
+				self.settings.integral_num = self.settings.integral_num.clamp(0, 500000);
+			}
+
+			// Error tolerance for adaptive integration
+			let tolerance_changed = self.settings.riemann_sum == Riemann::Adaptive
+				&& ui
+					.horizontal(|ui| {
+						let spacing_mut = ui.spacing_mut();
+
+						spacing_mut.item_spacing.x = 1.5;
+						ui.label(self.i18n.get("side_panel.tolerance"));
+						ui.add(
+							DragValue::new(&mut self.settings.integral_tolerance)
+								.speed(0.00001)
+								.max_decimals(12),
+						)
+						.changed()
+					})
+					.inner;
+
+			if tolerance_changed {
+				self.settings.integral_tolerance =
+					self.settings.integral_tolerance.clamp(1e-12, 1.0);
+			}
+
+			// Number of random samples for Monte Carlo integration
+			let samples_changed = self.settings.riemann_sum == Riemann::MonteCarlo
+				&& ui
+					.add(
+						egui::Slider::new(&mut self.settings.monte_carlo_samples, 10..=100000)
+							.logarithmic(true)
+							.text(self.i18n.get("side_panel.samples")),
+					)
+					.changed();
+
+			self.settings.integral_changed = any_using_integral
+				&& (max_x_changed
+					| min_x_changed | infinite_changed
+					| integral_num_changed
+					| animation_changed
+					| estimate_colors_changed
+					| compare_sum_changed
+					| tolerance_changed
+					| samples_changed
+					| riemann_changed
+					| area_mode_changed);
+		});
+
+		// Range of θ that polar functions are drawn and integrated over
+		self.settings.theta_changed = false;
+		if self.functions.any_polar() {
+			self.settings.theta_changed = ui
+				.horizontal(|ui| {
+					ui.label(self.i18n.get("side_panel.theta_range"));
+					let min_changed = ui
+						.add(
+							DragValue::new(&mut self.settings.theta_min)
+								.speed(0.01)
+								.custom_parser(parse_number),
+						)
+						.changed();
+					ui.label(",");
+					let max_changed = ui
+						.add(
+							DragValue::new(&mut self.settings.theta_max)
+								.speed(0.01)
+								.custom_parser(parse_number),
+						)
+						.changed();
+					ui.label("]");
+					min_changed | max_changed
+				})
+				.inner;
+		}
+
+		// Range of each axis shown by the plot, which can be typed in
+		ui.collapsing(self.i18n.get("side_panel.view"), |ui| {
+			let (mut min_x, mut max_x) = (self.settings.min_x, self.settings.max_x);
+			let (mut min_y, mut max_y) = (self.settings.min_y, self.settings.max_y);
+			let mut changed = false;
+			Grid::new("view_bounds").show(ui, |ui| {
+				for (name, min, max) in [
+					("x: [", &mut min_x, &mut max_x),
+					("y: [", &mut min_y, &mut max_y),
+				] {
+					ui.label(name);
+					changed |= ui
+						.add(DragValue::new(min).speed(0.1).custom_parser(parse_number))
+						.changed();
+					ui.label(",");
+					changed |= ui
+						.add(DragValue::new(max).speed(0.1).custom_parser(parse_number))
+						.changed();
+					ui.label("]");
+					ui.end_row();
+				}
+			});
+
+			if changed && (min_x < max_x) && (min_y < max_y) {
+				self.view_request = Some(ViewRequest::Set(PlotBounds::from_min_max(
+					[min_x, min_y],
+					[max_x, max_y],
+				)));
+			}
+
+			ui.checkbox(
+				&mut self.lock_aspect,
+				self.i18n.get("side_panel.lock_aspect"),
+			)
+			.on_hover_text(self.i18n.get("side_panel.lock_aspect.hover"));
+		});
+
+		// Text labels and pinned points on the plot, which can also be dragged there
+		ui.collapsing(self.i18n.get("side_panel.annotations"), |ui| {
+			let mut removed = None;
+			Grid::new("annotations").show(ui, |ui| {
+				for (i, annotation) in self.annotations.iter_mut().enumerate() {
+					ui.add(
+						TextEdit::singleline(&mut annotation.text)
+							.hint_text(self.i18n.get("side_panel.annotations.label"))
+							.desired_width(80.0),
+					);
+					ui.horizontal(|ui| {
+						ui.label("(");
+						ui.add(
+							DragValue::new(&mut annotation.x)
+								.speed(0.1)
+								.custom_parser(parse_number),
+						);
+						ui.label(",");
+						ui.add(
+							DragValue::new(&mut annotation.y)
+								.speed(0.1)
+								.custom_parser(parse_number),
+						);
+						ui.label(")");
+					});
+					ui.checkbox(
+						&mut annotation.pinned,
+						self.i18n.get("side_panel.annotations.point"),
+					)
+					.on_hover_text(self.i18n.get("side_panel.annotations.point.hover"));
+					if ui
+						.small_button("✖")
+						.on_hover_text(self.i18n.get("side_panel.annotations.remove"))
+						.clicked()
+					{
+						removed = Some(i);
+					}
+					ui.end_row();
+				}
+			});
+
+			if let Some(i) = removed {
+				self.annotations.remove(i);
+				self.hovered_annotation = None;
+				self.dragged_annotation = None;
+			}
+
+			// Placed in the middle of the view, from where it can be dragged
+			if ui
+				.button(self.i18n.get("side_panel.annotations.add"))
+				.on_hover_text(self.i18n.get("side_panel.annotations.add.hover"))
+				.clicked()
+			{
+				self.annotations.push(Annotation {
+					x: (self.settings.min_x + self.settings.max_x) / 2.0,
+					y: (self.settings.min_y + self.settings.max_y) / 2.0,
+					text: self.i18n.get("side_panel.annotations.label").to_owned(),
+					pinned: true,
+				});
+			}
+		});
+
+		// Unit of the angles used by trigonometric functions
+		ui.horizontal(|ui| {
+			let angle_label = |angle_unit: AngleUnit| match angle_unit {
+				AngleUnit::Radians => "angle.radians",
+				AngleUnit::Degrees => "angle.degrees",
+			};
+			ComboBox::from_label(self.i18n.get("angle"))
+				.selected_text(self.i18n.get(angle_label(self.settings.angle_unit)))
+				.show_ui(ui, |ui| {
+					for angle_unit in [AngleUnit::Radians, AngleUnit::Degrees] {
+						ui.selectable_value(
+							&mut self.settings.angle_unit,
+							angle_unit,
+							self.i18n.get(angle_label(angle_unit)),
+						);
+					}
+				});
+
+			match self.settings.angle_unit {
+				AngleUnit::Degrees => ui
+					.checkbox(
+						&mut self.settings.degree_ticks,
+						self.i18n.get("angle.degree_ticks"),
+					)
+					.on_hover_text(self.i18n.get("angle.degree_ticks.hover")),
+				AngleUnit::Radians => ui
+					.checkbox(&mut self.settings.pi_ticks, self.i18n.get("angle.pi_ticks"))
+					.on_hover_text(self.i18n.get("angle.pi_ticks.hover")),
+			};
+		});
+
+		ui.checkbox(
+			&mut self.settings.case_sensitive,
+			self.i18n.get("side_panel.case_sensitive"),
+		)
+		.on_hover_text(self.i18n.get("side_panel.case_sensitive.hover"));
+
+		// Slider for each parameter used by the functions, such as `a` in `a*sin(x)`, which is animated across its range with the
+		// button next to it (and can be scrubbed through by dragging the slider)
+		let parameter_names = self.functions.parameter_names();
+		parameter_names.iter().for_each(|name| {
+			let value = self
+				.parameters
+				.entry(name.clone())
+				.or_insert(DEFAULT_PARAMETER);
+			let sweep = self.sweeps.entry(name.clone()).or_default();
+			ui.horizontal(|ui| {
+				if ui
+					.button(match sweep.playing {
+						true => "⏸",
+						false => "▶",
+					})
+					.on_hover_text(self.i18n.get(match sweep.playing {
+						true => "side_panel.parameter.pause",
+						false => "side_panel.parameter.animate",
+					}))
+					.clicked()
+				{
+					sweep.playing = !sweep.playing;
+				}
+				ui.add(egui::Slider::new(value, sweep.min..=sweep.max).text(name.as_str()));
+			});
+		});
+
+		// Range and speed of each parameter's animation
+		if !parameter_names.is_empty() {
+			ui.collapsing(self.i18n.get("side_panel.animation"), |ui| {
+				Grid::new("parameter_sweeps").show(ui, |ui| {
+					for name in parameter_names.iter() {
+						let sweep = self.sweeps.entry(name.clone()).or_default();
+						ui.label(name.as_str());
+						ui.add(
+							DragValue::new(&mut sweep.min)
+								.speed(0.1)
+								.custom_parser(parse_number),
+						);
+						ui.label(self.i18n.get("settings.view_range.to"));
+						ui.add(
+							DragValue::new(&mut sweep.max)
+								.speed(0.1)
+								.custom_parser(parse_number),
+						);
+						ui.add(
+							DragValue::new(&mut sweep.speed)
+								.speed(0.1)
+								.clamp_range(0.01..=100.0)
+								.suffix("/s"),
+						)
+						.on_hover_text(self.i18n.get("side_panel.animation.speed.hover"));
+						ui.end_row();
+					}
+				});
+			});
+		}
+
+		// Toggle the window plotting how the sum converges
+		if any_using_integral
+			&& self.settings.riemann_sum.uses_intervals()
+			&& !self.settings.is_improper()
+		{
+			self.opened.convergence.bitxor_assign(
+				ui.button(self.i18n.get("convergence"))
+					.on_hover_text(self.i18n.get(match self.opened.convergence {
+						true => "convergence.hide",
+						false => "convergence.show",
+					}))
+					.clicked(),
+			);
+		}
+
+		// Areas of the two sums being compared, and how much they differ
+		if any_using_integral
+			&& let Some(compare_sum) = self.settings.compare_sum
+			&& (compare_sum != self.settings.riemann_sum)
+		{
+			ui.collapsing(self.i18n.get("side_panel.comparison"), |ui| {
+				let format = |value: f64| self.settings.number_format.format(value, &self.i18n);
+				Grid::new("comparison").striped(true).show(ui, |ui| {
+					ui.label("");
+					ui.label(self.i18n.get(self.settings.riemann_sum.label()));
+					ui.colored_label(COMPARISON_COLOR, self.i18n.get(compare_sum.label()));
+					ui.label(self.i18n.get("side_panel.comparison.difference"));
+					ui.end_row();
+
+					for (i, (_, function)) in self.functions.get_entries().iter().enumerate() {
+						let (Some((_, area)), Some((.., compared_area))) =
+							(&function.integral_data, &function.comparison_data)
+						else {
+							continue;
+						};
+						ui.label(format!("#{}", i));
+						ui.label(format(*area));
+						ui.label(format(*compared_area));
+						ui.label(format(compared_area - area));
+						ui.end_row();
+					}
+				});
+			});
+		}
+
+		// Toggle the window listing the areas calculated this session
+		if any_using_integral | !self.area_history.is_empty() {
+			self.opened.area_history.bitxor_assign(
+				ui.button(self.i18n.get("area_history"))
+					.on_hover_text(self.i18n.get(match self.opened.area_history {
+						true => "area_history.hide",
+						false => "area_history.show",
+					}))
+					.clicked(),
+			);
+		}
+
+		// Convergence table(s) of Romberg integration
+		if any_using_integral && self.settings.riemann_sum == Riemann::Romberg {
+			ui.collapsing(self.i18n.get("side_panel.romberg_table"), |ui| {
+				self.functions
+					.get_entries()
+					.iter()
+					.enumerate()
+					.filter(|(_, (_, function))| !function.romberg_table.is_empty())
+					.for_each(|(i, (_, function))| {
+						ui.label(self.i18n.format("function.number", &i.to_string()));
+						Grid::new(("romberg_table", i))
+							.striped(true)
+							.show(ui, |ui| {
+								function.romberg_table.iter().for_each(|row| {
+									row.iter().for_each(|value| {
+										ui.label(
+											self.settings.number_format.format(*value, &self.i18n),
+										);
+									});
+									ui.end_row();
+								});
+							});
+					});
+			});
+		}
+
+		// List of every function's critical points and their classification
+		if self.settings.do_extrema {
+			ui.collapsing(self.i18n.get("side_panel.critical_points"), |ui| {
+				if ui
+					.small_button(self.i18n.get("results.copy"))
+					.on_hover_text(self.i18n.get("side_panel.critical_points.copy.hover"))
+					.clicked()
+				{
+					let (i18n, number_format) = (&self.i18n, &self.settings.number_format);
+					let extrema: String = self
+						.functions
+						.get_entries()
+						.iter()
+						.enumerate()
+						.flat_map(|(i, (_, function))| {
+							function.extrema_data.iter().map(move |(point, kind)| {
+								format!(
+									"({}) ({}, {}) {}\n",
+									i18n.format("function.number", &i.to_string()),
+									number_format.format(point.x, i18n),
+									number_format.format(point.y, i18n),
+									i18n.get(kind.label())
+								)
+							})
+						})
+						.collect();
+					ui.output_mut(|output| output.copied_text = extrema);
+				}
+
+				self.functions
+					.get_entries()
+					.iter()
+					.enumerate()
+					.filter(|(_, (_, function))| !function.extrema_data.is_empty())
+					.for_each(|(i, (_, function))| {
+						ui.label(self.i18n.format("function.number", &i.to_string()));
+						Grid::new(("critical_points", i))
+							.striped(true)
+							.show(ui, |ui| {
+								function.extrema_data.iter().for_each(|(point, kind)| {
+									ui.label(format!(
+										"({}, {})",
+										self.settings.number_format.format(point.x, &self.i18n),
+										self.settings.number_format.format(point.y, &self.i18n)
+									));
+									ui.label(self.i18n.get(kind.label()));
+									ui.end_row();
+								});
+							});
+					});
+			});
+		}
+
+		// Refined roots of every function, in a text box so they can be copied
+		self.settings.roots_changed = false;
+		if self.settings.do_roots {
+			ui.collapsing(self.i18n.get("side_panel.roots"), |ui| {
+				self.settings.roots_changed = ui
+					.horizontal(|ui| {
+						ui.label(self.i18n.get("side_panel.tolerance"));
+						ui.add(
+							DragValue::new(&mut self.settings.root_tolerance)
+								.speed(1e-13)
+								.max_decimals(15)
+								.clamp_range(1e-15..=1e-3),
+						)
+						.changed()
+					})
+					.inner;
+
+				let (i18n, number_format) = (&self.i18n, &self.settings.number_format);
+				let roots: String = self
+					.functions
+					.get_entries()
+					.iter()
+					.enumerate()
+					.flat_map(|(i, (_, function))| {
+						function.root_data.iter().map(move |root| {
+							format!(
+								"({}) {}\n",
+								i18n.format("function.number", &i.to_string()),
+								number_format.format(root.x, i18n)
+							)
+						})
+					})
+					.collect();
+				if ui
+					.small_button(self.i18n.get("results.copy"))
+					.on_hover_text(self.i18n.get("side_panel.roots.copy.hover"))
+					.clicked()
+				{
+					ui.output_mut(|output| output.copied_text = roots.clone());
+				}
+				ui.add(egui::TextEdit::multiline(&mut roots.as_str()).code_editor());
+			});
+		}
+
+		// Intersections of a pair of functions, which can also be used as the integral's bounds
+		if self.functions.len() > 1 {
+			ui.collapsing(self.i18n.get("side_panel.intersections"), |ui| {
+				let len = self.functions.len();
+				let mut enabled = self.intersections.is_some();
+				let (mut a, mut b) = self.intersections.unwrap_or((0, 1));
+
+				ui.checkbox(&mut enabled, self.i18n.get("side_panel.intersections.find"));
+				ui.add_enabled_ui(enabled, |ui| {
+					ui.horizontal(|ui| {
+						function_selector(ui, &self.i18n, "intersection_a", &mut a, len);
+						ui.label(self.i18n.get("side_panel.intersections.and"));
+						function_selector(ui, &self.i18n, "intersection_b", &mut b, len);
+					});
+				});
+
+				let intersections = enabled.then_some((a, b));
+				if intersections != self.intersections {
+					self.intersections = intersections;
+					self.intersection_data = None;
+				}
+
+				if self.intersections.is_some()
+					&& let Some(ref intersection_data) = self.intersection_data
+				{
+					Grid::new("intersections").striped(true).show(ui, |ui| {
+						intersection_data.iter().for_each(|point| {
+							ui.label(format!(
+								"({}, {})",
+								self.settings.number_format.format(point.x, &self.i18n),
+								self.settings.number_format.format(point.y, &self.i18n)
+							));
+							ui.end_row();
+						});
+					});
+
+					// Snaps the integral's bounds to a pair of adjacent intersections
+					let mut bounds = None;
+					intersection_data
+						.iter()
+						.tuple_windows()
+						.for_each(|(left, right)| {
+							if ui
+								.button(self.i18n.format_all(
+									"side_panel.intersections.integrate",
+									&[
+										self.settings.number_format.format(left.x, &self.i18n),
+										self.settings.number_format.format(right.x, &self.i18n),
+									],
+								))
+								.on_hover_text(
+									self.i18n.get("side_panel.intersections.integrate.hover"),
+								)
+								.clicked()
+							{
+								bounds = Some((left.x, right.x));
+							}
+						});
+
+					if let Some((min_x, max_x)) = bounds {
+						self.settings.integral_min_x = min_x;
+						self.settings.integral_max_x = max_x;
+						self.settings.integral_changed = true;
+					}
+				}
+			});
+		}
+
+		// Snaps the integral's bounds to the nearest roots or extrema of a function, or intersections of a pair of functions
+		if any_using_integral {
+			ui.collapsing(self.i18n.get("side_panel.snap"), |ui| {
+				let len = self.functions.len();
+				function_selector(
+					ui,
+					&self.i18n,
+					"snap_function",
+					&mut self.snap_function,
+					len,
+				);
+
+				let (_, function) = &self.functions.get_entries()[self.snap_function];
+				let roots: Vec<f64> = function.root_data.iter().map(|point| point.x).collect();
+				let extrema: Vec<f64> = function
+					.extrema_data
+					.iter()
+					.map(|(point, _)| point.x)
+					.collect();
+				let intersections: Vec<f64> = self
+					.intersection_data
+					.iter()
+					.flatten()
+					.map(|point| point.x)
+					.collect();
+
+				let mut bounds = None;
+				ui.horizontal(|ui| {
+					for (name, points, hover_text) in [
+						("side_panel.roots", &roots, "side_panel.snap.roots.hover"),
+						(
+							"side_panel.extrema",
+							&extrema,
+							"side_panel.snap.extrema.hover",
+						),
+						(
+							"side_panel.intersections",
+							&intersections,
+							"side_panel.snap.intersections.hover",
+						),
+					] {
+						let snapped = snap_bounds(
+							self.settings.integral_min_x,
+							self.settings.integral_max_x,
+							points,
+						);
+						if ui
+							.add_enabled(snapped.is_some(), Button::new(self.i18n.get(name)))
+							.on_hover_text(self.i18n.get(hover_text))
+							.clicked()
+						{
+							bounds = snapped;
+						}
+					}
+				});
+
+				if let Some((min_x, max_x)) = bounds {
+					self.settings.integral_min_x = min_x;
+					self.settings.integral_max_x = max_x;
+					self.settings.integral_changed = true;
+				}
+			});
+		}
+
+		// Solutions of f(x) = c within the plot's bounds, when there's a function to solve
+		if !self.functions.is_empty() {
+			ui.collapsing(self.i18n.get("side_panel.solve"), |ui| {
+				let len = self.functions.len();
+				let mut enabled = self.solve.is_some();
+				let mut i = self.solve.unwrap_or(0);
+				let mut target = self.solve_target;
+
+				ui.checkbox(&mut enabled, self.i18n.get("side_panel.solve.enable"));
+				ui.add_enabled_ui(enabled, |ui| {
+					ui.horizontal(|ui| {
+						function_selector(ui, &self.i18n, "solve_function", &mut i, len);
+						ui.label("=");
+						ui.add(DragValue::new(&mut target).speed(0.1));
+					});
+				});
+
+				let solve = enabled.then_some(i);
+				if (solve != self.solve) | (target != self.solve_target) {
+					self.solve = solve;
+					self.solve_target = target;
+					self.solve_data = None;
+				}
+
+				if self.solve.is_some()
+					&& let Some(ref solve_data) = self.solve_data
+				{
+					let solutions: String = solve_data
+						.iter()
+						.map(|point| {
+							format!(
+								"x = {}\n",
+								self.settings.number_format.format(point.x, &self.i18n)
+							)
+						})
+						.collect();
+					ui.add(egui::TextEdit::multiline(&mut solutions.as_str()).code_editor());
+				}
+			});
+		}
+
+		ui.horizontal(|ui| {
+			self.settings.do_extrema.bitxor_assign(
+				ui.add(Button::new(self.i18n.get("side_panel.extrema")))
+					.on_hover_text(self.i18n.get(match self.settings.do_extrema {
+						true => "side_panel.extrema.hide",
+						false => "side_panel.extrema.show",
+					}))
+					.clicked(),
+			);
+
+			self.settings.do_roots.bitxor_assign(
+				ui.add(Button::new(self.i18n.get("side_panel.roots")))
+					.on_hover_text(self.i18n.get(match self.settings.do_roots {
+						true => "side_panel.roots.hide",
+						false => "side_panel.roots.show",
+					}))
+					.clicked(),
+			);
+
+			self.settings.do_trace.bitxor_assign(
+				ui.add(Button::new(self.i18n.get("side_panel.trace")))
+					.on_hover_text(self.i18n.get(match self.settings.do_trace {
+						true => "side_panel.trace.hide",
+						false => "side_panel.trace.show",
+					}))
+					.clicked(),
+			);
+		});
+
+		let functions_changed =
+			self.functions
+				.display_entries(ui, &self.i18n, &self.settings.number_format);
+
+		// Integrate over one period of a periodic function, starting at the lower bound
+		if let Some(period) = self.functions.take_integrate_period() {
+			let start = match self.settings.integral_bounds().0 {
+				start if start.is_finite() => start,
+				_ => 0.0,
+			};
+			self.settings.integral_min_x = start;
+			self.settings.integral_max_x = start + period;
+			self.settings.integral_changed = true;
+		}
+
+		if functions_changed {
+			self.intersection_data = None;
+			self.solve_data = None;
+		}
+
+		// Only render if there's enough space, which there isn't in the scrolling sheet
+		if !narrow && (ui.available_height() > crate::consts::FONT_SIZE) {
+			ui.with_layout(Layout::bottom_up(Align::Min), |ui| {
+				// Contents put in reverse order from bottom to top due to the 'buttom_up' layout
+
+				// Hyperlink to project's github
+				ui.hyperlink_to(
+					self.i18n.get("side_panel.open_source"),
+					"https://github.com/Titaniumtown/YTBN-Graphing-Software",
+				);
+			});
+		}
+	}
+}
+
+impl App for MathApp {
+	/// Called each time the UI needs repainting.
+	fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+		// start timer
+		let start = if self.opened.info {
+			Some(instant::Instant::now())
+		} else {
+			// if disabled, clear the stored formatted time
+			self.last_info.1 = None;
+
+			None
+		};
+
+		// If keyboard input isn't being grabbed, check for key combos
+		if !ctx.wants_keyboard_input() {
+			let pressed = |key: Key| ctx.input_mut(|x| x.consume_key(egui::Modifiers::NONE, key));
+
+			// Actions whose keys are pressed (`H` toggles the side panel by default, and so on)
+			for action in Action::ALL {
+				if !pressed(self.preferences.keys.get(action)) {
+					continue;
+				}
+
+				// Embedded figures can't be edited, only their view can be changed
+				if self.embedded & !matches!(action, Action::ResetView | Action::FitView) {
+					continue;
+				}
+
+				match action {
+					Action::TogglePanel => self.opened.side_panel = !self.opened.side_panel,
+					Action::AddFunction => {
+						if COLORS.len() > self.functions.len() {
+							self.functions.push_empty();
+						}
+					}
+					Action::ToggleHelp => self.opened.help = !self.opened.help,
+					Action::ToggleDarkMode => {
+						self.preferences.theme.dark_mode = !self.preferences.theme.dark_mode;
+						ctx.set_visuals(self.preferences.theme.visuals());
+					}
+					Action::ResetView => self.view_request = Some(ViewRequest::Reset),
+					Action::FitView => self.view_request = Some(ViewRequest::Fit),
+				}
+			}
+
+			// The arrow keys pan the plot, and + and - zoom it
+			for (key, request) in [
+				(Key::ArrowLeft, ViewRequest::Pan(-PAN_STEP, 0.0)),
+				(Key::ArrowRight, ViewRequest::Pan(PAN_STEP, 0.0)),
+				(Key::ArrowUp, ViewRequest::Pan(0.0, PAN_STEP)),
+				(Key::ArrowDown, ViewRequest::Pan(0.0, -PAN_STEP)),
+				(Key::Plus, ViewRequest::Zoom(ZOOM_STEP)),
+				(Key::Equals, ViewRequest::Zoom(ZOOM_STEP)),
+				(Key::Minus, ViewRequest::Zoom(1.0 / ZOOM_STEP)),
+			] {
+				if pressed(key) {
+					self.view_request = Some(request);
+				}
+			}
+		}
+
+		// Ctrl+= and Ctrl+- make the UI larger and smaller, and Ctrl+0 goes back to its default size
+		let shortcut = |key: Key| {
+			ctx.input_mut(|x| x.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, key)))
+		};
+		let ui_scale = &mut self.preferences.ui_scale;
+		if shortcut(Key::Equals) | shortcut(Key::Plus) {
+			*ui_scale = (*ui_scale + SCALE_STEP).min(*SCALE_RANGE.end());
+		}
+		if shortcut(Key::Minus) {
+			*ui_scale = (*ui_scale - SCALE_STEP).max(*SCALE_RANGE.start());
+		}
+		if shortcut(Key::Num0) {
+			*ui_scale = 1.0;
+		}
+
+		// Once a touchscreen is used, buttons are made larger and the keypad is shown
+		if !self.touch && ctx.input(|x| x.any_touches()) {
+			self.touch = true;
+			self.opened.keypad = !self.embedded;
+		}
+		self.preferences.apply_style(ctx, self.touch);
+
+		if self.preferences.language != self.i18n.language() {
+			self.i18n = Translations::new(self.preferences.language);
+		}
+
+		// Session files that were opened are loaded
+		let opened_session = self.opened_session.borrow_mut().take();
+		if let Some(bytes) = opened_session {
+			self.session_error = self.load_session(&bytes).err().map(|error| {
+				self.i18n
+					.format("top_bar.session.error", &error.to_string())
+			});
+		}
+
+		// JSON files dropped onto the window are loaded as sessions, and CSV files are added as data series (as long as there's room for more entries),
+		// unless the app is embedded
+		let dropped_files = match self.embedded {
+			true => Vec::new(),
+			false => ctx.input(|x| x.raw.dropped_files.clone()),
+		};
+		for file in dropped_files {
+			let is_session = file.name.ends_with(".json")
+				| file.path.as_ref().is_some_and(|path| {
+					path.extension()
+						.is_some_and(|extension| extension == "json")
+				});
+			if !is_session & (self.functions.len() >= COLORS.len()) {
+				continue;
+			}
+
+			let bytes = match (file.bytes, file.path) {
+				(Some(bytes), _) => Some(bytes.to_vec()),
+				(None, Some(path)) => std::fs::read(path).ok(),
+				(None, None) => None,
+			};
+			if let Some(bytes) = bytes {
+				match is_session {
+					true => {
+						self.session_error = self.load_session(&bytes).err().map(|error| {
+							self.i18n
+								.format("top_bar.session.error", &error.to_string())
+						});
+					}
+					false => self
+						.functions
+						.push_data_series(&String::from_utf8_lossy(&bytes)),
+				}
+			}
+		}
+
+		// Creates Top bar that contains some general options (which embedded figures don't have)
+		TopBottomPanel::top("top_bar").show_animated(ctx, !self.embedded, |ui| {
+			ui.horizontal(|ui| {
+				// Button in top bar to toggle showing the side panel
 				self.opened.side_panel.bitxor_assign(
-					ui.add(Button::new("Panel"))
-						.on_hover_text(match self.opened.side_panel {
-							true => "Hide Side Panel",
-							false => "Show Side Panel",
-						})
+					ui.add(Button::new(self.i18n.get("top_bar.panel")))
+						.on_hover_text(self.i18n.format(
+							match self.opened.side_panel {
+								true => "top_bar.panel.hide",
+								false => "top_bar.panel.show",
+							},
+							self.preferences.keys.get(Action::TogglePanel).name(),
+						))
 						.clicked(),
 				);
 
@@ -433,78 +3008,327 @@ impl App for MathApp {
 				if ui
 					.add_enabled(
 						COLORS.len() > self.functions.len(),
-						Button::new("Add Function"),
+						Button::new(self.i18n.get("top_bar.add_function")),
 					)
-					.on_hover_text("Create and graph new function")
+					.on_hover_text(self.i18n.format(
+						"top_bar.add_function.hover",
+						self.preferences.keys.get(Action::AddFunction).name(),
+					))
 					.clicked()
 				{
 					self.functions.push_empty();
 				}
 
+				// Buttons to reset the plot's view, or fit it to the functions
+				if ui
+					.add(Button::new(self.i18n.get("top_bar.reset_view")))
+					.on_hover_text(self.i18n.format(
+						"top_bar.reset_view.hover",
+						self.preferences.keys.get(Action::ResetView).name(),
+					))
+					.clicked()
+				{
+					self.view_request = Some(ViewRequest::Reset);
+				}
+
+				if ui
+					.add(Button::new(self.i18n.get("top_bar.fit_view")))
+					.on_hover_text(self.i18n.format(
+						"top_bar.fit_view.hover",
+						self.preferences.keys.get(Action::FitView).name(),
+					))
+					.clicked()
+				{
+					self.view_request = Some(ViewRequest::Fit);
+				}
+
+				// Exports the plot as an image the next time it's shown
+				ui.menu_button(self.i18n.get("top_bar.export_image"), |ui| {
+					ui.add(
+						egui::Slider::new(&mut self.export_scale, 1.0..=4.0)
+							.step_by(0.5)
+							.text(self.i18n.get("top_bar.export_image.resolution")),
+					)
+					.on_hover_text(self.i18n.get("top_bar.export_image.resolution.hover"));
+
+					for format in [ExportFormat::Png, ExportFormat::Svg] {
+						if ui
+							.button(self.i18n.format(
+								"top_bar.export_image.save",
+								&format.extension().to_uppercase(),
+							))
+							.clicked()
+						{
+							self.export_request = Some(format);
+							ui.close_menu();
+						}
+					}
+
+					ui.separator();
+					if ui
+						.button(self.i18n.get("top_bar.export_image.report"))
+						.on_hover_text(self.i18n.get("top_bar.export_image.report.hover"))
+						.clicked()
+					{
+						self.export_request = Some(ExportFormat::Report);
+						self.export_name = Some(String::from("report"));
+						ui.close_menu();
+					}
+				});
+
+				// Saves or loads the functions and settings as a JSON file
+				ui.menu_button(self.i18n.get("top_bar.session"), |ui| {
+					if ui
+						.button(self.i18n.get("top_bar.session.save"))
+						.on_hover_text(self.i18n.get("top_bar.session.save.hover"))
+						.clicked()
+					{
+						self.save_session();
+						ui.close_menu();
+					}
+
+					if ui
+						.button(self.i18n.get("top_bar.session.load"))
+						.on_hover_text(self.i18n.get("top_bar.session.load.hover"))
+						.clicked()
+					{
+						open_file(ctx, SESSION_FILE, ".json", &self.opened_session);
+						ui.close_menu();
+					}
+
+					if ui
+						.button(self.i18n.get("top_bar.session.share"))
+						.on_hover_text(self.i18n.get("top_bar.session.share.hover"))
+						.clicked()
+					{
+						if let Some(link) = self.share_link(false) {
+							ui.output_mut(|output| output.copied_text = link);
+						}
+						ui.close_menu();
+					}
+
+					if ui
+						.button(self.i18n.get("top_bar.session.embed"))
+						.on_hover_text(self.i18n.get("top_bar.session.embed.hover"))
+						.clicked()
+					{
+						if let Some(link) = self.share_link(true) {
+							let code = format!(
+								"<iframe src=\"{}\" width=\"800\" height=\"500\" style=\"border: none\"></iframe>",
+								link
+							);
+							ui.output_mut(|output| output.copied_text = code);
+						}
+						ui.close_menu();
+					}
+
+					if ui
+						.button(self.i18n.get("top_bar.session.reset"))
+						.on_hover_text(self.i18n.get("top_bar.session.reset.hover"))
+						.clicked()
+					{
+						self.reset();
+						ui.close_menu();
+					}
+
+					if let Some(ref error) = self.session_error {
+						ui.colored_label(Color32::RED, error);
+					}
+				});
+
+				// Toggles showing the table of values
+				self.opened.table.bitxor_assign(
+					ui.add(Button::new(self.i18n.get("top_bar.table")))
+						.on_hover_text(self.i18n.get(match self.opened.table {
+							true => "top_bar.table.hide",
+							false => "top_bar.table.show",
+						}))
+						.clicked(),
+				);
+
+				// Toggles showing the results panel
+				self.opened.results.bitxor_assign(
+					ui.add(Button::new(self.i18n.get("top_bar.results")))
+						.on_hover_text(self.i18n.get(match self.opened.results {
+							true => "top_bar.results.hide",
+							false => "top_bar.results.show",
+						}))
+						.clicked(),
+				);
+
+				// Toggles showing the on-screen keypad
+				self.opened.keypad.bitxor_assign(
+					ui.add(Button::new(self.i18n.get("top_bar.keypad")))
+						.on_hover_text(self.i18n.get(match self.opened.keypad {
+							true => "top_bar.keypad.hide",
+							false => "top_bar.keypad.show",
+						}))
+						.clicked(),
+				);
+
 				// Toggles opening the Help window
 				self.opened.help.bitxor_assign(
-					ui.add(Button::new("Help"))
-						.on_hover_text(match self.opened.help {
-							true => "Close Help Window",
-							false => "Open Help Window",
-						})
+					ui.add(Button::new(self.i18n.get("top_bar.help")))
+						.on_hover_text(self.i18n.format(
+							match self.opened.help {
+								true => "top_bar.help.close",
+								false => "top_bar.help.open",
+							},
+							self.preferences.keys.get(Action::ToggleHelp).name(),
+						))
+						.clicked(),
+				);
+
+				// Toggles opening the Settings window
+				self.opened.settings.bitxor_assign(
+					ui.add(Button::new(self.i18n.get("top_bar.settings")))
+						.on_hover_text(self.i18n.get(match self.opened.settings {
+							true => "top_bar.settings.close",
+							false => "top_bar.settings.open",
+						}))
 						.clicked(),
 				);
 
 				// Toggles opening the Info window
 				self.opened.info.bitxor_assign(
-					ui.add(Button::new("Info"))
-						.on_hover_text(match self.opened.info {
-							true => "Close Info Window",
-							false => "Open Info Window",
-						})
+					ui.add(Button::new(self.i18n.get("top_bar.info")))
+						.on_hover_text(self.i18n.get(match self.opened.info {
+							true => "top_bar.info.close",
+							false => "top_bar.info.open",
+						}))
 						.clicked(),
 				);
 
 				// Display Area and time of last frame
 				if let Some(ref area) = self.last_info.0 {
-					ui.label(area);
+					ui.label(self.i18n.format("plot.area", area));
+					if ui
+						.small_button(self.i18n.get("top_bar.copy"))
+						.on_hover_text(self.i18n.get("top_bar.copy.hover"))
+						.clicked()
+					{
+						ui.output_mut(|output| output.copied_text = area.clone());
+					}
 				}
 			});
 		});
 
+		// Tabs of the workspaces, each with its own functions and settings
+		let (mut switched, mut closed, mut added) = (None, None, false);
+		TopBottomPanel::top("workspaces").show_animated(ctx, !self.embedded, |ui| {
+			ui.horizontal(|ui| {
+				let closable = self.workspaces.len() > 1;
+				self.workspaces
+					.iter_mut()
+					.enumerate()
+					.for_each(|(i, workspace)| {
+						let tab = ui.selectable_label(i == self.workspace, &workspace.name);
+						if tab.clicked() {
+							switched = Some(i);
+						}
+
+						// Renamed or closed by right clicking on its tab
+						tab.on_hover_text(self.i18n.get("workspace.hover"))
+							.context_menu(|ui| {
+								ui.horizontal(|ui| {
+									ui.label(self.i18n.get("workspace.rename"));
+									ui.text_edit_singleline(&mut workspace.name);
+								});
+
+								if ui
+									.add_enabled(
+										closable,
+										Button::new(self.i18n.get("workspace.close")),
+									)
+									.clicked()
+								{
+									closed = Some(i);
+									ui.close_menu();
+								}
+							});
+					});
+
+				added = ui
+					.button("+")
+					.on_hover_text(self.i18n.get("workspace.add.hover"))
+					.clicked();
+			});
+		});
+
+		if let Some(i) = switched
+			&& i != self.workspace
+		{
+			self.store_workspace();
+			self.show_workspace(i);
+		}
+
+		if let Some(i) = closed {
+			self.close_workspace(i);
+		}
+
+		if added {
+			self.store_workspace();
+			self.workspaces.push(Workspace::new(
+				self.i18n
+					.format("workspace.name", &(self.workspaces.len() + 1).to_string()),
+				&self.preferences,
+			));
+			self.show_workspace(self.workspaces.len() - 1);
+		}
+
 		// Help window with information for users
-		Window::new("Help")
+		Window::new(self.i18n.get("help.title"))
+			.id(egui::Id::new("help"))
 			.open(&mut self.opened.help)
 			.default_pos([200.0, 200.0])
 			.resizable(false)
 			.collapsible(false)
 			.show(ctx, |ui| {
-				ui.collapsing("Supported Expressions", |ui| {
-					ui.label("abs, signum, sin, cos, tan, asin, acos, atan, sinh, cosh, tanh, floor, round, ceil, trunc, fract, exp, sqrt, cbrt, ln, log2, log10, log");
+				ui.collapsing(self.i18n.get("help.expressions"), |ui| {
+					ui.label(self.i18n.get("help.expressions.text"));
 				});
 
-				ui.collapsing("Supported Constants", |ui| {
-					ui.label("- Euler's number is supported via 'e' or 'E'\n- PI is available through 'pi' or 'π'");
+				ui.collapsing(self.i18n.get("help.constants"), |ui| {
+					let constants = CONSTANTS
+						.iter()
+						.map(|constant| {
+							let names = constant.names.iter().map(|name| format!("'{}'", name));
+							format!(
+								"- {}: {}",
+								self.i18n
+									.get(&format!("help.constants.{}", constant.names[0])),
+								names.format(self.i18n.get("help.constants.or"))
+							)
+						})
+						.join("\n");
+					ui.label(self.i18n.format("help.constants.text", &constants));
 				});
 
-				ui.collapsing("Panel", |ui| {
-					ui.label("- The 'Panel' button toggles if the side bar should be shown or not. This can also be accomplished by pressing the 'h' key.\n- The 'Add Function' button adds a new function to be graphed. You can then configure that function in the side panel.\n- The 'Help' button opens and closes this window!\n- The 'Info' button provides information on the build currently running.");
+				ui.collapsing(self.i18n.get("help.panel"), |ui| {
+					ui.label(self.i18n.get("help.panel.text"));
 				});
 
-				ui.collapsing("Functions", |ui| {
-					ui.label("(From Left to Right)\n`✖` allows you to delete the selected function. Deleting a function is prevented if only 1 function exists.\n`∫` toggles integration.\n`d/dx` toggles the calculation of derivatives.\n`⚙` opens a window to tweak function options.");
+				ui.collapsing(self.i18n.get("help.functions"), |ui| {
+					ui.label(self.i18n.get("help.functions.text"));
 				});
 
-				ui.collapsing("Other", |ui| {
-					ui.label("- Extrema (local minimums and maximums) and Roots (intersections with the x-axis) are displayed though yellow and light blue points respectively located on the graph. These can be toggled in the side panel.");
+				ui.collapsing(self.i18n.get("help.other"), |ui| {
+					ui.label(self.i18n.get("help.other.text"));
 				});
 			});
 
+		self.settings_window(ctx);
+
 		// Welcome window
 		if self.opened.welcome {
-			let welcome_response = Window::new("Welcome")
+			let welcome_response = Window::new(self.i18n.get("welcome.title"))
+				.id(egui::Id::new("welcome"))
 				.anchor(Align2::CENTER_CENTER, Vec2::ZERO)
 				.resizable(false)
 				.collapsible(false)
 				.title_bar(false)
 				.show(ctx, |ui| {
-					ui.label("Welcome to the (Yet-to-be-named) Graphing Software!\n\nThis project aims to provide an intuitive experience graphing mathematical functions with features such as Integration, Differentiation, Extrema, Roots, and much more! (see the Help Window for more details)");
+					ui.label(self.i18n.get("welcome.text"));
 				});
 
 			if let Some(response) = welcome_response {
@@ -516,7 +3340,8 @@ impl App for MathApp {
 		}
 
 		// Window with information about the build and current commit
-		Window::new("Info")
+		Window::new(self.i18n.get("info.title"))
+			.id(egui::Id::new("info"))
 			.open(&mut self.opened.info)
 			.default_pos([200.0, 200.0])
 			.resizable(false)
@@ -529,11 +3354,106 @@ impl App for MathApp {
 				}
 			});
 
+		// Parameters being animated move across their range, drawing frames until they're paused
+		let dt = ctx.input(|x| x.stable_dt).min(MAX_SWEEP_DT) as f64;
+		let mut playing = false;
+		for (name, sweep) in self.sweeps.iter_mut().filter(|(_, sweep)| sweep.playing) {
+			if let Some(value) = self.parameters.get_mut(name) {
+				*value = sweep.step(*value, dt);
+				playing = true;
+			}
+		}
+		if playing {
+			ctx.request_repaint();
+		}
+
 		// If side panel is enabled, show it.
 		if self.opened.side_panel {
 			self.side_panel(ctx);
 		}
 
+		// Functions are parsed from their entered text here rather than in the side panel, so embedded figures (which don't
+		// have one) are parsed too, and what the side panel changed is plotted right away
+		if self.functions.update_strings() {
+			self.intersection_data = None;
+			self.solve_data = None;
+		}
+
+		// User functions are only parsed again when their definitions change, and entries calling them are parsed again if those they call did
+		let definition_sources = self.functions.definition_sources();
+		if (definition_sources != self.definition_sources)
+			| (self.settings.case_sensitive != self.definitions.case_sensitive())
+		{
+			self.definitions = Definitions::new(&definition_sources, self.settings.case_sensitive);
+			self.definition_sources = definition_sources;
+		}
+		self.functions.set_definitions(&self.definitions);
+		self.functions.set_palette(self.preferences.theme.palette);
+		self.functions.set_angle_unit(self.settings.angle_unit);
+
+		// Every parameter is given a value (which the side panel's sliders change) even while the side panel is hidden, and the
+		// functions are evaluated with them
+		for name in self.functions.parameter_names() {
+			self.parameters.entry(name).or_insert(DEFAULT_PARAMETER);
+		}
+		self.functions.set_parameters(&self.parameters);
+
+		// The number of intervals being animated increases until it's back at the one that was set, exporting each frame while
+		// it's recorded
+		if self
+			.interval_animation
+			.is_some_and(|animation| animation.progress >= 1.0)
+		{
+			self.interval_animation = None;
+		} else if let Some(animation) = self.interval_animation.as_mut() {
+			animation.progress += match animation.record {
+				true => 1.0 / (INTERVAL_ANIMATION_DURATION * RECORD_FPS),
+				false => dt as f32 / INTERVAL_ANIMATION_DURATION,
+			};
+			self.settings.integral_num = animation.intervals();
+			self.settings.integral_changed = true;
+
+			if animation.record {
+				self.export_request = Some(ExportFormat::Png);
+				self.export_name = Some(format!("riemann_{:04}", animation.frame));
+				animation.frame += 1;
+			}
+			ctx.request_repaint();
+		}
+
+		if self.opened.table {
+			self.table_panel(ctx);
+		}
+
+		if self.opened.results {
+			self.results_panel(ctx);
+		}
+
+		if self.opened.keypad {
+			self.keypad_panel(ctx);
+		}
+
+		// Embedded figures only show the area below the plot, along with a link that opens them in the full app
+		if self.embedded {
+			TopBottomPanel::bottom("embed_bar").show(ctx, |ui| {
+				ui.horizontal(|ui| {
+					let open = ui
+						.small_button(self.i18n.get("embed.open"))
+						.on_hover_text(self.i18n.get("embed.open.hover"))
+						.clicked();
+					if open && let Some(link) = self.share_link(false) {
+						ui.output_mut(|output| {
+							output.open_url = Some(egui::OpenUrl::new_tab(link))
+						});
+					}
+
+					if let Some(ref area) = self.last_info.0 {
+						ui.label(self.i18n.format("plot.area", area));
+					}
+				});
+			});
+		}
+
 		// Central panel which contains the central plot (or an error created when parsing)
 		CentralPanel::default()
 			.frame(Frame {
@@ -549,14 +3469,25 @@ impl App for MathApp {
 					.functions
 					.get_entries()
 					.iter()
-					.map(|(_, func)| func.get_test_result())
+					.map(|(_, func)| (func.get_test_result(), func.correction()))
 					.enumerate()
-					.filter(|(_, error)| error.is_some())
-					.map(|(i, error)| {
+					.filter(|(_, (error, _))| error.is_some())
+					.map(|(i, (error, correction))| {
 						// use unwrap_unchecked as None Errors are already filtered out
-						unsafe {
-							format!("(Function #{}) {}\n", i, error.as_ref().unwrap_unchecked())
-						}
+						let error = self
+							.i18n
+							.error(unsafe { error.as_ref().unwrap_unchecked() });
+						let error = match correction {
+							Some((_, correction)) => self
+								.i18n
+								.format_all("plot.did_you_mean", &[error.as_str(), correction]),
+							None => error,
+						};
+						format!(
+							"({}) {}\n",
+							self.i18n.format("function.number", &i.to_string()),
+							error
+						)
 					})
 					.join("");
 
@@ -572,54 +3503,461 @@ impl App for MathApp {
 				self.settings.plot_width = available_width;
 
 				// Create and setup plot
-				Plot::new("plot")
+				// Pinching (or moving two fingers) on the plot zooms and pans it below, instead of the plot zooming around the first finger and dragging with it
+				let touch = ctx
+					.multi_touch()
+					.filter(|touch| ui.max_rect().contains(touch.start_pos));
+
+				let plot = Plot::new("plot")
 					.set_margin_fraction(Vec2::ZERO)
-					.data_aspect(1.0)
 					.include_y(0)
-					.show(ui, |plot_ui| {
-						let (min_x, max_x): (f64, f64) = {
+					.allow_zoom(touch.is_none())
+					.allow_drag(
+						self.hovered_bound.is_none()
+							&& self.dragged_bound.is_none()
+							&& self.hovered_annotation.is_none()
+							&& self.dragged_annotation.is_none()
+							&& touch.is_none(),
+					);
+
+				let plot = match self.lock_aspect {
+					true => plot.data_aspect(1.0),
+					false => plot,
+				};
+
+				// Label the x-axis in degrees, such as 90° instead of 90, or in fractions of π, such as π/2 instead of 1.5707963
+				let plot = match self.settings.angle_unit {
+					AngleUnit::Degrees if self.settings.degree_ticks => plot
+						.x_axis_formatter(|x, _, _| format!("{}°", emath::round_to_decimals(x, 6))),
+					AngleUnit::Radians if self.settings.pi_ticks => plot
+						.x_axis_formatter(|x, _, _| pi_label(x))
+						.x_grid_spacer(pi_grid_spacer),
+					_ => plot,
+				};
+
+				self.preferences.theme.apply_to_plot(ui);
+				plot.show(ui, |plot_ui| {
+					match self.view_request.take() {
+						Some(ViewRequest::Reset) => {
+							plot_ui.set_plot_bounds(PlotBounds::from_min_max(
+								[self.preferences.view_min, self.preferences.view_min],
+								[self.preferences.view_max, self.preferences.view_max],
+							))
+						}
+						Some(ViewRequest::Fit) => {
+							if let Some(bounds) = self.functions_extent() {
+								plot_ui.set_plot_bounds(bounds);
+							}
+						}
+						Some(ViewRequest::Set(bounds)) => plot_ui.set_plot_bounds(bounds),
+						Some(ViewRequest::Pan(x, y)) => {
 							let bounds = plot_ui.plot_bounds();
-							(bounds.min()[0], bounds.max()[0])
+							plot_ui.translate_bounds(Vec2::new(
+								(bounds.width() * x) as f32,
+								(bounds.height() * y) as f32,
+							));
+						}
+						Some(ViewRequest::Zoom(factor)) => {
+							let center = plot_ui.plot_bounds().center();
+							plot_ui.zoom_bounds(Vec2::splat(factor), center);
+						}
+						None => {}
+					}
+
+					// Zooms around where the fingers started, and pans along with them (flipping y, as it goes up on the plot but down on the screen)
+					if let Some(touch) = touch {
+						let transform = plot_ui.transform();
+						let center = transform.value_from_position(touch.start_pos);
+						let scale = transform.dvalue_dpos().map(|scale| scale as f32);
+						plot_ui.zoom_bounds(Vec2::splat(touch.zoom_delta), center);
+						plot_ui.translate_bounds(Vec2::new(
+							-touch.translation_delta.x * scale[0],
+							-touch.translation_delta.y * scale[1],
+						));
+					}
+
+					let bounds = plot_ui.plot_bounds();
+					let (min_x, max_x): (f64, f64) = (bounds.min()[0], bounds.max()[0]);
+
+					let min_max_changed =
+						(min_x != self.settings.min_x) | (max_x != self.settings.max_x);
+					let did_zoom = (max_x - min_x).abs()
+						!= (self.settings.max_x - self.settings.min_x).abs();
+					self.settings.min_x = min_x;
+					self.settings.max_x = max_x;
+					self.settings.min_y = bounds.min()[1];
+					self.settings.max_y = bounds.max()[1];
+
+					if self.functions.any_polar() {
+						polar_grid(plot_ui);
+					}
+
+					// Integral bounds are drawn as vertical lines, which can be dragged to change them
+					if self.functions.any_using_integral() {
+						self.drag_integral_bounds(plot_ui);
+					} else {
+						self.hovered_bound = None;
+						self.dragged_bound = None;
+					}
+
+					let ctx = plot_ui.ctx().clone();
+					self.functions
+						.get_entries_mut()
+						.iter_mut()
+						.for_each(|(_, function)| {
+							function.calculate(
+								width_changed,
+								min_max_changed,
+								did_zoom,
+								self.settings,
+							);
+							function.load_textures(&ctx);
+						});
+					self.record_areas();
+
+					let area: Vec<Option<String>> = self
+						.functions
+						.get_entries()
+						.iter()
+						.enumerate()
+						.map(|(i, (_, function))| {
+							function.display(
+								plot_ui,
+								&self.settings,
+								self.preferences.theme.color(i),
+								&self.i18n,
+							)
+						})
+						.collect();
+
+					// Annotations are drawn over the functions, and can be dragged to move them
+					self.drag_annotations(plot_ui);
+
+					// The number of intervals and the area are shown in the top left corner while the number is animated, which is
+					// included in the frames that are exported
+					let animation_label = self.interval_animation.map(|_| {
+						let scale = plot_ui.transform().dvalue_dpos().map(f64::abs);
+						(
+							PlotPoint::new(
+								min_x + (8.0 * scale[0]),
+								bounds.max()[1] - (24.0 * scale[1]),
+							),
+							self.i18n.format_all(
+								"plot.animation",
+								&[
+									self.settings.integral_num.to_string(),
+									option_vec_printer(area.as_slice()),
+								],
+							),
+						)
+					});
+					if let Some((position, label)) = &animation_label {
+						plot_ui.text(
+							Text::new(*position, label.clone())
+								.color(Color32::GRAY)
+								.anchor(Align2::LEFT_BOTTOM),
+						);
+					}
+
+					// Draws what's shown on the plot into an image, which is then saved
+					if let Some(format) = self.export_request.take() {
+						let transform = plot_ui.transform();
+						let mut image = ExportImage::new(
+							*transform.bounds(),
+							transform.frame().size(),
+							self.export_scale,
+						);
+						for (i, (_, function)) in self.functions.get_entries().iter().enumerate() {
+							function.export(
+								&mut image,
+								&self.settings,
+								self.preferences.theme.color(i),
+								&self.i18n,
+							);
+						}
+						for annotation in self.annotations.iter() {
+							if annotation.pinned {
+								image.push(ExportShape::Points {
+									points: vec![annotation.position()],
+									radius: ANNOTATION_RADIUS,
+									color: ANNOTATION_COLOR,
+								});
+							}
+							if !annotation.text.is_empty() {
+								image.push(ExportShape::Text {
+									position: annotation.position(),
+									text: annotation.text.clone(),
+									color: ANNOTATION_COLOR,
+								});
+							}
+						}
+						if let Some((position, label)) = animation_label {
+							image.push(ExportShape::Text {
+								position,
+								text: label,
+								color: Color32::GRAY,
+							});
+						}
+
+						let bytes = match format {
+							ExportFormat::Png => ctx.fonts(|fonts| image.to_png(fonts)),
+							ExportFormat::Svg => image.to_svg().into_bytes(),
+							ExportFormat::Report => report_html(
+								&image.to_svg(),
+								&utc_timestamp(unix_time()),
+								&self.report_functions(),
+								&self.i18n,
+							)
+							.into_bytes(),
 						};
+						let name = self.export_name.take();
+						save_file(
+							&format!(
+								"{}.{}",
+								name.as_deref().unwrap_or("plot"),
+								format.extension()
+							),
+							format.mime_type(),
+							&bytes,
+						);
+					}
 
-						let min_max_changed =
-							(min_x != self.settings.min_x) | (max_x != self.settings.max_x);
-						let did_zoom = (max_x - min_x).abs()
-							!= (self.settings.max_x - self.settings.min_x).abs();
-						self.settings.min_x = min_x;
-						self.settings.max_x = max_x;
+					// Mark the point of each function nearest to the mouse, along with its coordinates
+					if self.settings.do_trace
+						&& let Some(pointer) = plot_ui.pointer_coordinate()
+					{
+						let scale = plot_ui.transform().dvalue_dpos().map(f64::abs);
+						for (i, (_, function)) in self.functions.get_entries().iter().enumerate() {
+							let Some((point, derivative)) = function.trace(pointer, scale) else {
+								continue;
+							};
+
+							let color = function.color.unwrap_or(self.preferences.theme.color(i));
+							let round =
+								|value: f64| self.settings.number_format.format(value, &self.i18n);
+							let mut label = format!("({}, {})", round(point.x), round(point.y));
+							if let Some(derivative) = derivative {
+								label += &format!("\nf'(x) = {}", round(derivative));
+							}
+
+							plot_ui.points(vec![point].to_points().color(color).radius(5.0));
+							plot_ui.text(
+								Text::new(point, label)
+									.color(color)
+									.anchor(Align2::LEFT_BOTTOM),
+							);
+						}
+					}
 
-						self.functions
-							.get_entries_mut()
-							.iter_mut()
-							.for_each(|(_, function)| {
-								function.calculate(
-									width_changed,
-									min_max_changed,
-									did_zoom,
-									self.settings,
-								)
+					// Mark the intersections of the selected pair of functions
+					if let Some((a, b)) = self.intersections {
+						if min_max_changed | width_changed {
+							self.intersection_data = None;
+						}
+
+						let intersection_data =
+							self.intersection_data.get_or_insert_with(|| {
+								self.functions.intersections(a, b, &self.settings)
 							});
+						if !intersection_data.is_empty() {
+							plot_ui.points(
+								intersection_data
+									.clone()
+									.to_points()
+									.color(Color32::LIGHT_GREEN)
+									.name(self.i18n.get("plot.intersection"))
+									.radius(5.0),
+							);
+						}
+					}
+
+					// Mark the solutions of f(x) = c, along with the line y = c
+					if let Some(i) = self.solve
+						&& let Some((_, function)) = self.functions.get_entries_mut().get_mut(i)
+					{
+						if min_max_changed | width_changed {
+							self.solve_data = None;
+						}
 
-						let area: Vec<Option<f64>> = self
+						let target = self.solve_target;
+						let solve_data = self
+							.solve_data
+							.get_or_insert_with(|| function.solve(target, &self.settings));
+
+						plot_ui.line(
+							vec![PlotPoint::new(min_x, target), PlotPoint::new(max_x, target)]
+								.to_line()
+								.color(Color32::GRAY)
+								.style(LineStyle::dashed_loose()),
+						);
+						if !solve_data.is_empty() {
+							plot_ui.points(
+								solve_data
+									.clone()
+									.to_points()
+									.color(Color32::GOLD)
+									.name(self.i18n.get("plot.solution"))
+									.radius(5.0),
+							);
+						}
+					}
+
+					// Clicking on a function draws its tangent line at that point, clicking elsewhere adds an initial condition to slope fields
+					if plot_ui.response().clicked()
+						&& let Some(pointer) = plot_ui.pointer_coordinate()
+					{
+						/// Distance (in pixels) a click can be from a function and still select it
+						const CLICK_DISTANCE: f64 = 10.0;
+
+						let tolerance = CLICK_DISTANCE * (max_x - min_x)
+							/ (self.settings.plot_width as f64);
+						if let Some((_, function)) = self
 							.functions
-							.get_entries()
-							.iter()
-							.enumerate()
-							.map(|(i, (_, function))| {
-								function.display(plot_ui, &self.settings, COLORS[i])
+							.get_entries_mut()
+							.iter_mut()
+							.filter(|(_, function)| function.visible)
+							.filter_map(|(_, function)| {
+								function
+									.distance_to(pointer)
+									.map(|distance| (distance, function))
 							})
-							.collect();
-
-						self.last_info.0 = if area.iter().any(|e| e.is_some()) {
-							Some(format!("Area: {}", option_vec_printer(area.as_slice())))
+							.filter(|(distance, _)| *distance <= tolerance)
+							.min_by(|a, b| a.0.total_cmp(&b.0))
+						{
+							function.tangent_x = Some(pointer.x);
 						} else {
-							None
-						};
+							self.functions.get_entries_mut().iter_mut().for_each(
+								|(_, function)| function.add_initial_condition(pointer),
+							);
+						}
+					}
+
+					self.last_info.0 = if area.iter().any(|e| e.is_some()) {
+						Some(option_vec_printer(area.as_slice()))
+					} else {
+						None
+					};
+				});
+			});
+
+		// Window plotting the area calculated with an increasing number of rectangles
+		let settings = self.settings;
+		let theme = self.preferences.theme;
+		Window::new(self.i18n.get("convergence"))
+			.id(egui::Id::new("convergence"))
+			.open(&mut self.opened.convergence)
+			.default_pos([200.0, 200.0])
+			.default_size([300.0, 200.0])
+			.collapsible(false)
+			.show(ctx, |ui| {
+				Plot::new("convergence_plot")
+					.x_axis_label(self.i18n.get("convergence.rectangles"))
+					.y_axis_label(self.i18n.get("convergence.area"))
+					.show(ui, |plot_ui| {
+						self.functions
+							.get_entries_mut()
+							.iter_mut()
+							.enumerate()
+							.for_each(|(i, (_, function))| {
+								function.calculate_convergence(&settings);
+								if !function.convergence_data.is_empty() {
+									plot_ui.line(
+										function
+											.convergence_data
+											.clone()
+											.to_line()
+											.color(function.color.unwrap_or(theme.color(i))),
+									);
+								}
+							});
+					});
+			});
+
+		// Window listing every area calculated this session, so how it changed as the settings were changed can be compared
+		Window::new(self.i18n.get("area_history"))
+			.id(egui::Id::new("area_history"))
+			.open(&mut self.opened.area_history)
+			.default_pos([250.0, 250.0])
+			.default_size([400.0, 300.0])
+			.show(ctx, |ui| {
+				ui.horizontal(|ui| {
+					let has_history = !self.area_history.is_empty();
+					if ui
+						.add_enabled(
+							has_history,
+							Button::new(self.i18n.get("area_history.export")),
+						)
+						.on_hover_text(self.i18n.get("area_history.export.hover"))
+						.clicked()
+					{
+						save_file(
+							"area_history.csv",
+							"text/csv",
+							area_history_csv(&self.area_history).as_bytes(),
+						);
+					}
+					if ui
+						.add_enabled(
+							has_history,
+							Button::new(self.i18n.get("area_history.clear")),
+						)
+						.clicked()
+					{
+						self.area_history.clear();
+					}
+				});
+				ui.separator();
+
+				let format = |value: f64| self.settings.number_format.format(value, &self.i18n);
+				egui::ScrollArea::vertical()
+					.stick_to_bottom(true)
+					.show(ui, |ui| {
+						Grid::new("area_history").striped(true).show(ui, |ui| {
+							for header in [
+								"area_history.function",
+								"area_history.bounds",
+								"area_history.method",
+								"area_history.intervals",
+								"area_history.area",
+							] {
+								ui.label(self.i18n.get(header));
+							}
+							ui.end_row();
+
+							for record in self.area_history.iter() {
+								ui.label(record.function.as_str());
+								ui.label(format!(
+									"[{}, {}]",
+									format(record.min_x),
+									format(record.max_x)
+								));
+								ui.label(self.i18n.get(record.riemann_sum.label()));
+								ui.label(record.intervals.to_string());
+								ui.label(format(record.area));
+								ui.end_row();
+							}
+						});
 					});
 			});
 
+		// The app's state is saved to local storage at most once every `SAVE_INTERVAL`, after which another frame is drawn to save any changes made in the meantime.
+		// Embedded figures aren't saved, so they don't replace the state of the full app.
+		#[cfg(target_arch = "wasm32")]
+		if !self.embedded {
+			let elapsed = self.last_saved.elapsed();
+			match elapsed >= SAVE_INTERVAL {
+				true => {
+					self.last_saved = Instant::now();
+					self.save_state();
+				}
+				false => ctx.request_repaint_after(SAVE_INTERVAL - elapsed),
+			}
+		}
+
 		// Calculate and store the last time it took to draw the frame
-		self.last_info.1 = start.map(|a| format!("Took: {}ms", a.elapsed().as_micros()));
+		self.last_info.1 = start.map(|a| {
+			self.i18n
+				.format("info.took", &a.elapsed().as_micros().to_string())
+		});
 	}
 }
@@ -1,9 +1,9 @@
 use egui::Id;
-use egui_plot::{Line, PlotPoint, PlotPoints, Points};
+use egui_plot::{GridInput, GridMark, Line, PlotPoint, PlotPoints, Points};
 use emath::Pos2;
 use getrandom::getrandom;
 use itertools::Itertools;
-use parsing::FlatExWrapper;
+use parsing::{Expression, ExpressionWrapper};
 
 /// Implements traits that are useful when dealing with Vectors of egui's `Value`
 pub trait EguiHelper {
@@ -18,6 +18,9 @@ pub trait EguiHelper {
 
 	/// Converts Vector of Values into vector of tuples
 	fn to_tuple(self) -> Vec<(f64, f64)>;
+
+	/// Splits into segments after each index in `gaps` (and around values that aren't finite), so lines aren't drawn across discontinuities
+	fn to_segments(self, gaps: &[usize]) -> Vec<Vec<PlotPoint>>;
 }
 
 impl EguiHelper for Vec<PlotPoint> {
@@ -36,6 +39,22 @@ impl EguiHelper for Vec<PlotPoint> {
 	fn to_tuple(self) -> Vec<(f64, f64)> {
 		unsafe { std::mem::transmute::<Vec<PlotPoint>, Vec<(f64, f64)>>(self) }
 	}
+
+	fn to_segments(self, gaps: &[usize]) -> Vec<Vec<PlotPoint>> {
+		let mut segments: Vec<Vec<PlotPoint>> = vec![Vec::new()];
+		for (i, point) in self.into_iter().enumerate() {
+			if point.y.is_finite() {
+				segments.last_mut().unwrap().push(point);
+			}
+
+			if (!point.y.is_finite() | gaps.contains(&i)) && !segments.last().unwrap().is_empty() {
+				segments.push(Vec::new());
+			}
+		}
+
+		segments.retain(|segment| !segment.is_empty());
+		segments
+	}
 }
 
 pub trait Offset {
@@ -80,8 +99,8 @@ pub fn decimal_round(x: f64, n: usize) -> f64 {
 /// `f_1` is f'(x) aka the derivative of f(x)
 /// The function returns a Vector of `x` values where roots occur
 pub fn newtons_method_helper(
-	threshold: f64, range: &std::ops::Range<f64>, data: &[PlotPoint], f: &FlatExWrapper,
-	f_1: &FlatExWrapper,
+	threshold: f64, range: &std::ops::Range<f64>, data: &[PlotPoint], f: &ExpressionWrapper,
+	f_1: &ExpressionWrapper,
 ) -> Vec<f64> {
 	data.iter()
 		.tuple_windows()
@@ -99,19 +118,19 @@ pub fn newtons_method_helper(
 /// `f_1` is f'(x) aka the derivative of f(x)
 /// The function returns an `Option<f64>` of the x value at which a root occurs
 pub fn newtons_method(
-	f: &FlatExWrapper, f_1: &FlatExWrapper, start_x: f64, range: &std::ops::Range<f64>,
+	f: &ExpressionWrapper, f_1: &ExpressionWrapper, start_x: f64, range: &std::ops::Range<f64>,
 	threshold: f64,
 ) -> Option<f64> {
 	let mut x1: f64 = start_x;
 	let mut x2: f64;
 	let mut derivative: f64;
 	loop {
-		derivative = f_1.eval(&[x1]);
+		derivative = f_1.eval(x1);
 		if !derivative.is_finite() {
 			return None;
 		}
 
-		x2 = x1 - (f.eval(&[x1]) / derivative);
+		x2 = x1 - (f.eval(x1) / derivative);
 		if !x2.is_finite() | !range.contains(&x2) {
 			return None;
 		}
@@ -125,6 +144,781 @@ pub fn newtons_method(
 	}
 }
 
+/// Checks if `f` has a jump (or infinite) discontinuity between `a` and `b` by repeatedly halving the interval,
+/// keeping the half `f` changes the most over. The change shrinks towards 0 if `f` is continuous, but stays (or grows) across a discontinuity.
+pub fn is_discontinuous(f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, iterations: usize) -> bool {
+	/// Fraction of the original change that has to remain for it to be a discontinuity
+	const REMAINING: f64 = 0.5;
+
+	let (mut a, mut b) = (a, b);
+	let (mut fa, mut fb) = (f(a), f(b));
+	let initial = (fb - fa).abs();
+	if !initial.is_finite() {
+		return true;
+	}
+
+	for _ in 0..iterations {
+		let m = (a + b) / 2.0;
+		let fm = f(m);
+		if !fm.is_finite() {
+			return true;
+		}
+
+		if (fm - fa).abs() > (fb - fm).abs() {
+			(b, fb) = (m, fm);
+		} else {
+			(a, fa) = (m, fm);
+		}
+	}
+
+	(fb - fa).abs() > (initial * REMAINING)
+}
+
+/// Numerically estimates the limit of `f` as it approaches `x` from one side (`direction` being -1 for the left, 1 for the right)
+/// Samples `f` ever closer to `x`, then uses Richardson extrapolation to estimate the value at `x` itself
+/// The function returns an infinite value if `f` grows without bound, and NaN if `f` isn't defined near `x`
+pub fn one_sided_limit(f: &mut impl FnMut(f64) -> f64, x: f64, direction: f64) -> f64 {
+	/// Number of times the distance from `x` is halved
+	const LEVELS: usize = 10;
+
+	/// How much `f` has to keep growing by each time the distance is halved to be unbounded
+	const GROWTH: f64 = 1.2;
+
+	let h = 0.01 * (1.0 + x.abs());
+	let samples: Vec<f64> = (0..LEVELS)
+		.map(|k| f(x + (direction * h / (1usize << k) as f64)))
+		.collect();
+
+	let last = samples[LEVELS - 1];
+	if !last.is_finite() {
+		return last;
+	}
+
+	// Still growing quickly as it gets closer to `x`, so the limit is infinite
+	if samples
+		.windows(2)
+		.rev()
+		.take(3)
+		.all(|pair| pair[1].abs() > (GROWTH * pair[0].abs()))
+	{
+		return f64::INFINITY.copysign(last);
+	}
+
+	// Each column of the table removes the next power of h from the error
+	let mut table = samples;
+	for j in 1..LEVELS {
+		let factor = (1usize << j) as f64;
+		table = table
+			.windows(2)
+			.map(|pair| ((factor * pair[1]) - pair[0]) / (factor - 1.0))
+			.collect();
+	}
+
+	table[0]
+}
+
+/// Brent's method for finding a root of `f` in [`a`, `b`], where `f(a)` and `f(b)` have opposite signs
+/// Combines bisection, the secant method, and inverse quadratic interpolation
+/// The function returns an `Option<f64>` of the root, `None` if it isn't bracketed or wasn't found in `max_iterations`
+pub fn brent_method(
+	f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, tolerance: f64, max_iterations: usize,
+) -> Option<f64> {
+	let (mut a, mut b) = (a, b);
+	let (mut fa, mut fb) = (f(a), f(b));
+	if fa == 0.0 {
+		return Some(a);
+	} else if fb == 0.0 {
+		return Some(b);
+	} else if !fa.is_finite() | !fb.is_finite() | (fa.signum() == fb.signum()) {
+		return None;
+	}
+
+	// `b` is always the best estimate so far
+	if fa.abs() < fb.abs() {
+		std::mem::swap(&mut a, &mut b);
+		std::mem::swap(&mut fa, &mut fb);
+	}
+
+	let (mut c, mut fc) = (a, fa);
+	let mut d = c;
+	let mut bisected = true;
+
+	for _ in 0..max_iterations {
+		if (fb == 0.0) | ((b - a).abs() < tolerance) {
+			return Some(b);
+		}
+
+		let mut s = if (fa != fc) && (fb != fc) {
+			// Inverse quadratic interpolation
+			((a * fb * fc) / ((fa - fb) * (fa - fc)))
+				+ ((b * fa * fc) / ((fb - fa) * (fb - fc)))
+				+ ((c * fa * fb) / ((fc - fa) * (fc - fb)))
+		} else {
+			// Secant method
+			b - ((fb * (b - a)) / (fb - fa))
+		};
+
+		// Fall back to bisection if the interpolation isn't making enough progress
+		let bound = ((3.0 * a) + b) / 4.0;
+		let outside = !((bound.min(b) < s) && (s < bound.max(b)));
+		let last_step = match bisected {
+			true => (b - c).abs(),
+			false => (c - d).abs(),
+		};
+		if outside | ((s - b).abs() >= (last_step / 2.0)) | (last_step < tolerance) {
+			s = (a + b) / 2.0;
+			bisected = true;
+		} else {
+			bisected = false;
+		}
+
+		let fs = f(s);
+		d = c;
+		(c, fc) = (b, fb);
+
+		if fa.signum() != fs.signum() {
+			(b, fb) = (s, fs);
+		} else {
+			(a, fa) = (s, fs);
+		}
+
+		if fa.abs() < fb.abs() {
+			std::mem::swap(&mut a, &mut b);
+			std::mem::swap(&mut fa, &mut fb);
+		}
+	}
+
+	None
+}
+
+/// Polishes the approximate root `x` of `f` (which returns f(x) and f'(x)) to within `tolerance` using Newton's method
+/// Falls back to Brent's method over [`a`, `b`] if Newton's method leaves it or doesn't converge
+/// The function returns an `Option<f64>` of the refined root
+pub fn refine_root(
+	f: &mut impl FnMut(f64) -> (f64, f64), x: f64, a: f64, b: f64, tolerance: f64,
+) -> Option<f64> {
+	/// Maximum number of iterations of either method
+	const MAX_ITERATIONS: usize = 100;
+
+	// Tolerances smaller than the spacing of floats around the root can never be met
+	let tolerance = tolerance.max(4.0 * f64::EPSILON * x.abs());
+
+	let mut x = x;
+	for _ in 0..MAX_ITERATIONS {
+		let (y, derivative) = f(x);
+		if y == 0.0 {
+			return Some(x);
+		}
+
+		let next = x - (y / derivative);
+		if !next.is_finite() | !(a..=b).contains(&next) {
+			break;
+		}
+
+		if (next - x).abs() < tolerance {
+			return Some(next);
+		}
+		x = next;
+	}
+
+	brent_method(&mut |x| f(x).0, a, b, tolerance, MAX_ITERATIONS)
+}
+
+/// Finds every root of `f` (which returns f(x) and f'(x)) over [`min`, `max`]
+/// `f` is sampled at `samples` evenly spaced points, and each change in sign is refined to within `tolerance` via [`refine_root`]
+/// Sign changes across a discontinuity (such as 1/x at 0) aren't roots, and are discarded
+/// The function returns a Vector of `x` values where roots occur
+pub fn find_roots(
+	f: &mut impl FnMut(f64) -> (f64, f64), min: f64, max: f64, samples: usize, tolerance: f64,
+) -> Vec<f64> {
+	/// How close to 0 `f` has to be at a refined root for it to be counted
+	const MAX_RESIDUAL: f64 = 1e-6;
+
+	if samples < 2 {
+		return Vec::new();
+	}
+
+	let step = (max - min) / ((samples - 1) as f64);
+	let data: Vec<(f64, f64)> = (0..samples)
+		.map(|i| min + (step * (i as f64)))
+		.map(|x| (x, f(x).0))
+		.filter(|(_, y)| y.is_finite())
+		.collect();
+
+	let last_root = data.last().filter(|(_, y)| *y == 0.0).map(|(x, _)| *x);
+	data.into_iter()
+		.tuple_windows()
+		.filter_map(|((a, fa), (b, fb))| {
+			if fa == 0.0 {
+				Some(a)
+			} else if (fb != 0.0) && (fa.signum() != fb.signum()) {
+				refine_root(f, (a + b) / 2.0, a, b, tolerance)
+					.filter(|&root| f(root).0.abs() <= MAX_RESIDUAL)
+			} else {
+				None
+			}
+		})
+		.chain(last_root)
+		.collect()
+}
+
+/// Numerically detects the fundamental period of `f`, checking periods up to `max_period`
+/// `f` is compared against itself shifted by each candidate period, and the smallest shift where they line up is refined via a golden-section search
+/// The function returns `None` if `f` is constant or isn't periodic
+pub fn detect_period(f: &mut impl FnMut(f64) -> f64, max_period: f64) -> Option<f64> {
+	/// Number of points `f` is compared at
+	const SAMPLES: usize = 64;
+
+	/// Number of evenly spaced candidate periods checked before refining
+	const CANDIDATES: usize = 2000;
+
+	/// Candidates have to line up this much better than the average candidate to be refined
+	const COARSE_RATIO: f64 = 0.1;
+
+	/// Largest (relative) mismatch between `f` and its shifted self that is still considered periodic
+	const TOLERANCE: f64 = 1e-8;
+
+	/// Number of iterations of the golden-section search
+	const ITERATIONS: usize = 80;
+
+	// Offset the sample points so they don't line up with special points of `f` (such as the roots of sin)
+	let samples: Vec<(f64, f64)> = (0..SAMPLES)
+		.map(|i| 0.1234 + ((i as f64) * max_period / (SAMPLES as f64)))
+		.map(|x| (x, f(x)))
+		.filter(|(_, y)| y.is_finite())
+		.collect();
+
+	let (min, max) = samples.iter().map(|(_, y)| *y).minmax().into_option()?;
+	if (max - min) <= (TOLERANCE * (1.0 + max.abs().max(min.abs()))) {
+		return None;
+	}
+
+	// Average relative difference between `f` and `f` shifted by `period`
+	let mut mismatch = |period: f64| -> f64 {
+		let (total, count) = samples
+			.iter()
+			.map(|(x, y)| (f(x + period) - y).abs() / (1.0 + y.abs()))
+			.filter(|difference| difference.is_finite())
+			.fold((0.0, 0), |(total, count), difference| {
+				(total + difference, count + 1)
+			});
+		match count {
+			0 => f64::INFINITY,
+			_ => total / (count as f64),
+		}
+	};
+
+	let step = max_period / (CANDIDATES as f64);
+	let candidates: Vec<f64> = (0..=(CANDIDATES + 1))
+		.map(|i| mismatch((i as f64) * step))
+		.collect();
+	let average = candidates.iter().filter(|m| m.is_finite()).sum::<f64>() / (CANDIDATES as f64);
+
+	(1..=CANDIDATES)
+		.filter(|&i| (candidates[i - 1] >= candidates[i]) && (candidates[i] <= candidates[i + 1]))
+		.filter(|&i| candidates[i] <= (COARSE_RATIO * average))
+		.find_map(|i| {
+			// Golden-section search for the best period around the candidate
+			let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+			let (mut a, mut b) = (((i as f64) - 1.0) * step, ((i as f64) + 1.0) * step);
+			for _ in 0..ITERATIONS {
+				let c = b - (ratio * (b - a));
+				let d = a + (ratio * (b - a));
+				if mismatch(c) < mismatch(d) {
+					b = d;
+				} else {
+					a = c;
+				}
+			}
+
+			let period = (a + b) / 2.0;
+			(mismatch(period) <= TOLERANCE).then_some(period)
+		})
+}
+
+/// Maximum number of terms of a sequence that are calculated
+const MAX_TERMS: i64 = 100_000;
+
+/// Returns the terms a(n) of the sequence `a` for every integer n between `min_x` and `max_x` (and at least `start`), skipping terms that aren't defined
+pub fn sequence(
+	a: &mut impl FnMut(f64) -> f64, start: i64, min_x: f64, max_x: f64,
+) -> Vec<PlotPoint> {
+	let first = (min_x.ceil() as i64).max(start);
+	let last = (max_x.floor() as i64).min(first.saturating_add(MAX_TERMS));
+	(first..=last)
+		.map(|n| PlotPoint::new(n as f64, a(n as f64)))
+		.filter(|point| point.y.is_finite())
+		.collect()
+}
+
+/// Returns the partial sums S(n) = a(`start`) + ... + a(n) of the sequence `a` for every integer n between `min_x` and `max_x` (and at least `start`).
+/// Stops at the first term that isn't defined, or after [`MAX_TERMS`] terms.
+pub fn partial_sums(
+	a: &mut impl FnMut(f64) -> f64, start: i64, min_x: f64, max_x: f64,
+) -> Vec<PlotPoint> {
+	let last = (max_x.floor() as i64).min(start.saturating_add(MAX_TERMS));
+	let mut sum = 0.0;
+	let mut points = Vec::new();
+	for n in start..=last {
+		sum += a(n as f64);
+		if !sum.is_finite() {
+			break;
+		}
+
+		if (n as f64) >= min_x {
+			points.push(PlotPoint::new(n as f64, sum));
+		}
+	}
+	points
+}
+
+/// Parses (x, y) points from CSV, with one point per line and its coordinates separated by a comma, semicolon, or whitespace.
+/// Blank lines, lines starting with '#', and a header on the first line are skipped.
+pub fn parse_csv_points(csv: &str) -> Result<Vec<PlotPoint>, String> {
+	let mut points = Vec::new();
+	for (i, line) in csv.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() | line.starts_with('#') {
+			continue;
+		}
+
+		let values: Vec<Option<f64>> = line
+			.split(|c: char| (c == ',') | (c == ';') | c.is_whitespace())
+			.filter(|value| !value.is_empty())
+			.map(|value| value.parse::<f64>().ok())
+			.collect();
+
+		match values.as_slice() {
+			[Some(x), Some(y)] => points.push(PlotPoint::new(*x, *y)),
+			_ if points.is_empty() && (i == 0) => {}
+			_ => {
+				return Err(format!(
+					"Error: line {}: expected two numbers, found \"{}\"",
+					i + 1,
+					line
+				))
+			}
+		}
+	}
+	Ok(points)
+}
+
+/// Writes `rows` of numbers as CSV below the names of their columns in `header`, leaving values that aren't finite empty
+pub fn to_csv<const N: usize>(
+	header: [&str; N], rows: impl IntoIterator<Item = [f64; N]>,
+) -> String {
+	let mut csv = header.join(",");
+	for row in rows {
+		csv.push('\n');
+		csv.push_str(
+			&row.iter()
+				.map(|value| match value.is_finite() {
+					true => value.to_string(),
+					false => String::new(),
+				})
+				.join(","),
+		);
+	}
+	csv.push('\n');
+	csv
+}
+
+/// Evaluates `text` as a constant expression (such as `pi/2` or `sqrt(2)`), used for typing exact values into number fields.
+/// Returns `None` if it isn't a constant expression, or doesn't evaluate to a finite number.
+pub fn parse_number(text: &str) -> Option<f64> {
+	Expression::parse(text.trim(), &[])
+		.ok()
+		.map(|expression| expression.eval(0.0, 0.0))
+		.filter(|value| value.is_finite())
+}
+
+/// Snaps the integral's bounds `min_x` and `max_x` to the nearest of `points` (such as a function's roots), keeping their order.
+/// If both are nearest to the same point, they're snapped to the pair of consecutive points around their middle instead.
+/// Returns `None` if there aren't at least 2 distinct points to snap to.
+pub fn snap_bounds(min_x: f64, max_x: f64, points: &[f64]) -> Option<(f64, f64)> {
+	let points: Vec<f64> = points
+		.iter()
+		.cloned()
+		.filter(|x| x.is_finite())
+		.sorted_by(|a, b| a.total_cmp(b))
+		.dedup()
+		.collect();
+	if points.len() < 2 {
+		return None;
+	}
+
+	let nearest = |x: f64| {
+		(0..points.len())
+			.min_by(|&a, &b| (points[a] - x).abs().total_cmp(&(points[b] - x).abs()))
+			.unwrap_or(0)
+	};
+	let (lo, hi) = match min_x > max_x {
+		true => (max_x, min_x),
+		false => (min_x, max_x),
+	};
+	let (start, end) = match (nearest(lo), nearest(hi)) {
+		(start, end) if start != end => (start, end),
+		_ => {
+			let middle = (lo + hi) / 2.0;
+			let start = points
+				.partition_point(|x| *x <= middle)
+				.saturating_sub(1)
+				.min(points.len() - 2);
+			(start, start + 1)
+		}
+	};
+
+	Some(match min_x > max_x {
+		true => (points[end], points[start]),
+		false => (points[start], points[end]),
+	})
+}
+
+/// Largest denominator of the fractions of π that grid lines are placed at and labeled as
+const PI_DENOMINATOR: i64 = 8;
+
+/// Labels `x` as a multiple of π (such as `3π/2`), or as a number if it isn't a multiple of π/[`PI_DENOMINATOR`]
+pub fn pi_label(x: f64) -> String {
+	let parts = x / std::f64::consts::PI * (PI_DENOMINATOR as f64);
+	if (parts - parts.round()).abs() > 1e-6 * parts.abs().max(1.0) {
+		return emath::round_to_decimals(x, 6).to_string();
+	}
+
+	let parts = parts.round() as i64;
+	let (mut a, mut b) = (parts.abs(), PI_DENOMINATOR);
+	while b != 0 {
+		(a, b) = (b, a % b);
+	}
+	let (numerator, denominator) = (parts / a.max(1), PI_DENOMINATOR / a.max(1));
+
+	let multiple = match numerator {
+		0 => return String::from("0"),
+		1 => String::from("π"),
+		-1 => String::from("-π"),
+		numerator => format!("{}π", numerator),
+	};
+	match denominator {
+		1 => multiple,
+		denominator => format!("{}/{}", multiple, denominator),
+	}
+}
+
+/// Constants numbers are recognized as rational multiples of in [`closed_form`], the simplest first
+const CLOSED_FORM_CONSTANTS: [(f64, &str); 8] = [
+	(1.0, ""),
+	(std::f64::consts::PI, "π"),
+	(std::f64::consts::E, "e"),
+	(std::f64::consts::SQRT_2, "√2"),
+	(1.7320508075688772, "√3"),
+	(2.23606797749979, "√5"),
+	(std::f64::consts::LN_2, "ln2"),
+	(9.869604401089358, "π²"),
+];
+
+/// Largest denominator of the fractions [`closed_form`] recognizes
+const CLOSED_FORM_DENOMINATOR: i64 = 12;
+
+/// Largest numerator of the fractions [`closed_form`] recognizes, so only simple fractions are suggested
+const CLOSED_FORM_NUMERATOR: i64 = 100;
+
+/// Recognizes `value` as a simple closed form (such as `π/2`, `2/3`, or `3√2/4`) if it's within `tolerance` (relative to its size)
+/// of one, being a fraction or a fraction times one of [`CLOSED_FORM_CONSTANTS`].
+/// Returns `None` if it isn't close to one, or if it's an integer, which is already written exactly.
+pub fn closed_form(value: f64, tolerance: f64) -> Option<String> {
+	if !value.is_finite() | (value.round() == value) {
+		return None;
+	}
+
+	for (constant, name) in CLOSED_FORM_CONSTANTS {
+		for denominator in 1..=CLOSED_FORM_DENOMINATOR {
+			let numerator = (value * (denominator as f64) / constant).round();
+			if (numerator == 0.0)
+				| (numerator.abs() > CLOSED_FORM_NUMERATOR as f64)
+				| ((value - (numerator * constant / (denominator as f64))).abs()
+					> tolerance * value.abs().max(1.0))
+			{
+				continue;
+			}
+
+			let numerator = numerator as i64;
+			let (mut a, mut b) = (numerator.abs(), denominator);
+			while b != 0 {
+				(a, b) = (b, a % b);
+			}
+			// Found as a simpler fraction with a smaller denominator
+			if a != 1 {
+				continue;
+			}
+
+			let multiple = match (numerator, name) {
+				(numerator, "") => numerator.to_string(),
+				(1, name) => String::from(name),
+				(-1, name) => format!("-{}", name),
+				(numerator, name) => format!("{}{}", numerator, name),
+			};
+			return Some(match denominator {
+				1 => multiple,
+				denominator => format!("{}/{}", multiple, denominator),
+			});
+		}
+	}
+	None
+}
+
+/// Places grid lines at multiples of π/[`PI_DENOMINATOR`], π, or larger multiples of π, depending on how zoomed in the plot is.
+/// Too zoomed in for π/[`PI_DENOMINATOR`], they're placed like they are normally.
+pub fn pi_grid_spacer(input: GridInput) -> Vec<GridMark> {
+	use std::f64::consts::PI;
+
+	// Smallest multiple of π (out of 1/8, 1/4, 1/2, then 1, 2, and 5 times powers of 10) that's at least `min`
+	let pi_step = |min: f64| {
+		let mut multiple = 1.0 / (PI_DENOMINATOR as f64);
+		while multiple * PI < min {
+			multiple *= match (multiple / 10.0_f64.powf(multiple.log10().floor())).round() as u8 {
+				2 if multiple >= 1.0 => 2.5,
+				_ => 2.0,
+			};
+		}
+		multiple * PI
+	};
+
+	if input.base_step_size < PI / (PI_DENOMINATOR as f64) {
+		return egui_plot::log_grid_spacer(10)(input);
+	}
+
+	let small = pi_step(input.base_step_size);
+	let medium = pi_step(small * 1.5);
+	let large = pi_step(medium * 1.5);
+
+	let (min, max) = input.bounds;
+	((min / small).ceil() as i64..=(max / small).floor() as i64)
+		.map(|i| {
+			let value = (i as f64) * small;
+			let divides = |step: f64| ((value / step).round() * step - value).abs() < small * 1e-6;
+			let step_size = match (divides(large), divides(medium)) {
+				(true, _) => large,
+				(false, true) => medium,
+				(false, false) => small,
+			};
+			GridMark { value, step_size }
+		})
+		.collect()
+}
+
+/// Integrates the data `points` from `a` to `b` using the trapezoid rule, connecting the points (sorted by x) with straight lines.
+/// Only the part of [`a`, `b`] the points span is integrated, and the area is negative if `a` > `b`.
+pub fn trapezoid_points(points: &[PlotPoint], a: f64, b: f64) -> f64 {
+	if a > b {
+		return -trapezoid_points(points, b, a);
+	}
+
+	let mut sorted = points.to_vec();
+	sorted.sort_by(|p1, p2| p1.x.total_cmp(&p2.x));
+
+	sorted
+		.windows(2)
+		.map(|pair| {
+			let (p1, p2) = (pair[0], pair[1]);
+			let (left, right) = (p1.x.max(a), p2.x.min(b));
+			if left >= right {
+				return 0.0;
+			}
+
+			// Value of the line between `p1` and `p2` at `x`
+			let line = |x: f64| p1.y + ((p2.y - p1.y) * (x - p1.x) / (p2.x - p1.x));
+			(right - left) * (line(left) + line(right)) / 2.0
+		})
+		.sum()
+}
+
+/// Adaptive Simpson's quadrature of `f` over [`a`, `b`]
+/// `tolerance` is the target absolute error of the resulting area
+/// `max_depth` limits how many times an interval can be subdivided
+/// The function returns the area alongside each subinterval used in the form of `(left x, right x, area)`
+pub fn adaptive_simpson(
+	f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, tolerance: f64, max_depth: usize,
+) -> (f64, Vec<(f64, f64, f64)>) {
+	/// Recursively splits [`a`, `b`] in half until the estimate of the two halves agrees with `whole`
+	#[allow(clippy::too_many_arguments)]
+	fn helper(
+		f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, fa: f64, fm: f64, fb: f64, whole: f64,
+		tolerance: f64, depth: usize, intervals: &mut Vec<(f64, f64, f64)>,
+	) -> f64 {
+		let m = (a + b) / 2.0;
+		let (flm, frm) = (f((a + m) / 2.0), f((m + b) / 2.0));
+		let left = ((m - a) / 6.0) * (fa + (4.0 * flm) + fm);
+		let right = ((b - m) / 6.0) * (fm + (4.0 * frm) + fb);
+		let delta = left + right - whole;
+
+		// Stop subdividing if accurate enough, too deep, or if the function misbehaves
+		if (depth == 0) | !delta.is_finite() | (delta.abs() <= 15.0 * tolerance) {
+			let area = left + right + (delta / 15.0);
+			intervals.push((a, b, area));
+			return area;
+		}
+
+		helper(
+			f,
+			a,
+			m,
+			fa,
+			flm,
+			fm,
+			left,
+			tolerance / 2.0,
+			depth - 1,
+			intervals,
+		) + helper(
+			f,
+			m,
+			b,
+			fm,
+			frm,
+			fb,
+			right,
+			tolerance / 2.0,
+			depth - 1,
+			intervals,
+		)
+	}
+
+	let mut intervals = Vec::new();
+	let (fa, fm, fb) = (f(a), f((a + b) / 2.0), f(b));
+	let whole = ((b - a) / 6.0) * (fa + (4.0 * fm) + fb);
+	let area = helper(
+		f,
+		a,
+		b,
+		fa,
+		fm,
+		fb,
+		whole,
+		tolerance,
+		max_depth,
+		&mut intervals,
+	);
+
+	(area, intervals)
+}
+
+/// Integrates `f` over [`a`, `b`] via [`adaptive_simpson`], where either bound may be infinite
+/// Infinite bounds are handled by substituting `x` for a variable that has a finite range
+pub fn improper_integral(
+	f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, tolerance: f64, max_depth: usize,
+) -> f64 {
+	/// Distance kept from the ends of the substituted range, where the substitution itself is infinite
+	const EPSILON: f64 = 1e-9;
+
+	if a == b {
+		return 0.0;
+	} else if a > b {
+		return -improper_integral(f, b, a, tolerance, max_depth);
+	}
+
+	match (a.is_infinite(), b.is_infinite()) {
+		(false, false) => adaptive_simpson(f, a, b, tolerance, max_depth).0,
+		// x = t / (1 - t^2) for t in (-1, 1)
+		(true, true) => {
+			adaptive_simpson(
+				&mut |t| {
+					let d = 1.0 - (t * t);
+					f(t / d) * ((1.0 + (t * t)) / (d * d))
+				},
+				-1.0 + EPSILON,
+				1.0 - EPSILON,
+				tolerance,
+				max_depth,
+			)
+			.0
+		}
+		// x = a + t / (1 - t) for t in [0, 1)
+		(false, true) => {
+			adaptive_simpson(
+				&mut |t| f(a + (t / (1.0 - t))) / ((1.0 - t) * (1.0 - t)),
+				0.0,
+				1.0 - EPSILON,
+				tolerance,
+				max_depth,
+			)
+			.0
+		}
+		// x = b - (1 - t) / t for t in (0, 1]
+		(true, false) => {
+			adaptive_simpson(
+				&mut |t| f(b - ((1.0 - t) / t)) / (t * t),
+				EPSILON,
+				1.0,
+				tolerance,
+				max_depth,
+			)
+			.0
+		}
+	}
+}
+
+/// Integrates `f` over [`a`, `b`] where `f` is infinite at (or near) the bounds of the interval
+/// `x` is substituted so that `f` is never evaluated at the bounds themselves, then the area is checked to
+/// converge as the evaluated points approach the bounds. Returns `None` if the area diverges.
+pub fn singular_integral(
+	f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, tolerance: f64,
+) -> Option<f64> {
+	/// Maximum amount of times an interval can be split in half
+	const MAX_DEPTH: usize = 16;
+
+	let width = b - a;
+
+	// x = a + width * (3u^2 - 2u^3), which has a derivative of 0 at both ends
+	// (calculated relative to the closest bound in order to not lose precision near `b`)
+	let mut substituted = |u: f64| {
+		let x = match u < 0.5 {
+			true => a + (width * u * u * (3.0 - (2.0 * u))),
+			false => b - (width * (1.0 - u) * (1.0 - u) * (1.0 + (2.0 * u))),
+		};
+		f(x) * (6.0 * width * u * (1.0 - u))
+	};
+
+	let coarse = adaptive_simpson(&mut substituted, 1e-4, 1.0 - 1e-4, tolerance, MAX_DEPTH).0;
+	let fine = adaptive_simpson(&mut substituted, 1e-6, 1.0 - 1e-6, tolerance, MAX_DEPTH).0;
+
+	// If the area keeps on changing as the bounds are approached, the integral diverges
+	if fine.is_finite() && ((fine - coarse).abs() <= tolerance.max(1e-3 * fine.abs())) {
+		Some(fine)
+	} else {
+		None
+	}
+}
+
+/// Romberg integration of `f` over [`a`, `b`] using `levels` rows of Richardson extrapolation
+/// Row `k` starts with the trapezoid rule using 2^`k` intervals, each following column extrapolating further
+/// The function returns the triangular table of estimates, the last element of the last row being the most accurate
+pub fn romberg(f: &mut impl FnMut(f64) -> f64, a: f64, b: f64, levels: usize) -> Vec<Vec<f64>> {
+	let mut table: Vec<Vec<f64>> = Vec::with_capacity(levels);
+	let mut h = b - a;
+
+	for k in 0..levels.max(1) {
+		let trapezoid = if k == 0 {
+			(h / 2.0) * (f(a) + f(b))
+		} else {
+			// Reuse the previous row's trapezoid sum, only evaluating the new midpoints
+			h /= 2.0;
+			let new_points: f64 = (1..=(1usize << (k - 1)))
+				.map(|i| f(a + (((2 * i) - 1) as f64 * h)))
+				.sum();
+			(table[k - 1][0] / 2.0) + (h * new_points)
+		};
+
+		let mut row = Vec::with_capacity(k + 1);
+		row.push(trapezoid);
+		for j in 1..=k {
+			let factor = 4.0_f64.powi(j as i32);
+			row.push(row[j - 1] + ((row[j - 1] - table[k - 1][j - 1]) / (factor - 1.0)));
+		}
+		table.push(row);
+	}
+
+	table
+}
+
 /// Inputs `Vec<Option<T>>` and outputs a `String` containing a pretty representation of the Vector
 pub fn option_vec_printer<T: ToString>(data: &[Option<T>]) -> String {
 	let formatted: String = data
@@ -186,6 +980,70 @@ pub fn hashed_storage_read(data: &str) -> Option<(HashBytes, &[u8])> {
 	))
 }
 
+/// Characters of URL safe base64, in order of the values they represent
+const BASE64_ALPHABET: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Largest size (in bytes) data decoded from a URL's fragment can inflate to
+const MAX_FRAGMENT_SIZE: usize = 1 << 24;
+
+/// Compresses `data` and encodes it as URL safe base64 (without padding), so it can be put in a URL's fragment
+pub fn encode_fragment(data: &[u8]) -> String {
+	let compressed = miniz_oxide::deflate::compress_to_vec(data, 9);
+
+	let mut encoded = String::with_capacity(compressed.len().div_ceil(3) * 4);
+	compressed.chunks(3).for_each(|chunk| {
+		let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| {
+			bits | ((byte as u32) << (16 - 8 * i))
+		});
+
+		// Each byte is spread across (a little more than) one character
+		(0..=chunk.len()).for_each(|i| {
+			encoded.push(BASE64_ALPHABET[((bits >> (18 - 6 * i)) & 0x3F) as usize] as char);
+		});
+	});
+	encoded
+}
+
+/// Decodes data encoded with [`encode_fragment`], returns `None` if `fragment` isn't valid
+pub fn decode_fragment(fragment: &str) -> Option<Vec<u8>> {
+	let values = fragment
+		.bytes()
+		.map(|c| {
+			BASE64_ALPHABET
+				.iter()
+				.position(|&b| b == c)
+				.map(|i| i as u32)
+		})
+		.collect::<Option<Vec<u32>>>()?;
+
+	let mut compressed = Vec::with_capacity(values.len() * 3 / 4);
+	for chunk in values.chunks(4) {
+		// A single character can't encode a whole byte
+		if chunk.len() == 1 {
+			return None;
+		}
+
+		let bits = chunk
+			.iter()
+			.enumerate()
+			.fold(0u32, |bits, (i, &value)| bits | (value << (18 - 6 * i)));
+		(0..(chunk.len() - 1)).for_each(|i| compressed.push((bits >> (16 - 8 * i)) as u8));
+	}
+
+	miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_FRAGMENT_SIZE).ok()
+}
+
+/// If the flag `name` is set in the URL query `query` (such as "?embed=1"), either on its own or to a value other than "0" or
+/// "false"
+pub fn query_flag(query: &str, name: &str) -> bool {
+	query
+		.trim_start_matches('?')
+		.split('&')
+		.map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+		.any(|(key, value)| (key == name) & !matches!(value, "0" | "false"))
+}
+
 /// Creates and returns random u64
 pub fn random_u64() -> Result<u64, getrandom::Error> {
 	// Buffer of 8 `u8`s that are later merged into one u64
@@ -196,6 +1054,35 @@ pub fn random_u64() -> Result<u64, getrandom::Error> {
 	Ok(u64::from_be_bytes(buf))
 }
 
+/// Small xorshift pseudo-random number generator, used where calling `getrandom` for every number would be far too slow
+pub struct Xorshift(u64);
+
+impl Xorshift {
+	/// Creates a new generator from `seed` (a seed of 0 is replaced, as xorshift would only ever output 0)
+	pub const fn new(seed: u64) -> Self {
+		Self(match seed {
+			0 => 0x9E3779B97F4A7C15,
+			_ => seed,
+		})
+	}
+
+	/// Creates a new generator seeded via [`random_u64`]
+	pub fn from_entropy() -> Self { Self::new(random_u64().unwrap_or_default()) }
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	/// Returns a random `f64` in the range [0, 1)
+	pub fn next_f64(&mut self) -> f64 { (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 }
+
+	/// Returns a random `f64` in the range [`min`, `max`)
+	pub fn range_f64(&mut self, min: f64, max: f64) -> f64 { min + (self.next_f64() * (max - min)) }
+}
+
 include!(concat!(env!("OUT_DIR"), "/valid_chars.rs"));
 
 pub fn is_valid_char(c: char) -> bool { c.is_alphanumeric() | VALID_EXTRA_CHARS.contains(&c) }
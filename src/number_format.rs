@@ -0,0 +1,124 @@
+use crate::i18n::Translations;
+use serde::{Deserialize, Serialize};
+
+/// What the number of digits numbers are rounded to counts
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, Serialize, Deserialize)]
+pub enum Precision {
+	/// Digits after the decimal point
+	#[default]
+	DecimalPlaces,
+
+	/// Digits from the first one that isn't zero
+	SignificantFigures,
+}
+
+/// How numbers (such as areas, roots, and the values in tooltips) are written, which is set in the Settings window
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+	/// Number of digits numbers are rounded to
+	pub digits: usize,
+
+	/// If `digits` counts decimal places or significant figures
+	pub precision: Precision,
+
+	/// Numbers at least 10^`scientific_exponent`, or smaller than 10^-`scientific_exponent` (other than 0), are written in
+	/// scientific notation
+	pub scientific_exponent: i32,
+
+	/// If groups of three digits before the decimal point are separated by commas, such as 1,234,567
+	pub thousands_separators: bool,
+}
+
+impl const Default for NumberFormat {
+	fn default() -> NumberFormat {
+		Self {
+			digits: 8,
+			precision: Precision::DecimalPlaces,
+			scientific_exponent: 12,
+			thousands_separators: false,
+		}
+	}
+}
+
+impl NumberFormat {
+	/// Largest number of digits numbers can be rounded to, beyond which f64 isn't precise anyways
+	pub const MAX_DIGITS: usize = 15;
+
+	/// Writes `value` in this format, with NaN written as undefined in the language of `i18n`
+	pub fn format(&self, value: f64, i18n: &Translations) -> String {
+		if value.is_nan() {
+			return i18n.get("number.undefined").to_owned();
+		} else if value.is_infinite() {
+			return String::from(match value > 0.0 {
+				true => "∞",
+				false => "-∞",
+			});
+		}
+
+		let digits = self.digits.min(Self::MAX_DIGITS);
+		let exponent = match value == 0.0 {
+			true => 0,
+			false => value.abs().log10().floor() as i32,
+		};
+
+		if exponent.abs() >= self.scientific_exponent.max(1) {
+			// Digits of the mantissa after its decimal point, without trailing zeros
+			let decimals = match self.precision {
+				Precision::DecimalPlaces => digits,
+				Precision::SignificantFigures => digits.saturating_sub(1),
+			};
+			let scientific = format!("{:.*e}", decimals, value);
+			let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+			let mantissa = match mantissa.contains('.') {
+				true => mantissa.trim_end_matches('0').trim_end_matches('.'),
+				false => mantissa,
+			};
+			return format!("{}e{}", mantissa, exponent);
+		}
+
+		let decimals = match self.precision {
+			Precision::DecimalPlaces => digits as i32,
+			Precision::SignificantFigures => digits.max(1) as i32 - 1 - exponent,
+		};
+		let rounded = match decimals >= 0 {
+			true => emath::round_to_decimals(value, decimals as usize),
+			false => {
+				let scale = 10.0_f64.powi(-decimals);
+				(value / scale).round() * scale
+			}
+		};
+
+		// Numbers that round to 0 aren't written as -0
+		let text = match rounded == 0.0 {
+			true => String::from("0"),
+			false => rounded.to_string(),
+		};
+		match self.thousands_separators {
+			true => separate_thousands(&text),
+			false => text,
+		}
+	}
+}
+
+/// Separates groups of three digits before the decimal point of `number` with commas
+fn separate_thousands(number: &str) -> String {
+	let (sign, unsigned) = match number.strip_prefix('-') {
+		Some(unsigned) => ("-", unsigned),
+		None => ("", number),
+	};
+	let (integer, fraction) = match unsigned.find('.') {
+		Some(i) => unsigned.split_at(i),
+		None => (unsigned, ""),
+	};
+
+	let mut separated = String::from(sign);
+	for (i, digit) in integer.chars().enumerate() {
+		if (i != 0) & ((integer.len() - i) % 3 == 0) {
+			separated.push(',');
+		}
+		separated.push(digit);
+	}
+	separated.push_str(fraction);
+	separated
+}
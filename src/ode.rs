@@ -0,0 +1,160 @@
+use egui_plot::PlotPoint;
+
+/// Slope field of the differential equation dy/dx = f(x, y) over the plot, cached until the plot's bounds or width change
+#[derive(Clone)]
+pub struct SlopeField {
+	/// Bounds (minimum and maximum corners) and pixel width of the plot the slope field was calculated over
+	key: Option<([f64; 2], [f64; 2], usize)>,
+
+	/// Tails of the arrows
+	pub origins: Vec<PlotPoint>,
+
+	/// Heads of the arrows
+	pub tips: Vec<PlotPoint>,
+
+	/// Initial conditions (x₀, y₀) solution curves are drawn through
+	pub initial_conditions: Vec<PlotPoint>,
+
+	/// Solution curves through each of `initial_conditions`, empty if invalidated
+	pub solutions: Vec<Vec<PlotPoint>>,
+}
+
+impl const Default for SlopeField {
+	fn default() -> SlopeField {
+		SlopeField {
+			key: None,
+			origins: Vec::new(),
+			tips: Vec::new(),
+			initial_conditions: Vec::new(),
+			solutions: Vec::new(),
+		}
+	}
+}
+
+impl SlopeField {
+	/// Recalculates the slope field and solution curves if the plot's bounds (`min` and `max` corners) or `width` changed since they were last calculated
+	pub fn update(
+		&mut self, f: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2], max: [f64; 2], width: usize,
+	) {
+		/// Distance (in pixels) between neighboring arrows
+		const ARROW_SPACING: usize = 32;
+
+		/// Maximum number of rows of arrows, for plots much taller than they are wide
+		const MAX_ROWS: usize = 256;
+
+		let key = Some((min, max, width));
+		if self.key != key {
+			self.key = key;
+			self.solutions.clear();
+
+			let columns = (width / ARROW_SPACING).max(1);
+			let rows = ((columns as f64) * (max[1] - min[1]) / (max[0] - min[0]))
+				.round()
+				.clamp(1.0, MAX_ROWS as f64) as usize;
+			(self.origins, self.tips) = slope_field(f, min, max, [columns, rows]);
+		}
+
+		if self.solutions.is_empty() {
+			self.solutions = self
+				.initial_conditions
+				.iter()
+				.map(|initial| ode_solution(f, *initial, min, max, width))
+				.collect();
+		}
+	}
+
+	/// Invalidates the slope field and solution curves (keeping the initial conditions)
+	pub fn clear(&mut self) {
+		self.key = None;
+		self.origins.clear();
+		self.tips.clear();
+		self.solutions.clear();
+	}
+}
+
+/// Calculates the slope field of dy/dx = f(x, y) over the rectangle between the corners `min` and `max`.
+/// An arrow is placed in the center of each of the `cells` ([columns, rows]) cells, pointing along the slope f(x, y) with a length of 3/4 of the cell's width.
+/// Returns the tails and heads of the arrows, skipping cells where f(x, y) isn't defined.
+pub fn slope_field(
+	f: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2], max: [f64; 2], cells: [usize; 2],
+) -> (Vec<PlotPoint>, Vec<PlotPoint>) {
+	let [columns, rows] = cells;
+	let (dx, dy) = (
+		(max[0] - min[0]) / (columns as f64),
+		(max[1] - min[1]) / (rows as f64),
+	);
+	let half_length = 0.375 * dx;
+
+	(0..rows)
+		.flat_map(|j| (0..columns).map(move |i| (i, j)))
+		.filter_map(|(i, j)| {
+			let (x, y) = (
+				min[0] + (dx * (i as f64 + 0.5)),
+				min[1] + (dy * (j as f64 + 0.5)),
+			);
+			let slope = f(x, y);
+			if !slope.is_finite() {
+				return None;
+			}
+
+			// Unit vector along the slope, scaled to half of the arrow's length
+			let norm = slope.hypot(1.0);
+			let (offset_x, offset_y) = (half_length / norm, half_length * slope / norm);
+			Some((
+				PlotPoint::new(x - offset_x, y - offset_y),
+				PlotPoint::new(x + offset_x, y + offset_y),
+			))
+		})
+		.unzip()
+}
+
+/// Solves dy/dx = f(x, y) with the initial condition y(`start.x`) = `start.y` using the classic 4th order Runge-Kutta method.
+/// Steps of `step` (which is negative to solve backwards) are taken until `end` is passed, or the solution isn't defined.
+/// Returns the points of the solution, starting with `start`.
+pub fn rk4(
+	f: &mut impl FnMut(f64, f64) -> f64, start: PlotPoint, step: f64, end: f64,
+) -> Vec<PlotPoint> {
+	let steps = ((end - start.x) / step).ceil().max(0.0) as usize;
+	let mut points = Vec::with_capacity(steps + 1);
+	points.push(start);
+
+	let (mut x, mut y) = (start.x, start.y);
+	for _ in 0..steps {
+		let k1 = f(x, y);
+		let k2 = f(x + (step / 2.0), y + (step * k1 / 2.0));
+		let k3 = f(x + (step / 2.0), y + (step * k2 / 2.0));
+		let k4 = f(x + step, y + (step * k3));
+		y += step * (k1 + (2.0 * k2) + (2.0 * k3) + k4) / 6.0;
+		x += step;
+
+		if !y.is_finite() {
+			break;
+		}
+		points.push(PlotPoint::new(x, y));
+	}
+	points
+}
+
+/// Solution curve of dy/dx = f(x, y) through `initial` across the plot between the corners `min` and `max`, solved forwards and backwards with [`rk4`] using a step per pixel of the plot's `width`.
+/// The curve is cut off once it's more than the plot's height above or below the plot.
+pub fn ode_solution(
+	f: &mut impl FnMut(f64, f64) -> f64, initial: PlotPoint, min: [f64; 2], max: [f64; 2],
+	width: usize,
+) -> Vec<PlotPoint> {
+	let step = (max[0] - min[0]) / (width.max(1) as f64);
+	let height = max[1] - min[1];
+	let y_range = (min[1] - height)..=(max[1] + height);
+	let in_range = |point: &PlotPoint| y_range.contains(&point.y);
+
+	let backwards = rk4(f, initial, -step, min[0]);
+	let forwards = rk4(f, initial, step, max[0]);
+	backwards
+		.into_iter()
+		.skip(1)
+		.take_while(in_range)
+		.collect::<Vec<PlotPoint>>()
+		.into_iter()
+		.rev()
+		.chain(forwards.into_iter().take_while(in_range))
+		.collect()
+}
@@ -0,0 +1,55 @@
+/// Animation of a parameter back and forth across a range, which is played with the button next to its slider
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct ParameterSweep {
+	/// Value at one end of the range, which is also the slider's range
+	pub min: f64,
+
+	/// Value at the other end of the range
+	pub max: f64,
+
+	/// How much the parameter changes each second
+	pub speed: f64,
+
+	/// If the parameter is being animated, rather than paused
+	pub playing: bool,
+
+	/// If the parameter is going towards `max`, rather than back towards `min`
+	increasing: bool,
+}
+
+impl const Default for ParameterSweep {
+	fn default() -> ParameterSweep {
+		Self {
+			min: -10.0,
+			max: 10.0,
+			speed: 2.0,
+			playing: false,
+			increasing: true,
+		}
+	}
+}
+
+impl ParameterSweep {
+	/// Value of the parameter `dt` seconds after it was `value`, turning around at the ends of the range so that it goes back and
+	/// forth (more than once if it's fast enough)
+	pub fn step(&mut self, value: f64, dt: f64) -> f64 {
+		let (min, max) = (self.min.min(self.max), self.min.max(self.max));
+		let span = max - min;
+		// Empty (or infinite) ranges and values that aren't finite are moved to the start of the range
+		if !span.is_normal() | !value.is_finite() {
+			return min;
+		}
+
+		// Distance along a whole trip there and back, which is unfolded into a value in the range
+		let travelled = match self.increasing {
+			true => value.clamp(min, max) - min,
+			false => (2.0 * span) - (value.clamp(min, max) - min),
+		};
+		let travelled = (travelled + (self.speed.abs() * dt)).rem_euclid(2.0 * span);
+		self.increasing = travelled < span;
+		match self.increasing {
+			true => min + travelled,
+			false => min + (2.0 * span) - travelled,
+		}
+	}
+}
@@ -0,0 +1,152 @@
+use crate::consts::COLORS;
+use epaint::Color32;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Colors functions are automatically assigned, in order
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Hash, Serialize, Deserialize)]
+pub enum Palette {
+	/// The colors functions have always been assigned
+	#[default]
+	Default,
+
+	/// Okabe and Ito's palette, which can be told apart with deuteranopia and protanopia
+	OkabeIto,
+
+	/// Paul Tol's bright palette, which can also be told apart with deuteranopia and protanopia
+	TolBright,
+
+	/// IBM's palette for color vision deficiencies
+	Ibm,
+}
+
+/// Okabe and Ito's palette, with gray instead of black so it can be seen on the dark theme
+const OKABE_ITO: [Color32; 8] = [
+	Color32::from_rgb(230, 159, 0),
+	Color32::from_rgb(86, 180, 233),
+	Color32::from_rgb(0, 158, 115),
+	Color32::from_rgb(240, 228, 66),
+	Color32::from_rgb(0, 114, 178),
+	Color32::from_rgb(213, 94, 0),
+	Color32::from_rgb(204, 121, 167),
+	Color32::from_rgb(153, 153, 153),
+];
+
+/// Paul Tol's bright palette
+const TOL_BRIGHT: [Color32; 7] = [
+	Color32::from_rgb(68, 119, 170),
+	Color32::from_rgb(238, 102, 119),
+	Color32::from_rgb(34, 136, 51),
+	Color32::from_rgb(204, 187, 68),
+	Color32::from_rgb(102, 204, 238),
+	Color32::from_rgb(170, 51, 119),
+	Color32::from_rgb(187, 187, 187),
+];
+
+/// IBM's palette for color vision deficiencies
+const IBM: [Color32; 5] = [
+	Color32::from_rgb(100, 143, 255),
+	Color32::from_rgb(120, 94, 240),
+	Color32::from_rgb(220, 38, 127),
+	Color32::from_rgb(254, 97, 0),
+	Color32::from_rgb(255, 176, 0),
+];
+
+impl Palette {
+	/// Every palette, in the order they're listed in
+	pub const ALL: [Palette; 4] = [
+		Palette::Default,
+		Palette::OkabeIto,
+		Palette::TolBright,
+		Palette::Ibm,
+	];
+
+	/// Colors of the palette, which are cycled through
+	pub const fn colors(&self) -> &'static [Color32] {
+		match self {
+			Palette::Default => &COLORS,
+			Palette::OkabeIto => &OKABE_ITO,
+			Palette::TolBright => &TOL_BRIGHT,
+			Palette::Ibm => &IBM,
+		}
+	}
+
+	/// Color of the function `i`, starting over from the first color once every color has been used
+	pub const fn color(&self, i: usize) -> Color32 {
+		let colors = self.colors();
+		colors[i % colors.len()]
+	}
+
+	/// Key of the palette's name in the translations
+	pub const fn label(&self) -> &'static str {
+		match self {
+			Palette::Default => "palette.default",
+			Palette::OkabeIto => "palette.okabe_ito",
+			Palette::TolBright => "palette.tol_bright",
+			Palette::Ibm => "palette.ibm",
+		}
+	}
+}
+
+impl fmt::Display for Palette {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Palette::Default => write!(f, "Default"),
+			Palette::OkabeIto => write!(f, "Okabe-Ito"),
+			Palette::TolBright => write!(f, "Tol Bright"),
+			Palette::Ibm => write!(f, "IBM"),
+		}
+	}
+}
+
+/// Colors of the app, which are set in the Settings window
+#[derive(PartialEq, Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+	/// If the dark theme is used, rather than the light one
+	pub dark_mode: bool,
+
+	/// Colors functions are assigned (unless they're given one)
+	pub palette: Palette,
+
+	/// Color behind the plot, the theme's if `None`
+	pub plot_background: Option<Color32>,
+
+	/// Color of the plot's grid lines, the theme's if `None`
+	pub grid_color: Option<Color32>,
+}
+
+impl const Default for Theme {
+	fn default() -> Theme {
+		Self {
+			dark_mode: true,
+			palette: Palette::Default,
+			plot_background: None,
+			grid_color: None,
+		}
+	}
+}
+
+impl Theme {
+	/// Colors of egui's widgets
+	pub fn visuals(&self) -> egui::Visuals {
+		match self.dark_mode {
+			true => egui::Visuals::dark(),
+			false => egui::Visuals::light(),
+		}
+	}
+
+	/// Colors the plot shown in `ui` is drawn with, only the plot's `ui` should be given as the grid's color is set through the color of open widgets
+	pub fn apply_to_plot(&self, ui: &mut egui::Ui) {
+		let visuals = ui.visuals_mut();
+		if let Some(background) = self.plot_background {
+			visuals.extreme_bg_color = background;
+		}
+		if let Some(grid_color) = self.grid_color {
+			visuals.widgets.open.fg_stroke.color = grid_color;
+		}
+	}
+
+	/// Color of the function `i`
+	pub const fn color(&self, i: usize) -> Color32 { self.palette.color(i) }
+}
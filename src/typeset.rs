@@ -0,0 +1,303 @@
+use crate::consts::FONT_SIZE;
+use egui::Sense;
+use emath::{pos2, vec2, Align2, Pos2, Vec2};
+use epaint::{text::Fonts, Color32, FontId, Shape, Stroke};
+use parsing::Typeset;
+
+/// Smallest font size typeset math is shrunk to when it doesn't fit
+const MIN_FONT_SIZE: f32 = 8.0;
+
+/// Smallest font size of exponents, limits, and the like
+const MIN_SCRIPT_SIZE: f32 = 6.0;
+
+/// Size of exponents, limits, and the indices of roots relative to what they're attached to
+const SCRIPT_SCALE: f32 = 0.7;
+
+/// Size of numerators and denominators relative to the fraction
+const FRACTION_SCALE: f32 = 0.9;
+
+/// Size of large operators (such as Σ) relative to their body
+const BIG_OPERATOR_SCALE: f32 = 1.5;
+
+/// Thickness of lines (such as fraction bars) relative to the font size
+const LINE_WIDTH: f32 = 0.07;
+
+/// Part of typeset math, positioned relative to the top left corner of its [`Layout`]
+enum Item {
+	/// Text of the font size, with its top left corner at the position
+	Text(Pos2, String, f32),
+
+	/// Line through the points, of the width
+	Line(Vec<Pos2>, f32),
+}
+
+impl Item {
+	fn translate(self, offset: Vec2) -> Self {
+		match self {
+			Self::Text(pos, text, size) => Self::Text(pos + offset, text, size),
+			Self::Line(points, width) => Self::Line(
+				points.into_iter().map(|point| point + offset).collect(),
+				width,
+			),
+		}
+	}
+}
+
+/// Typeset math laid out in a box
+struct Layout {
+	size: Vec2,
+
+	/// Distance from the top of the box to the line it's vertically centered on when next to other layouts
+	axis: f32,
+
+	items: Vec<Item>,
+}
+
+impl Layout {
+	const EMPTY: Self = Self {
+		size: Vec2::ZERO,
+		axis: 0.0,
+		items: Vec::new(),
+	};
+
+	/// Items of the layout, moved to have its top left corner at `offset`
+	fn placed_at(self, offset: Vec2) -> impl Iterator<Item = Item> {
+		self.items
+			.into_iter()
+			.map(move |item| item.translate(offset))
+	}
+
+	fn text(fonts: &Fonts, text: &str, font_size: f32) -> Self {
+		let size = fonts
+			.layout_no_wrap(
+				text.to_owned(),
+				FontId::proportional(font_size),
+				Color32::WHITE,
+			)
+			.size();
+		Self {
+			size,
+			axis: size.y / 2.0,
+			items: vec![Item::Text(Pos2::ZERO, text.to_owned(), font_size)],
+		}
+	}
+
+	/// Lays out `layouts` next to each other, lining up their axes
+	fn row(layouts: Vec<Self>) -> Self {
+		let above = layouts.iter().map(|layout| layout.axis).fold(0.0, f32::max);
+		let below = layouts
+			.iter()
+			.map(|layout| layout.size.y - layout.axis)
+			.fold(0.0, f32::max);
+
+		let mut x = 0.0;
+		let mut items = Vec::new();
+		for layout in layouts {
+			let width = layout.size.x;
+			items.extend(layout.placed_at(vec2(x, above - layout.axis)));
+			x += width;
+		}
+
+		Self {
+			size: vec2(x, above + below),
+			axis: above,
+			items,
+		}
+	}
+
+	/// Lays out `typeset` with the font size `font_size`
+	fn new(fonts: &Fonts, typeset: &Typeset, font_size: f32) -> Self {
+		let script_size = (font_size * SCRIPT_SCALE).max(MIN_SCRIPT_SIZE);
+		let line_width = font_size * LINE_WIDTH;
+		match typeset {
+			Typeset::Text(text) => Self::text(fonts, text, font_size),
+			Typeset::Row(typesets) => Self::row(
+				typesets
+					.iter()
+					.map(|typeset| Self::new(fonts, typeset, font_size))
+					.collect(),
+			),
+			Typeset::Fraction(numerator, denominator) => {
+				let part_size = (font_size * FRACTION_SCALE).max(MIN_SCRIPT_SIZE);
+				let numerator = Self::new(fonts, numerator, part_size);
+				let denominator = Self::new(fonts, denominator, part_size);
+				let padding = font_size * 0.15;
+				let width = numerator.size.x.max(denominator.size.x) + (2.0 * padding);
+				let bar = numerator.size.y + padding;
+
+				let mut items = vec![Item::Line(
+					vec![pos2(0.0, bar), pos2(width, bar)],
+					line_width,
+				)];
+				let (numerator_size, denominator_size) = (numerator.size, denominator.size);
+				items.extend(numerator.placed_at(vec2((width - numerator_size.x) / 2.0, 0.0)));
+				items.extend(
+					denominator.placed_at(vec2((width - denominator_size.x) / 2.0, bar + padding)),
+				);
+
+				// Spaced out from its neighbors so the bar doesn't run into them
+				Self::row(vec![
+					Self::space(padding),
+					Self {
+						size: vec2(width, bar + padding + denominator_size.y),
+						axis: bar,
+						items,
+					},
+					Self::space(padding),
+				])
+			}
+			Typeset::Power(base, exponent) => {
+				let base = Self::new(fonts, base, font_size);
+				let exponent = Self::new(fonts, exponent, script_size);
+
+				// The bottom of the exponent is level with the axis of the base
+				let base_top = (exponent.size.y - base.axis).max(0.0);
+				let (base_size, base_axis, exponent_size) = (base.size, base.axis, exponent.size);
+				let mut items: Vec<Item> = base.placed_at(vec2(0.0, base_top)).collect();
+				items.extend(
+					exponent.placed_at(vec2(base_size.x, base_top + base_axis - exponent_size.y)),
+				);
+
+				Self {
+					size: vec2(base_size.x + exponent_size.x, base_top + base_size.y),
+					axis: base_top + base_axis,
+					items,
+				}
+			}
+			Typeset::Root(radicand, index) => {
+				let radicand = Self::new(fonts, radicand, font_size);
+				let sign_width = font_size * 0.6;
+				let gap = font_size * 0.15;
+				let height = radicand.size.y + gap;
+
+				// The index sits above the tick at the start of the root sign
+				let index = match index {
+					Some(index) => Self::new(fonts, index, script_size),
+					None => Self::EMPTY,
+				};
+				let left = (index.size.x - (sign_width * 0.3)).max(0.0);
+				let top = (index.size.y - (height * 0.5)).max(0.0);
+
+				let (radicand_size, radicand_axis) = (radicand.size, radicand.axis);
+				let width = left + sign_width + radicand_size.x + gap;
+				let sign = [(0.0, 0.6), (0.25, 0.5), (0.55, 1.0), (1.0, 0.0)]
+					.into_iter()
+					.map(|(x, y)| pos2(left + (sign_width * x), top + (height * y)))
+					.chain([pos2(width, top)])
+					.collect();
+
+				let index_size = index.size;
+				let mut items = vec![Item::Line(sign, line_width)];
+				items.extend(index.placed_at(vec2(
+					(left + (sign_width * 0.3) - index_size.x).max(0.0),
+					top + (height * 0.5) - index_size.y,
+				)));
+				items.extend(radicand.placed_at(vec2(left + sign_width, top + gap)));
+
+				Self {
+					size: vec2(width, top + height),
+					axis: top + gap + radicand_axis,
+					items,
+				}
+			}
+			Typeset::Delimited(open, inner, close) => {
+				let inner = Self::new(fonts, inner, font_size);
+
+				// Delimiters are centered on the axis, and tall enough to cover the inner layout on both sides of it
+				let height = (2.0 * inner.axis.max(inner.size.y - inner.axis))
+					.max(fonts.row_height(&FontId::proportional(font_size)));
+				let delimiter = |c: char| match c {
+					'|' => {
+						let width = font_size * 0.3;
+						Self {
+							size: vec2(width, height),
+							axis: height / 2.0,
+							items: vec![Item::Line(
+								vec![pos2(width / 2.0, 0.0), pos2(width / 2.0, height)],
+								line_width,
+							)],
+						}
+					}
+					_ => {
+						// Glyphs are about as tall as the height of a row of text of their size
+						let scale = height / fonts.row_height(&FontId::proportional(font_size));
+						Self::text(fonts, &c.to_string(), font_size * scale)
+					}
+				};
+				Self::row(vec![delimiter(*open), inner, delimiter(*close)])
+			}
+			Typeset::BigOperator(operator, below, above, body) => {
+				let operator =
+					Self::text(fonts, &operator.to_string(), font_size * BIG_OPERATOR_SCALE);
+				let below = Self::new(fonts, below, script_size);
+				let above = Self::new(fonts, above, script_size);
+				let width = operator.size.x.max(below.size.x).max(above.size.x);
+
+				// Limits are centered above and below the operator
+				let (operator_size, operator_axis) = (operator.size, operator.axis);
+				let (below_size, above_size) = (below.size, above.size);
+				let mut items: Vec<Item> = above
+					.placed_at(vec2((width - above_size.x) / 2.0, 0.0))
+					.collect();
+				items.extend(
+					operator.placed_at(vec2((width - operator_size.x) / 2.0, above_size.y)),
+				);
+				items.extend(below.placed_at(vec2(
+					(width - below_size.x) / 2.0,
+					above_size.y + operator_size.y,
+				)));
+
+				Self::row(vec![
+					Self {
+						size: vec2(width, above_size.y + operator_size.y + below_size.y),
+						axis: above_size.y + operator_axis,
+						items,
+					},
+					Self::space(font_size * 0.2),
+					Self::new(fonts, body, font_size),
+				])
+			}
+		}
+	}
+
+	/// Empty space of width `width`
+	fn space(width: f32) -> Self {
+		Self {
+			size: vec2(width, 0.0),
+			..Self::EMPTY
+		}
+	}
+}
+
+/// Shows `typeset` as typeset math, shrinking it (down to a limit) to fit in the available width
+pub fn typeset_label(ui: &mut egui::Ui, typeset: &Typeset) -> egui::Response {
+	let available_width = ui.available_width();
+	let mut layout = ui.fonts(|fonts| Layout::new(fonts, typeset, FONT_SIZE));
+	if (layout.size.x > available_width) && (available_width > 0.0) {
+		let font_size = (FONT_SIZE * available_width / layout.size.x).max(MIN_FONT_SIZE);
+		layout = ui.fonts(|fonts| Layout::new(fonts, typeset, font_size));
+	}
+
+	let (rect, response) = ui.allocate_exact_size(layout.size, Sense::hover());
+	if ui.is_rect_visible(rect) {
+		let color = ui.visuals().text_color();
+		let painter = ui.painter();
+		for item in layout.placed_at(rect.min.to_vec2()) {
+			match item {
+				Item::Text(pos, text, font_size) => {
+					painter.text(
+						pos,
+						Align2::LEFT_TOP,
+						text,
+						FontId::proportional(font_size),
+						color,
+					);
+				}
+				Item::Line(points, width) => {
+					painter.add(Shape::line(points, Stroke::new(width, color)));
+				}
+			}
+		}
+	}
+	response
+}
@@ -0,0 +1,136 @@
+use crate::misc::adaptive_simpson;
+use egui_plot::PlotPoint;
+
+/// Vector field F(x, y) = (P(x, y), Q(x, y)) over the plot, cached until the plot's bounds or width change
+#[derive(Clone)]
+pub struct VectorField {
+	/// Bounds (minimum and maximum corners) and pixel width of the plot the vector field was calculated over
+	key: Option<([f64; 2], [f64; 2], usize)>,
+
+	/// Tails of the arrows
+	pub origins: Vec<PlotPoint>,
+
+	/// Heads of the arrows
+	pub tips: Vec<PlotPoint>,
+}
+
+impl const Default for VectorField {
+	fn default() -> VectorField {
+		VectorField {
+			key: None,
+			origins: Vec::new(),
+			tips: Vec::new(),
+		}
+	}
+}
+
+impl VectorField {
+	/// Recalculates the vector field if the plot's bounds (`min` and `max` corners) or `width` changed since it was last calculated
+	pub fn update(
+		&mut self, p: &mut impl FnMut(f64, f64) -> f64, q: &mut impl FnMut(f64, f64) -> f64,
+		min: [f64; 2], max: [f64; 2], width: usize,
+	) {
+		/// Distance (in pixels) between neighboring arrows
+		const ARROW_SPACING: usize = 40;
+
+		/// Maximum number of rows of arrows, for plots much taller than they are wide
+		const MAX_ROWS: usize = 256;
+
+		let key = Some((min, max, width));
+		if self.key == key {
+			return;
+		}
+		self.key = key;
+
+		let columns = (width / ARROW_SPACING).max(1);
+		let rows = ((columns as f64) * (max[1] - min[1]) / (max[0] - min[0]))
+			.round()
+			.clamp(1.0, MAX_ROWS as f64) as usize;
+		(self.origins, self.tips) = vector_field(p, q, min, max, [columns, rows]);
+	}
+
+	/// Invalidates the vector field
+	pub fn clear(&mut self) {
+		self.key = None;
+		self.origins.clear();
+		self.tips.clear();
+	}
+}
+
+/// Calculates the vector field (P(x, y), Q(x, y)) over the rectangle between the corners `min` and `max`.
+/// An arrow is placed in the center of each of the `cells` ([columns, rows]) cells, scaled so the longest arrow is 9/10 of the cell's size.
+/// Returns the tails and heads of the arrows, skipping cells where the field is zero or isn't defined.
+pub fn vector_field(
+	p: &mut impl FnMut(f64, f64) -> f64, q: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2],
+	max: [f64; 2], cells: [usize; 2],
+) -> (Vec<PlotPoint>, Vec<PlotPoint>) {
+	let [columns, rows] = cells;
+	let (dx, dy) = (
+		(max[0] - min[0]) / (columns as f64),
+		(max[1] - min[1]) / (rows as f64),
+	);
+
+	let vectors: Vec<(PlotPoint, f64, f64)> = (0..rows)
+		.flat_map(|j| (0..columns).map(move |i| (i, j)))
+		.filter_map(|(i, j)| {
+			let (x, y) = (
+				min[0] + (dx * (i as f64 + 0.5)),
+				min[1] + (dy * (j as f64 + 0.5)),
+			);
+			let (vector_x, vector_y) = (p(x, y), q(x, y));
+			let magnitude = vector_x.hypot(vector_y);
+			(magnitude.is_finite() && (magnitude > 0.0)).then_some((
+				PlotPoint::new(x, y),
+				vector_x,
+				vector_y,
+			))
+		})
+		.collect();
+
+	let max_magnitude = vectors
+		.iter()
+		.map(|(_, vector_x, vector_y)| vector_x.hypot(*vector_y))
+		.fold(0.0, f64::max);
+	let scale = 0.9 * dx.min(dy) / max_magnitude;
+
+	vectors
+		.into_iter()
+		.map(|(center, vector_x, vector_y)| {
+			let (offset_x, offset_y) = (scale * vector_x / 2.0, scale * vector_y / 2.0);
+			(
+				PlotPoint::new(center.x - offset_x, center.y - offset_y),
+				PlotPoint::new(center.x + offset_x, center.y + offset_y),
+			)
+		})
+		.unzip()
+}
+
+/// Calculates the line integrals of the vector field (P(x, y), Q(x, y)) counterclockwise around the rectangle between the corners `min` and `max`.
+/// Returns the circulation ∮ P dx + Q dy and the (outward) flux ∮ P dy - Q dx.
+pub fn line_integrals(
+	p: &mut impl FnMut(f64, f64) -> f64, q: &mut impl FnMut(f64, f64) -> f64, min: [f64; 2],
+	max: [f64; 2],
+) -> (f64, f64) {
+	/// Tolerance each side of the rectangle is integrated to
+	const TOLERANCE: f64 = 1e-10;
+
+	/// Maximum number of times each side of the rectangle is split in half
+	const MAX_DEPTH: usize = 20;
+
+	// Integrals of P or Q along the sides of the rectangle, from the lower bound to the upper one
+	let horizontal = |f: &mut dyn FnMut(f64, f64) -> f64, y: f64| {
+		adaptive_simpson(&mut |x| f(x, y), min[0], max[0], TOLERANCE, MAX_DEPTH).0
+	};
+	let (p_bottom, p_top) = (horizontal(p, min[1]), horizontal(p, max[1]));
+	let (q_bottom, q_top) = (horizontal(q, min[1]), horizontal(q, max[1]));
+
+	let vertical = |f: &mut dyn FnMut(f64, f64) -> f64, x: f64| {
+		adaptive_simpson(&mut |y| f(x, y), min[1], max[1], TOLERANCE, MAX_DEPTH).0
+	};
+	let (p_left, p_right) = (vertical(p, min[0]), vertical(p, max[0]));
+	let (q_left, q_right) = (vertical(q, min[0]), vertical(q, max[0]));
+
+	let circulation = p_bottom + q_right - p_top - q_left;
+	let flux = p_right - p_left + q_top - q_bottom;
+	(circulation, flux)
+}
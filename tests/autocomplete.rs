@@ -4,7 +4,9 @@ enum Action<'a> {
 	AssertIndex(usize),
 	AssertString(&'a str),
 	AssertHint(&'a str),
+	AssertCursor(usize),
 	SetString(&'a str),
+	SetStringAt(&'a str, usize),
 	Move(Movement),
 }
 use Action::*;
@@ -56,9 +58,20 @@ fn ac_tester(actions: &[Action]) {
 					}
 				}
 			},
+			AssertCursor(target_cursor) => {
+				if &ac.cursor != target_cursor {
+					panic!(
+						"AssertCursor failed: Current: '{}' Expected: '{}'",
+						ac.cursor, target_cursor
+					)
+				}
+			}
 			SetString(target_string) => {
 				ac.update_string(target_string);
 			}
+			SetStringAt(target_string, cursor) => {
+				ac.update_string_at(target_string, *cursor);
+			}
 			Move(target_movement) => {
 				ac.register_movement(target_movement);
 			}
@@ -81,7 +94,8 @@ fn single() {
 		AssertHint("x^2"),
 		Move(Movement::Complete),
 		AssertString("x^2"),
-		AssertHint(""),
+		// Operators are suggested after numbers
+		AssertHint("+"),
 		AssertIndex(0),
 	]);
 }
@@ -90,26 +104,30 @@ fn single() {
 fn multi() {
 	ac_tester(&[
 		SetString("s"),
-		AssertHint("in("),
+		AssertHint("um("),
 		Move(Movement::Up),
-		AssertIndex(3),
+		AssertIndex(4),
 		AssertString("s"),
 		AssertHint("ignum("),
 		Move(Movement::Down),
 		AssertIndex(0),
 		AssertString("s"),
-		AssertHint("in("),
+		AssertHint("um("),
 		Move(Movement::Down),
 		AssertIndex(1),
 		AssertString("s"),
+		AssertHint("in("),
+		Move(Movement::Down),
+		AssertIndex(2),
+		AssertString("s"),
 		AssertHint("qrt("),
 		Move(Movement::Up),
-		AssertIndex(0),
+		AssertIndex(1),
 		AssertString("s"),
 		AssertHint("in("),
 		Move(Movement::Complete),
-		AssertString("sin("),
-		AssertHint(")"),
+		AssertString("sin()"),
+		AssertHint("x"),
 		AssertIndex(0),
 	]);
 }
@@ -157,3 +175,140 @@ fn parens() {
 		AssertIndex(0),
 	]);
 }
+
+#[test]
+fn cursor() {
+	ac_tester(&[
+		SetStringAt("sq+x", 2),
+		AssertHint("rt("),
+		Move(Movement::Complete),
+		AssertString("sqrt()+x"),
+		AssertCursor(5),
+		AssertHint("x"),
+		// At the end, hints are for the whole string (such as closing its parentheses)
+		SetStringAt("sqrt(+x", 7),
+		AssertHint(")"),
+		// Hints in the middle of a term would split it
+		SetStringAt("sqx", 2),
+		AssertHint(""),
+		// Closing parentheses are only hinted at the end
+		SetStringAt("sin(x+1", 5),
+		AssertHint(""),
+		SetStringAt("2*s+1", 3),
+		AssertHint("um("),
+		Move(Movement::Down),
+		AssertHint("in("),
+		Move(Movement::Complete),
+		AssertString("2*sin()+1"),
+		AssertCursor(6),
+	]);
+}
+
+#[test]
+fn current_hint() {
+	let mut ac = AutoComplete::default();
+	assert_eq!(ac.current_hint(), "x^2");
+
+	ac.update_string("s");
+	assert_eq!(ac.current_hint(), "um(");
+	ac.register_movement(&Movement::Down);
+	assert_eq!(ac.current_hint(), "in(");
+	ac.register_movement(&Movement::Up);
+	ac.register_movement(&Movement::Up);
+	assert_eq!(ac.current_hint(), "ignum(");
+
+	ac.update_string("sin(x");
+	assert_eq!(ac.current_hint(), ")");
+
+	ac.update_string("qwert987gybhj");
+	assert_eq!(ac.current_hint(), "");
+}
+
+#[test]
+fn correction() {
+	let mut ac = AutoComplete::default();
+	ac.update_string("sni(x)+1");
+	ac.apply_correction(0..3, "sin");
+	assert_eq!(ac.string, "sin(x)+1");
+	assert_eq!(ac.cursor, 8);
+
+	// The cursor stays next to the same text when the length changes
+	ac.update_string_at("2sqtr(x", 6);
+	ac.apply_correction(1..5, "sqrt");
+	assert_eq!(ac.string, "2sqrt(x");
+	assert_eq!(ac.cursor, 6);
+
+	ac.update_string_at("bessel(1, x)", 3);
+	ac.apply_correction(0..6, "besselj");
+	assert_eq!(ac.string, "besselj(1, x)");
+	assert_eq!(ac.cursor, 7);
+}
+
+#[test]
+fn parens_closing() {
+	let mut ac = AutoComplete::default();
+	ac.update_string("sq");
+	ac.register_movement(&Movement::Complete);
+	assert_eq!(ac.string, "sqrt()");
+	assert_eq!(ac.cursor, 5);
+
+	// Typing `)` right before the one that was closed skips over it
+	assert!(ac.skips_closing_parens("sqrt())", 6));
+	ac.update_string_at("sqrt(x)", 6);
+	assert!(ac.skips_closing_parens("sqrt(x))", 7));
+	assert!(!ac.skips_closing_parens("sqrt(x)2", 7));
+	ac.update_string_at("sqrt(x)", 7);
+	assert!(!ac.skips_closing_parens("sqrt(x))", 8));
+
+	// Hints that don't open parentheses aren't closed
+	ac.update_string("th");
+	ac.register_movement(&Movement::Complete);
+	assert_eq!(ac.string, "theta");
+}
+
+#[test]
+fn terms() {
+	let mut ac = AutoComplete::default();
+	ac.set_terms(vec![String::from("f("), String::from("force(")]);
+
+	// User functions are offered before the built in ones
+	ac.update_string("2f");
+	assert_eq!(ac.candidates(), vec!["(", "orce(", "ract(", "loor("]);
+	assert_eq!(ac.current_hint(), "(");
+	ac.register_movement(&Movement::Down);
+	ac.register_movement(&Movement::Complete);
+	assert_eq!(ac.string, "2force()");
+
+	// Names are completed as a whole
+	ac.update_string("xf");
+	assert!(ac.term_hints.is_empty());
+
+	// Terms are kept when the string is cleared
+	ac.update_string("");
+	ac.update_string("fo");
+	assert_eq!(ac.term_hints, vec![String::from("rce(")]);
+}
+
+#[test]
+fn ranking() {
+	let mut ac = AutoComplete::default();
+	ac.set_usage(&std::collections::BTreeMap::from([
+		(String::from("signum("), 3),
+		(String::from("sin("), 1),
+	]));
+
+	// The completions accepted the most come first, the rest keep their order
+	ac.update_string("s");
+	assert_eq!(
+		ac.candidates(),
+		vec!["ignum(", "in(", "um(", "qrt(", "inh("]
+	);
+	assert_eq!(ac.completion("in("), "sin(");
+	ac.register_movement(&Movement::Complete);
+	assert_eq!(ac.string, "signum()");
+
+	// Usage is kept when the string is cleared
+	ac.update_string("");
+	ac.update_string("s");
+	assert_eq!(ac.current_hint(), "ignum(");
+}
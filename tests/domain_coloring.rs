@@ -0,0 +1,33 @@
+use epaint::{Color32, Hsva};
+use parsing::Complex;
+use ytbn_graphing_software::domain_color;
+
+/// Ensures [`domain_color`] colors values by their argument, and handles values that aren't finite
+#[test]
+fn domain_colors() {
+	assert_eq!(
+		domain_color(Complex::new(f64::NAN, 0.0)),
+		Color32::TRANSPARENT
+	);
+	assert_eq!(domain_color(Complex::ZERO), Color32::BLACK);
+	assert_eq!(
+		domain_color(Complex::new(f64::INFINITY, 1.0)),
+		Color32::WHITE
+	);
+
+	// Positive reals are red and negative reals are cyan, which are darkest when |w| is a power of 2
+	assert_eq!(
+		domain_color(Complex::new(2.0, 0.0)),
+		Color32::from(Hsva::new(0.0, 0.9, 0.7, 1.0))
+	);
+	assert_eq!(
+		domain_color(Complex::new(-4.0, 0.0)),
+		Color32::from(Hsva::new(0.5, 0.9, 0.7, 1.0))
+	);
+
+	// Arguments in (-π, 0) wrap around to the end of the hue wheel
+	assert_eq!(
+		domain_color(Complex::new(0.0, -1.0)),
+		Color32::from(Hsva::new(0.75, 0.9, 0.7, 1.0))
+	);
+}
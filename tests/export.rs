@@ -0,0 +1,119 @@
+use egui::{ColorImage, Vec2};
+use egui_plot::{PlotBounds, PlotPoint};
+use epaint::Color32;
+use ytbn_graphing_software::{
+	encode_png, report_html, utc_timestamp, ExportImage, ExportShape, Language, ReportFunction,
+	Translations,
+};
+
+/// Ensures [`encode_png`] writes the size of the image and its (uncompressed) pixels, row by row
+#[test]
+fn png() {
+	let image = ColorImage {
+		size: [2, 1],
+		pixels: vec![Color32::RED, Color32::from_rgb(1, 2, 3)],
+	};
+	let png = encode_png(&image);
+
+	assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+	// Width and height, followed by RGB pixels with 8 bits per channel
+	assert_eq!(&png[12..16], b"IHDR");
+	assert_eq!(&png[16..29], &[0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+
+	// The row's filter, then its pixels
+	assert!(png
+		.windows(7)
+		.any(|window| window == [0, 255, 0, 0, 1, 2, 3]));
+
+	assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+}
+
+/// Ensures [`ExportImage::to_svg`] positions shapes in the image, and escapes text and the legend
+#[test]
+fn svg() {
+	let mut image = ExportImage::new(
+		PlotBounds::from_min_max([-1.0, -1.0], [1.0, 1.0]),
+		Vec2::new(200.0, 100.0),
+		2.0,
+	);
+	image.push(ExportShape::line(
+		vec![
+			PlotPoint::new(-1.0, 1.0),
+			PlotPoint::new(0.0, 0.0),
+			PlotPoint::new(f64::NAN, 0.0),
+			PlotPoint::new(1.0, -1.0),
+		],
+		Color32::RED,
+	));
+	image.push(ExportShape::Text {
+		position: PlotPoint::new(0.0, 0.0),
+		text: String::from("max < 2"),
+		color: Color32::RED,
+	});
+	image.push_legend(String::from("x < 1 & x > -1"), Color32::RED);
+	let svg = image.to_svg();
+
+	// Scaled to 2 pixels per point
+	assert!(svg.starts_with("<svg"));
+	assert!(svg.contains("width=\"400\" height=\"200\" viewBox=\"0 0 200 100\""));
+
+	// The line is split around the point that isn't finite
+	assert!(svg.contains("<polyline points=\"0,0 100,50\""));
+	assert!(svg.contains("<polyline points=\"200,100\""));
+
+	// Text is above and to the right of its position
+	assert!(svg.contains("<text x=\"100\" y=\"38\""));
+	assert!(svg.contains(">max &lt; 2</text>"));
+
+	assert!(svg.contains(">x &lt; 1 &amp; x &gt; -1</text>"));
+	assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+/// Ensures [`report_html`] includes the plot and each function's method and results, escaping the text
+#[test]
+fn report() {
+	let functions = [
+		ReportFunction {
+			expression: String::from("x < 1"),
+			color: Color32::from_rgb(255, 0, 128),
+			method: Some(String::from("Left, N = 10, from 0 to 1")),
+			results: vec![
+				(String::from("Root"), String::from("(1, 0)")),
+				(String::from("Area"), String::from("0.5")),
+			],
+		},
+		ReportFunction {
+			expression: String::from("sin(x)"),
+			color: Color32::RED,
+			method: None,
+			results: Vec::new(),
+		},
+	];
+	let html = report_html(
+		"<svg></svg>",
+		"2024-03-01 14:05 UTC",
+		&functions,
+		&Translations::new(Language::English),
+	);
+
+	assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+	assert!(html.contains("<p>Exported 2024-03-01 14:05 UTC</p>\n<svg></svg>"));
+	assert!(html.contains("color: #ff0080\">&#9632;</span> x &lt; 1</h2>"));
+	assert!(html.contains("<p>Integrated with Left, N = 10, from 0 to 1</p>"));
+	assert!(html.contains("<tr><td>Root</td><td>(1, 0)</td></tr>"));
+	assert!(html.contains("<tr><td>Area</td><td>0.5</td></tr>"));
+
+	// Functions without an integral or any results are still listed
+	assert!(html.contains("sin(x)</h2>\n<p>Nothing found</p>"));
+	assert!(html.trim_end().ends_with("</html>"));
+}
+
+/// Ensures [`utc_timestamp`] finds the date (including leap days) and time of a number of seconds since the Unix epoch
+#[test]
+fn timestamp() {
+	assert_eq!(utc_timestamp(0), "1970-01-01 00:00 UTC");
+	assert_eq!(utc_timestamp(951782400), "2000-02-29 00:00 UTC");
+	assert_eq!(utc_timestamp(1709301900), "2024-03-01 14:05 UTC");
+	assert_eq!(utc_timestamp(1735689599), "2024-12-31 23:59 UTC");
+}
@@ -1,4 +1,7 @@
-use ytbn_graphing_software::{AppSettings, EguiHelper, FunctionEntry, Riemann};
+use egui_plot::PlotPoint;
+use ytbn_graphing_software::{
+	AppSettings, AreaMode, EguiHelper, Extremum, FunctionEntry, IntegralOverride, Riemann,
+};
 
 fn app_settings_constructor(
 	sum: Riemann, integral_min_x: f64, integral_max_x: f64, pixel_width: usize,
@@ -15,6 +18,7 @@ fn app_settings_constructor(
 		do_extrema: false,
 		do_roots: false,
 		plot_width: pixel_width,
+		..AppSettings::default()
 	}
 }
 
@@ -253,10 +257,939 @@ fn do_test(sum: Riemann, area_target: f64) {
 }
 
 #[test]
-fn left_function() { do_test(Riemann::Left, 0.9600000000000001); }
+fn left_function() { do_test(Riemann::Left, 0.6800000000000002); }
 
 #[test]
-fn middle_function() { do_test(Riemann::Middle, 0.92); }
+fn middle_function() { do_test(Riemann::Middle, 0.6800000000000003); }
 
 #[test]
-fn right_function() { do_test(Riemann::Right, 0.8800000000000001); }
+fn right_function() { do_test(Riemann::Right, 0.6800000000000002); }
+
+#[test]
+fn trapezoid_function() { do_test(Riemann::Trapezoid, 0.6800000000000003); }
+
+#[test]
+fn partition_function() {
+	for sum in [
+		Riemann::Left,
+		Riemann::Middle,
+		Riemann::Right,
+		Riemann::Trapezoid,
+	] {
+		let settings = app_settings_constructor(sum, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+		let mut function = FunctionEntry::default();
+		function.update_string("x^2");
+		function.integral = true;
+		function.calculate(true, true, false, settings);
+
+		// The rectangles tile the bounds exactly, so the sums are symmetric around x = 0
+		let (bars, area) = function.integral_data.clone().unwrap();
+		assert!((area - 0.68).abs() < 1e-10, "{:?}: {}", sum, area);
+		assert_eq!(bars.len(), 10);
+		assert!((bars[0].argument + 0.9).abs() < 1e-10);
+		assert!((bars[9].argument - 0.9).abs() < 1e-10);
+	}
+}
+
+#[test]
+fn adaptive_function() { do_test(Riemann::Adaptive, 0.6666666666666666); }
+
+#[test]
+fn monte_carlo_function() {
+	let mut settings = app_settings_constructor(Riemann::MonteCarlo, -1.0, 1.0, 10, 10, -1.0, 1.0);
+	settings.monte_carlo_samples = 10000;
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	let area = function.integral_data.clone().unwrap().1;
+	let std_error = function.integral_std_error.unwrap();
+	assert!(std_error > 0.0);
+
+	// Should be within 6 standard errors of the actual area (2/3) practically every time
+	assert!((area - (2.0 / 3.0)).abs() < 6.0 * std_error);
+
+	let (hits, misses) = &function.monte_carlo_data;
+	assert_eq!(hits.len() + misses.len(), settings.monte_carlo_samples);
+}
+
+#[test]
+fn romberg_function() {
+	let settings = app_settings_constructor(Riemann::Romberg, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	// 10 intervals allows for rows using 1, 2, 4, and 8 intervals
+	assert_eq!(function.romberg_table.len(), 4);
+
+	let area = function.integral_data.clone().unwrap().1;
+	assert!((area - (2.0 / 3.0)).abs() < 1e-12);
+}
+
+#[test]
+fn improper_function() {
+	let settings = app_settings_constructor(
+		Riemann::Left,
+		f64::NEG_INFINITY,
+		f64::INFINITY,
+		10,
+		10,
+		-1.0,
+		1.0,
+	);
+	assert!(settings.is_improper());
+
+	let mut function = FunctionEntry::default();
+	function.update_string("e^(-x^2)");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	let area = function.integral_data.clone().unwrap().1;
+	assert!((area - std::f64::consts::PI.sqrt()).abs() < 1e-4);
+}
+
+#[test]
+fn singular_function() {
+	let settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("1/sqrt(x)");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	assert!(function.integral_singular);
+	assert!(!function.integral_diverges);
+	let area = function.integral_data.clone().unwrap().1;
+	assert!(area.is_finite());
+
+	// Every rectangle should be kept, including the one containing the singularity
+	assert_eq!(function.integral_data.clone().unwrap().0.len(), 10);
+
+	function.update_string("1/x");
+	function.calculate(true, true, false, settings);
+	assert!(function.integral_diverges);
+}
+
+#[test]
+fn antiderivative_function() {
+	let settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("2x");
+	function.antiderivative = true;
+	function.calculate(true, true, false, settings);
+
+	// Integral isn't displayed, but is still calculated for the running integral
+	assert!(!function.integral);
+	assert!(function.integral_data.is_some());
+
+	// One point for the lower bound and one for the end of each rectangle
+	let data = function.antiderivative_data.clone().to_tuple();
+	assert_eq!(data.len(), 11);
+	assert_eq!(data[0], (0.0, 0.0));
+
+	let (last_x, last_y) = *data.last().unwrap();
+	assert!((last_x - 1.0).abs() < 1e-10);
+	assert!((last_y - function.integral_data.clone().unwrap().1).abs() < 1e-10);
+}
+
+#[test]
+fn area_mode_function() {
+	let mut settings = app_settings_constructor(Riemann::Middle, 0.0, 2.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x-1");
+	function.integral = true;
+
+	// Signed area cancels out, and the parts aren't calculated
+	function.calculate(true, true, false, settings);
+	assert!(function.integral_data.clone().unwrap().1.abs() < 1e-10);
+	assert!(function.integral_parts.is_none());
+
+	settings.area_mode = AreaMode::Absolute;
+	function.calculate(true, true, false, settings);
+	let (positive, negative) = function.integral_parts.unwrap();
+	assert!((positive - 0.5).abs() < 1e-10);
+	assert!((negative + 0.5).abs() < 1e-10);
+}
+
+#[test]
+fn integral_override_function() {
+	let settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+	assert!((function.integral_data.clone().unwrap().1 - 0.45).abs() < 1e-10);
+
+	// The function's own settings are used instead of the global ones
+	function.set_integral_override(Some(IntegralOverride {
+		min_x: 0.0,
+		max_x: 2.0,
+		num: 2,
+		riemann_sum: Riemann::Middle,
+	}));
+	assert!(function.integral_data.is_none());
+	function.calculate(false, false, false, settings);
+	assert_eq!(function.integral_data.clone().unwrap().1, 2.0);
+
+	function.set_integral_override(None);
+	function.calculate(false, false, false, settings);
+	assert!((function.integral_data.clone().unwrap().1 - 0.45).abs() < 1e-10);
+}
+
+#[test]
+fn csv_function() {
+	let settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x");
+	function.integral = true;
+	function.set_integral_override(Some(IntegralOverride {
+		min_x: 0.0,
+		max_x: 2.0,
+		num: 2,
+		riemann_sum: Riemann::Middle,
+	}));
+	function.calculate(true, true, false, settings);
+
+	assert_eq!(
+		function.rectangles_csv(),
+		"x,width,height,area\n0.5,1,0.5,0.5\n1.5,1,1.5,1.5\n"
+	);
+
+	// Exported samples can be read back in as a data series
+	let samples = function.samples_csv();
+	assert!(samples.starts_with("x,y\n"));
+	assert_eq!(
+		ytbn_graphing_software::parse_csv_points(&samples).unwrap(),
+		function.back_data
+	);
+
+	// The derivative isn't sampled unless it's shown
+	assert_eq!(function.derivative_csv(), "x,f'(x)\n");
+}
+
+#[test]
+fn table_function() {
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	assert_eq!(
+		function.table(&[-1.0, 0.0, 1.5]),
+		Some(vec![(1.0, -2.0), (0.0, 0.0), (2.25, 3.0)])
+	);
+
+	// Updates as the function changes
+	function.update_string("1/x");
+	let values = function.table(&[0.0, 2.0]).unwrap();
+	assert!(!values[0].0.is_finite());
+	assert_eq!(values[1], (0.5, -0.25));
+
+	// Only functions of x have a table
+	function.update_string("x^2 + y^2 = 1");
+	assert_eq!(function.table(&[0.0]), None);
+	function.update_string("sin(");
+	assert_eq!(function.table(&[0.0]), None);
+}
+
+#[test]
+fn convergence_function() {
+	let settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+	function.calculate_convergence(&settings);
+
+	// One sum for each amount of rectangles between 2 and 10
+	let data = function.convergence_data.clone().to_tuple();
+	assert_eq!(data.len(), 9);
+	assert_eq!(data[0].0, 2.0);
+
+	// The last sum is the same as the displayed integral
+	let (last_num, last_area) = *data.last().unwrap();
+	assert_eq!(last_num, 10.0);
+	assert!((last_area - function.integral_data.clone().unwrap().1).abs() < 1e-10);
+}
+
+#[test]
+fn darboux_function() {
+	for sum in [Riemann::Upper, Riemann::Lower] {
+		let settings = app_settings_constructor(sum, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+		let mut function = FunctionEntry::default();
+		function.update_string("x^2");
+		function.integral = true;
+		function.calculate(true, true, false, settings);
+
+		// x^2 is increasing on [0, 1], so the sums are the same as the right and left sums
+		let (lower, upper) = function.darboux_bounds.unwrap();
+		assert!((lower - 0.285).abs() < 1e-10);
+		assert!((upper - 0.385).abs() < 1e-10);
+		assert_eq!(function.darboux_data.len(), 10);
+	}
+}
+
+#[test]
+fn reversed_function() {
+	for sum in [
+		Riemann::Left,
+		Riemann::Trapezoid,
+		Riemann::Adaptive,
+		Riemann::Romberg,
+	] {
+		let forward = app_settings_constructor(sum, 0.0, 1.0, 10, 10, -1.0, 1.0);
+		let reversed = app_settings_constructor(sum, 1.0, 0.0, 10, 10, -1.0, 1.0);
+		assert!(!forward.is_reversed());
+		assert!(reversed.is_reversed());
+
+		let mut function = FunctionEntry::default();
+		function.update_string("x^2");
+		function.integral = true;
+
+		function.calculate(true, true, false, forward);
+		let forward_area = function.integral_data.clone().unwrap().1;
+
+		function.calculate(true, true, false, reversed);
+		let reversed_area = function.integral_data.clone().unwrap().1;
+
+		assert!(forward_area > 0.0);
+		assert!((forward_area + reversed_area).abs() < 1e-10, "{:?}", sum);
+	}
+}
+
+#[test]
+fn error_bound_function() {
+	// |f'| <= 2 and |f''| = 2 for x^2 on [0, 1]
+	for (sum, bound) in [
+		(Riemann::Left, 2.0 / 20.0),
+		(Riemann::Right, 2.0 / 20.0),
+		(Riemann::Trapezoid, 2.0 / 1200.0),
+	] {
+		let settings = app_settings_constructor(sum, 0.0, 1.0, 10, 10, -1.0, 1.0);
+
+		let mut function = FunctionEntry::default();
+		function.update_string("x^2");
+		function.integral = true;
+		function.calculate(true, true, false, settings);
+
+		let error_bound = function.integral_error_bound.unwrap();
+		assert!(
+			(error_bound - bound).abs() < 1e-10,
+			"{:?}: {}",
+			sum,
+			error_bound
+		);
+
+		// The actual error is within the bound (which is exact for the trapezoid rule, as f'' is constant)
+		let area = function.integral_data.clone().unwrap().1;
+		assert!((area - (1.0 / 3.0)).abs() <= error_bound + 1e-12);
+	}
+}
+
+#[test]
+fn tangent_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.tangent_x = Some(1.0);
+	function.calculate(true, true, false, settings);
+
+	let (point, slope) = function.tangent_data.unwrap();
+	assert_eq!((point.x, point.y), (1.0, 1.0));
+	assert_eq!(slope, 2.0);
+
+	// Moving the point of tangency recalculates the tangent line
+	function.tangent_x = Some(-0.5);
+	function.calculate(false, false, false, settings);
+	let (point, slope) = function.tangent_data.unwrap();
+	assert_eq!((point.x, point.y), (-0.5, 0.25));
+	assert_eq!(slope, -1.0);
+
+	assert_eq!(function.distance_to(PlotPoint::new(0.0, 0.5)), Some(0.5));
+
+	// Changing the function recalculates the tangent line at the same point
+	function.update_string("x^3");
+	function.calculate(false, false, false, settings);
+	let (point, slope) = function.tangent_data.unwrap();
+	assert_eq!((point.x, point.y), (-0.5, -0.125));
+	assert_eq!(slope, 0.75);
+
+	function.tangent_x = None;
+	function.calculate(false, false, false, settings);
+	assert!(function.tangent_data.is_none());
+}
+
+#[test]
+fn trace_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.calculate(true, true, false, settings);
+
+	// Snaps to the nearest sampled point
+	let (point, derivative) = function
+		.trace(PlotPoint::new(0.45, 0.0), [1.0, 1.0])
+		.unwrap();
+	assert_eq!(
+		(point.x, point.y),
+		(0.3999999999999999, 0.15999999999999992)
+	);
+	assert_eq!(derivative, None);
+
+	// Distance is measured on the screen, where the y-axis can be stretched
+	let (point, _) = function
+		.trace(PlotPoint::new(0.0, 1.0), [1.0, 0.001])
+		.unwrap();
+	assert_eq!(point.y.abs(), 1.0);
+
+	function.derivative = true;
+	let (_, derivative) = function
+		.trace(PlotPoint::new(1.0, 1.0), [1.0, 1.0])
+		.unwrap();
+	assert_eq!(derivative, Some(2.0));
+
+	function.visible = false;
+	assert!(function
+		.trace(PlotPoint::new(0.0, 0.0), [1.0, 1.0])
+		.is_none());
+}
+
+#[test]
+fn extent_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	assert!(function.extent().is_none());
+
+	function.update_string("x^2");
+	function.calculate(true, true, false, settings);
+	let (min, max) = function.extent().unwrap();
+	assert_eq!((min.x, min.y), (-1.0, 0.0));
+	assert_eq!((max.x, max.y), (1.0, 1.0));
+
+	function.visible = false;
+	assert!(function.extent().is_none());
+}
+
+#[test]
+fn extrema_function() {
+	let mut settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 100, 10, -2.0, 2.0);
+	settings.do_extrema = true;
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^3-x");
+	function.calculate(true, true, false, settings);
+
+	// Critical points at x = ±1/sqrt(3)
+	let mut extrema = function.extrema_data.clone();
+	extrema.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+	assert_eq!(extrema.len(), 2);
+	assert_eq!(extrema[0].1, Extremum::Maximum);
+	assert_eq!(extrema[1].1, Extremum::Minimum);
+
+	// Inflection point with f''(0) = 0 and no sign change in f'
+	function.update_string("x^3");
+	function.calculate(true, true, false, settings);
+	assert!(function
+		.extrema_data
+		.iter()
+		.all(|(_, kind)| *kind == Extremum::Neither));
+}
+
+#[test]
+fn discontinuity_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("floor(x)");
+	function.calculate(true, true, false, settings);
+
+	// Jumps between -0.2 and 0, and between 0.8 and 1
+	assert_eq!(function.back_gaps, vec![4, 9]);
+	assert_eq!(
+		function
+			.back_data
+			.clone()
+			.to_segments(&function.back_gaps)
+			.len(),
+		3
+	);
+
+	function.update_string("x^2");
+	function.calculate(true, true, false, settings);
+	assert!(function.back_gaps.is_empty());
+}
+
+#[test]
+fn limit_function() {
+	let mut function = FunctionEntry::default();
+	function.update_string("sin(x)/x");
+	let (left, right) = function.limit(0.0);
+	assert!((left - 1.0).abs() < 1e-10);
+	assert!((right - 1.0).abs() < 1e-10);
+
+	function.update_string("floor(x)");
+	assert_eq!(function.limit(2.0), (1.0, 2.0));
+
+	function.update_string("1/x");
+	assert_eq!(function.limit(0.0), (f64::NEG_INFINITY, f64::INFINITY));
+}
+
+#[test]
+fn intersections_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.5, 2.5);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	let mut other = FunctionEntry::default();
+	other.update_string("x+2");
+
+	let intersections = function.intersections(&mut other, &settings);
+	assert_eq!(intersections.len(), 2);
+	assert!((intersections[0].x + 1.0).abs() < 1e-12);
+	assert!((intersections[0].y - 1.0).abs() < 1e-12);
+	assert!((intersections[1].x - 2.0).abs() < 1e-12);
+	assert!((intersections[1].y - 4.0).abs() < 1e-12);
+
+	other.update_string("x-2");
+	assert!(function.intersections(&mut other, &settings).is_empty());
+}
+
+#[test]
+fn mean_value_function() {
+	let mut settings = app_settings_constructor(Riemann::Left, 0.0, 2.0, 100, 10, -1.0, 3.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^3");
+	function.mean_value = true;
+	function.calculate(true, true, false, settings);
+
+	// Slope of the secant line is (8 - 0) / 2 = 4, and f'(c) = 3c^2 = 4 at c = 2/sqrt(3)
+	let ([start, end], points) = function.mean_value_data.clone().unwrap();
+	assert_eq!((start.x, start.y), (0.0, 0.0));
+	assert_eq!((end.x, end.y), (2.0, 8.0));
+	assert_eq!(points.len(), 1);
+	assert!((points[0].x - (2.0 / 3.0_f64.sqrt())).abs() < 1e-12);
+
+	// Moving the bounds recalculates the secant line
+	settings.integral_min_x = -2.0;
+	function.calculate(false, false, false, settings);
+	let (_, points) = function.mean_value_data.clone().unwrap();
+	assert_eq!(points.len(), 2);
+	assert!((points[0].x + (2.0 / 3.0_f64.sqrt())).abs() < 1e-12);
+	assert!((points[1].x - (2.0 / 3.0_f64.sqrt())).abs() < 1e-12);
+
+	function.mean_value = false;
+	function.calculate(false, false, false, settings);
+	assert!(function.mean_value_data.is_none());
+}
+
+#[test]
+fn curvature_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.0, 1.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.curvature = true;
+	function.osculating_x = Some(0.0);
+	function.calculate(true, true, false, settings);
+
+	// κ(x) = 2 / (1 + 4x^2)^(3/2)
+	let curvature_data = function.curvature_data.clone().unwrap();
+	assert_eq!(curvature_data.len(), 11);
+	curvature_data.iter().for_each(|point| {
+		let expected = 2.0 / (1.0 + (4.0 * point.x * point.x)).powf(1.5);
+		assert!((point.y - expected).abs() < 1e-12);
+	});
+
+	let (point, center, radius) = function.osculating_data.unwrap();
+	assert_eq!((point.x, point.y), (0.0, 0.0));
+	assert_eq!((center.x, center.y), (0.0, 0.5));
+	assert_eq!(radius, 0.5);
+
+	// Straight lines have no osculating circle
+	function.update_string("2x+1");
+	function.calculate(false, false, false, settings);
+	let (_, _, radius) = function.osculating_data.unwrap();
+	assert!(radius.is_infinite());
+
+	function.curvature = false;
+	function.osculating_x = None;
+	function.calculate(false, false, false, settings);
+	assert!(function.curvature_data.is_none());
+	assert!(function.osculating_data.is_none());
+}
+
+#[test]
+fn period_function() {
+	let mut function = FunctionEntry::default();
+	function.update_string("sin(x)+cos(2x)");
+	assert!((function.period().unwrap() - std::f64::consts::TAU).abs() < 1e-9);
+
+	function.update_string("x^2");
+	assert_eq!(function.period(), None);
+}
+
+#[test]
+fn solve_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -3.0, 3.0);
+
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+
+	let solutions = function.solve(2.0, &settings);
+	assert_eq!(solutions.len(), 2);
+	assert!((solutions[0].x + 2.0_f64.sqrt()).abs() < 1e-12);
+	assert!((solutions[1].x - 2.0_f64.sqrt()).abs() < 1e-12);
+	assert_eq!(solutions[0].y, 2.0);
+
+	assert!(function.solve(-1.0, &settings).is_empty());
+}
+
+#[test]
+fn polar_function() {
+	let settings = AppSettings {
+		theta_min: 0.0,
+		theta_max: std::f64::consts::PI,
+		..app_settings_constructor(Riemann::Middle, -1.0, 1.0, 100, 1000, -2.0, 2.0)
+	};
+
+	// Circle of radius 1 centered at (0, 1)
+	let mut function = FunctionEntry::default();
+	function.update_string("2sin(theta)");
+	function.polar = true;
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	assert_eq!(function.back_data.len(), 101);
+	function.back_data.iter().for_each(|point| {
+		assert!((point.x.hypot(point.y - 1.0) - 1.0).abs() < 1e-12);
+	});
+
+	// Polar area is (1/2)∫r^2 dθ over the θ range, rather than the integral's bounds
+	let (_, area) = function.integral_data.clone().unwrap();
+	assert!((area - std::f64::consts::PI).abs() < 1e-6);
+
+	// Derivatives aren't calculated for polar functions
+	assert!(function.derivative_data.is_empty());
+	assert!(function.solve(0.0, &settings).is_empty());
+}
+
+#[test]
+fn polar_darboux_function() {
+	for sum in [Riemann::Upper, Riemann::Lower] {
+		let settings = AppSettings {
+			theta_min: 0.0,
+			theta_max: std::f64::consts::PI,
+			..app_settings_constructor(sum, -1.0, 1.0, 100, 100, -2.0, 2.0)
+		};
+
+		let mut function = FunctionEntry::default();
+		function.update_string("2sin(theta)");
+		function.polar = true;
+		function.integral = true;
+		function.calculate(true, true, false, settings);
+
+		// The sums of (1/2)r^2 bound the circle's area from below and above
+		let (lower, upper) = function.darboux_bounds.unwrap();
+		assert!((lower < std::f64::consts::PI) && (std::f64::consts::PI < upper));
+		assert!(upper - lower < 0.2);
+	}
+}
+
+#[test]
+fn complex_values_function() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 4, 10, -2.0, 2.0);
+
+	// √x is imaginary for x < 0, and real for x ≥ 0
+	let mut function = FunctionEntry::default();
+	function.complex_values = true;
+	function.update_string("sqrt(x)");
+	assert!(function.get_test_result().is_none());
+	function.calculate(true, true, false, settings);
+
+	let (re_data, im_data) = function.complex_data.clone().unwrap();
+	assert_eq!(re_data.len(), 5);
+	assert_eq!(im_data.len(), 5);
+	for (re, im) in re_data.iter().zip(im_data.iter()) {
+		assert_eq!(re.x, im.x);
+		match re.x < 0.0 {
+			true => assert!((re.y == 0.0) && ((im.y - re.x.abs().sqrt()).abs() < 1e-12)),
+			false => assert!((im.y == 0.0) && ((re.y - re.x.sqrt()).abs() < 1e-12)),
+		}
+	}
+
+	// Invalid expressions are reported by the complex parser
+	function.update_string("sqrt(x");
+	assert!(function.get_test_result().is_some());
+}
+
+#[test]
+fn degrees_function() {
+	use parsing::AngleUnit;
+
+	let mut function = FunctionEntry::default();
+	function.set_angle_unit(AngleUnit::Degrees);
+	function.update_string("sin(x)");
+	assert!((function.period().unwrap() - 360.0).abs() < 1e-6);
+
+	// Changing the unit parses the function again, even though its string hasn't changed
+	function.set_angle_unit(AngleUnit::Radians);
+	function.update_string("sin(x)");
+	assert!((function.period().unwrap() - std::f64::consts::TAU).abs() < 1e-9);
+}
+
+#[test]
+fn riemann_rectangle_hover() {
+	let settings = app_settings_constructor(Riemann::Left, -1.0, 1.0, 10, 10, -1.0, 1.0);
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	// Second rectangle from the left, [-0.8, -0.6] sampled at its left end
+	let rectangle = function
+		.rectangle_at(PlotPoint::new(-0.7, 0.1), &settings)
+		.unwrap();
+	assert_eq!(rectangle.index, 1);
+	assert!((rectangle.interval.0 + 0.8).abs() < 1e-12);
+	assert!((rectangle.interval.1 + 0.6).abs() < 1e-12);
+	assert!((rectangle.height - 0.64).abs() < 1e-12);
+	assert!((rectangle.area - 0.128).abs() < 1e-12);
+	assert!((rectangle.cumulative - 0.328).abs() < 1e-12);
+
+	// Above the rectangle, and outside of the integral's bounds
+	assert!(function
+		.rectangle_at(PlotPoint::new(-0.7, 0.7), &settings)
+		.is_none());
+	assert!(function
+		.rectangle_at(PlotPoint::new(1.5, 0.1), &settings)
+		.is_none());
+}
+
+#[test]
+fn estimate_colors() {
+	use epaint::Color32;
+
+	/// Colors of the rectangles of `sum` of `x` from 0 to 1
+	fn colors(sum: Riemann) -> Vec<Color32> {
+		let mut settings = app_settings_constructor(sum, 0.0, 1.0, 10, 10, -1.0, 1.0);
+		settings.estimate_colors = true;
+		let mut function = FunctionEntry::default();
+		function.update_string("x");
+		function.integral = true;
+		function.calculate(true, true, false, settings);
+		let (bars, _) = function.integral_data.unwrap();
+		bars.into_iter().map(|bar| bar.stroke.color).collect()
+	}
+
+	// Left sums underestimate increasing functions, while right sums overestimate them
+	let left = colors(Riemann::Left);
+	let right = colors(Riemann::Right);
+	assert_eq!(left.len(), 10);
+	assert!(left.iter().all(|color| *color == left[0]));
+	assert!(right.iter().all(|color| *color == right[0]));
+	assert_ne!(left[0], right[0]);
+	assert_ne!(left[0], Color32::TRANSPARENT);
+	assert_ne!(right[0], Color32::TRANSPARENT);
+
+	// Midpoints of lines are exact, so neither color is used
+	assert!(colors(Riemann::Middle)
+		.iter()
+		.all(|color| *color == Color32::TRANSPARENT));
+}
+
+#[test]
+fn results() {
+	let mut settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 100, 10, -2.0, 2.0);
+	settings.do_roots = true;
+	let mut function = FunctionEntry::default();
+	function.update_string("x");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	// The arc length of a line is the distance between its ends
+	assert!((function.arc_length(&settings).unwrap() - 2.0_f64.sqrt()).abs() < 1e-9);
+
+	let results = function.results(&settings);
+	let (name, x, _) = &results[0];
+	assert_eq!(*name, "results.root");
+	assert!(x.unwrap().abs() < 1e-9);
+	assert!(results
+		.iter()
+		.any(|(name, x, value)| (*name == "results.area")
+			& x.is_none()
+			& ((value - 0.45).abs() < 1e-9)));
+	assert!(results
+		.iter()
+		.any(|(name, _, _)| *name == "results.arc_length"));
+
+	// The arc length is found again once the integral's bounds change
+	let arc_length = |results: Vec<(&str, Option<f64>, f64)>| {
+		results
+			.into_iter()
+			.find(|(name, _, _)| *name == "results.arc_length")
+			.map(|(_, _, value)| value)
+	};
+	function.set_integral_override(Some(IntegralOverride {
+		min_x: 0.0,
+		max_x: 2.0,
+		num: 10,
+		riemann_sum: Riemann::Left,
+	}));
+	function.calculate(true, true, false, settings);
+	let length = arc_length(function.results(&settings)).unwrap();
+	assert!((length - 8.0_f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn area_computation() {
+	let settings = app_settings_constructor(Riemann::Right, -1.0, 1.0, 10, 10, -1.0, 1.0);
+	let mut function = FunctionEntry::default();
+	function.update_string("x^2");
+	assert!(function.area_computation(&settings).is_none());
+
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+	let (min_x, max_x, riemann_sum, intervals, area) =
+		function.area_computation(&settings).unwrap();
+	assert_eq!((min_x, max_x), (-1.0, 1.0));
+	assert_eq!(riemann_sum, Riemann::Right);
+	assert_eq!(intervals, 10);
+	assert_eq!(area, function.integral_data.unwrap().1);
+}
+
+#[test]
+fn comparison() {
+	let mut settings = app_settings_constructor(Riemann::Left, 0.0, 1.0, 10, 10, -1.0, 1.0);
+	settings.compare_sum = Some(Riemann::Right);
+	let mut function = FunctionEntry::default();
+	function.update_string("x");
+	function.integral = true;
+	function.calculate(true, true, false, settings);
+
+	// Both sums are calculated, the right sum of an increasing function being larger
+	let (_, left) = function.integral_data.clone().unwrap();
+	let (bars, _, right) = function.comparison_data.clone().unwrap();
+	assert!((left - 0.45).abs() < 1e-12);
+	assert!((right - 0.55).abs() < 1e-12);
+	assert_eq!(bars.len(), 10);
+
+	// Nothing is compared once the sum is the same as the one being compared to
+	settings.compare_sum = Some(Riemann::Left);
+	function.calculate(false, false, false, settings);
+	assert!(function.comparison_data.is_none());
+}
+
+#[test]
+fn parameters_function() {
+	use std::collections::BTreeMap;
+
+	let mut function = FunctionEntry::default();
+	function.update_string("sin(bx)");
+	assert_eq!(function.parameter_names().collect::<Vec<_>>(), vec!["b"]);
+	assert!((function.period().unwrap() - std::f64::consts::TAU).abs() < 1e-9);
+
+	// A new value is substituted without parsing the function again, and its period is found once the value stops changing
+	let values = BTreeMap::from([(String::from("b"), 2.0)]);
+	function.set_parameters(&values);
+	assert!(function.period().is_none());
+	function.set_parameters(&values);
+	assert!((function.period().unwrap() - std::f64::consts::PI).abs() < 1e-9);
+}
+
+#[test]
+fn user_function_entries() {
+	use parsing::Definitions;
+	use std::collections::BTreeMap;
+
+	let mut definition = FunctionEntry::default();
+	definition.update_string("f(x) = x^2 + 1");
+	assert!(definition.error_span().is_none());
+	let (name, body) = definition.definition().unwrap().clone();
+	assert_eq!((name.as_str(), body.as_str()), ("f", " x^2 + 1"));
+
+	let mut function = FunctionEntry::default();
+	function.update_string("f(x-2)");
+	assert!(function.error_span().is_some());
+
+	// Defining f parses the function calling it again
+	function.set_definitions(&Definitions::new(&BTreeMap::from([(name, body)]), false));
+	function.update_string("f(x-2)");
+	assert!(function.error_span().is_none());
+
+	// Errors in a definition's body are located in the whole string
+	definition.update_string("f(x) = x^");
+	assert_eq!(definition.error_span(), Some(9..9));
+}
+
+#[test]
+fn settings_json() {
+	use parsing::AngleUnit;
+
+	let settings = AppSettings {
+		integral_min_x: -2.0,
+		integral_max_x: 3.0,
+		angle_unit: AngleUnit::Degrees,
+		plot_width: 300,
+		integral_changed: false,
+		..AppSettings::default()
+	};
+	let json = serde_json::to_string(&settings).unwrap();
+	assert!(json.contains("\"angle_unit\":\"Degrees\""));
+
+	// State that's only meaningful while running isn't saved
+	assert!(!json.contains("plot_width") & !json.contains("integral_changed"));
+
+	let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+	assert_eq!((loaded.integral_min_x, loaded.integral_max_x), (-2.0, 3.0));
+	assert_eq!(loaded.angle_unit, AngleUnit::Degrees);
+	assert_eq!(loaded.plot_width, 0);
+	assert!(loaded.integral_changed);
+
+	// Settings missing from older sessions are their defaults
+	let loaded: AppSettings = serde_json::from_str("{\"integral_num\": 50}").unwrap();
+	assert_eq!(loaded.integral_num, 50);
+	assert_eq!(loaded.integral_max_x, AppSettings::default().integral_max_x);
+}
+
+/// Ensures entries are parsed the way they were graphed when they're loaded, along with their toggles
+#[test]
+fn function_json() {
+	let mut function = FunctionEntry::default();
+	function.sequence = true;
+	function.update_string("n^2");
+	function.mean_value = true;
+	function.tangent_x = Some(1.5);
+	let json = serde_json::to_string(&function).unwrap();
+
+	let loaded: FunctionEntry = serde_json::from_str(&json).unwrap();
+	assert_eq!(loaded.raw_func_str, "n^2");
+	assert!(loaded.sequence & loaded.mean_value);
+	assert_eq!(loaded.tangent_x, Some(1.5));
+	assert_eq!(loaded.get_test_result(), &None);
+
+	// Equations are graphed implicitly
+	let loaded: FunctionEntry =
+		serde_json::from_str("{\"raw_func_str\": \"x^2 + y^2 = 4\"}").unwrap();
+	assert_eq!(loaded.get_test_result(), &None);
+
+	// Fields missing from older sessions are the defaults of a new entry
+	let loaded: FunctionEntry = serde_json::from_str("{\"raw_func_str\": \"sin(x)\"}").unwrap();
+	assert!(loaded.visible);
+	assert_eq!(
+		loaded.contour_levels,
+		FunctionEntry::default().contour_levels
+	);
+}
@@ -0,0 +1,82 @@
+use parsing::CONSTANTS;
+use ytbn_graphing_software::{Language, Translations};
+
+/// Ensures every language has text for the same keys as English, with the same number of values filled in
+#[test]
+fn complete() {
+	let english = Translations::new(Language::English);
+	assert!(english.keys().count() > 0);
+
+	for language in Language::ALL {
+		let translations = Translations::new(language);
+		assert_eq!(
+			translations.keys().collect::<Vec<&str>>(),
+			english.keys().collect::<Vec<&str>>(),
+			"{}",
+			language
+		);
+
+		for key in english.keys() {
+			assert_eq!(
+				translations.get(key).matches("{}").count(),
+				english.get(key).matches("{}").count(),
+				"{} in {}",
+				key,
+				language
+			);
+		}
+	}
+}
+
+/// Ensures text is looked up in the chosen language, and values are filled in
+#[test]
+fn get() {
+	let spanish = Translations::new(Language::Spanish);
+	assert_eq!(spanish.language(), Language::Spanish);
+	assert_eq!(spanish.get("top_bar.help"), "Ayuda");
+	assert_eq!(
+		spanish.format("top_bar.export_image.save", "PNG"),
+		"Guardar como PNG"
+	);
+
+	// Keys without any text are shown as they are
+	assert_eq!(spanish.get("missing"), "missing");
+}
+
+/// Ensures each value is filled in where it goes, in order
+#[test]
+fn format_all() {
+	let spanish = Translations::new(Language::Spanish);
+	assert_eq!(
+		spanish.format_all("function.number_expression", &["2", "sin(x)"]),
+		"Función #2: sin(x)"
+	);
+}
+
+/// Ensures errors are translated along with the text filled into them, and errors without a translation are shown as they are
+#[test]
+fn error() {
+	let english = Translations::new(Language::English);
+	let spanish = Translations::new(Language::Spanish);
+	let error = "Error: unexpected ')' at position 6";
+	assert_eq!(english.error(error), error);
+	assert_eq!(
+		spanish.error(error),
+		"Error: ')' inesperado en la posición 6"
+	);
+	assert_eq!(
+		spanish.error("'f' has an error: unknown function 'sni'"),
+		"'f' tiene un error: función desconocida 'sni'"
+	);
+	assert_eq!(spanish.error("Something else"), "Something else");
+}
+
+/// Ensures every constant is described in the help
+#[test]
+fn constants() {
+	let english = Translations::new(Language::English);
+	for constant in CONSTANTS {
+		let key = format!("help.constants.{}", constant.names[0]);
+		assert_ne!(english.get(&key), key);
+	}
+}
@@ -0,0 +1,104 @@
+use ytbn_graphing_software::{marching_squares, ContourPlot, ImplicitPlot, SampledGrid};
+
+/// Ensures [`marching_squares`] traces the circle x^2 + y^2 = 4 as a single closed curve
+#[test]
+fn marching_squares_circle() {
+	let mut circle = |x: f64, y: f64| x.powi(2) + y.powi(2) - 4.0;
+	let curves = marching_squares(&mut circle, [-3.0, -3.0], [3.0, 3.0], [60, 60]);
+
+	assert_eq!(curves.len(), 1);
+	let curve = &curves[0];
+	assert!(curve.len() > 20);
+	assert_eq!(curve.first().unwrap(), curve.last().unwrap());
+
+	for point in curve.iter() {
+		let radius = point.x.hypot(point.y);
+		assert!(
+			(radius - 2.0).abs() < 0.01,
+			"{:?} is not on the circle",
+			point
+		);
+	}
+}
+
+/// Ensures [`marching_squares`] returns nothing for curves outside of the bounds
+#[test]
+fn marching_squares_empty() {
+	let mut circle = |x: f64, y: f64| x.powi(2) + y.powi(2) - 4.0;
+	assert!(marching_squares(&mut circle, [5.0, 5.0], [10.0, 10.0], [20, 20]).is_empty());
+}
+
+/// Ensures [`ImplicitPlot`] only retraces the curve when the bounds change
+#[test]
+fn implicit_plot_cache() {
+	let mut calls = 0;
+	let mut line = |x: f64, y: f64| {
+		calls += 1;
+		x - y
+	};
+
+	let mut plot = ImplicitPlot::default();
+	plot.update(&mut line, [-1.0, -1.0], [1.0, 1.0], 100);
+	assert_eq!(plot.curves.len(), 1);
+	plot.update(&mut line, [-1.0, -1.0], [1.0, 1.0], 100);
+	plot.update(&mut line, [-2.0, -1.0], [1.0, 1.0], 100);
+
+	// 25 by 25 cells, then 25 by 17 cells once the plot is wider than it is tall
+	assert_eq!(calls, (26 * 26) + (26 * 18));
+
+	plot.clear();
+	assert!(plot.curves.is_empty());
+}
+
+/// Ensures [`SampledGrid`] spaces levels evenly between its smallest and largest values, and traces each of them
+#[test]
+fn sampled_grid_contours() {
+	let mut paraboloid = |x: f64, y: f64| x.powi(2) + y.powi(2);
+	let grid = SampledGrid::new(&mut paraboloid, [-2.0, -2.0], [2.0, 2.0], [80, 80]);
+
+	let levels = grid.levels(3);
+	assert_eq!(levels.len(), 3);
+	for (level, expected) in levels.iter().zip([2.0, 4.0, 6.0]) {
+		assert!((level - expected).abs() < 1e-12);
+	}
+
+	// Level curves of x^2 + y^2 are circles
+	for level in [1.0, 2.0] {
+		let curves = grid.contour(level);
+		assert_eq!(curves.len(), 1);
+		for point in curves[0].iter() {
+			assert!((point.x.hypot(point.y) - level.sqrt()).abs() < 0.01);
+		}
+	}
+
+	// Levels above 4 are circles cut off by the edges of the grid
+	assert_eq!(grid.contour(6.0).len(), 4);
+	assert!(grid.contour(10.0).is_empty());
+
+	let mut undefined = |_: f64, _: f64| f64::NAN;
+	let grid = SampledGrid::new(&mut undefined, [-2.0, -2.0], [2.0, 2.0], [4, 4]);
+	assert!(grid.levels(3).is_empty());
+}
+
+/// Ensures [`ContourPlot`] traces each level, and only retraces them when the bounds or number of levels change
+#[test]
+fn contour_plot_cache() {
+	let mut calls = 0;
+	let mut plane = |x: f64, y: f64| {
+		calls += 1;
+		x + y
+	};
+
+	let mut plot = ContourPlot::default();
+	plot.update(&mut plane, [-1.0, -1.0], [1.0, 1.0], 100, 4);
+	assert_eq!(plot.levels.len(), 4);
+	assert!(plot.levels.iter().all(|(_, curves)| curves.len() == 1));
+
+	plot.update(&mut plane, [-1.0, -1.0], [1.0, 1.0], 100, 4);
+	plot.update(&mut plane, [-1.0, -1.0], [1.0, 1.0], 100, 5);
+	assert_eq!(plot.levels.len(), 5);
+	assert_eq!(calls, 2 * 26 * 26);
+
+	plot.clear();
+	assert!(plot.levels.is_empty());
+}
@@ -0,0 +1,31 @@
+use egui::Key;
+use ytbn_graphing_software::{Action, Keybindings};
+
+/// Ensures actions are bound to their default keys until they're changed, which don't conflict
+#[test]
+fn defaults() {
+	let mut keys = Keybindings::new();
+	assert_eq!(keys.get(Action::TogglePanel), Key::H);
+	assert!(keys.conflicts().is_empty());
+
+	keys.set(Action::TogglePanel, Key::P);
+	assert_eq!(keys.get(Action::TogglePanel), Key::P);
+
+	// Setting it back to its default key is the same as never changing it
+	keys.set(Action::TogglePanel, Key::H);
+	assert_eq!(keys, Keybindings::new());
+}
+
+/// Ensures actions bound to the same key are found, and that bindings are kept through JSON
+#[test]
+fn conflicts() {
+	let mut keys = Keybindings::new();
+	keys.set(Action::ResetView, Key::F);
+	assert_eq!(
+		keys.conflicts().into_iter().collect::<Vec<Action>>(),
+		vec![Action::ResetView, Action::FitView]
+	);
+
+	let json = serde_json::to_string(&keys).unwrap();
+	assert_eq!(serde_json::from_str::<Keybindings>(&json).unwrap(), keys);
+}
@@ -142,17 +142,17 @@ fn invalid_hashed_storage() {
 #[test]
 fn newtons_method() {
 	use parsing::BackingFunction;
-	use parsing::FlatExWrapper;
-	fn get_flatexwrapper(func: &str) -> FlatExWrapper {
-		let mut backing_func = BackingFunction::new(func).unwrap();
+	use parsing::ExpressionWrapper;
+	fn get_expression_wrapper(func: &str) -> ExpressionWrapper {
+		let mut backing_func = BackingFunction::new(&parsing::process_func_str(func)).unwrap();
 		backing_func.get_function_derivative(0).clone()
 	}
 
 	use ytbn_graphing_software::newtons_method;
 
 	let data = newtons_method(
-		&get_flatexwrapper("x^2 -1"),
-		&get_flatexwrapper("2x"),
+		&get_expression_wrapper("x^2 -1"),
+		&get_expression_wrapper("2x"),
 		3.0,
 		&(0.0..5.0),
 		f64::EPSILON,
@@ -160,8 +160,8 @@ fn newtons_method() {
 	assert_eq!(data, Some(1.0));
 
 	let data = newtons_method(
-		&get_flatexwrapper("sin(x)"),
-		&get_flatexwrapper("cos(x)"),
+		&get_expression_wrapper("sin(x)"),
+		&get_expression_wrapper("cos(x)"),
 		3.0,
 		&(2.95..3.18),
 		f64::EPSILON,
@@ -169,6 +169,184 @@ fn newtons_method() {
 	assert_eq!(data, Some(std::f64::consts::PI));
 }
 
+#[test]
+fn is_discontinuous() {
+	use ytbn_graphing_software::is_discontinuous;
+
+	// Steep, but continuous
+	assert!(!is_discontinuous(&mut |x| 100.0 * x, 0.0, 1.0, 20));
+	assert!(!is_discontinuous(&mut |x| x * x * x, -1.0, 1.0, 20));
+
+	// Jump and infinite discontinuities
+	assert!(is_discontinuous(&mut f64::floor, 0.9, 1.1, 20));
+	assert!(is_discontinuous(&mut |x| 1.0 / x, -0.3, 0.7, 20));
+	assert!(is_discontinuous(&mut f64::tan, 1.5, 1.6, 20));
+}
+
+#[test]
+fn to_segments() {
+	use egui_plot::PlotPoint;
+	use ytbn_graphing_software::EguiHelper;
+
+	let data: Vec<PlotPoint> = [0.0, 1.0, 2.0, f64::NAN, 4.0, 5.0, 6.0]
+		.iter()
+		.enumerate()
+		.map(|(x, y)| PlotPoint::new(x as f64, *y))
+		.collect();
+
+	let segments: Vec<Vec<(f64, f64)>> = data
+		.to_segments(&[4])
+		.into_iter()
+		.map(|segment| segment.to_tuple())
+		.collect();
+
+	assert_eq!(
+		segments,
+		vec![
+			vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)],
+			vec![(4.0, 4.0)],
+			vec![(5.0, 5.0), (6.0, 6.0)],
+		]
+	);
+}
+
+#[test]
+fn one_sided_limit() {
+	use ytbn_graphing_software::one_sided_limit;
+
+	// Removable discontinuity
+	let mut sinc = |x: f64| x.sin() / x;
+	assert!((one_sided_limit(&mut sinc, 0.0, -1.0) - 1.0).abs() < 1e-10);
+	assert!((one_sided_limit(&mut sinc, 0.0, 1.0) - 1.0).abs() < 1e-10);
+
+	// Jump discontinuity
+	assert_eq!(one_sided_limit(&mut f64::floor, 1.0, -1.0), 0.0);
+	assert_eq!(one_sided_limit(&mut f64::floor, 1.0, 1.0), 1.0);
+
+	// Infinite discontinuity
+	let mut reciprocal = |x: f64| 1.0 / x;
+	assert_eq!(
+		one_sided_limit(&mut reciprocal, 0.0, -1.0),
+		f64::NEG_INFINITY
+	);
+	assert_eq!(one_sided_limit(&mut reciprocal, 0.0, 1.0), f64::INFINITY);
+
+	// Continuous
+	assert!((one_sided_limit(&mut |x| x * x, 2.0, 1.0) - 4.0).abs() < 1e-10);
+}
+
+#[test]
+fn brent_method() {
+	use ytbn_graphing_software::brent_method;
+
+	let root = brent_method(&mut |x| (x * x) - 2.0, 0.0, 2.0, 1e-14, 100).unwrap();
+	assert!((root - 2.0_f64.sqrt()).abs() < 1e-13);
+
+	let root = brent_method(&mut f64::cos, 1.0, 2.0, 1e-14, 100).unwrap();
+	assert!((root - std::f64::consts::FRAC_PI_2).abs() < 1e-13);
+
+	// Root isn't bracketed
+	assert_eq!(
+		brent_method(&mut |x| (x * x) + 1.0, -1.0, 1.0, 1e-14, 100),
+		None
+	);
+}
+
+#[test]
+fn refine_root() {
+	use ytbn_graphing_software::refine_root;
+
+	let root = refine_root(&mut |x| ((x * x) - 2.0, 2.0 * x), 1.4, 1.0, 2.0, 1e-14).unwrap();
+	assert!((root - 2.0_f64.sqrt()).abs() < 1e-14);
+
+	// f'(0) = 0 sends Newton's method out of [-1, 3], falling back to Brent's method
+	let root = refine_root(&mut |x| ((x * x) - 2.0, 2.0 * x), 0.0, -1.0, 3.0, 1e-14).unwrap();
+	assert!((root - 2.0_f64.sqrt()).abs() < 1e-13);
+}
+
+#[test]
+fn adaptive_simpson() {
+	use ytbn_graphing_software::adaptive_simpson;
+
+	// Simpson's rule is exact for polynomials of degree 3 or below, so no subdivision is needed
+	let (area, intervals) = adaptive_simpson(&mut |x| x * x * x, 0.0, 2.0, 1e-6, 16);
+	assert_eq!(area, 4.0);
+	assert_eq!(intervals.len(), 1);
+
+	let (area, intervals) = adaptive_simpson(&mut |x| x.sin(), 0.0, std::f64::consts::PI, 1e-8, 16);
+	assert!((area - 2.0).abs() < 1e-8);
+	assert!(intervals.len() > 1);
+
+	// Subintervals should tile the entire range
+	assert_eq!(intervals.first().unwrap().0, 0.0);
+	assert_eq!(intervals.last().unwrap().1, std::f64::consts::PI);
+	assert!(intervals.windows(2).all(|w| w[0].1 == w[1].0));
+}
+
+#[test]
+fn improper_integral() {
+	use std::f64::consts::PI;
+	use ytbn_graphing_software::improper_integral;
+
+	let gaussian = improper_integral(
+		&mut |x| (-(x * x)).exp(),
+		f64::NEG_INFINITY,
+		f64::INFINITY,
+		1e-10,
+		16,
+	);
+	assert!((gaussian - PI.sqrt()).abs() < 1e-8);
+
+	let area = improper_integral(&mut |x| 1.0 / (x * x), 1.0, f64::INFINITY, 1e-10, 16);
+	assert!((area - 1.0).abs() < 1e-6);
+
+	let area = improper_integral(&mut |x| x.exp(), f64::NEG_INFINITY, 0.0, 1e-10, 16);
+	assert!((area - 1.0).abs() < 1e-8);
+
+	// Finite bounds should behave just like `adaptive_simpson`
+	assert_eq!(improper_integral(&mut |x| x * x, 0.0, 3.0, 1e-6, 16), 9.0);
+}
+
+#[test]
+fn singular_integral() {
+	use ytbn_graphing_software::singular_integral;
+
+	// Singularities on either end of the interval
+	let area = singular_integral(&mut |x| 1.0 / x.sqrt(), 0.0, 1.0, 1e-8).unwrap();
+	assert!((area - 2.0).abs() < 1e-4);
+
+	let area = singular_integral(&mut |x| 1.0 / (1.0 - x).sqrt(), 0.0, 1.0, 1e-8).unwrap();
+	assert!((area - 2.0).abs() < 1e-4);
+
+	let area = singular_integral(&mut |x| x.ln(), 0.0, 1.0, 1e-8).unwrap();
+	assert!((area + 1.0).abs() < 1e-4);
+
+	// Non-integrable singularities
+	assert_eq!(singular_integral(&mut |x| 1.0 / x, 0.0, 1.0, 1e-8), None);
+	assert_eq!(
+		singular_integral(&mut |x| 1.0 / (x * x), 0.0, 1.0, 1e-8),
+		None
+	);
+}
+
+#[test]
+fn romberg() {
+	use ytbn_graphing_software::romberg;
+
+	let table = romberg(&mut |x| x.exp(), 0.0, 1.0, 5);
+	assert_eq!(table.len(), 5);
+	assert!(table.iter().enumerate().all(|(i, row)| row.len() == i + 1));
+
+	// First column is the plain trapezoid rule with a single interval
+	assert_eq!(table[0][0], (1.0 + std::f64::consts::E) / 2.0);
+
+	let area = *table.last().unwrap().last().unwrap();
+	assert!((area - (std::f64::consts::E - 1.0)).abs() < 1e-10);
+
+	// Should always create at least one row
+	assert_eq!(romberg(&mut |x| x, 0.0, 1.0, 0), vec![vec![0.5]]);
+}
+
 #[test]
 fn to_unicode_hash() {
 	use ytbn_graphing_software::to_unicode_hash;
@@ -183,3 +361,309 @@ fn to_chars_array() {
 		r"['\u{1f31e}', '\u{2d12c}']"
 	);
 }
+
+#[test]
+fn find_roots() {
+	use ytbn_graphing_software::find_roots;
+
+	let roots = find_roots(&mut |x| (x.sin(), x.cos()), -4.0, 4.0, 100, 1e-12);
+	assert_eq!(roots.len(), 3);
+	assert!((roots[0] + std::f64::consts::PI).abs() < 1e-12);
+	assert!(roots[1].abs() < 1e-12);
+	assert!((roots[2] - std::f64::consts::PI).abs() < 1e-12);
+
+	// Roots landing exactly on a sample
+	assert_eq!(
+		find_roots(&mut |x| ((x * x) - 1.0, 2.0 * x), -1.0, 1.0, 3, 1e-12),
+		vec![-1.0, 1.0]
+	);
+
+	// Sign changes across a discontinuity aren't roots
+	assert!(find_roots(&mut |x| (1.0 / x, -1.0 / (x * x)), -1.0, 1.5, 100, 1e-12).is_empty());
+
+	assert!(find_roots(&mut |x| (x, 1.0), -1.0, 1.0, 1, 1e-12).is_empty());
+}
+
+#[test]
+fn detect_period() {
+	use std::f64::consts::{PI, TAU};
+	use ytbn_graphing_software::detect_period;
+
+	let period = detect_period(&mut f64::sin, 100.0).unwrap();
+	assert!((period - TAU).abs() < 1e-9);
+
+	let period = detect_period(&mut |x| x.sin() + (2.0 * x).cos(), 100.0).unwrap();
+	assert!((period - TAU).abs() < 1e-9);
+
+	let period = detect_period(&mut |x| (PI * x).sin(), 100.0).unwrap();
+	assert!((period - 2.0).abs() < 1e-9);
+
+	let period = detect_period(&mut f64::tan, 100.0).unwrap();
+	assert!((period - PI).abs() < 1e-9);
+
+	assert_eq!(detect_period(&mut |x| x * x, 100.0), None);
+	assert_eq!(detect_period(&mut |_| 1.0, 100.0), None);
+	assert_eq!(
+		detect_period(&mut |x| x.sin() + (PI * x).sin(), 100.0),
+		None
+	);
+}
+
+#[test]
+fn sequence() {
+	use ytbn_graphing_software::sequence;
+
+	let points = sequence(&mut |n| 1.0 / n, 1, -3.5, 4.5);
+	assert_eq!(
+		points
+			.iter()
+			.map(|point| (point.x, point.y))
+			.collect::<Vec<_>>(),
+		vec![(1.0, 1.0), (2.0, 0.5), (3.0, 1.0 / 3.0), (4.0, 0.25)]
+	);
+
+	// Terms that aren't defined are skipped
+	let points = sequence(&mut |n| 1.0 / n, -2, -2.0, 2.0);
+	assert_eq!(
+		points.iter().map(|point| point.x).collect::<Vec<_>>(),
+		vec![-2.0, -1.0, 1.0, 2.0]
+	);
+
+	assert!(sequence(&mut |n| n, 10, 0.0, 5.0).is_empty());
+}
+
+#[test]
+fn partial_sums() {
+	use ytbn_graphing_software::partial_sums;
+
+	// Sums start at the starting index, even if it isn't visible
+	let points = partial_sums(&mut |n| n, 1, 2.5, 5.0);
+	assert_eq!(
+		points
+			.iter()
+			.map(|point| (point.x, point.y))
+			.collect::<Vec<_>>(),
+		vec![(3.0, 6.0), (4.0, 10.0), (5.0, 15.0)]
+	);
+
+	// The geometric series 1/2 + 1/4 + ... converges to 1
+	let points = partial_sums(&mut |n| 0.5_f64.powf(n), 1, 0.0, 60.0);
+	assert_eq!(points.len(), 60);
+	assert!((points.last().unwrap().y - 1.0).abs() < 1e-15);
+
+	// Stops at the first term that isn't defined
+	let points = partial_sums(&mut |n| 1.0 / n, -2, -5.0, 5.0);
+	assert_eq!(points.len(), 2);
+}
+
+#[test]
+fn to_csv() {
+	use ytbn_graphing_software::to_csv;
+
+	assert_eq!(
+		to_csv(
+			["x", "y"],
+			[[0.0, 1.5], [-2.0, f64::NAN], [1e-7, f64::INFINITY]]
+		),
+		"x,y\n0,1.5\n-2,\n0.0000001,\n"
+	);
+	assert_eq!(to_csv(["x"], []), "x\n");
+}
+
+#[test]
+fn parse_csv_points() {
+	use ytbn_graphing_software::parse_csv_points;
+
+	let points = parse_csv_points("x,y\n0,1\n\n# comment\n1.5, -2\n2;3e2\n  4\t5  \n").unwrap();
+	assert_eq!(
+		points
+			.iter()
+			.map(|point| (point.x, point.y))
+			.collect::<Vec<_>>(),
+		vec![(0.0, 1.0), (1.5, -2.0), (2.0, 300.0), (4.0, 5.0)]
+	);
+
+	assert!(parse_csv_points("").unwrap().is_empty());
+	assert_eq!(
+		parse_csv_points("0,1\n1,2,3"),
+		Err(String::from(
+			"Error: line 2: expected two numbers, found \"1,2,3\""
+		))
+	);
+	assert_eq!(
+		parse_csv_points("x,y\na,b"),
+		Err(String::from(
+			"Error: line 2: expected two numbers, found \"a,b\""
+		))
+	);
+}
+
+#[test]
+fn parse_number() {
+	use ytbn_graphing_software::parse_number;
+
+	assert_eq!(parse_number("1000"), Some(1000.0));
+	assert_eq!(parse_number(" -3.5 "), Some(-3.5));
+	assert_eq!(parse_number("pi/2"), Some(std::f64::consts::FRAC_PI_2));
+	assert_eq!(parse_number("sqrt(2)"), Some(std::f64::consts::SQRT_2));
+	assert_eq!(parse_number("2π"), Some(std::f64::consts::TAU));
+
+	// Only constant expressions evaluating to finite numbers are accepted
+	assert_eq!(parse_number("x"), None);
+	assert_eq!(parse_number("a"), None);
+	assert_eq!(parse_number("1/0"), None);
+	assert_eq!(parse_number("sin("), None);
+}
+
+#[test]
+fn pi_label() {
+	use std::f64::consts::PI;
+	use ytbn_graphing_software::pi_label;
+
+	assert_eq!(pi_label(0.0), "0");
+	assert_eq!(pi_label(PI), "π");
+	assert_eq!(pi_label(-PI), "-π");
+	assert_eq!(pi_label(2.0 * PI), "2π");
+	assert_eq!(pi_label(PI / 2.0), "π/2");
+	assert_eq!(pi_label(-3.0 * PI / 4.0), "-3π/4");
+	assert_eq!(pi_label(3.0 * PI / 2.0), "3π/2");
+	assert_eq!(pi_label(PI / 8.0), "π/8");
+
+	// Values that aren't a multiple of π/8 are labeled as is
+	assert_eq!(pi_label(1.0), "1");
+	assert_eq!(pi_label(PI / 3.0), "1.047198");
+}
+
+#[test]
+fn closed_form() {
+	use std::f64::consts::{E, PI, SQRT_2};
+	use ytbn_graphing_software::closed_form;
+
+	assert_eq!(closed_form(PI, 1e-9).as_deref(), Some("π"));
+	assert_eq!(closed_form(PI / 2.0, 1e-9).as_deref(), Some("π/2"));
+	assert_eq!(closed_form(-3.0 * PI / 4.0, 1e-9).as_deref(), Some("-3π/4"));
+	assert_eq!(closed_form(E, 1e-9).as_deref(), Some("e"));
+	assert_eq!(closed_form(2.0 / 3.0, 1e-9).as_deref(), Some("2/3"));
+	assert_eq!(closed_form(SQRT_2 / 2.0, 1e-9).as_deref(), Some("√2/2"));
+	assert_eq!(closed_form(PI * PI / 6.0, 1e-9).as_deref(), Some("π²/6"));
+
+	// Within the tolerance, but not outside of it
+	assert_eq!(closed_form(1.0 / 3.0 + 1e-12, 1e-9).as_deref(), Some("1/3"));
+	assert_eq!(closed_form(1.0 / 3.0 + 1e-6, 1e-9), None);
+
+	// Integers are already exact, and other numbers aren't close to anything simple
+	assert_eq!(closed_form(2.0, 1e-9), None);
+	assert_eq!(closed_form(1.2345678, 1e-9), None);
+	assert_eq!(closed_form(f64::NAN, 1e-9), None);
+}
+
+#[test]
+fn pi_grid_spacer() {
+	use egui_plot::GridInput;
+	use std::f64::consts::PI;
+	use ytbn_graphing_software::pi_grid_spacer;
+
+	let marks = pi_grid_spacer(GridInput {
+		bounds: (-PI, PI),
+		base_step_size: 0.3,
+	});
+
+	// Every mark is a multiple of its step size, which is a multiple of π
+	assert!(!marks.is_empty());
+	for mark in &marks {
+		let steps = mark.value / mark.step_size;
+		assert!((steps - steps.round()).abs() < 1e-9);
+		let parts = mark.step_size / PI * 8.0;
+		assert!((parts - parts.round()).abs() < 1e-9);
+	}
+	assert!(marks
+		.iter()
+		.any(|mark| (mark.value - PI / 2.0).abs() < 1e-9));
+}
+
+#[test]
+fn snap_bounds() {
+	use ytbn_graphing_software::snap_bounds;
+
+	let roots = [-3.0, 0.0, 3.0, 6.0];
+
+	// Each bound snaps to the point nearest to it
+	assert_eq!(snap_bounds(-2.5, 5.0, &roots), Some((-3.0, 6.0)));
+	assert_eq!(
+		snap_bounds(0.5, 2.0, &[3.0, 0.0, f64::NAN]),
+		Some((0.0, 3.0))
+	);
+
+	// Bounds nearest to the same point snap to the consecutive points around them
+	assert_eq!(snap_bounds(1.0, 1.2, &roots), Some((0.0, 3.0)));
+	assert_eq!(snap_bounds(-10.0, -9.0, &roots), Some((-3.0, 0.0)));
+	assert_eq!(snap_bounds(9.0, 10.0, &roots), Some((3.0, 6.0)));
+
+	// Reversed bounds stay reversed
+	assert_eq!(snap_bounds(1.2, 1.0, &roots), Some((3.0, 0.0)));
+
+	assert_eq!(snap_bounds(0.0, 1.0, &[2.0, 2.0]), None);
+	assert_eq!(snap_bounds(0.0, 1.0, &[]), None);
+}
+
+#[test]
+fn trapezoid_points() {
+	use egui_plot::PlotPoint;
+	use ytbn_graphing_software::trapezoid_points;
+
+	// Points of y = x^2, in no particular order
+	let points: Vec<PlotPoint> = [2.0, 0.0, 1.0, 3.0]
+		.iter()
+		.map(|x| PlotPoint::new(*x, x * x))
+		.collect();
+
+	assert_eq!(trapezoid_points(&points, 0.0, 3.0), 9.5);
+	assert_eq!(trapezoid_points(&points, 3.0, 0.0), -9.5);
+
+	// Only the part of the bounds the points span is integrated, and the bounds can be between points
+	assert_eq!(trapezoid_points(&points, -5.0, 10.0), 9.5);
+	assert_eq!(trapezoid_points(&points, 0.5, 1.5), 1.25);
+
+	assert_eq!(trapezoid_points(&[], 0.0, 1.0), 0.0);
+}
+
+/// Ensures [`encode_fragment`] only uses characters that can be in a URL's fragment, and [`decode_fragment`] reverses it
+#[test]
+fn fragment() {
+	use ytbn_graphing_software::{decode_fragment, encode_fragment};
+
+	[&b""[..], b"a", b"ab", b"abc", &[0xFF, 0x00, 0xFE, 0x01]]
+		.into_iter()
+		.chain(std::iter::once(
+			"{\"raw_func_str\": \"sin(x)\"}".repeat(100).as_bytes(),
+		))
+		.for_each(|data| {
+			let fragment = encode_fragment(data);
+			assert!(fragment
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() | (c == '-') | (c == '_')));
+			assert_eq!(decode_fragment(&fragment).as_deref(), Some(data));
+		});
+
+	// Repeated data is compressed
+	assert!(encode_fragment("sin(x)".repeat(100).as_bytes()).len() < 100);
+
+	assert_eq!(decode_fragment("not base64!"), None);
+	assert_eq!(decode_fragment("A"), None);
+	assert_eq!(decode_fragment("AAAA"), None);
+}
+
+/// Ensures [`query_flag`] finds flags set on their own or to a value, but not ones turned off or with other names
+#[test]
+fn query_flag() {
+	use ytbn_graphing_software::query_flag;
+
+	assert!(query_flag("?embed=1", "embed"));
+	assert!(query_flag("?lang=es&embed", "embed"));
+	assert!(query_flag("embed=true", "embed"));
+
+	assert!(!query_flag("", "embed"));
+	assert!(!query_flag("?embed=0", "embed"));
+	assert!(!query_flag("?embed=false", "embed"));
+	assert!(!query_flag("?embedded=1", "embed"));
+}
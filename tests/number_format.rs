@@ -0,0 +1,54 @@
+use ytbn_graphing_software::{Language, NumberFormat, Precision, Translations};
+
+/// Ensures numbers are rounded to decimal places by default, without trailing zeros or negative zero
+#[test]
+fn decimal_places() {
+	let i18n = Translations::new(Language::English);
+	let format = NumberFormat {
+		digits: 3,
+		..NumberFormat::default()
+	};
+	assert_eq!(format.format(1.23456, &i18n), "1.235");
+	assert_eq!(format.format(2.5, &i18n), "2.5");
+	assert_eq!(format.format(-0.0001, &i18n), "0");
+	assert_eq!(format.format(1234567.0, &i18n), "1234567");
+	assert_eq!(format.format(f64::NAN, &i18n), "undefined");
+	assert_eq!(
+		format.format(f64::NAN, &Translations::new(Language::Spanish)),
+		"indefinido"
+	);
+	assert_eq!(format.format(f64::NEG_INFINITY, &i18n), "-∞");
+}
+
+/// Ensures significant figures are counted from the first digit that isn't zero, including before the decimal point
+#[test]
+fn significant_figures() {
+	let i18n = Translations::new(Language::English);
+	let format = NumberFormat {
+		digits: 3,
+		precision: Precision::SignificantFigures,
+		..NumberFormat::default()
+	};
+	assert_eq!(format.format(0.000123456, &i18n), "0.000123");
+	assert_eq!(format.format(3.14159, &i18n), "3.14");
+	assert_eq!(format.format(123456.0, &i18n), "123000");
+	assert_eq!(format.format(0.0, &i18n), "0");
+}
+
+/// Ensures numbers far enough from 1 are written in scientific notation, and thousands are separated
+#[test]
+fn scientific_and_thousands() {
+	let i18n = Translations::new(Language::English);
+	let format = NumberFormat {
+		digits: 2,
+		scientific_exponent: 6,
+		thousands_separators: true,
+		..NumberFormat::default()
+	};
+	assert_eq!(format.format(12345678.0, &i18n), "1.23e7");
+	assert_eq!(format.format(-0.00000012, &i18n), "-1.2e-7");
+	assert_eq!(format.format(3000000.0, &i18n), "3e6");
+	assert_eq!(format.format(-12345.678, &i18n), "-12,345.68");
+	assert_eq!(format.format(123456.0, &i18n), "123,456");
+	assert_eq!(format.format(999.5, &i18n), "999.5");
+}
@@ -0,0 +1,146 @@
+use egui_plot::PlotPoint;
+use ytbn_graphing_software::{slope_field, SlopeField};
+
+/// Ensures [`slope_field`] places an arrow along the slope in the center of each cell
+#[test]
+fn slope_field_arrows() {
+	let mut f = |x: f64, y: f64| x * y;
+	let (origins, tips) = slope_field(&mut f, [-2.0, -1.0], [2.0, 1.0], [4, 2]);
+	assert_eq!(origins.len(), 8);
+	assert_eq!(tips.len(), 8);
+
+	for (origin, tip) in origins.iter().zip(tips.iter()) {
+		let center = ((origin.x + tip.x) / 2.0, (origin.y + tip.y) / 2.0);
+		assert!((center.0.abs() - 0.5).abs() < 1e-12 || (center.0.abs() - 1.5).abs() < 1e-12);
+		assert!((center.1.abs() - 0.5).abs() < 1e-12);
+
+		// Points to the right, along the slope, and is 3/4 of a cell long
+		assert!(tip.x > origin.x);
+		let slope = (tip.y - origin.y) / (tip.x - origin.x);
+		assert!((slope - f(center.0, center.1)).abs() < 1e-12);
+		let length = (tip.x - origin.x).hypot(tip.y - origin.y);
+		assert!((length - 0.75).abs() < 1e-12);
+	}
+}
+
+/// Ensures [`slope_field`] skips arrows where the slope isn't defined
+#[test]
+fn slope_field_undefined() {
+	let mut f = |x: f64, _: f64| 1.0 / x.max(0.0);
+	let (origins, tips) = slope_field(&mut f, [-2.0, -1.0], [2.0, 1.0], [4, 2]);
+	assert_eq!(origins.len(), 4);
+	assert_eq!(tips.len(), 4);
+	assert!(origins.iter().all(|origin| origin.x > 0.0));
+}
+
+/// Ensures the density of [`SlopeField`]'s arrows is tied to the plot's width, and that it's cached
+#[test]
+fn slope_field_cache() {
+	let mut calls = 0;
+	let mut f = |_: f64, _: f64| {
+		calls += 1;
+		0.0
+	};
+
+	let mut field = SlopeField::default();
+	field.update(&mut f, [-1.0, -1.0], [1.0, 1.0], 320);
+	assert_eq!(field.origins.len(), 100);
+	field.update(&mut f, [-1.0, -1.0], [1.0, 1.0], 320);
+	field.update(&mut f, [-1.0, -1.0], [1.0, 1.0], 640);
+	assert_eq!(field.origins.len(), 400);
+	assert_eq!(calls, 500);
+
+	field.clear();
+	assert!(field.origins.is_empty());
+	assert!(field.tips.is_empty());
+}
+
+/// Ensures [`rk4`] accurately solves dy/dx = y, whose solution is y = e^x
+#[test]
+fn rk4_exponential() {
+	use ytbn_graphing_software::rk4;
+	let mut f = |_: f64, y: f64| y;
+
+	let forwards = rk4(&mut f, PlotPoint::new(0.0, 1.0), 0.01, 1.0);
+	assert_eq!(forwards.len(), 101);
+	let end = forwards.last().unwrap();
+	assert!((end.x - 1.0).abs() < 1e-10);
+	assert!((end.y - std::f64::consts::E).abs() < 1e-9);
+
+	let backwards = rk4(&mut f, PlotPoint::new(0.0, 1.0), -0.01, -1.0);
+	let end = backwards.last().unwrap();
+	assert!((end.x + 1.0).abs() < 1e-10);
+	assert!((end.y - (-1.0_f64).exp()).abs() < 1e-9);
+}
+
+/// Ensures [`rk4`] stops once the solution isn't defined
+#[test]
+fn rk4_undefined() {
+	use ytbn_graphing_software::rk4;
+
+	// dy/dx = 1 / x isn't defined at x = 0
+	let points = rk4(
+		&mut |x: f64, _| 1.0 / x,
+		PlotPoint::new(-1.0, 0.0),
+		0.25,
+		1.0,
+	);
+	assert!(points.len() < 9);
+	assert!(points.iter().all(|point| point.y.is_finite()));
+}
+
+/// Ensures [`ode_solution`] solves through the initial condition in both directions and is cut off far from the plot
+#[test]
+fn ode_solution_curve() {
+	use ytbn_graphing_software::ode_solution;
+
+	// dy/dx = 2x, whose solutions are y = x^2 + C
+	let mut f = |x: f64, _: f64| 2.0 * x;
+	let curve = ode_solution(
+		&mut f,
+		PlotPoint::new(1.0, 2.0),
+		[-2.0, -2.0],
+		[2.0, 2.0],
+		400,
+	);
+	assert!(curve.windows(2).all(|pair| pair[0].x < pair[1].x));
+	assert!((curve.first().unwrap().x + 2.0).abs() < 1e-10);
+	assert!((curve.last().unwrap().x - 2.0).abs() < 1e-10);
+	for point in curve.iter() {
+		assert!((point.y - (point.x.powi(2) + 1.0)).abs() < 1e-9);
+	}
+
+	// dy/dx = y grows past the plot, so the curve is cut off before reaching x = 10
+	let curve = ode_solution(
+		&mut |_, y: f64| y,
+		PlotPoint::new(0.0, 1.0),
+		[-10.0, -5.0],
+		[10.0, 5.0],
+		200,
+	);
+	assert!(curve.last().unwrap().x < 3.0);
+	assert!(curve.iter().all(|point| point.y <= 15.0));
+}
+
+/// Ensures [`SlopeField`] solves through each initial condition
+#[test]
+fn slope_field_solutions() {
+	let mut f = |x: f64, _: f64| 2.0 * x;
+	let mut field = SlopeField::default();
+	field.initial_conditions = vec![PlotPoint::new(0.0, 0.0), PlotPoint::new(0.0, 1.0)];
+	field.update(&mut f, [-1.0, -1.0], [1.0, 1.0], 320);
+	assert_eq!(field.solutions.len(), 2);
+
+	field.initial_conditions.remove(0);
+	field.solutions.clear();
+	field.update(&mut f, [-1.0, -1.0], [1.0, 1.0], 320);
+	assert_eq!(field.solutions.len(), 1);
+	assert!(field.solutions[0]
+		.iter()
+		.all(|point| (point.y - (point.x.powi(2) + 1.0)).abs() < 1e-9));
+
+	// Clearing keeps the initial conditions
+	field.clear();
+	assert!(field.solutions.is_empty());
+	assert_eq!(field.initial_conditions.len(), 1);
+}
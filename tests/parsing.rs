@@ -69,11 +69,13 @@ fn test_expected() {
 		("E^sin(x)", true),
 		("e^x", true),
 		("x**2", true),
+		("theta^2", true),
+		("1+cos(θ)", true),
 		("a", false),
 		("log222(x)", false),
 		("abcdef", false),
 		("log10(x", false),
-		("x^a", false),
+		("x^a", true),
 		("sin(cos(x)))", false),
 		("0/0", false),
 	]);
@@ -106,6 +108,16 @@ fn func_process_test() {
 		("e^sin(x)", "e^sin(x)"),
 		("x**2", "x^2"),
 		("(x+1)(x-3)", "(x+1)*(x-3)"),
+		("2theta", "2*x"),
+		("sin(theta)", "sin(x)"),
+		("θ^2", "x^2"),
+		("thetapi", "x*π"),
+		("2xy", "2*x*y"),
+		("x^2+y^2=4", "x^2+y^2=4"),
+		("2i", "2*i"),
+		("xi", "x*i"),
+		("sinh(x)", "sinh(x)"),
+		("ceil(x)", "ceil(x)"),
 	]);
 
 	for (key, value) in values {
@@ -124,14 +136,45 @@ fn hints() {
 	let values = HashMap::from([
 		("", Hint::Single("x^2")),
 		("si", Hint::Many(&["n(", "nh(", "gnum("])),
-		("log", Hint::Many(&["2(", "10("])),
+		("log", Hint::Many(&["2(", "10(", "("])),
 		("cos", Hint::Many(&["(", "h("])),
-		("sin(", Hint::Single(")")),
+		("sin(", Hint::Single("x")),
 		("sqrt", Hint::Single("(")),
 		("ln(x)", Hint::None),
 		("ln(x)cos", Hint::Many(&["(", "h("])),
 		("ln(x)*cos", Hint::Many(&["(", "h("])),
 		("sin(cos", Hint::Many(&["(", "h("])),
+		("th", Hint::Single("eta")),
+		("sin(the", Hint::Single("ta")),
+		("cla", Hint::Single("mp(")),
+		("max(x, 2", Hint::Many(&[")", "+", "-", "*", "/", "^"])),
+		("|x", Hint::Single("|")),
+		("|x|", Hint::None),
+		("|sin(x", Hint::Single(")")),
+		("|sin(x)", Hint::Single("|")),
+		("|si", Hint::Many(&["n(", "nh(", "gnum("])),
+		("erf", Hint::Many(&["(", "c("])),
+		("2ex", Hint::Single("p(")),
+		("besse", Hint::Single("lj(")),
+		("atan", Hint::Many(&["(", "h(", "2("])),
+		("lights", Hint::Single("peed")),
+		("2avog", Hint::Single("adro")),
+		("(", Hint::Single("x")),
+		("|", Hint::Single("x")),
+		("2", Hint::Many(&["+", "-", "*", "/", "^"])),
+		("x^2.5", Hint::Many(&["+", "-", "*", "/", "^"])),
+		("|2", Hint::Many(&["|", "+", "-", "*", "/", "^"])),
+		("sin(x)+", Hint::None),
+		("sin(x+", Hint::None),
+		("max(x,", Hint::None),
+		("sin(x!", Hint::Single(")")),
+		("xsi", Hint::None),
+		("xs", Hint::None),
+		("log2", Hint::Single("(")),
+		("sin(cos(x", Hint::Single("))")),
+		("sin(cos(x)", Hint::Single(")")),
+		("sin(x))", Hint::None),
+		("max(x, min(2", Hint::Many(&["))", "+", "-", "*", "/", "^"])),
 	]);
 
 	for (key, value) in values {
@@ -292,3 +335,1229 @@ fn hint_accessor() {
 	assert_eq!(Hint::None.single(), None);
 	assert_eq!(Hint::None.many(), None);
 }
+
+#[test]
+fn hashmap_terms_gen_test() {
+	let data = ["tan(", "theta"];
+	let expect = [
+		("t", "Hint::Many(&[\"an(\", \"heta\"])"),
+		("th", "Hint::Single(\"eta\")"),
+		("the", "Hint::Single(\"ta\")"),
+		("thet", "Hint::Single(\"a\")"),
+		("ta", "Hint::Single(\"n(\")"),
+		("tan", "Hint::Single(\"(\")"),
+	];
+
+	assert_eq!(
+		parsing::compile_hashmap_terms(data.iter().map(|e| e.to_string()).collect()),
+		expect
+			.iter()
+			.map(|(a, b)| (a.to_string(), b.to_string()))
+			.collect::<Vec<(String, String)>>()
+	);
+}
+
+#[test]
+fn bivariate_function() {
+	use parsing::BivariateFunction;
+
+	let function = BivariateFunction::new("x*y-y").unwrap();
+	assert_eq!(function.get(3.0, 2.0), 4.0);
+	assert_eq!(function.get(1.0, 5.0), 0.0);
+
+	// Only one of the variables is used
+	let function = BivariateFunction::new("y^2").unwrap();
+	assert_eq!(function.get(100.0, 3.0), 9.0);
+
+	assert!(BivariateFunction::new("x+i").is_err());
+}
+
+#[test]
+fn implicit_function() {
+	use parsing::BivariateFunction;
+
+	let circle = BivariateFunction::new_implicit("x^2+y^2=4").unwrap();
+	assert_eq!(circle.get(2.0, 0.0), 0.0);
+	assert_eq!(circle.get(0.0, -2.0), 0.0);
+	assert_eq!(circle.get(1.0, 1.0), -2.0);
+
+	let line = BivariateFunction::new_implicit("y=3").unwrap();
+	assert_eq!(line.get(100.0, 3.0), 0.0);
+	assert_eq!(line.get(100.0, 5.0), 2.0);
+
+	assert!(BivariateFunction::new_implicit("x=y=2").is_err());
+	assert!(BivariateFunction::new_implicit("x+2").is_err());
+	assert!(BivariateFunction::new_implicit("x+i=1").is_err());
+}
+
+#[test]
+fn replace_variable() {
+	use parsing::replace_variable;
+
+	let values = HashMap::from([
+		("1/n^2", "1/x^2"),
+		("(-1)^n/n", "(-1)^x/x"),
+		("2n", "2x"),
+		("sin(n)", "sin(x)"),
+		("ln(n)+n", "ln(x)+x"),
+		("round(n)", "round(x)"),
+		("x", "x"),
+	]);
+
+	for (key, value) in values {
+		assert_eq!(replace_variable(key, 'n', 'x'), value);
+	}
+}
+
+/// Evaluates `func_str` (after passing it through [`process_func_str`]) over complex numbers at `z`
+fn eval_complex_helper(func_str: &str, z: parsing::Complex) -> parsing::Complex {
+	parsing::Expression::parse(&parsing::process_func_str(func_str), &['x', 'i'])
+		.unwrap()
+		.eval_complex(z)
+}
+
+/// Asserts `a` and `b` are equal, within rounding errors
+fn assert_complex_eq(a: parsing::Complex, b: parsing::Complex) {
+	assert!(
+		((a.re - b.re).abs() < 1e-12) && ((a.im - b.im).abs() < 1e-12),
+		"{:?} != {:?}",
+		a,
+		b
+	);
+}
+
+#[test]
+fn complex_expression() {
+	use parsing::Complex;
+	use std::f64::consts::PI;
+
+	let values = [
+		("x^2", Complex::new(3.0, 0.0), Complex::new(9.0, 0.0)),
+		("2x+1", Complex::new(2.0, 0.0), Complex::new(5.0, 0.0)),
+		("-x^2", Complex::new(3.0, 0.0), Complex::new(-9.0, 0.0)),
+		("2^3^2", Complex::ZERO, Complex::new(512.0, 0.0)),
+		("8/4/2", Complex::ZERO, Complex::new(1.0, 0.0)),
+		("sqrt(x)", Complex::new(-4.0, 0.0), Complex::new(0.0, 2.0)),
+		("ln(x)", Complex::new(-1.0, 0.0), Complex::new(0.0, PI)),
+		("cbrt(x)", Complex::new(-8.0, 0.0), Complex::new(-2.0, 0.0)),
+		("i^2", Complex::ZERO, Complex::new(-1.0, 0.0)),
+		("e^(ipi)", Complex::ZERO, Complex::new(-1.0, 0.0)),
+		("x^2+1", Complex::I, Complex::ZERO),
+		("(x+i)(x-i)", Complex::new(2.0, 0.0), Complex::new(5.0, 0.0)),
+		("abs(x)", Complex::new(3.0, 4.0), Complex::new(5.0, 0.0)),
+		(
+			"log10(x)",
+			Complex::new(1000.0, 0.0),
+			Complex::new(3.0, 0.0),
+		),
+		(
+			"sin(x)^2+cos(x)^2",
+			Complex::new(1.0, 2.0),
+			Complex::new(1.0, 0.0),
+		),
+		(
+			"sin(asin(x))",
+			Complex::new(2.0, 1.0),
+			Complex::new(2.0, 1.0),
+		),
+		(
+			"tan(atan(x))",
+			Complex::new(0.5, 0.5),
+			Complex::new(0.5, 0.5),
+		),
+		("x^0.5", Complex::new(-9.0, 0.0), Complex::new(0.0, 3.0)),
+	];
+
+	for (func_str, z, expected) in values {
+		assert_complex_eq(eval_complex_helper(func_str, z), expected);
+	}
+}
+
+#[test]
+fn parse_errors() {
+	use parsing::{Expression, ParseError};
+
+	let values = [
+		("", "unexpected end of expression", 0..0),
+		("x+", "unexpected end of expression", 2..2),
+		("sin(x))", "unexpected ')'", 6..7),
+		("(x+1", "unexpected end of expression", 4..4),
+		("i+x", "invalid variable: i", 0..1),
+		("y*x", "invalid variable: y", 0..1),
+		("foo(x)", "unknown function 'foo'", 0..3),
+		("1.2.3", "invalid number '1.2.3'", 0..5),
+		("sin x", "unexpected 'x'", 4..5),
+		("x#2", "unexpected '#'", 1..2),
+		("π^x+", "unexpected end of expression", 5..5),
+	];
+
+	for (func_str, message, span) in values {
+		assert_eq!(
+			Expression::parse(func_str, &['x']),
+			Err(ParseError {
+				message: message.to_owned(),
+				span,
+			}),
+			"{}",
+			func_str
+		);
+	}
+
+	assert_eq!(
+		Expression::parse("sin(x))", &['x'])
+			.unwrap_err()
+			.to_string(),
+		"Error: unexpected ')' at position 6"
+	);
+}
+
+#[test]
+fn equation_errors() {
+	use parsing::{Expression, ParseError};
+
+	let values = [
+		("x=y=2", "unexpected '='", 3..4),
+		("x+2", "equations need an '='", 0..3),
+		("x=", "unexpected end of expression", 2..2),
+	];
+
+	for (func_str, message, span) in values {
+		assert_eq!(
+			Expression::parse_equation(func_str, &['x', 'y']),
+			Err(ParseError {
+				message: message.to_owned(),
+				span,
+			})
+		);
+	}
+}
+
+#[test]
+fn derivatives() {
+	use parsing::BackingFunction;
+
+	let values = [
+		("x^2", 3.0, 6.0),
+		("x^3-2x", 2.0, 10.0),
+		("sin(x)", 0.0, 1.0),
+		("xsin(x)", std::f64::consts::PI, -std::f64::consts::PI),
+		("e^x", 1.0, std::f64::consts::E),
+		("2^x", 1.0, 2.0 * std::f64::consts::LN_2),
+		("ln(x)", 4.0, 0.25),
+		("1/x", 2.0, -0.25),
+		("sqrt(x)", 4.0, 0.25),
+		("x^x", 1.0, 1.0),
+		("atan(x)", 1.0, 0.5),
+		("abs(x)", -2.0, -1.0),
+	];
+
+	for (func_str, x, expected) in values {
+		let mut function = BackingFunction::new(&parsing::process_func_str(func_str)).unwrap();
+		function.generate_derivative(1);
+		let derivative = function.get(1, x);
+		assert!(
+			(derivative - expected).abs() < 1e-12,
+			"{}: {} != {}",
+			func_str,
+			derivative,
+			expected
+		);
+	}
+
+	// Higher derivatives
+	let mut function = BackingFunction::new("x^4").unwrap();
+	function.generate_derivative(3);
+	assert_eq!(function.get(3, 2.0), 48.0);
+}
+
+#[test]
+fn implicit_multiplication() {
+	use parsing::Expression;
+
+	// (function string, equivalent function string with explicit multiplication)
+	let values = [
+		("2x", "2*x"),
+		("x2", "x*2"),
+		("2(x)", "2*x"),
+		("(x)2", "x*2"),
+		("2 x", "2*x"),
+		("3sin(x)", "3*sin(x)"),
+		("xsin(x)", "x*sin(x)"),
+		("sin(x)cos(x)", "sin(x)*cos(x)"),
+		("(x+1)(x-2)", "(x+1)*(x-2)"),
+		("2x^2", "2*(x^2)"),
+		("-2x", "-(2*x)"),
+		("1/2x", "(1/2)*x"),
+		("pipipi", "pi*pi*pi"),
+		("xe^x", "x*(e^x)"),
+		("exp(x)", "e^x"),
+		("sinh(x)", "(e^x-e^(-x))/2"),
+		("2theta", "2*x"),
+		("x**2", "x^2"),
+	];
+
+	for (func_str, expected) in values {
+		let function = Expression::parse(func_str, &['x']).unwrap();
+		let expected = Expression::parse(expected, &['x']).unwrap();
+		for x in [-2.5, 0.5, 3.0] {
+			let (value, expected_value) = (function.eval(x, 0.0), expected.eval(x, 0.0));
+			assert!(
+				(value - expected_value).abs() < 1e-9,
+				"{}: {} != {}",
+				func_str,
+				value,
+				expected_value
+			);
+		}
+	}
+
+	// Implicitly multiplied names only split into known functions, constants, and variables
+	let errors = [
+		("mix(x)", "unknown function 'mix'", 0..3),
+		("2sinx", "unexpected 'x'", 4..5),
+		("xi", "invalid variable: i", 1..2),
+	];
+
+	for (func_str, message, span) in errors {
+		let error = Expression::parse(func_str, &['x']).unwrap_err();
+		assert_eq!(
+			(error.message.as_str(), error.span),
+			(message, span),
+			"{}",
+			func_str
+		);
+	}
+}
+
+#[test]
+fn absolute_value_bars() {
+	use parsing::Expression;
+
+	// (function string, equivalent function string using abs)
+	let values = [
+		("|x|", "abs(x)"),
+		("|x-3|", "abs(x-3)"),
+		("2|x|", "2*abs(x)"),
+		("|x|x", "abs(x)*x"),
+		("|x||x-1|", "abs(x)*abs(x-1)"),
+		("||x|-2|", "abs(abs(x)-2)"),
+		("|-x|^2", "abs(-x)^2"),
+		("|sin(x)|", "abs(sin(x))"),
+		("|2*|x|-1|", "abs(2*abs(x)-1)"),
+	];
+
+	for (func_str, expected) in values {
+		let function = Expression::parse(func_str, &['x']).unwrap();
+		let expected = Expression::parse(expected, &['x']).unwrap();
+		for x in [-2.5, 0.5, 3.0] {
+			assert_eq!(function.eval(x, 0.0), expected.eval(x, 0.0), "{}", func_str);
+		}
+	}
+
+	assert!(Expression::parse("|x", &['x']).is_err());
+	assert!(Expression::parse("x|", &['x']).is_err());
+	assert!(Expression::parse("||", &['x']).is_err());
+}
+
+#[test]
+fn factorials() {
+	use parsing::{BackingFunction, Expression};
+
+	let values = [
+		("0!", 1.0),
+		("5!", 120.0),
+		("3!!", 720.0),
+		("2*3!", 12.0),
+		("-3!", -6.0),
+		("2^3!", 64.0),
+		("(1+2)!", 6.0),
+		("|-4|!", 24.0),
+		("0.5!", std::f64::consts::PI.sqrt() / 2.0),
+		("(-0.5)!", std::f64::consts::PI.sqrt()),
+	];
+
+	for (func_str, expected) in values {
+		let value = Expression::parse(func_str, &['x']).unwrap().eval(0.0, 0.0);
+		assert!(
+			(value - expected).abs() < 1e-9 * expected.abs().max(1.0),
+			"{}: {} != {}",
+			func_str,
+			value,
+			expected
+		);
+	}
+
+	// Factorials of negative integers are undefined, and ones of large numbers overflow
+	let eval = |func_str: &str| Expression::parse(func_str, &['x']).unwrap().eval(0.0, 0.0);
+	assert!(eval("(-2)!").is_nan());
+	assert_eq!(eval("171!"), f64::INFINITY);
+
+	// (x!)' = x! ψ(x + 1), where ψ(2) = 1 - γ
+	let mut function = BackingFunction::new("x!").unwrap();
+	function.generate_derivative(1);
+	function.generate_derivative(2);
+	let euler_gamma = 0.577_215_664_901_532_9;
+	assert!((function.get(1, 1.0) - (1.0 - euler_gamma)).abs() < 1e-9);
+
+	// (x!)'' = x! (ψ(x + 1)² + ψ₁(x + 1)), where ψ₁(1) = π²/6
+	let expected = euler_gamma.powi(2) + (std::f64::consts::PI.powi(2) / 6.0);
+	assert!((function.get(2, 0.0) - expected).abs() < 1e-9);
+
+	assert!(Expression::parse("!x", &['x']).is_err());
+}
+
+#[test]
+fn multi_argument_functions() {
+	use parsing::{BackingFunction, Expression};
+
+	let values = [
+		("min(x, 1)", 3.0, 1.0),
+		("min(x, 1)", -2.0, -2.0),
+		("max(x, 1)", 3.0, 3.0),
+		("max(x^2, 2x)", -1.0, 1.0),
+		("clamp(x, -1, 1)", 0.5, 0.5),
+		("clamp(x, -1, 1)", -4.0, -1.0),
+		("clamp(x, -1, 1)", 4.0, 1.0),
+		("mod(x, 3)", 7.0, 1.0),
+		("mod(x, 3)", -1.0, 2.0),
+		("x % 3", 7.5, 1.5),
+		("2x % 3", 2.0, 1.0),
+		("x % 3 * 2", 4.0, 2.0),
+		("1 + x % 3", 5.0, 3.0),
+		("2min(x, 0)", -3.0, -6.0),
+		("max(min(x, 2), 1)", 5.0, 2.0),
+	];
+
+	for (func_str, x, expected) in values {
+		let value = Expression::parse(func_str, &['x']).unwrap().eval(x, 0.0);
+		assert_eq!(value, expected, "{} at x = {}", func_str, x);
+	}
+
+	let derivatives = [
+		("min(x^2, 4)", 1.0, 2.0),
+		("min(x^2, 4)", 3.0, 0.0),
+		("max(x, 3x)", 1.0, 3.0),
+		("clamp(x^2, 1, 4)", 1.5, 3.0),
+		("clamp(x^2, 1, 4)", 0.5, 0.0),
+		("mod(x, 2)", 3.5, 1.0),
+		("x^2 % 5", 3.0, 6.0),
+	];
+
+	for (func_str, x, expected) in derivatives {
+		let mut function = BackingFunction::new(func_str).unwrap();
+		function.generate_derivative(1);
+		assert_eq!(function.get(1, x), expected, "{} at x = {}", func_str, x);
+	}
+
+	let errors = [
+		("min(x)", "min takes 2 arguments, not 1", 0..6),
+		("clamp(x, 1)", "clamp takes 3 arguments, not 2", 0..11),
+		("max(x, 1, 2)", "max takes 2 arguments, not 3", 0..12),
+		("min x", "unexpected 'x'", 4..5),
+		("sin(x, 1)", "unexpected ','", 5..6),
+		("x %", "unexpected end of expression", 3..3),
+	];
+
+	for (func_str, message, span) in errors {
+		let error = Expression::parse(func_str, &['x']).unwrap_err();
+		assert_eq!(
+			(error.message.as_str(), error.span),
+			(message, span),
+			"{}",
+			func_str
+		);
+	}
+}
+
+#[test]
+fn angle_units() {
+	use parsing::{AngleUnit, BackingFunction, Expression};
+
+	let values = [
+		("sin(x)", 30.0, 0.5),
+		("cos(x)", 180.0, -1.0),
+		("tan(x)", 45.0, 1.0),
+		("asin(x)", 0.5, 30.0),
+		("acos(x)", -1.0, 180.0),
+		("atan(x)", 1.0, 45.0),
+		("sin(asin(x))", 0.25, 0.25),
+		("2sin(x+30)", 60.0, 2.0),
+		("sinh(x)", 1.0, 1.0_f64.sinh()),
+		("max(sin(x), 0)", 90.0, 1.0),
+		("atan2(x, -1)", 1.0, 135.0),
+		("asinh(x)", 1.0, 1.0_f64.asinh()),
+	];
+
+	for (func_str, x, expected) in values {
+		let function = Expression::parse(func_str, &['x'])
+			.unwrap()
+			.with_angle_unit(AngleUnit::Degrees);
+		let value = function.eval(x, 0.0);
+		assert!(
+			(value - expected).abs() < 1e-12,
+			"{}: {} != {}",
+			func_str,
+			value,
+			expected
+		);
+
+		// Radians are what expressions are parsed as using
+		assert_eq!(
+			Expression::parse(func_str, &['x'])
+				.unwrap()
+				.with_angle_unit(AngleUnit::Radians),
+			Expression::parse(func_str, &['x']).unwrap()
+		);
+	}
+
+	// d/dx sin(x°) = (π/180) cos(x°)
+	let mut function = BackingFunction::new("sin(x)")
+		.unwrap()
+		.with_angle_unit(AngleUnit::Degrees);
+	function.generate_derivative(1);
+	assert!((function.get(1, 0.0) - (std::f64::consts::PI / 180.0)).abs() < 1e-15);
+}
+
+#[test]
+fn parameters() {
+	use parsing::{BackingFunction, BivariateFunction, Expression};
+	use std::collections::BTreeMap;
+
+	let values = BTreeMap::from([("a".to_owned(), 2.0), ("b".to_owned(), 3.0)]);
+
+	// (function string, parameters, value at x = 1 with the parameters' values)
+	let functions = [
+		("a*x", vec!["a"], 2.0),
+		("ax^2+bx+c", vec!["a", "b", "c"], f64::NAN),
+		("ax^2+bx", vec!["a", "b"], 5.0),
+		("asin(x)", vec![], std::f64::consts::FRAC_PI_2),
+		("ab+x", vec!["a", "b"], 7.0),
+		("a(x+1)", vec![], f64::NAN),
+		("sin(bx)/b", vec!["b"], 3.0_f64.sin() / 3.0),
+		("max(a, x)+a", vec!["a"], 4.0),
+	];
+
+	for (func_str, parameters, expected) in functions {
+		let expression = match Expression::parse(func_str, &['x']) {
+			Ok(expression) => expression,
+			// Only names called like functions aren't parameters
+			Err(error) => {
+				assert_eq!(error.message, "unknown function 'a'", "{}", func_str);
+				continue;
+			}
+		};
+		assert_eq!(expression.parameters(), parameters, "{}", func_str);
+
+		// Parameters are NaN until they're given values
+		if !parameters.is_empty() {
+			assert!(expression.eval(1.0, 0.0).is_nan(), "{}", func_str);
+		}
+
+		let value = expression.with_parameters(&values).eval(1.0, 0.0);
+		match expected.is_nan() {
+			true => assert!(value.is_nan(), "{}", func_str),
+			false => assert!((value - expected).abs() < 1e-12, "{}", func_str),
+		}
+	}
+
+	// Parameters are constants when differentiating
+	let mut function = BackingFunction::new("a*x^2").unwrap();
+	assert_eq!(function.parameters(), vec!["a"]);
+	function = function.with_parameters(&values);
+	function.generate_derivative(1);
+	assert_eq!(function.get(1, 3.0), 12.0);
+
+	// Functions still need to use x
+	assert!(BackingFunction::new("a+b").is_err());
+
+	let function = BivariateFunction::new("a*x+y").unwrap();
+	assert_eq!(function.parameters(), vec!["a"]);
+	assert_eq!(function.with_parameters(&values).get(1.0, 1.0), 3.0);
+}
+
+#[test]
+fn user_functions() {
+	use parsing::{BackingFunction, BivariateFunction, Definitions, Expression};
+	use std::collections::BTreeMap;
+
+	assert_eq!(
+		Definitions::split("f(x) = x^2 + 1"),
+		Some((String::from("f"), 6))
+	);
+	assert_eq!(
+		Definitions::split("area(X)=x"),
+		Some((String::from("area"), 8))
+	);
+	assert_eq!(Definitions::split("f(y) = y^2"), None);
+	assert_eq!(Definitions::split("sin(x) = 0"), None);
+	assert_eq!(Definitions::split("x^2 + y^2 = 4"), None);
+	assert_eq!(Definitions::split("f(x)"), None);
+
+	assert_eq!(Definitions::calls("f(x)+sin(g(x))"), vec!["f", "g"]);
+
+	let sources = BTreeMap::from([
+		(String::from("f"), String::from("x^2+1")),
+		// Functions can call functions defined after them
+		(String::from("area"), String::from("pi*r(x)^2")),
+		(String::from("r"), String::from("f(x)-1")),
+		(String::from("g"), String::from("h(x)")),
+		(String::from("h"), String::from("g(x)+1")),
+		(String::from("k"), String::from("k(x-1)")),
+		(String::from("bad"), String::from("x+")),
+	]);
+	let definitions = Definitions::new(&sources, false);
+
+	let eval = |func_str: &str| {
+		Expression::parse_using(func_str, &['x'], &definitions)
+			.map(|expression| expression.eval(2.0, 0.0))
+	};
+
+	assert_eq!(eval("f(x)"), Ok(5.0));
+	assert_eq!(eval("f(x-2)+f(3)"), Ok(11.0));
+	assert_eq!(eval("2f(f(x))"), Ok(52.0));
+	assert!((eval("area(x)").unwrap() - 16.0 * std::f64::consts::PI).abs() < 1e-12);
+
+	// Errors are reported where the function is called
+	let error =
+		|func_str: &str| Expression::parse_using(func_str, &['x'], &definitions).unwrap_err();
+	assert_eq!(error("x+q(x)").message, "unknown function 'q'");
+	assert_eq!(error("x+q(x)").span, 2..3);
+	assert_eq!(
+		error("g(x)").message,
+		"'g' is defined in terms of itself (g → h → g)"
+	);
+	assert_eq!(
+		error("1+h(x)").message,
+		"'h' is defined in terms of itself (g → h → g)"
+	);
+	assert_eq!(error("1+h(x)").span, 2..3);
+	assert_eq!(
+		error("k(x)").message,
+		"'k' is defined in terms of itself (k → k)"
+	);
+	assert_eq!(
+		error("bad(x)").message,
+		"'bad' has an error: unexpected end of expression"
+	);
+
+	// Without the definitions, they're unknown
+	assert!(Expression::parse("f(x)", &['x']).is_err());
+
+	// Derivatives go through the definitions
+	let mut function = BackingFunction::new_using("f(2x)", &definitions).unwrap();
+	function.generate_derivative(1);
+	assert_eq!(function.get(1, 1.0), 8.0);
+
+	// User functions are functions of whatever they're called with
+	let function = BivariateFunction::new_using("f(y)-x", &definitions).unwrap();
+	assert_eq!(function.get(1.0, 2.0), 4.0);
+	let function = BivariateFunction::new_implicit_using("y=f(x)", &definitions).unwrap();
+	assert_eq!(function.get(2.0, 5.0), 0.0);
+}
+
+#[test]
+fn series() {
+	use parsing::{BackingFunction, Expression};
+	use std::collections::BTreeMap;
+
+	let eval = |func_str: &str, x: f64| Expression::parse(func_str, &['x']).unwrap().eval(x, 0.0);
+
+	// (function string, x, expected value)
+	let values = [
+		("sum(n, 1, 4, n)", 0.0, 10.0),
+		("sum(n, 1, 3, 1/n)", 0.0, 11.0 / 6.0),
+		("prod(k, 1, 5, k)", 0.0, 120.0),
+		("sum(n, 0, 3, x^n/n!)", 2.0, 19.0 / 3.0),
+		// Bounds are rounded down, and can depend on x
+		("sum(n, 1, x, n)", 3.7, 6.0),
+		("sum(n, 0.5, 2.5, n)", 0.0, 3.0),
+		// Empty sums and products
+		("sum(n, 3, 1, n)", 0.0, 0.0),
+		("prod(n, 3, 1, n)", 0.0, 1.0),
+		// Series can be nested and use the indices of those they're in
+		("sum(n, 1, 3, sum(m, 1, n, m))", 0.0, 10.0),
+		("2sum(n, 1, 2, n)x", 1.0, 6.0),
+	];
+
+	for (func_str, x, expected) in values {
+		let value = eval(func_str, x);
+		assert!((value - expected).abs() < 1e-12, "{}: {}", func_str, value);
+	}
+
+	// Too many terms are undefined
+	assert!(eval("sum(n, 0, 10^9, n)", 0.0).is_nan());
+
+	for (func_str, message) in [
+		(
+			"sum(x, 1, 2, x)",
+			"sum's index needs to be a letter, such as n",
+		),
+		("prod(n, 1, 2)", "unexpected ')'"),
+		("sum(n, 1, 2, n, 3)", "unexpected ','"),
+	] {
+		assert_eq!(
+			Expression::parse(func_str, &['x']).unwrap_err().message,
+			message,
+			"{}",
+			func_str
+		);
+	}
+
+	// The index isn't a parameter, but other letters in the body are
+	let expression = Expression::parse("sum(n, 1, 3, a*n)", &['x']).unwrap();
+	assert_eq!(expression.parameters(), vec!["a"]);
+	let values = BTreeMap::from([("a".to_owned(), 2.0), ("n".to_owned(), 100.0)]);
+	assert_eq!(expression.with_parameters(&values).eval(0.0, 0.0), 12.0);
+
+	// Derivatives of sums are sums of derivatives, and of products follow the product rule
+	let mut function = BackingFunction::new("sum(n, 1, 3, x^n)").unwrap();
+	function.generate_derivative(1);
+	assert_eq!(function.get(1, 2.0), 1.0 + 4.0 + 12.0);
+
+	let mut function = BackingFunction::new("prod(n, 1, 3, x+n)").unwrap();
+	function.generate_derivative(1);
+	assert!((function.get(1, 0.0) - 11.0).abs() < 1e-12);
+
+	// Complex evaluation
+	let expression = Expression::parse("sum(n, 0, 3, i^n)", &['x', 'i']).unwrap();
+	let value = expression.eval_complex(parsing::Complex::new(0.0, 0.0));
+	assert!(value.re.abs() < 1e-12 && value.im.abs() < 1e-12);
+}
+
+#[test]
+fn special_functions() {
+	use parsing::{BackingFunction, Expression};
+
+	let eval = |func_str: &str| Expression::parse(func_str, &['x']).unwrap().eval(0.0, 0.0);
+
+	// (function string, expected value)
+	let values = [
+		("erf(0)", 0.0),
+		("erf(0.5)", 0.520_499_877_813_046_5),
+		("erf(-1)", -0.842_700_792_949_714_9),
+		("erf(3)", 0.999_977_909_503_001_4),
+		("erfc(0.5)", 0.479_500_122_186_953_5),
+		("erfc(3)", 2.209_049_699_858_544e-5),
+		("erfc(-3)", 1.999_977_909_503_001_4),
+		("erfc(10)", 2.088_487_583_762_545e-45),
+		("gamma(5)", 24.0),
+		("gamma(0.5)", 1.772_453_850_905_516),
+		("gamma(-0.5)", -3.544_907_701_811_032),
+		("lgamma(100)", 359.134_205_369_575_4),
+		("lgamma(-0.5)", 1.265_512_123_484_645_4),
+		("besselj(0, 1)", 0.765_197_686_557_966_6),
+		("besselj(1, 2.5)", 0.497_094_102_464_274_2),
+		("besselj(2, -4)", 0.364_128_145_852_072_8),
+		("besselj(-1, 2.5)", -0.497_094_102_464_274_2),
+		("besselj(3, 0.1)", 2.082_031_575_458_764_7e-5),
+		("besselj(5, 30)", -0.143_240_295_512_076_6),
+		("besselj(0, 2000)", 0.007_098_341_833_194),
+	];
+
+	for (func_str, expected) in values {
+		let value = eval(func_str);
+		assert!(
+			((value - expected) / expected.abs().max(1e-300)).abs() < 1e-11
+				|| (value - expected).abs() < 1e-15,
+			"{}: {} != {}",
+			func_str,
+			value,
+			expected
+		);
+	}
+
+	// Bessel functions are only of integer orders
+	assert!(eval("besselj(0.5, 1)").is_nan());
+	assert!(Expression::parse("besselj(1)", &['x']).is_err());
+
+	// (function string, x, expected derivative)
+	let derivatives = [
+		(
+			"erf(x)",
+			1.0,
+			2.0 / (std::f64::consts::PI.sqrt() * std::f64::consts::E),
+		),
+		("erfc(2x)", 0.0, -4.0 / std::f64::consts::PI.sqrt()),
+		// Γ'(1) = -γ
+		("gamma(x)", 1.0, -0.577_215_664_901_532_9),
+		("lgamma(x)", 1.0, -0.577_215_664_901_532_9),
+		// J₀'(x) = -J₁(x)
+		("besselj(0, x)", 2.5, -0.497_094_102_464_274_2),
+	];
+
+	for (func_str, x, expected) in derivatives {
+		let mut function = BackingFunction::new(func_str).unwrap();
+		function.generate_derivative(1);
+		let value = function.get(1, x);
+		assert!((value - expected).abs() < 1e-9, "{}: {}", func_str, value);
+	}
+}
+
+#[test]
+fn inverse_hyperbolic_functions() {
+	use parsing::{BackingFunction, Complex, Expression};
+
+	// (function string, x, expected value, expected derivative)
+	let values = [
+		("asinh(x)", 0.5, 0.5_f64.asinh(), 1.0 / 1.25_f64.sqrt()),
+		("acosh(x)", 2.0, 2.0_f64.acosh(), 1.0 / 3.0_f64.sqrt()),
+		("atanh(x)", 0.5, 0.5_f64.atanh(), 1.0 / 0.75),
+		("atan2(x, 1)", 1.0, std::f64::consts::FRAC_PI_4, 0.5),
+		("atan2(1, x)", -1.0, 3.0 * std::f64::consts::FRAC_PI_4, -0.5),
+		("hypot(x, 4)", 3.0, 5.0, 0.6),
+		("hypot(2x, x)", 1.0, 5.0_f64.sqrt(), 5.0_f64.sqrt()),
+	];
+
+	for (func_str, x, expected, expected_derivative) in values {
+		let mut function = BackingFunction::new(func_str).unwrap();
+		function.generate_derivative(1);
+		assert!(
+			(function.get(0, x) - expected).abs() < 1e-12,
+			"{}",
+			func_str
+		);
+		assert!(
+			(function.get(1, x) - expected_derivative).abs() < 1e-12,
+			"{}: {}",
+			func_str,
+			function.get(1, x)
+		);
+	}
+
+	// Outside of their real domains, the complex values are the principal values
+	let eval_complex = |func_str: &str, x: f64| {
+		Expression::parse(func_str, &['x', 'i'])
+			.unwrap()
+			.eval_complex(Complex::from(x))
+	};
+	assert!(Expression::parse("acosh(x)", &['x'])
+		.unwrap()
+		.eval(0.0, 0.0)
+		.is_nan());
+	let value = eval_complex("acosh(x)", 0.0);
+	assert!(value.re.abs() < 1e-12 && (value.im - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+	let value = eval_complex("atanh(x)", 2.0);
+	assert!((value.re - (3.0_f64.ln() / 2.0)).abs() < 1e-12);
+	assert!((value.im.abs() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+	let value = eval_complex("asinh(x)", 1.0);
+	assert!((value.re - 1.0_f64.asinh()).abs() < 1e-12 && value.im.abs() < 1e-12);
+
+	assert!(Expression::parse("atan2(x)", &['x']).is_err());
+}
+
+#[test]
+fn constants() {
+	use parsing::{Expression, CONSTANTS};
+	use std::f64::consts::{E, PI, TAU};
+
+	let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+	let values = [
+		("tau", TAU),
+		("τ/2", PI),
+		("phi^2 - phi", 1.0),
+		("φ", phi),
+		("2lightspeed", 599_584_916.0),
+		("avogadro*boltzmann", 8.314_462_618_153_24),
+		("1e-3", 0.001),
+		("2.5E6", 2_500_000.0),
+		("1e3x", 2000.0),
+		("3E+2", 300.0),
+		("2e", 2.0 * E),
+		("2ex", 4.0 * E),
+		("2e-x", (2.0 * E) - 2.0),
+		("1e-3pi", 0.001 * PI),
+	];
+
+	for (func_str, expected) in values {
+		let value = Expression::parse(func_str, &['x']).unwrap().eval(2.0, 0.0);
+		assert!(
+			((value - expected) / expected).abs() < 1e-12,
+			"{}: {}",
+			func_str,
+			value
+		);
+	}
+
+	// Every name of every constant is recognised
+	for constant in CONSTANTS.iter() {
+		for name in constant.names {
+			assert_eq!(
+				Expression::parse(name, &[]).unwrap().eval(0.0, 0.0),
+				constant.value,
+				"{}",
+				name
+			);
+		}
+	}
+}
+
+#[test]
+fn typesetting() {
+	use parsing::{Expression, Typeset};
+
+	let text = |text: &str| Typeset::Text(text.to_owned());
+	let typeset = |func_str: &str| {
+		Expression::parse(func_str, &['x', 'y'])
+			.unwrap()
+			.typeset("x")
+	};
+
+	let values = [
+		(
+			"(x+1)/2",
+			Typeset::Fraction(
+				Box::new(Typeset::Row(vec![text("x"), text(" + "), text("1")])),
+				Box::new(text("2")),
+			),
+		),
+		(
+			"2x^2",
+			Typeset::Row(vec![
+				text("2"),
+				Typeset::Power(Box::new(text("x")), Box::new(text("2"))),
+			]),
+		),
+		(
+			"(x/2)^(y+1)",
+			Typeset::Power(
+				Box::new(Typeset::Delimited(
+					'(',
+					Box::new(Typeset::Fraction(Box::new(text("x")), Box::new(text("2")))),
+					')',
+				)),
+				Box::new(Typeset::Row(vec![text("y"), text(" + "), text("1")])),
+			),
+		),
+		(
+			"x-(y-1)",
+			Typeset::Row(vec![
+				text("x"),
+				text(" − "),
+				Typeset::Delimited(
+					'(',
+					Box::new(Typeset::Row(vec![text("y"), text(" − "), text("1")])),
+					')',
+				),
+			]),
+		),
+		(
+			"sqrt(x)*cbrt(pi)",
+			Typeset::Row(vec![
+				Typeset::Root(Box::new(text("x")), None),
+				text("·"),
+				Typeset::Root(Box::new(text("π")), Some(Box::new(text("3")))),
+			]),
+		),
+		(
+			"-|x|!",
+			Typeset::Row(vec![
+				text("−"),
+				Typeset::Row(vec![
+					Typeset::Delimited('|', Box::new(text("x")), '|'),
+					text("!"),
+				]),
+			]),
+		),
+		(
+			"sum(n, 1, 10, x^n)",
+			Typeset::BigOperator(
+				'Σ',
+				Box::new(Typeset::Row(vec![text("n"), text(" = "), text("1")])),
+				Box::new(text("10")),
+				Box::new(Typeset::Power(Box::new(text("x")), Box::new(text("n")))),
+			),
+		),
+		(
+			"2.5e-7x",
+			Typeset::Row(vec![
+				Typeset::Row(vec![
+					text("2.5×"),
+					Typeset::Power(Box::new(text("10")), Box::new(text("−7"))),
+				]),
+				text("·"),
+				text("x"),
+			]),
+		),
+	];
+
+	for (func_str, expected) in values {
+		assert_eq!(typeset(func_str), expected, "{}", func_str);
+	}
+
+	// x is written as the variable it stands for
+	assert_eq!(
+		Expression::parse("sin(θ)", &['x']).unwrap().typeset("θ"),
+		Typeset::Row(vec![
+			text("sin"),
+			Typeset::Delimited('(', Box::new(text("θ")), ')')
+		])
+	);
+}
+
+#[test]
+fn formulas() {
+	use parsing::Expression;
+
+	// (function string, simplified function, simplified derivative)
+	let values = [
+		("3x^2", "3x^2", "6x"),
+		("sin(2x)", "sin(2x)", "2cos(2x)"),
+		("x^3 - x", "x^3 - x", "3x^2 - 1"),
+		("cos(x)", "cos(x)", "-sin(x)"),
+		("e^x", "e^x", "e^x"),
+		("0*x + pi/2 + 1*x", "pi/2 + x", "1"),
+		("x*2*3 - -x", "6x + x", "7"),
+		(
+			"(x+1)/(x-1)",
+			"(x + 1)/(x - 1)",
+			"(x - 1 - (x + 1))/(x - 1)^2",
+		),
+		("-x^2 + sqrt(4)", "-x^2 + 2", "-2x"),
+		("x!^2 % 3", "x!^2 % 3", ""),
+		("sum(n, 1, 3, x^n/n)", "sum(n, 1, 3, x^n/n)", ""),
+		("a*x^-1", "a*x^(-1)", ""),
+		("2.5e-7x + 1e10", "2.5e-7*x + 1e10", "2.5e-7"),
+	];
+
+	for (func_str, expected, expected_derivative) in values {
+		let function = Expression::parse(func_str, &['x']).unwrap().simplified();
+		assert_eq!(function.to_string(), expected, "{}", func_str);
+
+		// The text parses back to the same function
+		let reparsed = Expression::parse(&function.to_string(), &['x']).unwrap();
+		for x in [0.5, 2.0, 3.5] {
+			let value = function
+				.clone()
+				.with_parameters(&[("a".to_owned(), 2.0)].into())
+				.eval(x, 0.0);
+			assert_eq!(
+				reparsed
+					.clone()
+					.with_parameters(&[("a".to_owned(), 2.0)].into())
+					.eval(x, 0.0),
+				value,
+				"{}",
+				func_str
+			);
+		}
+
+		if !expected_derivative.is_empty() {
+			assert_eq!(
+				function.derivative().simplified().to_string(),
+				expected_derivative,
+				"{}",
+				func_str
+			);
+		}
+	}
+}
+
+#[test]
+fn interval_arithmetic() {
+	use parsing::{Expression, Interval};
+
+	// (function string, interval of x, expected interval of values)
+	let values = [
+		("x^2", Interval::new(-1.0, 2.0), Interval::new(0.0, 4.0)),
+		("x^3", Interval::new(-2.0, 1.0), Interval::new(-8.0, 1.0)),
+		("sin(x)", Interval::new(0.0, 10.0), Interval::new(-1.0, 1.0)),
+		(
+			"cos(x)",
+			Interval::new(0.0, 1.0),
+			Interval::new(1.0_f64.cos(), 1.0),
+		),
+		(
+			"abs(x) - 1",
+			Interval::new(-3.0, 2.0),
+			Interval::new(-1.0, 2.0),
+		),
+		("1/x", Interval::new(1.0, 4.0), Interval::new(0.25, 1.0)),
+		("1/x", Interval::new(-1.0, 1.0), Interval::ENTIRE),
+		(
+			"1/x",
+			Interval::new(0.0, 2.0),
+			Interval::new(0.5, f64::INFINITY),
+		),
+		("tan(x)", Interval::new(1.0, 2.0), Interval::ENTIRE),
+		("sqrt(x)", Interval::new(-4.0, 4.0), Interval::new(0.0, 2.0)),
+		("floor(x)", Interval::new(0.5, 2.5), Interval::new(0.0, 2.0)),
+		(
+			"max(x, 1)",
+			Interval::new(-3.0, 3.0),
+			Interval::new(1.0, 3.0),
+		),
+		("x % 4", Interval::new(5.0, 6.0), Interval::new(1.0, 2.0)),
+		("x % 4", Interval::new(3.0, 6.0), Interval::new(0.0, 4.0)),
+		(
+			"sum(n, 1, 3, x^n)",
+			Interval::new(0.0, 1.0),
+			Interval::new(0.0, 3.0),
+		),
+		("5", Interval::new(-1.0, 1.0), Interval::from(5.0)),
+	];
+
+	for (func_str, x, expected) in values {
+		let function = Expression::parse(func_str, &['x']).unwrap();
+		assert_eq!(function.eval_interval(x), expected, "{}", func_str);
+	}
+
+	assert!(Expression::parse("sqrt(x)", &['x'])
+		.unwrap()
+		.eval_interval(Interval::new(-2.0, -1.0))
+		.is_empty());
+
+	// Every sampled value is within the bounds, even where there are too many oscillations to sample
+	for func_str in [
+		"sin(1/x)",
+		"x sin(1/x)",
+		"e^x cos(3x) / (x^2 + 1)",
+		"ln(x) + atan(x)",
+	] {
+		let function = Expression::parse(func_str, &['x']).unwrap();
+		for (lo, hi) in [(0.001, 0.01), (-0.5, 0.5), (0.1, 0.3), (1.0, 5.0)] {
+			let bounds = function.eval_interval(Interval::new(lo, hi));
+			for i in 0..=1000 {
+				let value = function.eval(lo + ((hi - lo) * (i as f64) / 1000.0), 0.0);
+				assert!(
+					value.is_nan() || bounds.contains(value),
+					"{} on [{}, {}]: {} isn't in {:?}",
+					func_str,
+					lo,
+					hi,
+					value,
+					bounds
+				);
+			}
+		}
+	}
+}
+
+#[test]
+fn sanitizing() {
+	use parsing::{sanitize_func_str, Expression};
+
+	let values = [
+		("x − 1", "x - 1"),
+		("2×x ÷ 3", "2*x / 3"),
+		("2·x⋅y", "2*x*y"),
+		("sin(πx)", "sin(pix)"),
+		("x² + 1", "x^2 + 1"),
+		("3x¹⁰", "3x^10"),
+		("x⁻¹", "x^-1"),
+		("x²y³", "x^2y^3"),
+		("“x” ‘y’", "\"x\" 'y'"),
+		("x\u{a0}+\t1", "x + 1"),
+		("sqrt(x) + 1", "sqrt(x) + 1"),
+	];
+
+	for (func_str, expected) in values {
+		assert_eq!(sanitize_func_str(func_str), expected);
+	}
+
+	// Pasted math parses the same way as if it were typed
+	for (pasted, typed) in [("x² − 3×x", "x^2 - 3x"), ("2π ÷ x⁻²", "2pi/x^(-2)")] {
+		let pasted = Expression::parse(&sanitize_func_str(pasted), &['x']).unwrap();
+		let typed = Expression::parse(typed, &['x']).unwrap();
+		for x in [0.5, 2.0, -3.0] {
+			assert_eq!(pasted.eval(x, 0.0), typed.eval(x, 0.0));
+		}
+	}
+}
+
+#[test]
+fn case_insensitivity() {
+	use parsing::{Definitions, Expression};
+	use std::collections::BTreeMap;
+
+	// Differently capitalized (and spaced) functions are the same function
+	for (func_str, expected) in [
+		("Sin(X)", "sin(x)"),
+		("SIN (x)", "sin(x)"),
+		("Sqrt( X ) + Abs(x)", "sqrt(x)+abs(x)"),
+		("Max (x, 1) * PI", "max(x, 1)*pi"),
+		("Pi Tau", "pi*tau"),
+		("Sum (n, 1, 3, n)", "sum(n, 1, 3, n)"),
+		("E^X", "e^x"),
+	] {
+		assert_eq!(
+			Expression::parse(func_str, &['x']),
+			Expression::parse(expected, &['x']),
+			"{}",
+			func_str
+		);
+	}
+
+	// Case sensitive variables are only matched in lowercase, so uppercase letters are parameters
+	let case_sensitive = Definitions::new(&BTreeMap::new(), true);
+	let function = Expression::parse_using("Sin(X) + x", &['x'], &case_sensitive).unwrap();
+	assert_eq!(function.parameters(), vec![String::from("X")]);
+	assert_eq!(
+		function
+			.with_parameters(&[("X".to_owned(), 1.0)].into())
+			.eval(2.0, 0.0),
+		1.0_f64.sin() + 2.0
+	);
+	assert_eq!(
+		Expression::parse_using("COS(Pi)", &['x'], &case_sensitive)
+			.unwrap()
+			.eval(0.0, 0.0),
+		-1.0
+	);
+}
+
+/// Every function that's suggested has to be understood by the parser, rather than being read as an unknown variable
+#[test]
+fn supported_functions_parse() {
+	use parsing::Expression;
+
+	for func in SUPPORTED_FUNCTIONS.iter() {
+		if let Err(error) = Expression::parse(&format!("{}(x)", func), &['x']) {
+			assert!(
+				!error.message.starts_with("invalid variable"),
+				"{} isn't parsed as a function: {:?}",
+				func,
+				error
+			);
+		}
+	}
+}
+
+#[test]
+fn function_correction() {
+	use parsing::closest_function;
+
+	let values = HashMap::from([
+		("sni", Some("sin")),
+		("sqtr", Some("sqrt")),
+		("cso", Some("cos")),
+		("SNI", Some("sin")),
+		("bessel", Some("besselj")),
+		("lgoo10", Some("log10")),
+		("sin", None),
+		("log", None),
+		("f", None),
+		("foo", None),
+		("x)", None),
+		("", None),
+	]);
+
+	for (key, value) in values {
+		assert_eq!(closest_function(key), value, "{}", key);
+	}
+}
+
+#[test]
+fn unmatched_parens() {
+	let values = HashMap::from([
+		("sin(x)", vec![]),
+		("sin(x", vec![3]),
+		("sin(cos(x", vec![3, 7]),
+		("x)+(2", vec![1, 3]),
+		("(x))(", vec![3, 4]),
+		("θ(x", vec![2]),
+	]);
+
+	for (key, value) in values {
+		assert_eq!(parsing::unmatched_parens(key), value, "{}", key);
+	}
+}
@@ -0,0 +1,37 @@
+use ytbn_graphing_software::ParameterSweep;
+
+/// Ensures a sweep moves its parameter at its speed, turning around at the ends of its range
+#[test]
+fn step() {
+	let mut sweep = ParameterSweep {
+		min: 0.0,
+		max: 1.0,
+		speed: 0.5,
+		..ParameterSweep::default()
+	};
+	assert_eq!(sweep.step(0.0, 1.0), 0.5);
+
+	// Goes past the end by 0.25, which it turns around from
+	assert_eq!(sweep.step(0.75, 1.0), 0.75);
+	assert_eq!(sweep.step(0.75, 1.0), 0.25);
+
+	// Back and forth more than once when the step is long, then increasing again
+	assert_eq!(sweep.step(0.25, 4.5), 0.0);
+	assert_eq!(sweep.step(0.0, 1.0), 0.5);
+}
+
+/// Ensures values outside of the range (and ranges that are reversed or empty) are brought into it
+#[test]
+fn out_of_range() {
+	let mut sweep = ParameterSweep {
+		min: 2.0,
+		max: -2.0,
+		speed: 1.0,
+		..ParameterSweep::default()
+	};
+	assert_eq!(sweep.step(10.0, 1.0), 1.0);
+	assert_eq!(sweep.step(f64::NAN, 1.0), -2.0);
+
+	sweep.max = 2.0;
+	assert_eq!(sweep.step(1.0, 1.0), 2.0);
+}
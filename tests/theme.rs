@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+use ytbn_graphing_software::{Palette, Theme};
+
+/// Ensures every palette starts over from its first color once each color has been used
+#[test]
+fn palette_cycles() {
+	for palette in Palette::ALL {
+		let len = palette.colors().len();
+		assert!(len > 0, "{} has no colors", palette);
+		for i in 0..len {
+			assert_eq!(palette.color(i), palette.color(i + len));
+		}
+	}
+}
+
+/// Ensures the colors of each palette can all be told apart
+#[test]
+fn palette_distinct() {
+	for palette in Palette::ALL {
+		let colors: HashSet<_> = palette.colors().iter().collect();
+		assert_eq!(colors.len(), palette.colors().len(), "{}", palette);
+	}
+}
+
+/// Ensures the default theme assigns the colors of the default palette, and roundtrips through JSON
+#[test]
+fn theme() {
+	let theme = Theme::default();
+	assert_eq!(theme.palette, Palette::Default);
+	assert_eq!(theme.color(1), Palette::Default.color(1));
+
+	let theme = Theme {
+		palette: Palette::OkabeIto,
+		plot_background: Some(epaint::Color32::BLACK),
+		..Theme::default()
+	};
+	let json = serde_json::to_string(&theme).unwrap();
+	assert_eq!(serde_json::from_str::<Theme>(&json).unwrap(), theme);
+	assert_eq!(
+		serde_json::from_str::<Theme>("{}").unwrap(),
+		Theme::default()
+	);
+}
@@ -0,0 +1,97 @@
+use egui_plot::PlotPoint;
+use ytbn_graphing_software::{line_integrals, vector_field, VectorField};
+
+/// Ensures [`vector_field`] places arrows along the field in the center of each cell, scaled to the longest one
+#[test]
+fn vector_field_arrows() {
+	// F(x, y) = (x, y) points away from the origin, and is longest in the corners
+	let (origins, tips) = vector_field(
+		&mut |x, _| x,
+		&mut |_, y| y,
+		[-2.0, -2.0],
+		[2.0, 2.0],
+		[4, 4],
+	);
+	assert_eq!(origins.len(), 16);
+	assert_eq!(tips.len(), 16);
+
+	let lengths: Vec<f64> = origins
+		.iter()
+		.zip(tips.iter())
+		.map(|(origin, tip)| {
+			let center = PlotPoint::new((origin.x + tip.x) / 2.0, (origin.y + tip.y) / 2.0);
+			let (vector_x, vector_y) = (tip.x - origin.x, tip.y - origin.y);
+
+			// Parallel to (x, y)
+			assert!(((vector_x * center.y) - (vector_y * center.x)).abs() < 1e-12);
+			assert!((vector_x * center.x) > 0.0);
+			vector_x.hypot(vector_y)
+		})
+		.collect();
+
+	let longest = lengths.iter().cloned().fold(0.0, f64::max);
+	assert!((longest - 0.9).abs() < 1e-12);
+}
+
+/// Ensures [`vector_field`] skips arrows where the field is zero or isn't defined
+#[test]
+fn vector_field_skipped() {
+	let (origins, _) = vector_field(
+		&mut |x, _| x.max(0.0),
+		&mut |x, _| x.ln(),
+		[-2.0, -1.0],
+		[2.0, 1.0],
+		[4, 2],
+	);
+	assert_eq!(origins.len(), 4);
+}
+
+/// Ensures [`VectorField`] only recalculates when the bounds change
+#[test]
+fn vector_field_cache() {
+	let mut calls = 0;
+	let mut p = |_: f64, _: f64| {
+		calls += 1;
+		1.0
+	};
+
+	let mut field = VectorField::default();
+	field.update(&mut p, &mut |_, _| 0.0, [-1.0, -1.0], [1.0, 1.0], 400);
+	assert_eq!(field.origins.len(), 100);
+	field.update(&mut p, &mut |_, _| 0.0, [-1.0, -1.0], [1.0, 1.0], 400);
+	assert_eq!(calls, 100);
+
+	field.clear();
+	assert!(field.origins.is_empty());
+	assert!(field.tips.is_empty());
+}
+
+/// Ensures [`line_integrals`] agrees with Green's theorem and the divergence theorem
+#[test]
+fn line_integrals_rectangle() {
+	let (min, max) = ([-1.0, 0.0], [2.0, 2.0]);
+	let area = 6.0;
+
+	// F(x, y) = (-y, x) has a curl of 2 and a divergence of 0
+	let (circulation, flux) = line_integrals(&mut |_, y| -y, &mut |x, _| x, min, max);
+	assert!((circulation - (2.0 * area)).abs() < 1e-9);
+	assert!(flux.abs() < 1e-9);
+
+	// F(x, y) = (x, y) has a curl of 0 and a divergence of 2
+	let (circulation, flux) = line_integrals(&mut |x, _| x, &mut |_, y| y, min, max);
+	assert!(circulation.abs() < 1e-9);
+	assert!((flux - (2.0 * area)).abs() < 1e-9);
+
+	// F(x, y) = (x^2 y, sin(x) + y^3) has a curl of cos(x) - x^2 and a divergence of 2xy + 3y^2
+	let (circulation, flux) = line_integrals(
+		&mut |x, y| x * x * y,
+		&mut |x, y| x.sin() + y.powi(3),
+		min,
+		max,
+	);
+	// ∫∫ cos(x) - x^2 dA and ∫∫ 2xy + 3y^2 dA over [-1, 2] × [0, 2]
+	let expected_circulation = 2.0 * ((2.0_f64.sin() + 1.0_f64.sin()) - 3.0);
+	let expected_flux = 6.0 + 24.0;
+	assert!((circulation - expected_circulation).abs() < 1e-8);
+	assert!((flux - expected_flux).abs() < 1e-8);
+}